@@ -0,0 +1,83 @@
+//! Colors sampled from the captured screen.
+//!
+//! The pixels are read from cairo ARGB32 buffers, whose channels are premultiplied by the
+//! alpha, and written as hex, rgb or hsl strings for the eyedropper.
+
+/// Straight (r, g, b) of a premultiplied cairo ARGB32 pixel, as stored in memory
+pub fn unpremultiply(pixel: [u8; 4]) -> [u8; 3] {
+    let argb = u32::from_ne_bytes(pixel);
+    let a = argb >> 24;
+    let channel = |shift: u32| {
+        let c = (argb >> shift) & 0xff;
+        (c * 255 + a / 2).checked_div(a).unwrap_or(0).min(255) as u8
+    };
+    [channel(16), channel(8), channel(0)]
+}
+
+/// Hue in degrees, saturation and lightness in percent
+pub fn rgb_to_hsl(rgb: [u8; 3]) -> (f64, f64, f64) {
+    let [r, g, b] = rgb.map(|c| c as f64 / 255.0);
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let l = (max + min) / 2.0;
+    let d = max - min;
+    if d == 0.0 {
+        return (0.0, 0.0, l * 100.0);
+    }
+
+    let s = d / (1.0 - (2.0 * l - 1.0).abs());
+    let h = if max == r {
+        ((g - b) / d).rem_euclid(6.0)
+    } else if max == g {
+        (b - r) / d + 2.0
+    } else {
+        (r - g) / d + 4.0
+    };
+    (h * 60.0, s * 100.0, l * 100.0)
+}
+
+/// Write the color as "hex" (#rrggbb), "rgb" (rgb(r, g, b)) or "hsl" (hsl(h, s%, l%))
+pub fn format_color(rgb: [u8; 3], format: &str) -> String {
+    let [r, g, b] = rgb;
+    match format {
+        "rgb" => format!("rgb({}, {}, {})", r, g, b),
+        "hsl" => {
+            let (h, s, l) = rgb_to_hsl(rgb);
+            format!("hsl({:.0}, {:.0}%, {:.0}%)", h, s, l)
+        }
+        _ => format!("#{:02x}{:02x}{:02x}", r, g, b),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pixel(a: u8, r: u8, g: u8, b: u8) -> [u8; 4] {
+        (((a as u32) << 24) | ((r as u32) << 16) | ((g as u32) << 8) | b as u32).to_ne_bytes()
+    }
+
+    #[test]
+    fn unpremultiply_opaque_and_transparent() {
+        assert_eq!(unpremultiply(pixel(255, 12, 34, 56)), [12, 34, 56]);
+        assert_eq!(unpremultiply(pixel(0, 0, 0, 0)), [0, 0, 0]);
+        assert_eq!(unpremultiply(pixel(128, 64, 128, 0)), [128, 255, 0]);
+    }
+
+    #[test]
+    fn hsl_of_primary_and_gray() {
+        assert_eq!(rgb_to_hsl([255, 0, 0]), (0.0, 100.0, 50.0));
+        assert_eq!(rgb_to_hsl([0, 255, 0]), (120.0, 100.0, 50.0));
+        assert_eq!(rgb_to_hsl([0, 0, 255]), (240.0, 100.0, 50.0));
+        let (h, s, _) = rgb_to_hsl([128, 128, 128]);
+        assert_eq!((h, s), (0.0, 0.0));
+    }
+
+    #[test]
+    fn formats() {
+        let rgb = [255, 128, 0];
+        assert_eq!(format_color(rgb, "hex"), "#ff8000");
+        assert_eq!(format_color(rgb, "rgb"), "rgb(255, 128, 0)");
+        assert_eq!(format_color(rgb, "hsl"), "hsl(30, 100%, 50%)");
+    }
+}
@@ -0,0 +1,219 @@
+//! Pixel filters working on tightly packed 4 bytes per pixel buffers.
+
+/// Replace each `block` x `block` square with its average color
+pub fn pixelate(data: &mut [u8], width: i32, height: i32, block: i32) {
+    let block = block.max(1);
+    let (w, h) = (width as usize, height as usize);
+
+    for by in (0..h).step_by(block as usize) {
+        for bx in (0..w).step_by(block as usize) {
+            let y_end = (by + block as usize).min(h);
+            let x_end = (bx + block as usize).min(w);
+
+            // Average
+            let mut sum = [0_u64; 4];
+            for y in by..y_end {
+                for x in bx..x_end {
+                    let i = (y * w + x) * 4;
+                    for c in 0..4 {
+                        sum[c] += data[i + c] as u64;
+                    }
+                }
+            }
+            let n = ((y_end - by) * (x_end - bx)) as u64;
+            let avg = sum.map(|v| (v / n) as u8);
+
+            // Fill
+            for y in by..y_end {
+                for x in bx..x_end {
+                    let i = (y * w + x) * 4;
+                    data[i..i + 4].copy_from_slice(&avg);
+                }
+            }
+        }
+    }
+}
+
+/// Approximate a gaussian blur with three box blur passes
+pub fn gaussian_blur(data: &mut [u8], width: i32, height: i32, radius: i32) {
+    if radius < 1 {
+        return;
+    }
+    // Three box blurs of radius r/√3 have roughly the variance of a gaussian of sigma r/2
+    let box_radius = ((radius as f64) / 3_f64.sqrt()).round().max(1.0) as usize;
+    for _ in 0..3 {
+        box_blur(data, width as usize, height as usize, box_radius);
+    }
+}
+
+/// Horizontal and vertical moving average with clamped edges
+fn box_blur(data: &mut [u8], w: usize, h: usize, r: usize) {
+    let mut line: Vec<u8> = Vec::with_capacity(w.max(h) * 4);
+
+    // Rows
+    for y in 0..h {
+        line.clear();
+        line.extend_from_slice(&data[y * w * 4..(y + 1) * w * 4]);
+        blur_line(&line, w, r, |x, px| {
+            let i = (y * w + x) * 4;
+            data[i..i + 4].copy_from_slice(&px);
+        });
+    }
+
+    // Columns
+    for x in 0..w {
+        line.clear();
+        for y in 0..h {
+            let i = (y * w + x) * 4;
+            line.extend_from_slice(&data[i..i + 4]);
+        }
+        blur_line(&line, h, r, |y, px| {
+            let i = (y * w + x) * 4;
+            data[i..i + 4].copy_from_slice(&px);
+        });
+    }
+}
+
+/// Running average over a line of `n` pixels; `write` receives every blurred pixel
+fn blur_line<F>(line: &[u8], n: usize, r: usize, mut write: F)
+where
+    F: FnMut(usize, [u8; 4]),
+{
+    let px = |i: isize| -> usize { i.clamp(0, n as isize - 1) as usize * 4 };
+    let window = (2 * r + 1) as u32;
+
+    // Initial window centered on the first pixel
+    let mut sum = [0_u32; 4];
+    for k in -(r as isize)..=(r as isize) {
+        let i = px(k);
+        for c in 0..4 {
+            sum[c] += line[i + c] as u32;
+        }
+    }
+
+    for j in 0..n {
+        write(j, sum.map(|v| (v / window) as u8));
+
+        // Slide the window
+        let add = px(j as isize + r as isize + 1);
+        let sub = px(j as isize - r as isize);
+        for c in 0..4 {
+            sum[c] = sum[c] + line[add + c] as u32 - line[sub + c] as u32;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Image filled with a recognizable pattern
+    fn image(width: i32, height: i32) -> Vec<u8> {
+        (0..width * height * 4)
+            .map(|i| (i * 37 % 251) as u8)
+            .collect()
+    }
+
+    fn pixel(data: &[u8], width: i32, x: i32, y: i32) -> [u8; 4] {
+        let i = ((y * width + x) * 4) as usize;
+        data[i..i + 4].try_into().unwrap()
+    }
+
+    #[test]
+    fn pixelate_blocks_are_uniform() {
+        // The last row and column of blocks are cut by the border
+        let (w, h, block) = (7, 5, 3);
+        let original = image(w, h);
+        let mut data = original.clone();
+
+        pixelate(&mut data, w, h, block);
+
+        for by in (0..h).step_by(block as usize) {
+            for bx in (0..w).step_by(block as usize) {
+                let (x_end, y_end) = ((bx + block).min(w), (by + block).min(h));
+                let n = ((x_end - bx) * (y_end - by)) as u64;
+                let mut sum = [0_u64; 4];
+                for y in by..y_end {
+                    for x in bx..x_end {
+                        let p = pixel(&original, w, x, y);
+                        for c in 0..4 {
+                            sum[c] += p[c] as u64;
+                        }
+                    }
+                }
+                let average = sum.map(|v| (v / n) as u8);
+                for y in by..y_end {
+                    for x in bx..x_end {
+                        assert_eq!(pixel(&data, w, x, y), average);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn pixelate_single_pixel_blocks_change_nothing() {
+        let (w, h) = (4, 3);
+        let original = image(w, h);
+        for block in [1, 0, -2] {
+            let mut data = original.clone();
+            pixelate(&mut data, w, h, block);
+            assert_eq!(data, original);
+        }
+    }
+
+    #[test]
+    fn blur_radius_zero_changes_nothing() {
+        let (w, h) = (6, 4);
+        let original = image(w, h);
+        for radius in [0, -3] {
+            let mut data = original.clone();
+            gaussian_blur(&mut data, w, h, radius);
+            assert_eq!(data, original);
+        }
+    }
+
+    #[test]
+    fn blur_keeps_a_flat_area() {
+        // The border pixels are repeated outside the buffer, so even the corners keep the color
+        let (w, h) = (9, 7);
+        let mut data: Vec<u8> = [10, 80, 160, 255].repeat((w * h) as usize);
+        gaussian_blur(&mut data, w, h, 4);
+        for y in 0..h {
+            for x in 0..w {
+                assert_eq!(pixel(&data, w, x, y), [10, 80, 160, 255]);
+            }
+        }
+    }
+
+    #[test]
+    fn blur_smooths_an_edge_up_to_the_border() {
+        // Left half black, right half white: the rows become a ramp that still starts black
+        // and ends white at the border
+        let (w, h) = (20, 3);
+        let mut data: Vec<u8> = (0..w * h)
+            .flat_map(|i| if i % w < w / 2 { [0; 4] } else { [200; 4] })
+            .collect();
+        gaussian_blur(&mut data, w, h, 3);
+
+        for y in 0..h {
+            let row: Vec<u8> = (0..w).map(|x| pixel(&data, w, x, y)[0]).collect();
+            assert!(row.windows(2).all(|p| p[0] <= p[1]), "not a ramp: {row:?}");
+            assert_eq!(row[0], 0);
+            assert_eq!(row[(w - 1) as usize], 200);
+            assert!(row[(w / 2 - 1) as usize] > 0 && row[(w / 2) as usize] < 200);
+        }
+    }
+
+    #[test]
+    fn blur_radius_larger_than_the_image() {
+        let (w, h) = (3, 2);
+        let mut data: Vec<u8> = [50, 50, 50, 50].repeat((w * h) as usize);
+        gaussian_blur(&mut data, w, h, 40);
+        assert!(data.iter().all(|v| *v == 50));
+
+        let mut single = vec![1, 2, 3, 4];
+        gaussian_blur(&mut single, 1, 1, 5);
+        assert_eq!(single, vec![1, 2, 3, 4]);
+    }
+}
@@ -0,0 +1,247 @@
+pub mod color;
+pub mod filters;
+pub mod redact;
+
+use gtk::prelude::*;
+use gtk::{cairo, gdk, glib};
+use std::rc::Rc;
+
+/// Pixels of the screen, captured with grim before the window is shown.
+/// The tools that work on the screenshot content (pixelate, blur, ...) read from here,
+/// since the drawing area itself is only a transparent overlay.
+#[derive(Clone, Default)]
+pub struct CapturedScreen {
+    image: Rc<Option<CapturedImage>>,
+}
+
+pub struct CapturedImage {
+    pub width: i32,
+    pub height: i32,
+    /// Cairo ARGB32 pixels (B, G, R, A premultiplied), 4 bytes per pixel without padding
+    pub data: Vec<u8>,
+    /// Image pixels per window pixel (the output scale)
+    pub scale: f64,
+}
+
+/// A rectangular copy of the captured pixels
+pub struct Region {
+    /// Position and size in image pixels
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+    pub data: Vec<u8>,
+}
+
+impl CapturedScreen {
+    /// Capture the whole screen. `window_width` is used to compute the output scale.
+    pub fn capture(window_width: i32) -> Self {
+        let output = match std::process::Command::new("grim")
+            .arg("-")
+            .stdout(std::process::Stdio::piped())
+            .output()
+        {
+            Ok(output) if output.status.success() => output,
+            Ok(output) => {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                eprintln!("Error executing grim: {}", stderr);
+                return Self::default();
+            }
+            Err(e) => {
+                eprintln!("Failed to execute grim: {}", e);
+                return Self::default();
+            }
+        };
+
+        match Self::decode_png(&output.stdout, window_width) {
+            Ok(image) => Self {
+                image: Rc::new(Some(image)),
+            },
+            Err(e) => {
+                eprintln!("Impossible to decode the captured screen: {}", e);
+                Self::default()
+            }
+        }
+    }
+
+    /// Decode a png into cairo ARGB32 pixels
+    pub fn decode_png(png: &[u8], window_width: i32) -> Result<CapturedImage, glib::Error> {
+        let texture = gdk::Texture::from_bytes(&glib::Bytes::from(png))?;
+        let (width, height) = (texture.width(), texture.height());
+        let mut data = vec![0_u8; (width * height * 4) as usize];
+        texture.download(&mut data, (width * 4) as usize);
+
+        Ok(CapturedImage {
+            width,
+            height,
+            data,
+            scale: if window_width > 0 {
+                width as f64 / window_width as f64
+            } else {
+                1.0
+            },
+        })
+    }
+
+    pub fn is_available(&self) -> bool {
+        self.image.is_some()
+    }
+
+    /// Image pixels per window pixel
+    pub fn scale(&self) -> f64 {
+        match self.image.as_ref() {
+            Some(image) => image.scale,
+            None => 1.0,
+        }
+    }
+
+    /// Straight (r, g, b) of the image pixel under (x, y), given in window coordinates
+    pub fn pixel(&self, x: f64, y: f64) -> Option<[u8; 3]> {
+        let image = self.image.as_ref().as_ref()?;
+        let px = (x * image.scale).floor() as i32;
+        let py = (y * image.scale).floor() as i32;
+        if px < 0 || py < 0 || px >= image.width || py >= image.height {
+            return None;
+        }
+        let start = ((py * image.width + px) * 4) as usize;
+        let pixel: [u8; 4] = image.data[start..start + 4].try_into().ok()?;
+        Some(color::unpremultiply(pixel))
+    }
+
+    /// Copy the (x, y, w, h) rectangle, given in window coordinates, clamped to the image.
+    pub fn region(&self, x: f64, y: f64, w: f64, h: f64) -> Option<Region> {
+        let image = self.image.as_ref().as_ref()?;
+        let s = image.scale;
+
+        // Normalize negative sizes and convert to image pixels
+        let x0 = ((x.min(x + w) * s).floor() as i32).clamp(0, image.width);
+        let y0 = ((y.min(y + h) * s).floor() as i32).clamp(0, image.height);
+        let x1 = ((x.max(x + w) * s).ceil() as i32).clamp(0, image.width);
+        let y1 = ((y.max(y + h) * s).ceil() as i32).clamp(0, image.height);
+        if x1 <= x0 || y1 <= y0 {
+            return None;
+        }
+
+        let (width, height) = (x1 - x0, y1 - y0);
+        let mut data = Vec::with_capacity((width * height * 4) as usize);
+        for row in y0..y1 {
+            let start = ((row * image.width + x0) * 4) as usize;
+            data.extend_from_slice(&image.data[start..start + (width * 4) as usize]);
+        }
+
+        Some(Region {
+            x: x0,
+            y: y0,
+            width,
+            height,
+            data,
+        })
+    }
+}
+
+/// Copy the pixels of a texture (decoded file, clipboard content, ...) into a cairo surface
+pub fn texture_to_surface(texture: &gdk::Texture) -> Result<cairo::ImageSurface, cairo::Error> {
+    let (width, height) = (texture.width(), texture.height());
+    let mut data = vec![0_u8; (width * height * 4) as usize];
+    texture.download(&mut data, (width * 4) as usize);
+    cairo::ImageSurface::create_for_data(data, cairo::Format::ARgb32, width, height, width * 4)
+}
+
+/// Encode cairo ARGB32 pixels as png
+pub fn encode_png(data: &[u8], width: i32, height: i32) -> glib::Bytes {
+    let format = if cfg!(target_endian = "little") {
+        gdk::MemoryFormat::B8g8r8a8Premultiplied
+    } else {
+        gdk::MemoryFormat::A8r8g8b8Premultiplied
+    };
+    let texture = gdk::MemoryTexture::new(
+        width,
+        height,
+        format,
+        &glib::Bytes::from(data),
+        (width * 4) as usize,
+    );
+    texture.save_to_png_bytes()
+}
+
+/// Burn the redactions into the pixels, except the `kept` ones, and encode them as png. The
+/// png is then decoded and the redacted rectangles checked again, so the returned bytes, the
+/// ones that are exported, are verified. An error means the image must not be exported.
+pub fn encode_redacted_png(
+    data: &mut [u8],
+    width: i32,
+    height: i32,
+    rects: &[(redact::PixelRect, [u8; 3])],
+    kept: &[bool],
+) -> Result<glib::Bytes, String> {
+    redact::redact(data, width, height, rects, kept);
+    let png = encode_png(data, width, height);
+
+    let exported = CapturedScreen::decode_png(&png, width)
+        .map_err(|e| format!("impossible to decode the exported png: {}", e))?;
+    if (exported.width, exported.height) != (width, height) {
+        return Err(format!(
+            "the exported png is {}x{} instead of {}x{}",
+            exported.width, exported.height, width, height
+        ));
+    }
+    redact::verify(&exported.data, width, height, rects, kept).map_err(|e| e.to_string())?;
+
+    Ok(png)
+}
+
+impl Region {
+    pub fn to_surface(&self) -> Result<cairo::ImageSurface, cairo::Error> {
+        cairo::ImageSurface::create_for_data(
+            self.data.clone(),
+            cairo::Format::ARgb32,
+            self.width,
+            self.height,
+            self.width * 4,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::captured_screen::redact::PixelRect;
+
+    #[test]
+    fn redactions_survive_the_png_round_trip() {
+        let (w, h) = (12, 9);
+        // Opaque pixels, so the premultiplied colors are kept exactly by the png
+        let original: Vec<u8> = (0..w * h)
+            .flat_map(|i| {
+                [
+                    (i * 7 % 256) as u8,
+                    (i * 13 % 256) as u8,
+                    (i * 29 % 256) as u8,
+                    255,
+                ]
+            })
+            .collect();
+        let rect = PixelRect {
+            x: 2,
+            y: 3,
+            width: 5,
+            height: 4,
+        };
+
+        let mut data = original.clone();
+        let png = encode_redacted_png(&mut data, w, h, &[(rect, [0, 0, 0])], &[]).unwrap();
+
+        let exported = CapturedScreen::decode_png(&png, w).unwrap();
+        assert_eq!((exported.width, exported.height), (w, h));
+        assert!(redact::is_redacted(&exported.data, w, h, rect, [0, 0, 0]));
+        for y in 0..h {
+            for x in 0..w {
+                let i = ((y * w + x) * 4) as usize;
+                let inside = (2..7).contains(&x) && (3..7).contains(&y);
+                if !inside {
+                    assert_eq!(exported.data[i..i + 4], original[i..i + 4]);
+                }
+            }
+        }
+    }
+}
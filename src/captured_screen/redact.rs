@@ -0,0 +1,405 @@
+//! Destructive redaction of the exported image.
+//!
+//! The redacted rectangles are overwritten with opaque pixels directly in the image
+//! buffer (tightly packed cairo ARGB32 pixels). The exported png is then decoded and checked
+//! pixel by pixel (see `encode_redacted_png`), so that nothing of the original content can
+//! survive in the exported file.
+//!
+//! The items drawn above a redaction (an arrow crossing it, a label on top) are part of the
+//! export: their pixels are kept as captured, on screen there is only the opaque redaction
+//! under them.
+
+/// Rectangle in image pixels
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PixelRect {
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+}
+
+impl PixelRect {
+    /// Convert a (x, y, w, h) rectangle in window coordinates to the pixels of an image whose
+    /// top left corner is at `origin` and that has `scale` pixels per window pixel.
+    /// The rectangle is rounded outwards (a partially covered pixel is redacted) and clamped
+    /// to the image; `None` if nothing is left.
+    pub fn from_window(
+        rect: (f64, f64, f64, f64),
+        origin: (f64, f64),
+        scale: f64,
+        image_width: i32,
+        image_height: i32,
+    ) -> Option<Self> {
+        let (x, y, w, h) = rect;
+        let x0 = (((x.min(x + w) - origin.0) * scale).floor() as i32).clamp(0, image_width);
+        let y0 = (((y.min(y + h) - origin.1) * scale).floor() as i32).clamp(0, image_height);
+        let x1 = (((x.max(x + w) - origin.0) * scale).ceil() as i32).clamp(0, image_width);
+        let y1 = (((y.max(y + h) - origin.1) * scale).ceil() as i32).clamp(0, image_height);
+        if x1 <= x0 || y1 <= y0 {
+            return None;
+        }
+        Some(Self {
+            x: x0,
+            y: y0,
+            width: x1 - x0,
+            height: y1 - y0,
+        })
+    }
+}
+
+/// Opaque ARGB32 pixel, in memory order
+pub fn opaque_pixel(rgb: [u8; 3]) -> [u8; 4] {
+    let [r, g, b] = rgb;
+    (0xff00_0000_u32 | (r as u32) << 16 | (g as u32) << 8 | b as u32).to_ne_bytes()
+}
+
+/// What the export is made of, bottom to top
+pub enum Stacked {
+    /// Rectangle burnt into the image with its opaque color
+    Redaction(PixelRect, [u8; 3]),
+    /// Any other item, as the image pixels it paints (one flag per pixel, row by row)
+    Item(Vec<bool>),
+}
+
+/// Pixels painted by an item stacked above the topmost redaction under them, one flag per
+/// image pixel. These are kept as captured instead of being burnt.
+pub fn kept_pixels(width: i32, height: i32, stack: &[Stacked]) -> Vec<bool> {
+    let mut kept = vec![false; (width.max(0) * height.max(0)) as usize];
+    for layer in stack {
+        match layer {
+            Stacked::Redaction(rect, _) => {
+                for y in rect.y.max(0)..(rect.y + rect.height).min(height) {
+                    for x in rect.x.max(0)..(rect.x + rect.width).min(width) {
+                        kept[(y * width + x) as usize] = false;
+                    }
+                }
+            }
+            Stacked::Item(painted) => {
+                for (kept, painted) in kept.iter_mut().zip(painted) {
+                    *kept |= *painted;
+                }
+            }
+        }
+    }
+    kept
+}
+
+/// Redaction rectangles of the stack, in drawing order
+pub fn redaction_rects(stack: &[Stacked]) -> Vec<(PixelRect, [u8; 3])> {
+    stack
+        .iter()
+        .filter_map(|layer| match layer {
+            Stacked::Redaction(rect, rgb) => Some((*rect, *rgb)),
+            Stacked::Item(_) => None,
+        })
+        .collect()
+}
+
+fn is_kept(kept: &[bool], width: i32, x: i32, y: i32) -> bool {
+    kept.get((y * width + x) as usize).copied().unwrap_or(false)
+}
+
+/// Overwrite every pixel of `rect` with the opaque `rgb` color, except the `kept` ones
+pub fn fill_opaque(
+    data: &mut [u8],
+    width: i32,
+    height: i32,
+    rect: PixelRect,
+    rgb: [u8; 3],
+    kept: &[bool],
+) {
+    let pixel = opaque_pixel(rgb);
+    for y in rect.y.max(0)..(rect.y + rect.height).min(height) {
+        for x in rect.x.max(0)..(rect.x + rect.width).min(width) {
+            if !is_kept(kept, width, x, y) {
+                let i = ((y * width + x) * 4) as usize;
+                data[i..i + 4].copy_from_slice(&pixel);
+            }
+        }
+    }
+}
+
+/// True if every pixel of `rect` is exactly the opaque `rgb` color
+pub fn is_redacted(data: &[u8], width: i32, height: i32, rect: PixelRect, rgb: [u8; 3]) -> bool {
+    let pixel = opaque_pixel(rgb);
+    (rect.y.max(0)..(rect.y + rect.height).min(height)).all(|y| {
+        (rect.x.max(0)..(rect.x + rect.width).min(width)).all(|x| {
+            let i = ((y * width + x) * 4) as usize;
+            data[i..i + 4] == pixel
+        })
+    })
+}
+
+/// Overwrite all the rectangles, in order, except the `kept` pixels (see `kept_pixels`)
+pub fn redact(
+    data: &mut [u8],
+    width: i32,
+    height: i32,
+    rects: &[(PixelRect, [u8; 3])],
+    kept: &[bool],
+) {
+    for (rect, rgb) in rects {
+        fill_opaque(data, width, height, *rect, *rgb, kept);
+    }
+}
+
+/// Check that every rectangle is redacted in `data`, except the `kept` pixels.
+/// Returns an error, and the image must not be exported, if any pixel survived.
+pub fn verify(
+    data: &[u8],
+    width: i32,
+    height: i32,
+    rects: &[(PixelRect, [u8; 3])],
+    kept: &[bool],
+) -> std::io::Result<()> {
+    // A later rectangle may cover an earlier one with another color, so the check is done
+    // against the union: each pixel must be the color of the last rectangle covering it.
+    for (idx, (rect, _)) in rects.iter().enumerate() {
+        for y in rect.y..rect.y + rect.height {
+            for x in rect.x..rect.x + rect.width {
+                if is_kept(kept, width, x, y) {
+                    continue;
+                }
+                let (_, rgb) = rects[idx..]
+                    .iter()
+                    .rev()
+                    .find(|(r, _)| x >= r.x && x < r.x + r.width && y >= r.y && y < r.y + r.height)
+                    .expect("the pixel is inside at least this rectangle");
+                let pixel = PixelRect {
+                    x,
+                    y,
+                    width: 1,
+                    height: 1,
+                };
+                if !is_redacted(data, width, height, pixel, *rgb) {
+                    return Err(std::io::Error::other(format!(
+                        "redaction failed at pixel ({}, {})",
+                        x, y
+                    )));
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Image filled with a recognizable, semi transparent, pattern
+    fn image(width: i32, height: i32) -> Vec<u8> {
+        (0..width * height * 4).map(|i| (i % 251) as u8).collect()
+    }
+
+    #[test]
+    fn fill_overwrites_only_the_rectangle() {
+        let (w, h) = (8, 6);
+        let original = image(w, h);
+        let mut data = original.clone();
+        let rect = PixelRect {
+            x: 2,
+            y: 1,
+            width: 3,
+            height: 4,
+        };
+
+        fill_opaque(&mut data, w, h, rect, [10, 20, 30], &[]);
+
+        for y in 0..h {
+            for x in 0..w {
+                let i = ((y * w + x) * 4) as usize;
+                let inside = (2..5).contains(&x) && (1..5).contains(&y);
+                if inside {
+                    assert_eq!(data[i..i + 4], opaque_pixel([10, 20, 30]));
+                } else {
+                    assert_eq!(data[i..i + 4], original[i..i + 4]);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn redacted_pixels_are_opaque() {
+        let pixel = u32::from_ne_bytes(opaque_pixel([1, 2, 3]));
+        assert_eq!(pixel >> 24, 0xff);
+        assert_eq!(pixel & 0x00ff_ffff, 0x0001_0203);
+    }
+
+    #[test]
+    fn original_pixels_are_gone() {
+        let (w, h) = (16, 16);
+        let mut data = image(w, h);
+        let rect = PixelRect {
+            x: 3,
+            y: 4,
+            width: 9,
+            height: 7,
+        };
+        assert!(!is_redacted(&data, w, h, rect, [0, 0, 0]));
+
+        redact(&mut data, w, h, &[(rect, [0, 0, 0])], &[]);
+        verify(&data, w, h, &[(rect, [0, 0, 0])], &[]).unwrap();
+
+        assert!(is_redacted(&data, w, h, rect, [0, 0, 0]));
+        let original = image(w, h);
+        for y in rect.y..rect.y + rect.height {
+            for x in rect.x..rect.x + rect.width {
+                let i = ((y * w + x) * 4) as usize;
+                assert_ne!(data[i..i + 4], original[i..i + 4]);
+            }
+        }
+    }
+
+    #[test]
+    fn overlapping_rectangles_are_verified() {
+        let (w, h) = (10, 10);
+        let mut data = image(w, h);
+        let rects = [
+            (
+                PixelRect {
+                    x: 0,
+                    y: 0,
+                    width: 6,
+                    height: 6,
+                },
+                [255, 0, 0],
+            ),
+            (
+                PixelRect {
+                    x: 4,
+                    y: 4,
+                    width: 6,
+                    height: 6,
+                },
+                [0, 0, 255],
+            ),
+        ];
+
+        redact(&mut data, w, h, &rects, &[]);
+        verify(&data, w, h, &rects, &[]).unwrap();
+
+        let overlap = PixelRect {
+            x: 4,
+            y: 4,
+            width: 2,
+            height: 2,
+        };
+        assert!(is_redacted(&data, w, h, overlap, [0, 0, 255]));
+    }
+
+    #[test]
+    fn surviving_pixels_fail_the_check() {
+        let (w, h) = (8, 8);
+        let rect = PixelRect {
+            x: 1,
+            y: 1,
+            width: 4,
+            height: 4,
+        };
+        let mut data = image(w, h);
+        assert!(verify(&data, w, h, &[(rect, [0, 0, 0])], &[]).is_err());
+
+        // A single pixel left over is enough to refuse the export
+        redact(&mut data, w, h, &[(rect, [0, 0, 0])], &[]);
+        data[((2 * w + 3) * 4) as usize] = 1;
+        assert!(verify(&data, w, h, &[(rect, [0, 0, 0])], &[]).is_err());
+    }
+
+    #[test]
+    fn items_above_a_redaction_are_kept() {
+        let (w, h) = (10, 6);
+        let row = |y: i32| -> Vec<bool> { (0..w * h).map(|i| i / w == y).collect() };
+        let rect = PixelRect {
+            x: 2,
+            y: 1,
+            width: 6,
+            height: 4,
+        };
+        // A line below the redaction on row 2, an arrow crossing it on row 3
+        let stack = [
+            Stacked::Item(row(2)),
+            Stacked::Redaction(rect, [0, 0, 0]),
+            Stacked::Item(row(3)),
+        ];
+        let kept = kept_pixels(w, h, &stack);
+        let rects = redaction_rects(&stack);
+        let original = image(w, h);
+        let mut data = original.clone();
+
+        redact(&mut data, w, h, &rects, &kept);
+        verify(&data, w, h, &rects, &kept).unwrap();
+
+        for y in rect.y..rect.y + rect.height {
+            for x in rect.x..rect.x + rect.width {
+                let i = ((y * w + x) * 4) as usize;
+                if y == 3 {
+                    assert_eq!(data[i..i + 4], original[i..i + 4]);
+                } else {
+                    assert_eq!(data[i..i + 4], opaque_pixel([0, 0, 0]));
+                }
+            }
+        }
+
+        // A pixel of the redaction that the arrow does not cover still refuses the export
+        data[((4 * w + 2) * 4) as usize] = 1;
+        assert!(verify(&data, w, h, &rects, &kept).is_err());
+    }
+
+    #[test]
+    fn a_later_redaction_burns_the_items_below_it() {
+        let (w, h) = (6, 6);
+        let rect = PixelRect {
+            x: 0,
+            y: 0,
+            width: 6,
+            height: 6,
+        };
+        let stack = [
+            Stacked::Redaction(rect, [255, 0, 0]),
+            Stacked::Item(vec![true; (w * h) as usize]),
+            Stacked::Redaction(rect, [0, 0, 255]),
+        ];
+        let kept = kept_pixels(w, h, &stack);
+        assert!(kept.iter().all(|kept| !kept));
+
+        let mut data = image(w, h);
+        redact(&mut data, w, h, &redaction_rects(&stack), &kept);
+        assert!(is_redacted(&data, w, h, rect, [0, 0, 255]));
+    }
+
+    #[test]
+    fn window_rectangle_is_rounded_outwards_and_scaled() {
+        // Screenshot starting at (100, 50) on a 2x output
+        let rect = PixelRect::from_window((110.5, 60.25, 10.0, 5.0), (100.0, 50.0), 2.0, 400, 300);
+        assert_eq!(
+            rect,
+            Some(PixelRect {
+                x: 21,
+                y: 20,
+                width: 20,
+                height: 11,
+            })
+        );
+    }
+
+    #[test]
+    fn window_rectangle_is_normalized_and_clamped() {
+        // Dragged from bottom right to top left, partially outside the screenshot
+        let rect = PixelRect::from_window((20.0, 20.0, -40.0, -40.0), (0.0, 0.0), 1.0, 15, 15);
+        assert_eq!(
+            rect,
+            Some(PixelRect {
+                x: 0,
+                y: 0,
+                width: 15,
+                height: 15,
+            })
+        );
+
+        // Completely outside
+        let rect = PixelRect::from_window((50.0, 50.0, 5.0, 5.0), (0.0, 0.0), 1.0, 15, 15);
+        assert_eq!(rect, None);
+    }
+}
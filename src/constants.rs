@@ -10,6 +10,7 @@ pub const CSS_CLASS_PRESSED: &str = "pressed";
 pub const CSS_CLASS_PRESSED_PERSISTENT: &str = "pressed-persistent";
 pub const CSS_CLASS_HANDLES: &str = "corner-handle";
 pub const CSS_CLASS_TOOLBOX_BTN: &str = "toolbox-btn";
+pub const CSS_CLASS_LAYERS_PANEL: &str = "layers-panel";
 // pub const CSS_CLASS_TOOLBOX_BAR: &str = "test";
 pub const CSS_CLASS_TOOLBOX_BAR: &str = "transparent";
 
@@ -17,8 +18,22 @@ pub const HANDLE_SIZE_PX: i32 = 10;
 pub const TOOLBOX_BTN_SIZE: i32 = 50;
 pub const TOOLBOX_SETTINGS_BTN_SIZE: i32 = 10;
 
+// Layers panel
+pub const LAYERS_PANEL_WIDTH: i32 = 260;
+pub const LAYERS_PANEL_HEIGHT: i32 = 400;
+
+// Selection
+pub const SELECTION_TOLERANCE_PX: f64 = 5.0;
+
 // Drawing area settings toml
 pub const DRAWING_AREA_SETTINGS_TOML: &str = concat!(
     env!("CARGO_MANIFEST_DIR"),
     "/src/drawing_area_settings/drawing_area_settings.toml"
 );
+
+// Stamps directory, inside the user config directory
+pub const STAMPS_DIR: &str = "rustshot-gtk/stamps";
+pub const STAMP_THUMBNAIL_SIZE: i32 = 24;
+
+// Drop shadows, largest off screen silhouette in device pixels
+pub const MAX_SHADOW_SIZE: i32 = 8192;
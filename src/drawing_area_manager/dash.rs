@@ -0,0 +1,41 @@
+//! Custom dash patterns of the strokes.
+//!
+//! The pattern lists the dash and gap lengths in pixels, separated by commas or spaces, like
+//! "8,4" or "12 4 2 4".
+
+/// Dash and gap lengths of `pattern`, None if any length is not a number or is negative, or if
+/// nothing would be drawn (no length above zero). The stroke is then solid.
+pub fn parse_pattern(pattern: &str) -> Option<Vec<f64>> {
+    let dashes = pattern
+        .split([',', ' '])
+        .filter(|s| !s.trim().is_empty())
+        .map(|s| s.trim().parse::<f64>().ok().filter(|v| *v >= 0.0))
+        .collect::<Option<Vec<f64>>>()?;
+    dashes.iter().any(|d| *d > 0.0).then_some(dashes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn commas_and_spaces_separate_the_lengths() {
+        assert_eq!(parse_pattern("8,4"), Some(vec![8.0, 4.0]));
+        assert_eq!(parse_pattern("12 4 2 4"), Some(vec![12.0, 4.0, 2.0, 4.0]));
+        assert_eq!(parse_pattern(" 6, 2.5 "), Some(vec![6.0, 2.5]));
+    }
+
+    #[test]
+    fn any_bad_length_draws_solid() {
+        assert_eq!(parse_pattern("8,x,4"), None);
+        assert_eq!(parse_pattern("8,-4"), None);
+        assert_eq!(parse_pattern("4,NaN"), None);
+    }
+
+    #[test]
+    fn empty_or_zero_patterns_draw_solid() {
+        assert_eq!(parse_pattern(""), None);
+        assert_eq!(parse_pattern(" , "), None);
+        assert_eq!(parse_pattern("0,0"), None);
+    }
+}
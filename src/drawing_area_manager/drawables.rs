@@ -1,10 +1,15 @@
+use crate::captured_screen::{CapturedScreen, color, filters};
+use crate::drawing_area_manager::eraser::Point;
+use crate::drawing_area_manager::text_editor::{TextAction, TextEditor};
+use crate::drawing_area_manager::{dash, gradient, numbering};
 use crate::drawing_area_settings::SettingValue;
 use crate::drawing_area_settings::{Settings, SettingsRc};
 use core::f64;
-use gtk::cairo;
+use gtk::{cairo, glib};
+use rustshot_gtk::constants::MAX_SHADOW_SIZE;
 use std::cell::Cell;
 use std::cell::RefCell;
-use std::rc::Rc;
+use std::rc::{Rc, Weak};
 
 #[derive(Clone)]
 pub struct InputText {
@@ -13,8 +18,32 @@ pub struct InputText {
     pub x: Rc<Cell<f64>>,
     pub y: Rc<Cell<f64>>,
     pub text: Rc<RefCell<String>>,
+    /// Caret, selection and input method text while the text is typed
+    pub typed: TypedText,
     pub settings: SettingsRc,
     pub saved_settings: Rc<RefCell<Option<Settings>>>,
+    pub layer: LayerState,
+    pub shadow_cache: ShadowCache,
+    pub drawing: Rc<Cell<bool>>,
+}
+
+/// Speech bubble: a rounded box with wrapped text and a tail pointing at (tip_x, tip_y).
+/// The box is centered on (x, y), its size follows the text.
+#[derive(Clone)]
+pub struct Callout {
+    pub tip_x: Rc<Cell<f64>>,
+    pub tip_y: Rc<Cell<f64>>,
+    pub x: Rc<Cell<f64>>,
+    pub y: Rc<Cell<f64>>,
+    pub width: Rc<Cell<f64>>,
+    pub height: Rc<Cell<f64>>,
+    pub text: Rc<RefCell<String>>,
+    /// Caret, selection and input method text while the text is typed
+    pub typed: TypedText,
+    pub settings: SettingsRc,
+    pub saved_settings: Rc<RefCell<Option<Settings>>>,
+    pub layer: LayerState,
+    pub shadow_cache: ShadowCache,
     pub drawing: Rc<Cell<bool>>,
 }
 
@@ -22,8 +51,14 @@ pub struct InputText {
 pub struct NumberedCircle {
     pub x0: Rc<Cell<f64>>,
     pub y0: Rc<Cell<f64>>,
+    /// Number of a placed badge, changed when the badges are renumbered
+    pub number: Rc<Cell<i32>>,
+    /// Monotonic time of the placement, to renumber the badges in placement order
+    pub placed: Rc<Cell<i64>>,
     pub settings: SettingsRc,
     pub saved_settings: Rc<RefCell<Option<Settings>>>,
+    pub layer: LayerState,
+    pub shadow_cache: ShadowCache,
     pub drawing: Rc<Cell<bool>>,
 }
 
@@ -33,8 +68,11 @@ pub struct FreeHandDraw {
     pub y0: Rc<Cell<f64>>,
     pub x: RefCell<Vec<f64>>,
     pub y: RefCell<Vec<f64>>,
+    pub captured: CapturedScreen,
     pub settings: SettingsRc,
     pub saved_settings: Rc<RefCell<Option<Settings>>>,
+    pub layer: LayerState,
+    pub shadow_cache: ShadowCache,
     pub drawing: Rc<Cell<bool>>,
 }
 
@@ -46,6 +84,8 @@ pub struct Line {
     pub y2: Rc<Cell<f64>>,
     pub settings: SettingsRc,
     pub saved_settings: Rc<RefCell<Option<Settings>>>,
+    pub layer: LayerState,
+    pub shadow_cache: ShadowCache,
     pub drawing: Rc<Cell<bool>>,
 }
 
@@ -55,19 +95,35 @@ pub struct Arrow {
     pub y1: Rc<Cell<f64>>,
     pub x2: Rc<Cell<f64>>,
     pub y2: Rc<Cell<f64>>,
+    // Bezier control point of the curved arrows
+    pub cx: Rc<Cell<f64>>,
+    pub cy: Rc<Cell<f64>>,
     pub settings: SettingsRc,
     pub saved_settings: Rc<RefCell<Option<Settings>>>,
+    pub layer: LayerState,
+    pub shadow_cache: ShadowCache,
     pub drawing: Rc<Cell<bool>>,
     // fill: bool,
 }
 
+/// Paths of an arrow: the shaft, the heads drawn with a stroke and the filled ones
+struct ArrowPaths {
+    shaft: cairo::Path,
+    strokes: Vec<cairo::Path>,
+    fills: Vec<cairo::Path>,
+}
+
 #[derive(Clone)]
 pub struct Arc {
     pub radius: Rc<Cell<f64>>,
     pub center_x: Rc<Cell<f64>>,
     pub center_y: Rc<Cell<f64>>,
+    pub width: Rc<Cell<f64>>,
+    pub height: Rc<Cell<f64>>,
     pub settings: SettingsRc,
     pub saved_settings: Rc<RefCell<Option<Settings>>>,
+    pub layer: LayerState,
+    pub shadow_cache: ShadowCache,
     pub drawing: Rc<Cell<bool>>,
 }
 
@@ -79,18 +135,386 @@ pub struct AreaBox {
     pub end_y: Rc<Cell<f64>>,
     pub settings: SettingsRc,
     pub saved_settings: Rc<RefCell<Option<Settings>>>,
+    pub layer: LayerState,
+    pub shadow_cache: ShadowCache,
+    pub drawing: Rc<Cell<bool>>,
+}
+
+/// Rectangle (x, y, w, h) and opaque rgb color of a redaction
+pub type Redaction = ((f64, f64, f64, f64), [u8; 3]);
+
+/// Polyline or polygon: every click places a vertex, a double click or Enter finishes it.
+#[derive(Clone)]
+pub struct Polygon {
+    pub x: Rc<RefCell<Vec<f64>>>,
+    pub y: Rc<RefCell<Vec<f64>>>,
+    pub last_click: Rc<Cell<Option<(i64, f64, f64)>>>,
+    pub finished: Rc<Cell<bool>>,
+    pub settings: SettingsRc,
+    pub saved_settings: Rc<RefCell<Option<Settings>>>,
+    pub layer: LayerState,
+    pub shadow_cache: ShadowCache,
+    pub drawing: Rc<Cell<bool>>,
+}
+
+/// Marker box: the screen underneath is multiplied by the color, like a real highlighter.
+#[derive(Clone)]
+pub struct Highlight {
+    pub start_x: Rc<Cell<f64>>,
+    pub start_y: Rc<Cell<f64>>,
+    pub end_x: Rc<Cell<f64>>,
+    pub end_y: Rc<Cell<f64>>,
+    pub captured: CapturedScreen,
+    pub settings: SettingsRc,
+    pub saved_settings: Rc<RefCell<Option<Settings>>>,
+    pub layer: LayerState,
+    pub shadow_cache: ShadowCache,
+    pub drawing: Rc<Cell<bool>>,
+}
+
+/// Opaque redaction box. It ignores any alpha and it is also burnt into the exported image.
+#[derive(Clone)]
+pub struct Redact {
+    pub start_x: Rc<Cell<f64>>,
+    pub start_y: Rc<Cell<f64>>,
+    pub end_x: Rc<Cell<f64>>,
+    pub end_y: Rc<Cell<f64>>,
+    pub settings: SettingsRc,
+    pub saved_settings: Rc<RefCell<Option<Settings>>>,
+    pub layer: LayerState,
+    pub shadow_cache: ShadowCache,
+    pub drawing: Rc<Cell<bool>>,
+}
+
+/// Area that stays bright while the rest of the screen is dimmed.
+/// All the spotlights created by the tool share `regions`, so that they are composed in a
+/// single dimmed layer and several spotlights do not darken each other.
+#[derive(Clone)]
+pub struct Spotlight {
+    pub region: Rc<SpotlightRegion>,
+    pub regions: SpotlightRegions,
+    pub settings: SettingsRc,
+    pub saved_settings: Rc<RefCell<Option<Settings>>>,
+    pub layer: LayerState,
+    pub shadow_cache: ShadowCache,
+    pub drawing: Rc<Cell<bool>>,
+}
+
+/// Geometry of a single spotlight, as seen by the other spotlights
+#[derive(Default)]
+pub struct SpotlightRegion {
+    pub start_x: Cell<f64>,
+    pub start_y: Cell<f64>,
+    pub end_x: Cell<f64>,
+    pub end_y: Cell<f64>,
+    pub ellipse: Cell<bool>,
+    pub dim: Cell<f64>,
+    pub layer: LayerState,
+}
+
+/// Spotlights composed together. Deleted spotlights are dropped, so weak references are kept.
+pub type SpotlightRegions = Rc<RefCell<Vec<Weak<SpotlightRegion>>>>;
+
+/// Zoomed copy of a source rectangle of the captured screen, shown around a destination point.
+/// It takes two drags: the first one picks the source, the second one the destination.
+#[derive(Clone)]
+pub struct Magnifier {
+    pub start_x: Rc<Cell<f64>>,
+    pub start_y: Rc<Cell<f64>>,
+    pub end_x: Rc<Cell<f64>>,
+    pub end_y: Rc<Cell<f64>>,
+    pub dest: Rc<Cell<Option<(f64, f64)>>>,
+    pub dest_origin: Rc<Cell<(f64, f64)>>,
+    pub picking_source: Rc<Cell<bool>>,
+    pub captured: CapturedScreen,
+    pub cache: FilterCache,
+    pub settings: SettingsRc,
+    pub saved_settings: Rc<RefCell<Option<Settings>>>,
+    pub layer: LayerState,
+    pub shadow_cache: ShadowCache,
+    pub drawing: Rc<Cell<bool>>,
+}
+
+/// Pixelated region of the captured screen.
+#[derive(Clone)]
+pub struct Pixelate {
+    pub start_x: Rc<Cell<f64>>,
+    pub start_y: Rc<Cell<f64>>,
+    pub end_x: Rc<Cell<f64>>,
+    pub end_y: Rc<Cell<f64>>,
+    pub captured: CapturedScreen,
+    pub cache: FilterCache,
+    pub settings: SettingsRc,
+    pub saved_settings: Rc<RefCell<Option<Settings>>>,
+    pub layer: LayerState,
+    pub shadow_cache: ShadowCache,
+    pub drawing: Rc<Cell<bool>>,
+}
+
+/// Blurred region of the captured screen.
+#[derive(Clone)]
+pub struct Blur {
+    pub start_x: Rc<Cell<f64>>,
+    pub start_y: Rc<Cell<f64>>,
+    pub end_x: Rc<Cell<f64>>,
+    pub end_y: Rc<Cell<f64>>,
+    pub captured: CapturedScreen,
+    pub cache: FilterCache,
+    pub settings: SettingsRc,
+    pub saved_settings: Rc<RefCell<Option<Settings>>>,
+    pub layer: LayerState,
+    pub shadow_cache: ShadowCache,
+    pub drawing: Rc<Cell<bool>>,
+}
+
+/// Image from the stamps directory, centered on (x, y).
+/// Dragging away from the center scales it, the rotation comes from the settings.
+#[derive(Clone)]
+pub struct Stamp {
+    pub x: Rc<Cell<f64>>,
+    pub y: Rc<Cell<f64>>,
+    pub scale: Rc<Cell<f64>>,
+    pub settings: SettingsRc,
+    pub saved_settings: Rc<RefCell<Option<Settings>>>,
+    pub layer: LayerState,
+    pub shadow_cache: ShadowCache,
+    pub drawing: Rc<Cell<bool>>,
+}
+
+/// Ruler from (start_x, start_y), with its length, dx/dy and angle written in a label.
+/// The numbers are in device pixels, using the output scale of the captured screen.
+#[derive(Clone)]
+pub struct Measure {
+    pub start_x: Rc<Cell<f64>>,
+    pub start_y: Rc<Cell<f64>>,
+    pub end_x: Rc<Cell<f64>>,
+    pub end_y: Rc<Cell<f64>>,
+    pub captured: CapturedScreen,
+    pub settings: SettingsRc,
+    pub saved_settings: Rc<RefCell<Option<Settings>>>,
+    pub layer: LayerState,
+    pub shadow_cache: ShadowCache,
+    pub drawing: Rc<Cell<bool>>,
+}
+
+/// Color picker: while dragging it shows the zoomed pixels around the pointer and the color
+/// of the one under it, on release the color is copied or set as a tool color.
+/// It is never stored in the drawn items.
+#[derive(Clone)]
+pub struct Eyedropper {
+    pub x: Rc<Cell<f64>>,
+    pub y: Rc<Cell<f64>>,
+    pub start_x: Rc<Cell<f64>>,
+    pub start_y: Rc<Cell<f64>>,
+    pub captured: CapturedScreen,
+    pub settings: SettingsRc,
+    pub layer: LayerState,
+    pub drawing: Rc<Cell<bool>>,
+}
+
+/// Picture loaded from a file or pasted from the clipboard, drawn in the (x, y, w, h) box.
+/// The box keeps the aspect ratio of the picture and its corner handle scales it.
+#[derive(Clone)]
+pub struct Image {
+    pub x: Rc<Cell<f64>>,
+    pub y: Rc<Cell<f64>>,
+    pub width: Rc<Cell<f64>>,
+    pub height: Rc<Cell<f64>>,
+    pub surface: Rc<RefCell<Option<cairo::ImageSurface>>>,
+    pub source: ImageSource,
+    pub settings: SettingsRc,
+    pub saved_settings: Rc<RefCell<Option<Settings>>>,
+    pub layer: LayerState,
+    pub shadow_cache: ShadowCache,
     pub drawing: Rc<Cell<bool>>,
 }
+/// Picture that the next image drawable places, shared with the image tool
+pub type ImageSource = Rc<RefCell<Option<cairo::ImageSurface>>>;
+
+/// Drop shadow: offset, blur radius and straight rgba color
+#[derive(Clone, Copy, Debug)]
+pub struct Shadow {
+    pub offset: (f64, f64),
+    pub blur: f64,
+    pub rgba: (f64, f64, f64, f64),
+}
+
+/// Last filtered region, so that the filter does not run at every redraw.
+/// The key is the region in image pixels plus the filter strength.
+#[derive(Clone, Default)]
+pub struct FilterCache {
+    cached: Rc<RefCell<Option<(FilterKey, cairo::ImageSurface)>>>,
+}
+type FilterKey = (i32, i32, i32, i32, i32);
+
+/// Last blurred shadow of a drawable and its top left corner, so that it is not rendered and
+/// blurred at every redraw. The key is the drawable bounds, the shadow blur and color, and the
+/// device scale.
+#[derive(Clone, Default)]
+pub struct ShadowCache {
+    cached: Rc<RefCell<Option<CachedShadow>>>,
+}
+type ShadowKey = [u64; 10];
+type CachedShadow = (ShadowKey, (f64, f64), cairo::ImageSurface);
+
+impl ShadowCache {
+    /// Forget the shadow, for the changes that keep the bounds (e.g. a new text)
+    pub fn clear(&self) {
+        *self.cached.borrow_mut() = None;
+    }
+}
+
+/// Text typed on the canvas by the text annotations and the callouts: the caret and selection
+/// of the editor, and the input method text not committed yet.
+#[derive(Clone, Default)]
+pub struct TypedText {
+    editor: Rc<RefCell<TextEditor>>,
+    /// Draw the caret and the selection, only while the text is typed
+    pub caret: Rc<Cell<bool>>,
+    /// Input method text not committed yet and its cursor, as a byte index
+    preedit: Rc<RefCell<(String, usize)>>,
+}
+
+impl TypedText {
+    /// Apply a key to the text and return it, the settings text follows it
+    fn edit(&self, action: &TextAction, settings: &SettingsRc) -> String {
+        let mut editor = self.editor.borrow_mut();
+        editor.apply(action);
+        settings
+            .input_text
+            .set_value("text", SettingValue::String(editor.text().to_string()))
+            .expect("Error in the text edit");
+        editor.text().to_string()
+    }
+
+    /// Show the input method text at the caret, `cursor` counted in chars like GTK does
+    fn set_preedit(&self, text: &str, cursor: i32) {
+        let cursor = text
+            .char_indices()
+            .nth(cursor.max(0) as usize)
+            .map_or(text.len(), |(i, _)| i);
+        *self.preedit.borrow_mut() = (text.to_string(), cursor);
+    }
+
+    /// Put `text` in the layout, with the preedit string underlined at the caret while
+    /// typing. Returns the caret position in the layout text.
+    fn set_layout_text(&self, pg: &gtk::pango::Layout, text: &str) -> usize {
+        let editor = self.editor.borrow();
+        let (preedit, cursor) = &*self.preedit.borrow();
+        if !self.caret.get() || preedit.is_empty() {
+            pg.set_text(text);
+            pg.set_attributes(None);
+            return editor.caret();
+        }
+
+        let caret = editor.caret();
+        let mut text = editor.text().to_string();
+        text.insert_str(caret, preedit);
+        pg.set_text(&text);
+
+        let attrs = gtk::pango::AttrList::new();
+        let mut underline = gtk::pango::AttrInt::new_underline(gtk::pango::Underline::Single);
+        underline.set_start_index(caret as u32);
+        underline.set_end_index((caret + preedit.len()) as u32);
+        attrs.insert(underline);
+        pg.set_attributes(Some(&attrs));
+        caret + cursor
+    }
+
+    /// Caret rectangle (x, y, height) of the layout drawn at `origin`
+    fn caret_rect(pg: &gtk::pango::Layout, caret: usize, origin: (f64, f64)) -> (f64, f64, f64) {
+        let (rect, _) = pg.cursor_pos(caret as i32);
+        let units = |v: i32| v as f64 / gtk::pango::SCALE as f64;
+        (
+            origin.0 + units(rect.x()),
+            origin.1 + units(rect.y()),
+            units(rect.height()),
+        )
+    }
+
+    /// Draw the selected text and the caret of the layout drawn at `origin`, while typing
+    fn draw_caret(
+        &self,
+        cr: &cairo::Context,
+        pg: &gtk::pango::Layout,
+        caret: usize,
+        origin: (f64, f64),
+        (r, g, b, a): (f64, f64, f64, f64),
+    ) {
+        if !self.caret.get() {
+            return;
+        }
+        let (text_x, text_y) = origin;
+        let editor = self.editor.borrow();
+        let units = |v: i32| v as f64 / gtk::pango::SCALE as f64;
+        if let Some((start, end)) = editor.selection()
+            && self.preedit.borrow().0.is_empty()
+        {
+            let mut iter = pg.iter();
+            loop {
+                let (_, line_rect) = iter.line_extents();
+                if let Some(line) = iter.line_readonly() {
+                    let ranges = line.x_ranges(start as i32, end as i32);
+                    for range in ranges.chunks(2) {
+                        cr.rectangle(
+                            text_x + units(range[0]),
+                            text_y + units(line_rect.y()),
+                            units(range[1] - range[0]),
+                            units(line_rect.height()),
+                        );
+                    }
+                }
+                if !iter.next_line() {
+                    break;
+                }
+            }
+            cr.set_source_rgba(r, g, b, a * 0.3);
+            cr.fill().expect("Failed to fill the text selection");
+        }
+
+        let (x, y, height) = Self::caret_rect(pg, caret, origin);
+        cr.rectangle(x, y, 1.5, height);
+        cr.set_source_rgba(r, g, b, a);
+        cr.fill().expect("Failed to draw the caret");
+    }
+}
+
+/// Composite drawable: a set of items that move, delete and draw as a unit.
+#[derive(Clone)]
+pub struct Group {
+    pub items: Rc<RefCell<Vec<DrawableCollection>>>,
+    pub layer: LayerState,
+}
+
+/// Visibility and lock state of a drawable, controlled from the layers panel.
+#[derive(Clone, Default)]
+pub struct LayerState {
+    pub hidden: Rc<Cell<bool>>,
+    pub locked: Rc<Cell<bool>>,
+}
 
 #[derive(Clone)]
 pub enum DrawableCollection {
     InputTexts(InputText),
+    Callouts(Callout),
     NumberedCircles(NumberedCircle),
     FreeHands(FreeHandDraw),
     Lines(Line),
     Arrows(Arrow),
     Arcs(Arc),
     AreaBoxes(AreaBox),
+    Polygons(Polygon),
+    Redacts(Redact),
+    Highlights(Highlight),
+    Stamps(Stamp),
+    Images(Image),
+    Measures(Measure),
+    Eyedroppers(Eyedropper),
+    Spotlights(Spotlight),
+    Magnifiers(Magnifier),
+    Pixelates(Pixelate),
+    Blurs(Blur),
+    Groups(Group),
 }
 
 impl AreaBox {
@@ -102,15 +526,147 @@ impl AreaBox {
             end_y: Rc::new(Cell::new(0.0)),
             settings: settings_rc.clone(),
             saved_settings: Rc::new(RefCell::new(None)),
+            layer: LayerState::default(),
+            shadow_cache: ShadowCache::default(),
             drawing: Rc::new(Cell::new(true)),
         }
     }
     pub fn is_drawing(&self) -> bool {
         self.drawing.get()
     }
+
+    /// Fill and stroke the box. The "highlight" style only strokes a wide, translucent border.
+    fn draw_box<F>(&self, cr: &cairo::Context, get: F)
+    where
+        F: Fn(&str) -> SettingValue,
+    {
+        let (x, y) = (self.start_x.get(), self.start_y.get());
+        let (w, h) = (self.end_x.get(), self.end_y.get());
+        let radius = get("corner_radius").get_f64().unwrap_or(0.0);
+        let highlight = get("style").get_string().is_ok_and(|s| s == "highlight");
+
+        if !highlight && get("fill").get_bool().expect("draw error") {
+            rounded_rectangle(cr, x, y, w, h, radius);
+            set_fill_source(cr, &get);
+            cr.fill().expect("No box fill to unwrap");
+        }
+
+        if highlight || get("border").get_bool().expect("draw error") {
+            let (r, g, b, a) = rgba_setting(&get, "border").expect("draw error");
+            let mut border_size = get("border_size").get_f64().expect("draw error");
+            if highlight {
+                border_size = border_size.max(6.0);
+            }
+
+            cr.save().expect("Failed to save the context");
+            rounded_rectangle(cr, x, y, w, h, radius);
+            cr.set_source_rgba(r, g, b, if highlight { a.min(0.5) } else { a });
+            cr.set_line_width(border_size);
+            cr.set_line_join(cairo::LineJoin::Round);
+            set_dash_style(
+                cr,
+                &get("dash").get_string().unwrap_or_default(),
+                "",
+                border_size,
+            );
+            cr.stroke().unwrap();
+            cr.restore().expect("Failed to restore the context");
+        }
+    }
+}
+
+/// Source for filling the current path: the "fill" color, or a linear ("fill_angle" degrees,
+/// 0 left to right, 90 top to bottom) or radial gradient over the path extents when
+/// "fill_mode" asks for one and "fill_stops" are valid
+fn set_fill_source<F>(cr: &cairo::Context, get: F)
+where
+    F: Fn(&str) -> SettingValue,
+{
+    let mode = get("fill_mode").get_string().unwrap_or_default();
+    let stops = get("fill_stops")
+        .get_string()
+        .ok()
+        .and_then(|stops| gradient::parse_stops(&stops));
+
+    let (x1, y1, x2, y2) = cr.fill_extents().expect("Failed to measure the fill");
+    let (cx, cy) = ((x1 + x2) / 2.0, (y1 + y2) / 2.0);
+    let pattern: cairo::Gradient = match (mode.as_str(), stops.as_ref()) {
+        ("linear", Some(_)) => {
+            // The gradient line crosses the whole box whatever the angle
+            let angle = get("fill_angle").get_f64().unwrap_or(90.0).to_radians();
+            let (sin, cos) = angle.sin_cos();
+            let half = ((x2 - x1) * cos.abs() + (y2 - y1) * sin.abs()) / 2.0;
+            let linear = cairo::LinearGradient::new(
+                cx - half * cos,
+                cy - half * sin,
+                cx + half * cos,
+                cy + half * sin,
+            );
+            (*linear).clone()
+        }
+        ("radial", Some(_)) => {
+            let radius = (x2 - x1).hypot(y2 - y1) / 2.0;
+            let radial = cairo::RadialGradient::new(cx, cy, 0.0, cx, cy, radius);
+            (*radial).clone()
+        }
+        _ => {
+            if let Some((r, g, b, a)) = rgba_setting(&get, "fill") {
+                cr.set_source_rgba(r, g, b, a);
+            }
+            return;
+        }
+    };
+    for stop in stops.iter().flatten() {
+        let (r, g, b, a) = stop.rgba;
+        pattern.add_color_stop_rgba(stop.offset, r, g, b, a);
+    }
+    cr.set_source(&pattern).expect("Failed to set the gradient");
+}
+
+/// Rectangle path with rounded corners, the radius is clamped to half the shortest side
+fn rounded_rectangle(cr: &cairo::Context, x: f64, y: f64, w: f64, h: f64, radius: f64) {
+    let (x, w) = if w < 0.0 { (x + w, -w) } else { (x, w) };
+    let (y, h) = if h < 0.0 { (y + h, -h) } else { (y, h) };
+    let r = radius.min(w / 2.0).min(h / 2.0).max(0.0);
+    if r == 0.0 {
+        cr.rectangle(x, y, w, h);
+        return;
+    }
+
+    let (half_pi, pi) = (f64::consts::FRAC_PI_2, f64::consts::PI);
+    cr.new_sub_path();
+    cr.arc(x + w - r, y + r, r, -half_pi, 0.0);
+    cr.arc(x + w - r, y + h - r, r, 0.0, half_pi);
+    cr.arc(x + r, y + h - r, r, half_pi, pi);
+    cr.arc(x + r, y + r, r, pi, 3.0 * half_pi);
+    cr.close_path();
+}
+
+/// Set the dash pattern of the stroke: "solid", "dashed", "dotted", "dash_dot" or "custom".
+/// Lengths scale with the line width, dots use round caps. The custom `pattern` lists the
+/// dash and gap lengths in pixels, like "8,4" or "12 4 2 4"; an invalid one draws solid.
+fn set_dash_style(cr: &cairo::Context, style: &str, pattern: &str, width: f64) {
+    let w = width.max(1.0);
+    match style {
+        "custom" => match dash::parse_pattern(pattern) {
+            Some(dashes) => cr.set_dash(&dashes, 0.0),
+            None => cr.set_dash(&[], 0.0),
+        },
+        "dashed" => cr.set_dash(&[3.0 * w, 2.0 * w], 0.0),
+        "dotted" => {
+            cr.set_line_cap(cairo::LineCap::Round);
+            cr.set_dash(&[0.0, 2.0 * w], 0.0);
+        }
+        "dash_dot" => {
+            cr.set_line_cap(cairo::LineCap::Round);
+            cr.set_dash(&[3.0 * w, 1.5 * w, 0.0, 1.5 * w], 0.0);
+        }
+        _ => cr.set_dash(&[], 0.0),
+    }
 }
+
 impl FreeHandDraw {
-    pub fn new(settings_rc: &SettingsRc) -> Self {
+    pub fn new(settings_rc: &SettingsRc, captured: &CapturedScreen) -> Self {
         let vex_x: Vec<f64> = Vec::new();
         let vex_y: Vec<f64> = Vec::new();
 
@@ -119,14 +675,187 @@ impl FreeHandDraw {
             y0: Rc::new(Cell::new(0.0)),
             x: RefCell::new(vex_x),
             y: RefCell::new(vex_y),
+            captured: captured.clone(),
             settings: settings_rc.clone(),
             saved_settings: Rc::new(RefCell::new(None)),
+            layer: LayerState::default(),
+            shadow_cache: ShadowCache::default(),
             drawing: Rc::new(Cell::new(false)),
         }
     }
     pub fn is_drawing(&self) -> bool {
         self.drawing.get()
     }
+
+    /// True while the freehand tool erases the other strokes instead of drawing
+    pub fn is_eraser(&self) -> bool {
+        self.settings
+            .freehand
+            .get_value("tool")
+            .get_string()
+            .is_ok_and(|tool| tool == "eraser")
+    }
+
+    /// Last movement of the eraser, from the previous point to the current one, and its radius
+    pub fn eraser_step(&self) -> Option<(Point, Point, f64)> {
+        let (xs, ys) = (self.x.borrow(), self.y.borrow());
+        let n = xs.len();
+        if n == 0 {
+            return None;
+        }
+        let current = (xs[n - 1], ys[n - 1]);
+        let previous = if n > 1 {
+            (xs[n - 2], ys[n - 2])
+        } else {
+            current
+        };
+        let size = self
+            .settings
+            .freehand
+            .get_value("eraser_size")
+            .get_f64()
+            .ok()?;
+        Some((previous, current, size / 2.0))
+    }
+
+    /// Piece of this stroke left by the eraser, drawn with the same settings
+    pub fn with_points(&self, x: Vec<f64>, y: Vec<f64>) -> Self {
+        Self {
+            x0: Rc::new(Cell::new(self.x0.get())),
+            y0: Rc::new(Cell::new(self.y0.get())),
+            x: RefCell::new(x),
+            y: RefCell::new(y),
+            captured: self.captured.clone(),
+            settings: self.settings.clone(),
+            saved_settings: Rc::new(RefCell::new(self.saved_settings.borrow().clone())),
+            layer: LayerState::default(),
+            shadow_cache: ShadowCache::default(),
+            drawing: Rc::new(Cell::new(false)),
+        }
+    }
+
+    /// The whole stroke is a single path, so that translucent strokes do not get darker where
+    /// the segments overlap
+    fn draw_freehand<F>(&self, cr: &cairo::Context, get: F)
+    where
+        F: Fn(&str) -> SettingValue,
+    {
+        let (xs, ys) = (self.x.borrow(), self.y.borrow());
+
+        // The eraser is never stored, only its outline follows the pointer
+        if get("tool").get_string().is_ok_and(|tool| tool == "eraser") {
+            if let (Some(x), Some(y)) = (xs.last(), ys.last()) {
+                let size = get("eraser_size").get_f64().unwrap_or(1.0);
+                cr.arc(*x, *y, size / 2.0, 0.0, 2.0 * std::f64::consts::PI);
+                cr.set_line_width(1.0);
+                cr.set_source_rgba(0.5, 0.5, 0.5, 0.8);
+                cr.stroke().expect("Failed to draw the eraser");
+            }
+            return;
+        }
+
+        if xs.len() < 2 {
+            return;
+        }
+        cr.move_to(xs[0], ys[0]);
+        for (x, y) in xs.iter().zip(ys.iter()).skip(1) {
+            cr.line_to(*x, *y);
+        }
+
+        cr.set_line_width(get("size").get_f64().expect("Freehand error"));
+        cr.set_line_join(cairo::LineJoin::Round);
+
+        let tool = get("tool").get_string().expect("tool error");
+        let (r, g, b, a) = rgba_setting(&get, "color").expect("freehand error");
+        match tool.as_str() {
+            "pen" => {
+                cr.set_line_cap(cairo::LineCap::Round);
+                cr.set_source_rgba(r, g, b, a);
+            }
+            "highlighter" => {
+                cr.set_line_cap(cairo::LineCap::Square);
+                let path = cr.copy_path().expect("Failed to copy the freehand path");
+                cr.new_path();
+                draw_highlight(cr, &self.captured, (r, g, b), &path, true);
+                return;
+            }
+            "fountain-pen" => {
+                cr.set_line_cap(cairo::LineCap::Round);
+                cr.set_source_rgba(r, g, b, 0.3);
+            }
+            _ => println!("Error, no line_cap found"),
+        };
+        cr.stroke().unwrap();
+    }
+}
+
+/// Marker effect: the screen under `path` (stroked with the current line settings, or filled)
+/// multiplied by the (r, g, b) color, so that dark text underneath stays fully legible.
+/// The drawing area is a transparent overlay, so the backdrop of the multiply blend is the
+/// captured screen with the items already drawn over it; without a capture the color is
+/// painted translucent instead.
+fn draw_highlight(
+    cr: &cairo::Context,
+    captured: &CapturedScreen,
+    rgb: (f64, f64, f64),
+    path: &cairo::Path,
+    stroke: bool,
+) {
+    cr.save().expect("Failed to save the context");
+    cr.append_path(path);
+    let (x1, y1, x2, y2) = if stroke {
+        cr.stroke_extents()
+    } else {
+        cr.fill_extents()
+    }
+    .expect("Failed to measure the highlight");
+    cr.new_path();
+
+    // The items drawn so far, to put them back over the screen in the backdrop
+    let below = cairo::SurfacePattern::create(cr.group_target());
+    below.set_matrix(cr.matrix());
+
+    // 1️⃣ Screen and lower items multiplied by the color, over the whole extents
+    cr.push_group();
+    if let Some(region) = captured.region(x1, y1, x2 - x1, y2 - y1)
+        && let Ok(surface) = region.to_surface()
+    {
+        let scale = captured.scale();
+        cr.save().expect("Failed to save the context");
+        cr.scale(1.0 / scale, 1.0 / scale);
+        cr.set_source_surface(&surface, region.x as f64, region.y as f64)
+            .expect("Failed to set the highlight backdrop");
+        cr.paint().expect("Failed to paint the highlight backdrop");
+        cr.restore().expect("Failed to restore the context");
+        cr.set_source(&below)
+            .expect("Failed to set the highlight backdrop");
+        cr.paint().expect("Failed to paint the highlight backdrop");
+        cr.set_operator(cairo::Operator::Multiply);
+        cr.set_source_rgba(rgb.0, rgb.1, rgb.2, 1.0);
+    } else {
+        cr.set_source_rgba(rgb.0, rgb.1, rgb.2, 0.4);
+    }
+    cr.rectangle(x1, y1, x2 - x1, y2 - y1);
+    cr.fill().expect("Failed to fill the highlight");
+    let content = cr.pop_group().expect("Failed to compose the highlight");
+
+    // 2️⃣ Shape of the highlight, painted once so that it never overlaps itself
+    cr.push_group();
+    cr.append_path(path);
+    cr.set_source_rgba(0.0, 0.0, 0.0, 1.0);
+    if stroke {
+        cr.stroke().expect("Failed to stroke the highlight");
+    } else {
+        cr.fill().expect("Failed to fill the highlight");
+    }
+    let shape = cr
+        .pop_group()
+        .expect("Failed to compose the highlight shape");
+
+    cr.set_source(&content)
+        .expect("Failed to set the highlight");
+    cr.mask(&shape).expect("Failed to paint the highlight");
+    cr.restore().expect("Failed to restore the context");
 }
 impl Line {
     pub fn new(settings_rc: &SettingsRc) -> Self {
@@ -137,6 +866,8 @@ impl Line {
             y2: Rc::new(Cell::new(0.0)),
             settings: settings_rc.clone(),
             saved_settings: Rc::new(RefCell::new(None)),
+            layer: LayerState::default(),
+            shadow_cache: ShadowCache::default(),
             drawing: Rc::new(Cell::new(false)),
         }
     }
@@ -151,1176 +882,2531 @@ impl Arrow {
             y1: Rc::new(Cell::new(0.0)),
             x2: Rc::new(Cell::new(0.0)),
             y2: Rc::new(Cell::new(0.0)),
+            cx: Rc::new(Cell::new(0.0)),
+            cy: Rc::new(Cell::new(0.0)),
             settings: settings_rc.clone(),
             saved_settings: Rc::new(RefCell::new(None)),
+            layer: LayerState::default(),
+            shadow_cache: ShadowCache::default(),
             drawing: Rc::new(Cell::new(false)),
         }
     }
     pub fn is_drawing(&self) -> bool {
         self.drawing.get()
     }
-}
-impl NumberedCircle {
-    pub fn new(settings_rc: &SettingsRc) -> Self {
-        Self {
-            x0: Rc::new(Cell::new(0.0)),
-            y0: Rc::new(Cell::new(0.0)),
-            settings: settings_rc.clone(),
-            saved_settings: Rc::new(RefCell::new(None)),
-            drawing: Rc::new(Cell::new(false)),
+
+    fn is_curved(&self) -> bool {
+        match self.saved_settings.borrow().as_ref() {
+            Some(settings) => settings.arrow.get_value("curved"),
+            None => self.settings.arrow.get_value("curved"),
         }
+        .get_bool()
+        .unwrap_or(false)
     }
-    pub fn is_drawing(&self) -> bool {
-        self.drawing.get()
-    }
-}
-impl Arc {
-    pub fn new(settings_rc: &SettingsRc) -> Self {
-        Self {
-            radius: Rc::new(Cell::new(0.0)),
-            center_x: Rc::new(Cell::new(0.0)),
-            center_y: Rc::new(Cell::new(0.0)),
-            settings: settings_rc.clone(),
-            saved_settings: Rc::new(RefCell::new(None)),
-            drawing: Rc::new(Cell::new(false)),
-        }
+
+    /// Control point of the curve, draggable from the selection tool
+    pub fn control_point(&self) -> Option<(f64, f64)> {
+        self.is_curved().then(|| (self.cx.get(), self.cy.get()))
     }
-    pub fn is_drawing(&self) -> bool {
-        self.drawing.get()
+
+    pub fn move_control_point(&self, dx: f64, dy: f64) {
+        self.cx.set(self.cx.get() + dx);
+        self.cy.set(self.cy.get() + dy);
     }
-}
-impl InputText {
-    pub fn new(settings_rc: &SettingsRc) -> Self {
-        Self {
-            x0: Rc::new(Cell::new(0.0)),
+
+    /// Paths of the shaft, of the stroked heads and of the filled heads.
+    /// The head is on the drag start (x1, y1), the second one on the drag end.
+    fn build_paths<F>(&self, cr: &cairo::Context, get: &F) -> ArrowPaths
+    where
+        F: Fn(&str) -> SettingValue,
+    {
+        let (tip_x, tip_y) = (self.x1.get(), self.y1.get());
+        let (tail_x, tail_y) = (self.x2.get(), self.y2.get());
+        let curved = get("curved").get_bool().unwrap_or(false);
+        let (cx, cy) = if curved {
+            (self.cx.get(), self.cy.get())
+        } else {
+            (tail_x, tail_y)
+        };
+
+        // Shaft, a quadratic Bezier when curved
+        cr.new_path();
+        cr.move_to(tail_x, tail_y);
+        if curved {
+            cr.curve_to(
+                tail_x + 2.0 / 3.0 * (cx - tail_x),
+                tail_y + 2.0 / 3.0 * (cy - tail_y),
+                tip_x + 2.0 / 3.0 * (cx - tip_x),
+                tip_y + 2.0 / 3.0 * (cy - tip_y),
+                tip_x,
+                tip_y,
+            );
+        } else {
+            cr.line_to(tip_x, tip_y);
+        }
+        let shaft = cr.copy_path().expect("Failed to copy the arrow path");
+
+        let style = get("head_style").get_string().unwrap_or_default();
+        let size = get("arrow_size").get_f64().expect("Arrow arrow_size");
+        let mut heads = vec![(tip_x, tip_y, (tip_y - cy).atan2(tip_x - cx))];
+        if get("double_headed").get_bool().unwrap_or(false) {
+            let (from_x, from_y) = if curved { (cx, cy) } else { (tip_x, tip_y) };
+            heads.push((tail_x, tail_y, (tail_y - from_y).atan2(tail_x - from_x)));
+        }
+
+        let mut strokes = Vec::new();
+        let mut fills = Vec::new();
+        for (x, y, angle) in heads {
+            let (cos, sin) = (angle.cos(), angle.sin());
+            cr.new_path();
+            match style.as_str() {
+                "triangle" => {
+                    cr.move_to(x, y);
+                    cr.line_to(
+                        x - 2.0 * size * cos + size * sin,
+                        y - 2.0 * size * sin - size * cos,
+                    );
+                    cr.line_to(
+                        x - 2.0 * size * cos - size * sin,
+                        y - 2.0 * size * sin + size * cos,
+                    );
+                    cr.close_path();
+                    fills.push(cr.copy_path().expect("Failed to copy the arrow head"));
+                }
+                "dot" => {
+                    cr.arc(x, y, size, 0.0, 2.0 * f64::consts::PI);
+                    fills.push(cr.copy_path().expect("Failed to copy the arrow head"));
+                }
+                "bar" => {
+                    cr.move_to(x + size * sin, y - size * cos);
+                    cr.line_to(x - size * sin, y + size * cos);
+                    strokes.push(cr.copy_path().expect("Failed to copy the arrow head"));
+                }
+                _ => {
+                    cr.move_to(x - size * cos + size * sin, y - size * sin - size * cos);
+                    cr.line_to(x, y);
+                    cr.line_to(x - size * cos - size * sin, y - size * sin + size * cos);
+                    strokes.push(cr.copy_path().expect("Failed to copy the arrow head"));
+                }
+            }
+        }
+
+        cr.new_path();
+        ArrowPaths {
+            shaft,
+            strokes,
+            fills,
+        }
+    }
+
+    /// Stroke the arrow, `halo` widens it to draw the outline underneath
+    fn trace<F>(&self, cr: &cairo::Context, get: &F, halo: f64)
+    where
+        F: Fn(&str) -> SettingValue,
+    {
+        let paths = self.build_paths(cr, get);
+        let size = get("size").get_f64().expect("Arrow size");
+        cr.set_line_width(size + 2.0 * halo);
+
+        // Only the shaft is dashed
+        cr.append_path(&paths.shaft);
+        set_dash_style(
+            cr,
+            &get("dash").get_string().unwrap_or_default(),
+            &get("dash_pattern").get_string().unwrap_or_default(),
+            size,
+        );
+        cr.stroke().unwrap();
+        cr.set_dash(&[], 0.0);
+        cr.set_line_cap(cairo::LineCap::Round);
+
+        for path in paths.strokes {
+            cr.append_path(&path);
+        }
+        cr.stroke().unwrap();
+
+        for path in paths.fills {
+            cr.append_path(&path);
+            if halo > 0.0 {
+                cr.set_line_width(2.0 * halo);
+                cr.stroke_preserve().unwrap();
+            }
+            cr.fill().unwrap();
+        }
+    }
+
+    fn draw_arrow<F>(&self, cr: &cairo::Context, get: F)
+    where
+        F: Fn(&str) -> SettingValue,
+    {
+        cr.save().expect("Failed to save the context");
+        cr.set_line_cap(cairo::LineCap::Round);
+        cr.set_line_join(cairo::LineJoin::Round);
+
+        // Outline underneath, to stay visible over both dark and light backgrounds
+        if get("outline").get_bool().unwrap_or(false)
+            && let Some((r, g, b, a)) = rgba_setting(&get, "outline")
+        {
+            cr.set_source_rgba(r, g, b, a);
+            self.trace(cr, &get, get("outline_size").get_f64().unwrap_or(2.0));
+        }
+
+        let (r, g, b, a) = rgba_setting(&get, "color").expect("Arrow color");
+        cr.set_source_rgba(r, g, b, a);
+        self.trace(cr, &get, 0.0);
+        cr.restore().expect("Failed to restore the context");
+    }
+}
+impl NumberedCircle {
+    pub fn new(settings_rc: &SettingsRc) -> Self {
+        Self {
+            x0: Rc::new(Cell::new(0.0)),
             y0: Rc::new(Cell::new(0.0)),
-            x: Rc::new(Cell::new(0.0)),
-            y: Rc::new(Cell::new(0.0)),
-            text: Rc::new(RefCell::new("".to_string())),
+            number: Rc::new(Cell::new(0)),
+            placed: Rc::new(Cell::new(0)),
             settings: settings_rc.clone(),
             saved_settings: Rc::new(RefCell::new(None)),
+            layer: LayerState::default(),
+            shadow_cache: ShadowCache::default(),
             drawing: Rc::new(Cell::new(false)),
         }
     }
     pub fn is_drawing(&self) -> bool {
         self.drawing.get()
     }
-}
 
-// Drag Begin trait
-pub trait DragBegin {
-    fn drag_begin(&self, x: f64, y: f64);
-}
-impl DragBegin for AreaBox {
-    fn drag_begin(&self, x: f64, y: f64) {
-        self.start_x.set(x);
-        self.start_y.set(y);
-        self.end_x.set(0.0);
-        self.end_y.set(0.0);
-        self.drawing.set(true);
-    }
-}
-impl DragBegin for Arc {
-    fn drag_begin(&self, x: f64, y: f64) {
-        self.center_x.set(x);
-        self.center_y.set(y);
-        self.radius.set(0.0);
-        self.drawing.set(true);
-    }
-}
-impl DragBegin for Arrow {
-    fn drag_begin(&self, x: f64, y: f64) {
-        self.x1.set(x);
-        self.y1.set(y);
-        self.x2.set(x);
-        self.y2.set(y);
-        self.drawing.set(true);
-    }
-}
-impl DragBegin for Line {
-    fn drag_begin(&self, x: f64, y: f64) {
-        self.x1.set(x);
-        self.y1.set(y);
-        self.x2.set(x);
-        self.y2.set(y);
-        self.drawing.set(true);
-    }
-}
-impl DragBegin for FreeHandDraw {
-    fn drag_begin(&self, x: f64, y: f64) {
-        self.x0.set(x);
-        self.y0.set(y);
-        self.drawing.set(true);
+    /// Sequence of the badge and its first number; a new sequence begins at every restart
+    pub fn sequence(&self) -> (i32, i32) {
+        let get = |key: &str| {
+            match self.saved_settings.borrow().as_ref() {
+                Some(settings) => settings.numbered_circle.get_value(key),
+                None => self.settings.numbered_circle.get_value(key),
+            }
+            .get_i32()
+            .unwrap_or_default()
+        };
+        (get("sequence"), get("sequence_start"))
     }
-}
-impl DragBegin for NumberedCircle {
-    fn drag_begin(&self, x: f64, y: f64) {
-        self.x0.set(x);
-        self.y0.set(y);
+
+    /// Text of the badge, e.g. "3", "C", "III" or "Step 3"
+    fn label<F>(number: i32, get: F) -> String
+    where
+        F: Fn(&str) -> SettingValue,
+    {
+        let style = get("style").get_string().unwrap_or_default();
+        let prefix = get("prefix").get_string().unwrap_or_default();
+        numbering::format_number(number, &style, &prefix)
     }
-}
-impl DragBegin for InputText {
-    fn drag_begin(&self, x: f64, y: f64) {
-        // Set the initial position
-        self.x0.set(x);
-        self.y0.set(y);
 
-        println!("begin");
+    fn draw_badge<F>(&self, cr: &cairo::Context, pg: &gtk::pango::Layout, number: i32, get: F)
+    where
+        F: Fn(&str) -> SettingValue,
+    {
+        let radius = get("radius").get_f64().expect("Missing setting 'radius'");
+        let (x, y) = (self.x0.get(), self.y0.get());
+
+        // 1️⃣ Badge shape
+        match get("shape").get_string().unwrap_or_default().as_str() {
+            "square" => cr.rectangle(x - radius, y - radius, 2.0 * radius, 2.0 * radius),
+            "rounded" => rounded_rectangle(
+                cr,
+                x - radius,
+                y - radius,
+                2.0 * radius,
+                2.0 * radius,
+                radius * 0.4,
+            ),
+            _ => cr.arc(x, y, radius, 0.0, 2.0 * f64::consts::PI),
+        }
 
-        // Se the initial height and width
-        let sz = self
-            .settings
-            .input_text
-            .get_value("font_size")
-            .get_f64()
-            .expect("");
-        self.x.set(30.0);
-        self.y.set(sz);
+        if get("fill").get_bool().expect("draw error") {
+            set_fill_source(cr, &get);
+            cr.fill_preserve()
+                .expect("Failed to fill the numbered_circle");
+        }
 
-        // Every time there is a drag_begin reset the settings text
-        self.settings
-            .input_text
-            .set_value("text", SettingValue::String("".to_string()))
-            .expect("Error in stop_controller_key");
-    }
-}
-impl DragBegin for DrawableCollection {
-    fn drag_begin(&self, x: f64, y: f64) {
-        match self {
-            DrawableCollection::FreeHands(state) => {
-                state.drag_begin(x, y);
-            }
-            DrawableCollection::Lines(state) => {
-                state.drag_begin(x, y);
-            }
-            DrawableCollection::AreaBoxes(state) => {
-                state.drag_begin(x, y);
-            }
-            DrawableCollection::Arcs(state) => {
-                state.drag_begin(x, y);
-            }
-            DrawableCollection::Arrows(state) => {
-                state.drag_begin(x, y);
-            }
-            DrawableCollection::NumberedCircles(state) => {
-                state.drag_begin(x, y);
-            }
-            DrawableCollection::InputTexts(state) => {
-                state.drag_begin(x, y);
-            }
+        if get("border").get_bool().expect("draw error") {
+            let (r, g, b, a) = rgba_setting(&get, "border").expect("draw error");
+            cr.set_source_rgba(r, g, b, a);
+            cr.set_line_width(get("border_size").get_f64().expect("draw error"));
+            cr.stroke().unwrap();
+        } else {
+            cr.new_path();
         }
-    }
-}
 
-// Drag Update trait
-pub trait DragUpdate {
-    fn drag_update(&self, x: f64, y: f64);
-}
+        // 2️⃣ Label, centred on (x0, y0)
+        let (r, g, b, a) = rgba_setting(&get, "font").expect("error");
+        cr.set_source_rgba(r, g, b, a);
+        let font_face = get("font_face")
+            .get_string()
+            .expect("NumberedCircle font_face error");
+        let fd: gtk::pango::FontDescription =
+            gtk::pango::FontDescription::from_string(font_face.as_str());
+        pg.set_font_description(Some(&fd));
+        pg.set_text(&Self::label(number, &get));
 
-impl DragUpdate for AreaBox {
-    fn drag_update(&self, x: f64, y: f64) {
-        self.end_x.set(x);
-        self.end_y.set(y);
-    }
-}
-impl DragUpdate for Arc {
-    fn drag_update(&self, x: f64, y: f64) {
-        self.radius.set(f64::sqrt(x * x + y * y));
-    }
-}
-impl DragUpdate for Line {
-    fn drag_update(&self, x: f64, y: f64) {
-        self.x1.set(self.x2.get() + x);
-        self.y1.set(self.y2.get() + y);
-    }
-}
-impl DragUpdate for Arrow {
-    fn drag_update(&self, x: f64, y: f64) {
-        self.x2.set(self.x1.get() + x);
-        self.y2.set(self.y1.get() + y);
+        // Get logical extents (in Pango units) and convert to device units.
+        let (_ink_rect, logical_rect) = pg.extents();
+        let text_width = logical_rect.width() as f64 / gtk::pango::SCALE as f64;
+        let text_height = logical_rect.height() as f64 / gtk::pango::SCALE as f64;
+
+        cr.move_to(x - text_width / 2.0, y - text_height / 2.0);
+        pangocairo::functions::show_layout(cr, pg);
+        cr.new_path();
     }
 }
-impl DragUpdate for FreeHandDraw {
-    fn drag_update(&self, x: f64, y: f64) {
-        self.x.borrow_mut().push(self.x0.get() + x);
-        self.y.borrow_mut().push(self.y0.get() + y);
+impl Arc {
+    pub fn new(settings_rc: &SettingsRc) -> Self {
+        Self {
+            radius: Rc::new(Cell::new(0.0)),
+            center_x: Rc::new(Cell::new(0.0)),
+            center_y: Rc::new(Cell::new(0.0)),
+            width: Rc::new(Cell::new(0.0)),
+            height: Rc::new(Cell::new(0.0)),
+            settings: settings_rc.clone(),
+            saved_settings: Rc::new(RefCell::new(None)),
+            layer: LayerState::default(),
+            shadow_cache: ShadowCache::default(),
+            drawing: Rc::new(Cell::new(false)),
+        }
     }
-}
-impl DragUpdate for InputText {
-    fn drag_update(&self, _x: f64, _y: f64) {
-        // self.x.set(x);
-        // self.y.set(y);
+    pub fn is_drawing(&self) -> bool {
+        self.drawing.get()
     }
-}
-impl DragUpdate for DrawableCollection {
-    fn drag_update(&self, x: f64, y: f64) {
-        match self {
-            DrawableCollection::FreeHands(state) => {
-                state.drag_update(x, y);
-            }
-            DrawableCollection::Lines(state) => {
-                state.drag_update(x, y);
-            }
-            DrawableCollection::AreaBoxes(state) => {
-                state.drag_update(x, y);
-            }
-            DrawableCollection::Arcs(state) => {
-                state.drag_update(x, y);
-            }
-            DrawableCollection::Arrows(state) => {
-                state.drag_update(x, y);
+
+    /// Center and radii (cx, cy, rx, ry).
+    /// A circle is centered on the drag start, an ellipse is inscribed in the dragged box.
+    fn geometry(&self, shape: &str) -> (f64, f64, f64, f64) {
+        match shape {
+            "ellipse" => {
+                let (w, h) = (self.width.get(), self.height.get());
+                (
+                    self.center_x.get() + w / 2.0,
+                    self.center_y.get() + h / 2.0,
+                    w.abs() / 2.0,
+                    h.abs() / 2.0,
+                )
             }
-            DrawableCollection::InputTexts(state) => {
-                state.drag_update(x, y);
+            _ => {
+                let r = self.radius.get();
+                (self.center_x.get(), self.center_y.get(), r, r)
             }
-            _ => {}
         }
     }
-}
 
-// Drag End
-pub trait DragEnd {
-    fn drag_end(&self) -> Option<DrawableCollection>;
-}
+    /// Build the path of the circle, ellipse, arc or pie slice
+    fn build_path<F>(&self, cr: &cairo::Context, get: F)
+    where
+        F: Fn(&str) -> SettingValue,
+    {
+        let shape = get("shape").get_string().unwrap_or_default();
+        let style = get("style").get_string().unwrap_or_default();
+        let (cx, cy, rx, ry) = self.geometry(&shape);
+
+        cr.new_path();
+        if rx <= 0.0 || ry <= 0.0 {
+            return;
+        }
 
-impl DragEnd for AreaBox {
-    fn drag_end(&self) -> Option<DrawableCollection> {
-        // Create an hard copy of the settings for future draws
-        *self.saved_settings.borrow_mut() = Some(self.settings.hard_copy());
+        // Angles are in degrees, clockwise from 3 o'clock
+        let (start, end) = match style.as_str() {
+            "arc" | "pie" => (
+                get("start_angle").get_f64().unwrap_or(0.0).to_radians(),
+                get("end_angle").get_f64().unwrap_or(360.0).to_radians(),
+            ),
+            _ => (0.0, 2.0 * f64::consts::PI),
+        };
 
-        // Shallow clone last settings and return the new element
-        let s = self.settings.clone();
-        Some(DrawableCollection::AreaBoxes(AreaBox::new(&s)))
+        // Unit circle scaled to the ellipse, the path keeps the line width untouched
+        cr.save().expect("Failed to save the context");
+        cr.translate(cx, cy);
+        cr.scale(rx, ry);
+        if style == "pie" {
+            cr.move_to(0.0, 0.0);
+        }
+        cr.arc(0.0, 0.0, 1.0, start, end);
+        if style == "pie" {
+            cr.close_path();
+        }
+        cr.restore().expect("Failed to restore the context");
     }
 }
-impl DragEnd for Arc {
-    fn drag_end(&self) -> Option<DrawableCollection> {
-        // Create an hard copy of the settings for future draws
-        *self.saved_settings.borrow_mut() = Some(self.settings.hard_copy());
+impl InputText {
+    pub fn new(settings_rc: &SettingsRc) -> Self {
+        Self {
+            x0: Rc::new(Cell::new(0.0)),
+            y0: Rc::new(Cell::new(0.0)),
+            x: Rc::new(Cell::new(0.0)),
+            y: Rc::new(Cell::new(0.0)),
+            text: Rc::new(RefCell::new("".to_string())),
+            typed: TypedText::default(),
+            settings: settings_rc.clone(),
+            saved_settings: Rc::new(RefCell::new(None)),
+            layer: LayerState::default(),
+            shadow_cache: ShadowCache::default(),
+            drawing: Rc::new(Cell::new(false)),
+        }
+    }
+    pub fn is_drawing(&self) -> bool {
+        self.drawing.get()
+    }
 
-        // Shallow clone last settings and return the new element
-        let s = self.settings.clone();
-        Some(DrawableCollection::Arcs(Arc::new(&s)))
+    /// Apply a key to the text, the settings text follows it
+    pub fn edit(&self, action: &TextAction) {
+        *self.text.borrow_mut() = self.typed.edit(action, &self.settings);
+        self.shadow_cache.clear();
     }
-}
-impl DragEnd for Line {
-    fn drag_end(&self) -> Option<DrawableCollection> {
-        // Create an hard copy of the settings for future draws
-        *self.saved_settings.borrow_mut() = Some(self.settings.hard_copy());
 
-        // Shallow clone last settings and return the new element
-        let s = self.settings.clone();
-        Some(DrawableCollection::Lines(Line::new(&s)))
+    /// Show the input method text at the caret, `cursor` counted in chars like GTK does
+    pub fn set_preedit(&self, text: &str, cursor: i32) {
+        self.typed.set_preedit(text, cursor);
+        self.shadow_cache.clear();
     }
-}
-impl DragEnd for Arrow {
-    fn drag_end(&self) -> Option<DrawableCollection> {
-        // Create an hard copy of the settings for future draws
-        *self.saved_settings.borrow_mut() = Some(self.settings.hard_copy());
 
-        // Shallow clone last settings and return the new element
-        let s = self.settings.clone();
-        Some(DrawableCollection::Arrows(Arrow::new(&s)))
+    /// Put the text in the layout, returns the caret position in the layout text
+    fn set_layout_text<F>(&self, pg: &gtk::pango::Layout, get: F) -> usize
+    where
+        F: Fn(&str) -> SettingValue,
+    {
+        let font_face = get("font_face")
+            .get_string()
+            .expect("InputText font_face error");
+        let fd = gtk::pango::FontDescription::from_string(font_face.as_str());
+        pg.set_font_description(Some(&fd));
+        self.typed.set_layout_text(pg, &self.text.borrow())
     }
-}
-impl DragEnd for FreeHandDraw {
-    fn drag_end(&self) -> Option<DrawableCollection> {
-        // Create an hard copy of the settings for future draws
-        *self.saved_settings.borrow_mut() = Some(self.settings.hard_copy());
 
-        // Shallow clone last settings and return the new element
-        let s = self.settings.clone();
-        Some(DrawableCollection::FreeHands(FreeHandDraw::new(&s)))
+    /// Caret rectangle (x, y, height) in window coordinates, for the input method popups
+    pub fn caret_area(&self, pg: &gtk::pango::Layout) -> (f64, f64, f64) {
+        let caret = match self.saved_settings.borrow().as_ref() {
+            Some(settings) => self.set_layout_text(pg, |key| settings.input_text.get_value(key)),
+            None => self.set_layout_text(pg, |key| self.settings.input_text.get_value(key)),
+        };
+        let area = TypedText::caret_rect(pg, caret, (self.x0.get(), self.y0.get()));
+        pg.set_attributes(None);
+        area
     }
-}
-impl DragEnd for NumberedCircle {
-    fn drag_end(&self) -> Option<DrawableCollection> {
-        // // Create an hard copy of the settings for future draws
-        // *self.saved_settings.borrow_mut() = Some(self.settings.hard_copy());
 
-        // Set new value
-        let num: i32 = self
-            .settings
-            .numbered_circle
-            .get_value("number")
-            .get_i32()
-            .expect("drag_end error")
-            + 1_i32;
-        self.settings
-            .numbered_circle
-            .set_value(
-                "number",
-                crate::drawing_area_settings::SettingValue::I32(num),
-            )
-            .expect("NumberedCircle::new error");
+    fn draw_text<F>(&self, cr: &cairo::Context, pg: &gtk::pango::Layout, get: F)
+    where
+        F: Fn(&str) -> SettingValue,
+    {
+        // Font face and text, before measuring it
+        let caret = self.set_layout_text(pg, &get);
 
-        // Shallow clone last settings and return the new element
-        let s = self.settings.clone();
-        Some(DrawableCollection::NumberedCircles(NumberedCircle::new(&s)))
-    }
-}
-impl DragEnd for InputText {
-    fn drag_end(&self) -> Option<DrawableCollection> {
-        println!("end");
-        // Create an hard copy of the settings for future draws
-        *self.saved_settings.borrow_mut() = Some(self.settings.hard_copy());
+        // Get logical extents (in Pango units) and convert to device units.
+        let (_ink_rect, logical_rect) = pg.extents();
+        let text_width = logical_rect.width() as f64 / gtk::pango::SCALE as f64;
+        let text_height = logical_rect.height() as f64 / gtk::pango::SCALE as f64;
 
-        // Shallow clone last settings and return the new element
-        let s = self.settings.clone();
-        Some(DrawableCollection::InputTexts(InputText::new(&s)))
-    }
-}
-impl DragEnd for DrawableCollection {
-    fn drag_end(&self) -> Option<DrawableCollection> {
-        match self {
-            DrawableCollection::AreaBoxes(state) => state.drag_end(),
-            DrawableCollection::Arcs(state) => state.drag_end(),
-            DrawableCollection::Lines(state) => state.drag_end(),
-            DrawableCollection::Arrows(state) => state.drag_end(),
-            DrawableCollection::FreeHands(state) => state.drag_end(),
-            DrawableCollection::NumberedCircles(state) => state.drag_end(),
-            DrawableCollection::InputTexts(state) => state.drag_end(),
+        // Keep the size of the box, it is needed by the selection
+        self.x.set(text_width);
+        self.y.set(text_height);
+
+        // The text starts at (x0, y0)
+        let (text_x, text_y) = (self.x0.get(), self.y0.get());
+
+        // Fill
+        if get("fill").get_bool().expect("draw error") {
+            cr.rectangle(text_x, text_y, text_width, text_height);
+            set_fill_source(cr, &get);
+            cr.fill().expect("No arc fill to unwrap");
         }
-    }
-}
 
-// ControllerKey
-pub trait ControllerKey {
-    fn event_controller_key(&self);
-    fn stop_controller_key(&self) -> Option<DrawableCollection>;
-}
+        // Border
+        if get("border").get_bool().expect("draw error") {
+            cr.rectangle(text_x, text_y, text_width, text_height);
+            let (r, g, b, a) = rgba_setting(&get, "border").expect("draw error");
+            cr.set_source_rgba(r, g, b, a);
+            cr.set_line_width(get("border_size").get_f64().expect("draw error"));
+            cr.stroke().unwrap();
+        }
 
-impl ControllerKey for AreaBox {
-    fn event_controller_key(&self) {}
-    fn stop_controller_key(&self) -> Option<DrawableCollection> {
-        None
-    }
-}
-impl ControllerKey for Arc {
-    fn event_controller_key(&self) {}
-    fn stop_controller_key(&self) -> Option<DrawableCollection> {
-        None
-    }
-}
-impl ControllerKey for Line {
-    fn event_controller_key(&self) {}
-    fn stop_controller_key(&self) -> Option<DrawableCollection> {
-        None
-    }
-}
-impl ControllerKey for Arrow {
-    fn event_controller_key(&self) {}
-    fn stop_controller_key(&self) -> Option<DrawableCollection> {
-        None
-    }
-}
-impl ControllerKey for FreeHandDraw {
-    fn event_controller_key(&self) {}
-    fn stop_controller_key(&self) -> Option<DrawableCollection> {
-        None
+        let (r, g, b, a) = rgba_setting(&get, "font").expect("draw error");
+
+        // Selected text and caret while typing, in the font color
+        self.typed
+            .draw_caret(cr, pg, caret, (text_x, text_y), (r, g, b, a));
+
+        // Render the layout in the font color
+        cr.set_source_rgba(r, g, b, a);
+        cr.move_to(text_x, text_y);
+        pangocairo::functions::show_layout(cr, pg);
+
+        // The layout is shared with the next drawables
+        pg.set_attributes(None);
     }
 }
-impl ControllerKey for NumberedCircle {
-    fn event_controller_key(&self) {}
-    fn stop_controller_key(&self) -> Option<DrawableCollection> {
-        None
+impl Callout {
+    pub fn new(settings_rc: &SettingsRc) -> Self {
+        Self {
+            tip_x: Rc::new(Cell::new(0.0)),
+            tip_y: Rc::new(Cell::new(0.0)),
+            x: Rc::new(Cell::new(0.0)),
+            y: Rc::new(Cell::new(0.0)),
+            width: Rc::new(Cell::new(0.0)),
+            height: Rc::new(Cell::new(0.0)),
+            text: Rc::new(RefCell::new("".to_string())),
+            typed: TypedText::default(),
+            settings: settings_rc.clone(),
+            saved_settings: Rc::new(RefCell::new(None)),
+            layer: LayerState::default(),
+            shadow_cache: ShadowCache::default(),
+            drawing: Rc::new(Cell::new(false)),
+        }
     }
-}
-impl ControllerKey for InputText {
-    fn event_controller_key(&self) {
-        println!("eventcontollerkey");
-        let num = self
-            .settings
-            .input_text
-            .get_value("text")
-            .get_string()
-            .expect("InputText::new error");
-        let text = num.to_string();
 
-        *self.text.borrow_mut() = text;
+    /// Apply a key to the text, the settings text follows it
+    pub fn edit(&self, action: &TextAction) {
+        *self.text.borrow_mut() = self.typed.edit(action, &self.settings);
+        self.shadow_cache.clear();
     }
-    fn stop_controller_key(&self) -> Option<DrawableCollection> {
-        println!("stopcontrollerkey");
-        // Create an hard copy of the settings for future draws
-        *self.saved_settings.borrow_mut() = Some(self.settings.hard_copy());
 
-        // Shallow clone last settings and return the new element
-        let s = self.settings.clone();
+    /// Show the input method text at the caret, `cursor` counted in chars like GTK does
+    pub fn set_preedit(&self, text: &str, cursor: i32) {
+        self.typed.set_preedit(text, cursor);
+        self.shadow_cache.clear();
+    }
 
-        Some(DrawableCollection::InputTexts(InputText::new(&s)))
+    /// Put the wrapped text in the layout, returns the caret position in the layout text
+    fn set_layout_text<F, G>(&self, pg: &gtk::pango::Layout, style: F, geometry: G) -> usize
+    where
+        F: Fn(&str) -> SettingValue,
+        G: Fn(&str) -> SettingValue,
+    {
+        let wrap_width = geometry("wrap_width").get_f64().unwrap_or(240.0);
+        let font_face = style("font_face")
+            .get_string()
+            .expect("Callout font_face error");
+        let fd = gtk::pango::FontDescription::from_string(font_face.as_str());
+        pg.set_font_description(Some(&fd));
+        pg.set_wrap(gtk::pango::WrapMode::WordChar);
+        pg.set_width((wrap_width * gtk::pango::SCALE as f64) as i32);
+        self.typed.set_layout_text(pg, &self.text.borrow())
     }
-}
-impl ControllerKey for DrawableCollection {
-    fn event_controller_key(&self) {
-        match self {
-            DrawableCollection::AreaBoxes(state) => state.event_controller_key(),
-            DrawableCollection::Arcs(state) => state.event_controller_key(),
-            DrawableCollection::Lines(state) => state.event_controller_key(),
-            DrawableCollection::Arrows(state) => state.event_controller_key(),
-            DrawableCollection::FreeHands(state) => state.event_controller_key(),
-            DrawableCollection::NumberedCircles(state) => state.event_controller_key(),
-            DrawableCollection::InputTexts(state) => state.event_controller_key(),
+
+    /// Caret rectangle (x, y, height) in window coordinates, for the input method popups
+    pub fn caret_area(&self, pg: &gtk::pango::Layout) -> (f64, f64, f64) {
+        let (caret, padding) = match self.saved_settings.borrow().as_ref() {
+            Some(settings) => (
+                self.set_layout_text(
+                    pg,
+                    |key| settings.input_text.get_value(key),
+                    |key| settings.callout.get_value(key),
+                ),
+                settings.callout.get_value("padding").get_f64(),
+            ),
+            None => (
+                self.set_layout_text(
+                    pg,
+                    |key| self.settings.input_text.get_value(key),
+                    |key| self.settings.callout.get_value(key),
+                ),
+                self.settings.callout.get_value("padding").get_f64(),
+            ),
         };
+        let padding = padding.unwrap_or(8.0);
+        let (l, t, _, _) = self.bubble();
+        let area = TypedText::caret_rect(pg, caret, (l + padding, t + padding));
+        pg.set_attributes(None);
+        pg.set_width(-1);
+        area
     }
-    fn stop_controller_key(&self) -> Option<DrawableCollection> {
-        match self {
-            DrawableCollection::AreaBoxes(state) => state.stop_controller_key(),
-            DrawableCollection::Arcs(state) => state.stop_controller_key(),
-            DrawableCollection::Lines(state) => state.stop_controller_key(),
-            DrawableCollection::Arrows(state) => state.stop_controller_key(),
-            DrawableCollection::FreeHands(state) => state.stop_controller_key(),
-            DrawableCollection::NumberedCircles(state) => state.stop_controller_key(),
-            DrawableCollection::InputTexts(state) => state.stop_controller_key(),
-        }
-    }
-}
-
-// Draw trait
-pub trait Draw {
-    fn draw(&self, cr: &cairo::Context, pg: &gtk::pango::Layout);
-    fn draw_with_saved_settings(&self, cr: &cairo::Context, pg: &gtk::pango::Layout);
-}
 
-impl Draw for FreeHandDraw {
-    fn draw(&self, cr: &cairo::Context, _pg: &gtk::pango::Layout) {
-        let settings = self.settings.freehand.clone();
+    /// Box of the bubble as (left, top, right, bottom)
+    fn bubble(&self) -> (f64, f64, f64, f64) {
+        let (hw, hh) = (self.width.get() / 2.0, self.height.get() / 2.0);
+        (
+            self.x.get() - hw,
+            self.y.get() - hh,
+            self.x.get() + hw,
+            self.y.get() + hh,
+        )
+    }
 
-        // get freehands size
-        let sz = self.x.borrow().iter().len();
+    /// Outline of the bubble: a rounded box with the tail inserted on the side facing the tip
+    fn bubble_path(&self, cr: &cairo::Context, radius: f64) {
+        let (l, t, r, b) = self.bubble();
+        let (tip_x, tip_y) = (self.tip_x.get(), self.tip_y.get());
+        let rad = radius.min((r - l) / 2.0).min((b - t) / 2.0).max(0.0);
+
+        // Side of the tail, none when the tip is inside the box
+        let (dx, dy) = (tip_x - self.x.get(), tip_y - self.y.get());
+        let (hw, hh) = ((r - l) / 2.0, (b - t) / 2.0);
+        let inside = dx.abs() <= hw && dy.abs() <= hh;
+        let horizontal = dx.abs() * hh > dy.abs() * hw;
+        let side = match (inside, horizontal) {
+            (true, _) => None,
+            (false, true) if dx > 0.0 => Some(1),
+            (false, true) => Some(3),
+            (false, false) if dy > 0.0 => Some(2),
+            (false, false) => Some(0),
+        };
 
-        if sz == 0 {
-            return;
+        // Half base of the tail, following the tip along the side
+        let base_x = (((r - l) / 2.0 - rad) * 0.5).clamp(0.0, 12.0);
+        let base_y = (((b - t) / 2.0 - rad) * 0.5).clamp(0.0, 12.0);
+        let cx = tip_x.clamp(l + rad + base_x, r - rad - base_x);
+        let cy = tip_y.clamp(t + rad + base_y, b - rad - base_y);
+
+        let (half_pi, pi) = (f64::consts::FRAC_PI_2, f64::consts::PI);
+        cr.new_sub_path();
+        cr.move_to(l + rad, t);
+        if side == Some(0) {
+            cr.line_to(cx - base_x, t);
+            cr.line_to(tip_x, tip_y);
+            cr.line_to(cx + base_x, t);
+        }
+        cr.line_to(r - rad, t);
+        cr.arc(r - rad, t + rad, rad, -half_pi, 0.0);
+        if side == Some(1) {
+            cr.line_to(r, cy - base_y);
+            cr.line_to(tip_x, tip_y);
+            cr.line_to(r, cy + base_y);
+        }
+        cr.line_to(r, b - rad);
+        cr.arc(r - rad, b - rad, rad, 0.0, half_pi);
+        if side == Some(2) {
+            cr.line_to(cx + base_x, b);
+            cr.line_to(tip_x, tip_y);
+            cr.line_to(cx - base_x, b);
+        }
+        cr.line_to(l + rad, b);
+        cr.arc(l + rad, b - rad, rad, half_pi, pi);
+        if side == Some(3) {
+            cr.line_to(l, cy + base_y);
+            cr.line_to(tip_x, tip_y);
+            cr.line_to(l, cy - base_y);
         }
+        cr.line_to(l, t + rad);
+        cr.arc(l + rad, t + rad, rad, pi, 3.0 * half_pi);
+        cr.close_path();
+    }
 
-        // define previous point
-        let mut x_prev = self.x.borrow()[0];
-        let mut y_prev = self.y.borrow()[0];
+    /// Draw the bubble; `style` reads the input_text keys, `geometry` the callout keys
+    fn draw_callout<F, G>(
+        &self,
+        cr: &cairo::Context,
+        pg: &gtk::pango::Layout,
+        style: F,
+        geometry: G,
+    ) where
+        F: Fn(&str) -> SettingValue,
+        G: Fn(&str) -> SettingValue,
+    {
+        let padding = geometry("padding").get_f64().unwrap_or(8.0);
+        let radius = geometry("corner_radius").get_f64().unwrap_or(10.0);
+
+        // Wrapped text
+        let caret = self.set_layout_text(pg, &style, &geometry);
+        let (_ink_rect, logical_rect) = pg.extents();
+        let text_width = logical_rect.width() as f64 / gtk::pango::SCALE as f64;
+        let text_height = logical_rect.height() as f64 / gtk::pango::SCALE as f64;
 
-        // Get the tool
-        let tool = settings.get_value("tool").get_string().expect("tool error");
+        // An empty bubble still shows where the text will go
+        let font_size = style("font_size").get_f64().unwrap_or(15.0);
+        self.width
+            .set(text_width.max(2.0 * font_size) + 2.0 * padding);
+        self.height.set(text_height.max(font_size) + 2.0 * padding);
 
-        // cr.set_line_join(cairo::LineJoin::Round);
-        // cr.set_line_join(cairo::LineJoin::Miter);
-        // cr.set_line_join(cairo::LineJoin::Bevel);
+        if style("fill").get_bool().expect("draw error") {
+            self.bubble_path(cr, radius);
+            if let Some((r, g, b, a)) = rgba_setting(&style, "fill") {
+                cr.set_source_rgba(r, g, b, a);
+            }
+            cr.fill().expect("No callout fill to unwrap");
+        }
 
-        // for a continuous draw we need to fill the blank space between points
-        let mut cc: usize = 1;
-        while cc < sz {
-            // get current x and y
-            let (x, y) = (self.x.borrow()[cc], self.y.borrow()[cc]);
+        if style("border").get_bool().expect("draw error") {
+            self.bubble_path(cr, radius);
+            if let Some((r, g, b, a)) = rgba_setting(&style, "border") {
+                cr.set_source_rgba(r, g, b, a);
+            }
+            cr.set_line_width(style("border_size").get_f64().expect("draw error"));
+            cr.set_line_join(cairo::LineJoin::Round);
+            cr.stroke().expect("No callout border to unwrap");
+        }
 
-            // Draw the line
-            cr.move_to(x_prev, y_prev);
-            cr.line_to(x, y);
-            // Set line properties
-            cr.set_line_width(
-                settings
-                    .get_value("size")
-                    .get_f64()
-                    .expect("Freehand error"),
-            );
+        // Selected text and caret while typing, in the font color
+        let font = rgba_setting(&style, "font").unwrap_or((0.0, 0.0, 0.0, 1.0));
+        let (l, t, _, _) = self.bubble();
+        self.typed
+            .draw_caret(cr, pg, caret, (l + padding, t + padding), font);
 
-            match tool.as_str() {
-                "pen" => {
-                    cr.set_line_cap(cairo::LineCap::Round);
-
-                    // Get the saved settings
-                    cr.set_source_rgba(
-                        settings
-                            .get_value("color_r")
-                            .get_f64()
-                            .expect("freehand error"),
-                        settings
-                            .get_value("color_g")
-                            .get_f64()
-                            .expect("freehand error"),
-                        settings
-                            .get_value("color_b")
-                            .get_f64()
-                            .expect("freehand error"),
-                        settings
-                            .get_value("color_a")
-                            .get_f64()
-                            .expect("freehand error"),
-                    );
-                }
-                "highlighter" => {
-                    cr.set_line_cap(cairo::LineCap::Square);
-
-                    // Get the saved settings
-                    cr.set_source_rgba(
-                        settings
-                            .get_value("color_r")
-                            .get_f64()
-                            .expect("freehand error"),
-                        settings
-                            .get_value("color_g")
-                            .get_f64()
-                            .expect("freehand error"),
-                        settings
-                            .get_value("color_b")
-                            .get_f64()
-                            .expect("freehand error"),
-                        0.1,
-                    );
-                }
-                "fountain-pen" => {
-                    cr.set_line_cap(cairo::LineCap::Round);
-
-                    // Get the saved settings
-                    cr.set_source_rgba(
-                        settings
-                            .get_value("color_r")
-                            .get_f64()
-                            .expect("freehand error"),
-                        settings
-                            .get_value("color_g")
-                            .get_f64()
-                            .expect("freehand error"),
-                        settings
-                            .get_value("color_b")
-                            .get_f64()
-                            .expect("freehand error"),
-                        0.3,
-                    );
-                }
-                _ => println!("Error, no line_cap found"),
-            };
+        let (r, g, b, a) = font;
+        cr.set_source_rgba(r, g, b, a);
+        cr.move_to(l + padding, t + padding);
+        pangocairo::functions::show_layout(cr, pg);
+        cr.new_path();
 
-            cr.stroke().unwrap();
+        // The layout is shared with the other drawables
+        pg.set_attributes(None);
+        pg.set_width(-1);
+    }
 
-            x_prev = x;
-            y_prev = y;
+    /// Move only the tip of the tail
+    pub fn move_tip(&self, dx: f64, dy: f64) {
+        self.tip_x.set(self.tip_x.get() + dx);
+        self.tip_y.set(self.tip_y.get() + dy);
+    }
+}
+impl Polygon {
+    pub fn new(settings_rc: &SettingsRc) -> Self {
+        Self {
+            x: Rc::new(RefCell::new(Vec::new())),
+            y: Rc::new(RefCell::new(Vec::new())),
+            last_click: Rc::new(Cell::new(None)),
+            finished: Rc::new(Cell::new(false)),
+            settings: settings_rc.clone(),
+            saved_settings: Rc::new(RefCell::new(None)),
+            layer: LayerState::default(),
+            shadow_cache: ShadowCache::default(),
+            drawing: Rc::new(Cell::new(false)),
+        }
+    }
 
-            // update counter
-            cc += 1;
+    /// Stop adding vertices. A polygon needs at least two of them.
+    pub fn finish(&self) -> bool {
+        if self.x.borrow().len() >= 2 {
+            self.finished.set(true);
         }
+        self.finished.get()
     }
 
-    fn draw_with_saved_settings(&self, cr: &cairo::Context, _pg: &gtk::pango::Layout) {
-        // get freehands size
-        let sz = self.x.borrow().iter().len();
+    /// True if the click at (x, y) is the second one of a double click
+    fn is_double_click(&self, x: f64, y: f64) -> bool {
+        let Some((time, last_x, last_y)) = self.last_click.get() else {
+            return false;
+        };
+        let (max_time, max_distance) = match gtk::Settings::default() {
+            Some(settings) => (
+                settings.gtk_double_click_time(),
+                settings.gtk_double_click_distance(),
+            ),
+            None => (400, 5),
+        };
+        let elapsed_ms = (glib::monotonic_time() - time) / 1000;
+        elapsed_ms <= max_time as i64 && (x - last_x).hypot(y - last_y) <= max_distance as f64
+    }
 
-        if sz == 0 {
+    fn draw_polygon<F>(&self, cr: &cairo::Context, get: F)
+    where
+        F: Fn(&str) -> SettingValue,
+    {
+        let xs = self.x.borrow();
+        let ys = self.y.borrow();
+        if xs.len() < 2 {
             return;
         }
 
-        // define previous point
-        let mut x_prev = self.x.borrow()[0];
-        let mut y_prev = self.y.borrow()[0];
-
-        let settings_hard_copy = self.saved_settings.borrow().clone();
-        if let Some(settings_to_unwrap) = settings_hard_copy {
-            let settings = settings_to_unwrap.freehand;
-
-            // Get tool
-            let tool = settings.get_value("tool").get_string().expect("tool error");
-
-            // for a continuous draw we need to fill the blank space between points
-            let mut cc: usize = 1;
-            while cc < sz {
-                // get current x and y
-                let (x, y) = (self.x.borrow()[cc], self.y.borrow()[cc]);
-
-                // Draw the line
-                cr.move_to(x_prev, y_prev);
-                cr.line_to(x, y);
-                // Set line properties
-                cr.set_line_width(
-                    settings
-                        .get_value("size")
-                        .get_f64()
-                        .expect("Freehand error"),
-                );
-
-                match tool.as_str() {
-                    "pen" => {
-                        cr.set_line_cap(cairo::LineCap::Round);
-
-                        // Get the saved settings
-                        cr.set_source_rgba(
-                            settings
-                                .get_value("color_r")
-                                .get_f64()
-                                .expect("freehand error"),
-                            settings
-                                .get_value("color_g")
-                                .get_f64()
-                                .expect("freehand error"),
-                            settings
-                                .get_value("color_b")
-                                .get_f64()
-                                .expect("freehand error"),
-                            settings
-                                .get_value("color_a")
-                                .get_f64()
-                                .expect("freehand error"),
-                        );
-                    }
-                    "highlighter" => {
-                        cr.set_line_cap(cairo::LineCap::Square);
-
-                        // Get the saved settings
-                        cr.set_source_rgba(
-                            settings
-                                .get_value("color_r")
-                                .get_f64()
-                                .expect("freehand error"),
-                            settings
-                                .get_value("color_g")
-                                .get_f64()
-                                .expect("freehand error"),
-                            settings
-                                .get_value("color_b")
-                                .get_f64()
-                                .expect("freehand error"),
-                            0.1,
-                        );
-                    }
-                    "fountain-pen" => {
-                        cr.set_line_cap(cairo::LineCap::Round);
-
-                        // Get the saved settings
-                        cr.set_source_rgba(
-                            settings
-                                .get_value("color_r")
-                                .get_f64()
-                                .expect("freehand error"),
-                            settings
-                                .get_value("color_g")
-                                .get_f64()
-                                .expect("freehand error"),
-                            settings
-                                .get_value("color_b")
-                                .get_f64()
-                                .expect("freehand error"),
-                            0.3,
-                        );
-                    }
-                    _ => println!("Error, no line_cap found"),
-                };
-
-                cr.stroke().unwrap();
+        // Build the path, closed only once the polygon is finished
+        let build_path = || {
+            cr.move_to(xs[0], ys[0]);
+            for (x, y) in xs.iter().zip(ys.iter()).skip(1) {
+                cr.line_to(*x, *y);
+            }
+        };
+        let closed = get("closed").get_bool().unwrap_or(true) && self.finished.get();
 
-                x_prev = x;
-                y_prev = y;
+        if closed && get("fill").get_bool().unwrap_or(false) {
+            build_path();
+            cr.close_path();
+            if let Some((r, g, b, a)) = rgba_setting(&get, "fill") {
+                cr.set_source_rgba(r, g, b, a);
+            }
+            cr.fill().expect("No polygon fill to unwrap");
+        }
 
-                // update counter
-                cc += 1;
+        if get("border").get_bool().unwrap_or(true) {
+            build_path();
+            if closed {
+                cr.close_path();
             }
-        } else {
-            println!("Warning! saved_settings is None!");
+            if let Some((r, g, b, a)) = rgba_setting(&get, "border") {
+                cr.set_source_rgba(r, g, b, a);
+            }
+            cr.set_line_width(get("border_size").get_f64().unwrap_or(1.0));
+            cr.set_line_join(cairo::LineJoin::Round);
+            cr.stroke().expect("No polygon border to unwrap");
         }
     }
 }
-impl Draw for Line {
-    fn draw(&self, cr: &cairo::Context, _pg: &gtk::pango::Layout) {
-        let settings = self.settings.line.clone();
-        // Set color RGBA
-        cr.set_source_rgba(
-            settings
-                .get_value("color_r")
-                .get_f64()
-                .expect("draw line error"),
-            settings
-                .get_value("color_g")
-                .get_f64()
-                .expect("draw line error"),
-            settings
-                .get_value("color_b")
-                .get_f64()
-                .expect("draw line error"),
-            settings
-                .get_value("color_a")
-                .get_f64()
-                .expect("draw line error"),
-        );
-
-        // Draw the line
-        cr.move_to(self.x1.get(), self.y1.get());
-        cr.line_to(self.x2.get(), self.y2.get());
+impl Highlight {
+    pub fn new(settings_rc: &SettingsRc, captured: &CapturedScreen) -> Self {
+        Self {
+            start_x: Rc::new(Cell::new(0.0)),
+            start_y: Rc::new(Cell::new(0.0)),
+            end_x: Rc::new(Cell::new(0.0)),
+            end_y: Rc::new(Cell::new(0.0)),
+            captured: captured.clone(),
+            settings: settings_rc.clone(),
+            saved_settings: Rc::new(RefCell::new(None)),
+            layer: LayerState::default(),
+            shadow_cache: ShadowCache::default(),
+            drawing: Rc::new(Cell::new(false)),
+        }
+    }
 
-        // Set line properties
-        cr.set_line_width(
-            settings
-                .get_value("size")
-                .get_f64()
-                .expect("draw line error"),
-        );
+    fn draw_box<F>(&self, cr: &cairo::Context, get: F)
+    where
+        F: Fn(&str) -> SettingValue,
+    {
+        let (x, y) = (self.start_x.get(), self.start_y.get());
+        let (x, y, w, h) = bounds_from_corners(x, y, x + self.end_x.get(), y + self.end_y.get());
+        if w == 0.0 || h == 0.0 {
+            return;
+        }
+        let channel = |key: &str| get(key).get_f64().unwrap_or(0.0);
+        let rgb = (channel("color_r"), channel("color_g"), channel("color_b"));
+
+        cr.new_path();
+        rounded_rectangle(cr, x, y, w, h, channel("corner_radius"));
+        let path = cr.copy_path().expect("Failed to copy the highlight path");
+        cr.new_path();
+        draw_highlight(cr, &self.captured, rgb, &path, false);
+    }
+}
+impl Redact {
+    pub fn new(settings_rc: &SettingsRc) -> Self {
+        Self {
+            start_x: Rc::new(Cell::new(0.0)),
+            start_y: Rc::new(Cell::new(0.0)),
+            end_x: Rc::new(Cell::new(0.0)),
+            end_y: Rc::new(Cell::new(0.0)),
+            settings: settings_rc.clone(),
+            saved_settings: Rc::new(RefCell::new(None)),
+            layer: LayerState::default(),
+            shadow_cache: ShadowCache::default(),
+            drawing: Rc::new(Cell::new(false)),
+        }
+    }
 
-        // Set line cap
-        match settings
-            .get_value("line_cap")
-            .get_string()
-            .expect("No line_cap value found")
-            .as_str()
-        {
-            "round" => cr.set_line_cap(cairo::LineCap::Round),
-            "butt" => cr.set_line_cap(cairo::LineCap::Butt),
-            "square" => cr.set_line_cap(cairo::LineCap::Square),
-            _ => println!("Error, no line_cap found"),
+    /// Redaction rectangle (x, y, w, h) and its opaque color
+    pub fn redaction(&self) -> Redaction {
+        let channel = |key: &str| {
+            let v = match self.saved_settings.borrow().as_ref() {
+                Some(settings) => settings.redact.get_value(key),
+                None => self.settings.redact.get_value(key),
+            }
+            .get_f64()
+            .unwrap_or(0.0);
+            (v.clamp(0.0, 1.0) * 255.0).round() as u8
         };
+        (
+            (
+                self.start_x.get(),
+                self.start_y.get(),
+                self.end_x.get(),
+                self.end_y.get(),
+            ),
+            [channel("color_r"), channel("color_g"), channel("color_b")],
+        )
+    }
+}
+impl Spotlight {
+    pub fn new(settings_rc: &SettingsRc, regions: &SpotlightRegions) -> Self {
+        let region = Rc::new(SpotlightRegion::default());
+        Self {
+            layer: region.layer.clone(),
+            shadow_cache: ShadowCache::default(),
+            region,
+            regions: regions.clone(),
+            settings: settings_rc.clone(),
+            saved_settings: Rc::new(RefCell::new(None)),
+            drawing: Rc::new(Cell::new(false)),
+        }
+    }
 
-        // Set line join
-        match settings
-            .get_value("line_join")
-            .get_string()
-            .expect("No line_join value found")
-            .as_str()
-        {
-            "round" => cr.set_line_join(cairo::LineJoin::Round),
-            "miter" => cr.set_line_join(cairo::LineJoin::Miter),
-            "bevel" => cr.set_line_join(cairo::LineJoin::Bevel),
-            _ => println!("Error, no line_join found"),
-        };
-        cr.stroke().unwrap();
+    /// Visible, non empty, regions of all the spotlights
+    fn visible_regions(&self) -> Vec<Rc<SpotlightRegion>> {
+        self.regions
+            .borrow()
+            .iter()
+            .filter_map(Weak::upgrade)
+            .filter(|r| !r.layer.hidden.get() && r.end_x.get() != 0.0 && r.end_y.get() != 0.0)
+            .collect()
+    }
+}
+impl Stamp {
+    pub fn new(settings_rc: &SettingsRc) -> Self {
+        Self {
+            x: Rc::new(Cell::new(0.0)),
+            y: Rc::new(Cell::new(0.0)),
+            scale: Rc::new(Cell::new(1.0)),
+            settings: settings_rc.clone(),
+            saved_settings: Rc::new(RefCell::new(None)),
+            layer: LayerState::default(),
+            shadow_cache: ShadowCache::default(),
+            drawing: Rc::new(Cell::new(false)),
+        }
     }
-    fn draw_with_saved_settings(&self, cr: &cairo::Context, _pg: &gtk::pango::Layout) {
-        let settings_hard_copy = self.saved_settings.borrow().clone();
-        if let Some(settings_to_unwrap) = settings_hard_copy {
-            let settings = settings_to_unwrap.line;
-            // Set color RGBA
-            cr.set_source_rgba(
-                settings
-                    .get_value("color_r")
-                    .get_f64()
-                    .expect("draw line error"),
-                settings
-                    .get_value("color_g")
-                    .get_f64()
-                    .expect("draw line error"),
-                settings
-                    .get_value("color_b")
-                    .get_f64()
-                    .expect("draw line error"),
-                settings
-                    .get_value("color_a")
-                    .get_f64()
-                    .expect("draw line error"),
-            );
 
-            // Draw the line
-            cr.move_to(self.x1.get(), self.y1.get());
-            cr.line_to(self.x2.get(), self.y2.get());
+    fn get_value(&self, key: &str) -> SettingValue {
+        match self.saved_settings.borrow().as_ref() {
+            Some(settings) => settings.stamp.get_value(key),
+            None => self.settings.stamp.get_value(key),
+        }
+    }
 
-            // Set line properties
-            cr.set_line_width(
-                settings
-                    .get_value("size")
-                    .get_f64()
-                    .expect("draw line error"),
-            );
+    /// Stamp image at its natural size, which gives the size of the stamp
+    fn image(&self) -> Option<cairo::ImageSurface> {
+        crate::stamps::load_stamp(&self.get_value("file").get_string().unwrap_or_default())
+    }
 
-            // Set line cap
-            match settings
-                .get_value("line_cap")
-                .get_string()
-                .expect("No line_cap value found")
-                .as_str()
-            {
-                "round" => cr.set_line_cap(cairo::LineCap::Round),
-                "butt" => cr.set_line_cap(cairo::LineCap::Butt),
-                "square" => cr.set_line_cap(cairo::LineCap::Square),
-                _ => println!("Error, no line_cap found"),
-            };
+    /// Stamp image to paint on `cr`, rasterized at the drawn size for the svg files
+    fn image_for(&self, cr: &cairo::Context, image: &cairo::ImageSurface) -> cairo::ImageSurface {
+        let (device_scale, _) = cr.target().device_scale();
+        let (dx, dy) = cr
+            .user_to_device_distance(image.width() as f64 * self.scale.get(), 0.0)
+            .unwrap_or((image.width() as f64 * self.scale.get(), 0.0));
+        crate::stamps::load_stamp_at(
+            &self.get_value("file").get_string().unwrap_or_default(),
+            dx.hypot(dy) * device_scale,
+        )
+        .unwrap_or_else(|| image.clone())
+    }
 
-            // Set line join
-            match settings
-                .get_value("line_join")
-                .get_string()
-                .expect("No line_join value found")
-                .as_str()
-            {
-                "round" => cr.set_line_join(cairo::LineJoin::Round),
-                "miter" => cr.set_line_join(cairo::LineJoin::Miter),
-                "bevel" => cr.set_line_join(cairo::LineJoin::Bevel),
-                _ => println!("Error, no line_join found"),
-            };
-            cr.stroke().unwrap();
-        } else {
-            println!("Warning! saved_settings is None!");
+    /// Rotation in radians
+    fn rotation(&self) -> f64 {
+        self.get_value("rotation")
+            .get_f64()
+            .unwrap_or(0.0)
+            .to_radians()
+    }
+}
+/// Paint `image` centered on (x, y), scaled and rotated by `angle` radians
+fn paint_image(
+    cr: &cairo::Context,
+    image: &cairo::ImageSurface,
+    x: f64,
+    y: f64,
+    scale: f64,
+    angle: f64,
+) {
+    let (w, h) = (image.width() as f64, image.height() as f64);
+    cr.save().expect("Failed to save the context");
+    cr.translate(x, y);
+    cr.rotate(angle);
+    cr.scale(scale, scale);
+    cr.set_source_surface(image, -w / 2.0, -h / 2.0)
+        .expect("Failed to set the image source");
+    cr.source().set_filter(cairo::Filter::Good);
+    cr.rectangle(-w / 2.0, -h / 2.0, w, h);
+    cr.fill().expect("Failed to paint the image");
+    cr.restore().expect("Failed to restore the context");
+}
+/// Bounding box of a w x h image centered on (x, y), scaled and rotated by `angle` radians
+fn rotated_bounds(x: f64, y: f64, w: f64, h: f64, angle: f64) -> (f64, f64, f64, f64) {
+    let (sin, cos) = angle.sin_cos();
+    let half_w = (w * cos.abs() + h * sin.abs()) / 2.0;
+    let half_h = (w * sin.abs() + h * cos.abs()) / 2.0;
+    bounds_from_corners(x - half_w, y - half_h, x + half_w, y + half_h)
+}
+impl Image {
+    pub fn new(settings_rc: &SettingsRc, source: &ImageSource) -> Self {
+        Self {
+            x: Rc::new(Cell::new(0.0)),
+            y: Rc::new(Cell::new(0.0)),
+            width: Rc::new(Cell::new(0.0)),
+            height: Rc::new(Cell::new(0.0)),
+            surface: Rc::new(RefCell::new(None)),
+            source: source.clone(),
+            settings: settings_rc.clone(),
+            saved_settings: Rc::new(RefCell::new(None)),
+            layer: LayerState::default(),
+            shadow_cache: ShadowCache::default(),
+            drawing: Rc::new(Cell::new(false)),
         }
     }
+
+    /// Width over height of the picture
+    fn aspect_ratio(&self) -> Option<f64> {
+        let surface = self.surface.borrow();
+        let surface = surface.as_ref()?;
+        Some(surface.width() as f64 / surface.height().max(1) as f64)
+    }
+
+    /// Set the width of the box, the height follows the aspect ratio
+    fn resize(&self, width: f64, height_sign: f64) {
+        let Some(ratio) = self.aspect_ratio() else {
+            return;
+        };
+        self.width.set(width);
+        self.height
+            .set(width.abs() / ratio * if height_sign < 0.0 { -1.0 } else { 1.0 });
+    }
+
+    /// Corner opposite to (x, y), dragged to scale the picture
+    pub fn corner(&self) -> Option<(f64, f64)> {
+        self.surface.borrow().as_ref()?;
+        Some((
+            self.x.get() + self.width.get(),
+            self.y.get() + self.height.get(),
+        ))
+    }
+
+    pub fn move_corner(&self, dx: f64, _dy: f64) {
+        self.resize(self.width.get() + dx, self.height.get());
+    }
 }
-impl Draw for Arrow {
-    fn draw(&self, cr: &cairo::Context, _pg: &gtk::pango::Layout) {
-        let settings = self.settings.arrow.clone();
+impl Measure {
+    pub fn new(settings_rc: &SettingsRc, captured: &CapturedScreen) -> Self {
+        Self {
+            start_x: Rc::new(Cell::new(0.0)),
+            start_y: Rc::new(Cell::new(0.0)),
+            end_x: Rc::new(Cell::new(0.0)),
+            end_y: Rc::new(Cell::new(0.0)),
+            captured: captured.clone(),
+            settings: settings_rc.clone(),
+            saved_settings: Rc::new(RefCell::new(None)),
+            layer: LayerState::default(),
+            shadow_cache: ShadowCache::default(),
+            drawing: Rc::new(Cell::new(false)),
+        }
+    }
 
-        // Set color RGBA
-        cr.set_source_rgba(
-            settings
-                .get_value("color_r")
-                .get_f64()
-                .expect("draw line error"),
-            settings
-                .get_value("color_g")
-                .get_f64()
-                .expect("draw line error"),
-            settings
-                .get_value("color_b")
-                .get_f64()
-                .expect("draw line error"),
-            settings
-                .get_value("color_a")
-                .get_f64()
-                .expect("draw line error"),
-        );
+    /// Length, dx, dy in device pixels and angle in degrees, counterclockwise from the x axis
+    pub fn measurement(&self) -> (f64, f64, f64, f64) {
+        let scale = self.captured.scale();
+        let (dx, dy) = (self.end_x.get() * scale, self.end_y.get() * scale);
+        let angle = (-dy).atan2(dx).to_degrees();
+        (dx.hypot(dy), dx, dy, angle)
+    }
+
+    /// False if the measurement is only shown on screen
+    pub fn in_export(&self) -> bool {
+        match self.saved_settings.borrow().as_ref() {
+            Some(settings) => settings.measure.get_value("export"),
+            None => self.settings.measure.get_value("export"),
+        }
+        .get_bool()
+        .unwrap_or(true)
+    }
 
-        let x2 = self.x1.get();
-        let y2 = self.y1.get();
-        let x1 = self.x2.get();
-        let y1 = self.y2.get();
+    fn draw_measure<F>(&self, cr: &cairo::Context, pg: &gtk::pango::Layout, get: F)
+    where
+        F: Fn(&str) -> SettingValue,
+    {
+        let (x1, y1) = (self.start_x.get(), self.start_y.get());
+        let (x2, y2) = (x1 + self.end_x.get(), y1 + self.end_y.get());
+        let size = get("size").get_f64().unwrap_or(2.0);
+
+        cr.save().expect("Failed to save the context");
+        if let Some((r, g, b, a)) = rgba_setting(&get, "color") {
+            cr.set_source_rgba(r, g, b, a);
+        }
+        cr.set_line_width(size);
+        cr.set_line_cap(cairo::LineCap::Butt);
 
-        // Draw the line
+        // Ruler with a tick on both ends
         cr.move_to(x1, y1);
         cr.line_to(x2, y2);
+        let length = (x2 - x1).hypot(y2 - y1);
+        if length > 0.0 {
+            let tick = 4.0 + 2.0 * size;
+            let (nx, ny) = (-(y2 - y1) / length * tick, (x2 - x1) / length * tick);
+            for (x, y) in [(x1, y1), (x2, y2)] {
+                cr.move_to(x - nx, y - ny);
+                cr.line_to(x + nx, y + ny);
+            }
+        }
+        cr.stroke().expect("Failed to draw the ruler");
 
-        // Draw the arrowhead
-        let arrow_size = settings
-            .get_value("arrow_size")
-            .get_f64()
-            .expect("Arrow arrow_size");
-        let angle = (y2 - y1).atan2(x2 - x1);
-        cr.line_to(
-            x2 - arrow_size * angle.cos() + arrow_size * angle.sin(),
-            y2 - arrow_size * angle.sin() - arrow_size * angle.cos(),
+        // Label next to the middle of the ruler, on a dark box so that it reads on any background
+        let (len, dx, dy, angle) = self.measurement();
+        let mut fd = gtk::pango::FontDescription::from_string("Sans");
+        fd.set_absolute_size(get("font_size").get_f64().unwrap_or(12.0) * gtk::pango::SCALE as f64);
+        pg.set_font_description(Some(&fd));
+        pg.set_text(&format!(
+            "{:.0} px\ndx {:.0}  dy {:.0}  {:.1}\u{b0}",
+            len, dx, dy, angle
+        ));
+        let (_ink_rect, logical_rect) = pg.extents();
+        let text_width = logical_rect.width() as f64 / gtk::pango::SCALE as f64;
+        let text_height = logical_rect.height() as f64 / gtk::pango::SCALE as f64;
+        let padding = 4.0;
+        let (lx, ly) = (
+            (x1 + x2) / 2.0 + 2.0 * padding,
+            (y1 + y2) / 2.0 + 2.0 * padding,
         );
-        cr.move_to(x2, y2);
-        cr.line_to(
-            x2 - arrow_size * angle.cos() - arrow_size * angle.sin(),
-            y2 - arrow_size * angle.sin() + arrow_size * angle.cos(),
+
+        rounded_rectangle(
+            cr,
+            lx,
+            ly,
+            text_width + 2.0 * padding,
+            text_height + 2.0 * padding,
+            padding,
         );
+        cr.set_source_rgba(0.0, 0.0, 0.0, 0.7);
+        cr.fill().expect("Failed to draw the label box");
+        cr.move_to(lx + padding, ly + padding);
+        cr.set_source_rgba(1.0, 1.0, 1.0, 1.0);
+        pangocairo::functions::show_layout(cr, pg);
+        cr.restore().expect("Failed to restore the context");
+    }
+}
+impl Eyedropper {
+    pub fn new(settings_rc: &SettingsRc, captured: &CapturedScreen) -> Self {
+        Self {
+            x: Rc::new(Cell::new(0.0)),
+            y: Rc::new(Cell::new(0.0)),
+            start_x: Rc::new(Cell::new(0.0)),
+            start_y: Rc::new(Cell::new(0.0)),
+            captured: captured.clone(),
+            settings: settings_rc.clone(),
+            layer: LayerState::default(),
+            drawing: Rc::new(Cell::new(false)),
+        }
+    }
 
-        // Set line properties
-        cr.set_line_width(settings.get_value("size").get_f64().expect("Arrow size"));
-        cr.set_line_cap(cairo::LineCap::Round);
-        cr.stroke().unwrap();
+    /// Color of the pixel under the pointer
+    pub fn color(&self) -> Option<[u8; 3]> {
+        self.captured.pixel(self.x.get(), self.y.get())
     }
-    fn draw_with_saved_settings(&self, cr: &cairo::Context, _pg: &gtk::pango::Layout) {
-        let settings_hard_copy = self.saved_settings.borrow().clone();
-        if let Some(settings_to_unwrap) = settings_hard_copy {
-            let settings = settings_to_unwrap.arrow;
-            cr.set_source_rgba(
-                settings
-                    .get_value("color_r")
-                    .get_f64()
-                    .expect("Arrow error"),
-                settings
-                    .get_value("color_g")
-                    .get_f64()
-                    .expect("Arrow error"),
-                settings
-                    .get_value("color_b")
-                    .get_f64()
-                    .expect("Arrow error"),
-                settings
-                    .get_value("color_a")
-                    .get_f64()
-                    .expect("Arrow error"),
-            );
 
-            let x2 = self.x1.get();
-            let y2 = self.y1.get();
-            let x1 = self.x2.get();
-            let y1 = self.y2.get();
-
-            // Draw the line
-            cr.move_to(x1, y1);
-            cr.line_to(x2, y2);
-
-            // Draw the arrowhead
-            let arrow_size = settings
-                .get_value("arrow_size")
-                .get_f64()
-                .expect("Arrow arrow_size");
-            let angle = (y2 - y1).atan2(x2 - x1);
-            cr.line_to(
-                x2 - arrow_size * angle.cos() + arrow_size * angle.sin(),
-                y2 - arrow_size * angle.sin() - arrow_size * angle.cos(),
-            );
-            cr.move_to(x2, y2);
-            cr.line_to(
-                x2 - arrow_size * angle.cos() - arrow_size * angle.sin(),
-                y2 - arrow_size * angle.sin() + arrow_size * angle.cos(),
-            );
+    /// Copy the picked color to the clipboard, or set it as the color of the target tool
+    fn pick(&self) {
+        let Some(rgb) = self.color() else {
+            println!("No color under the pointer");
+            return;
+        };
+        let eyedropper = &self.settings.eyedropper;
+        let action = eyedropper
+            .get_value("action")
+            .get_string()
+            .unwrap_or_default();
+        if action == "tool" {
+            let target = eyedropper
+                .get_value("target")
+                .get_string()
+                .unwrap_or_default();
+            let [r, g, b] = rgb.map(|c| c as f64 / 255.0);
+            if let Err(e) = self.settings.set_color(&target, r, g, b) {
+                eprintln!("Impossible to set the picked color: {}", e);
+            }
+            return;
+        }
 
-            // Set line properties
-            cr.set_line_width(settings.get_value("size").get_f64().expect("Arrow size"));
-            cr.set_line_cap(cairo::LineCap::Round);
-            cr.stroke().unwrap();
-        } else {
-            println!("Warning! saved_settings is None!");
+        let format = eyedropper
+            .get_value("format")
+            .get_string()
+            .unwrap_or_default();
+        let text = color::format_color(rgb, &format);
+        match std::process::Command::new("wl-copy").arg(&text).status() {
+            Ok(status) if status.success() => println!("Color {} copied to clipboard.", text),
+            Ok(status) => eprintln!("wl-copy failed: {}", status),
+            Err(e) => eprintln!("Failed to execute wl-copy: {}", e),
         }
     }
-}
-impl Draw for Arc {
-    fn draw(&self, cr: &cairo::Context, _pg: &gtk::pango::Layout) {
-        let settings = self.settings.arc.clone();
-
-        if settings.get_value("fill").get_bool().expect("draw error") {
-            cr.arc(
-                self.center_x.get(),
-                self.center_y.get(),
-                self.radius.get(),
-                0.0,
-                2.0 * f64::consts::PI,
-            );
 
-            cr.set_source_rgba(
-                settings.get_value("fill_r").get_f64().expect("draw error"),
-                settings.get_value("fill_g").get_f64().expect("draw error"),
-                settings.get_value("fill_b").get_f64().expect("draw error"),
-                settings.get_value("fill_a").get_f64().expect("draw error"),
-            );
-            cr.fill().expect("No arc fill to unwrap");
-            cr.stroke().unwrap();
+    fn draw_eyedropper(&self, cr: &cairo::Context, pg: &gtk::pango::Layout) {
+        const PIXELS: i32 = 11;
+        const CELL: f64 = 8.0;
+        let (x, y) = (self.x.get(), self.y.get());
+        let scale = self.captured.scale();
+        let loupe = PIXELS as f64 * CELL;
+        let (lx, ly) = (x + 16.0, y + 16.0);
+
+        cr.save().expect("Failed to save the context");
+
+        // 1️⃣ Zoomed pixels around the pointer, each image pixel is a CELL wide square
+        let half = (PIXELS / 2) as f64 / scale;
+        let (cx, cy) = ((x * scale).floor(), (y * scale).floor());
+        cr.rectangle(lx, ly, loupe, loupe);
+        cr.set_source_rgba(0.0, 0.0, 0.0, 1.0);
+        cr.fill_preserve().expect("Failed to fill the loupe");
+        if let Some(region) =
+            self.captured
+                .region(x - half, y - half, 2.0 * half + 1.0, 2.0 * half + 1.0)
+            && let Ok(surface) = region.to_surface()
+        {
+            cr.save().expect("Failed to save the context");
+            cr.clip_preserve();
+            cr.translate(lx, ly);
+            cr.scale(CELL, CELL);
+            cr.set_source_surface(
+                &surface,
+                region.x as f64 - (cx - (PIXELS / 2) as f64),
+                region.y as f64 - (cy - (PIXELS / 2) as f64),
+            )
+            .expect("Failed to set the loupe source");
+            cr.source().set_filter(cairo::Filter::Nearest);
+            cr.paint().expect("Failed to paint the loupe");
+            cr.restore().expect("Failed to restore the context");
         }
+        cr.set_source_rgba(1.0, 1.0, 1.0, 1.0);
+        cr.set_line_width(2.0);
+        cr.stroke().expect("Failed to draw the loupe border");
+
+        // 2️⃣ Picked pixel in the middle
+        let middle = (PIXELS / 2) as f64 * CELL;
+        cr.rectangle(lx + middle, ly + middle, CELL, CELL);
+        cr.set_source_rgba(0.0, 0.0, 0.0, 1.0);
+        cr.set_line_width(1.0);
+        cr.stroke().expect("Failed to draw the picked pixel");
+
+        // 3️⃣ Swatch and values below the loupe
+        let Some(rgb) = self.color() else {
+            cr.restore().expect("Failed to restore the context");
+            return;
+        };
+        let [r, g, b] = rgb.map(|c| c as f64 / 255.0);
+        let mut fd = gtk::pango::FontDescription::from_string("Monospace");
+        fd.set_absolute_size(11.0 * gtk::pango::SCALE as f64);
+        pg.set_font_description(Some(&fd));
+        pg.set_text(&format!(
+            "{}\n{}\n{}",
+            color::format_color(rgb, "hex"),
+            color::format_color(rgb, "rgb"),
+            color::format_color(rgb, "hsl")
+        ));
+        let (_ink_rect, logical_rect) = pg.extents();
+        let text_height = logical_rect.height() as f64 / gtk::pango::SCALE as f64;
+        let padding = 4.0;
+        let top = ly + loupe + padding;
 
-        if settings.get_value("border").get_bool().expect("draw error") {
-            cr.arc(
-                self.center_x.get(),
-                self.center_y.get(),
-                self.radius.get(),
-                0.0,
-                2.0 * f64::consts::PI,
-            );
+        cr.rectangle(lx, top, loupe, CELL * 3.0);
+        cr.set_source_rgba(r, g, b, 1.0);
+        cr.fill_preserve().expect("Failed to fill the swatch");
+        cr.set_source_rgba(1.0, 1.0, 1.0, 1.0);
+        cr.stroke().expect("Failed to draw the swatch border");
 
-            cr.set_source_rgba(
-                settings
-                    .get_value("border_r")
-                    .get_f64()
-                    .expect("draw error"),
-                settings
-                    .get_value("border_g")
-                    .get_f64()
-                    .expect("draw error"),
-                settings
-                    .get_value("border_b")
-                    .get_f64()
-                    .expect("draw error"),
-                settings
-                    .get_value("border_a")
-                    .get_f64()
-                    .expect("draw error"),
-            );
-            let border_size = settings
-                .get_value("border_size")
-                .get_f64()
-                .expect("draw error");
-            cr.set_line_width(border_size);
-            cr.stroke().unwrap();
+        let text_width = logical_rect.width() as f64 / gtk::pango::SCALE as f64;
+        rounded_rectangle(
+            cr,
+            lx,
+            top + CELL * 3.0 + padding,
+            text_width.max(loupe - 2.0 * padding) + 2.0 * padding,
+            text_height + 2.0 * padding,
+            padding,
+        );
+        cr.set_source_rgba(0.0, 0.0, 0.0, 0.7);
+        cr.fill().expect("Failed to draw the values box");
+        cr.move_to(lx + padding, top + CELL * 3.0 + 2.0 * padding);
+        cr.set_source_rgba(1.0, 1.0, 1.0, 1.0);
+        pangocairo::functions::show_layout(cr, pg);
+        cr.restore().expect("Failed to restore the context");
+    }
+}
+impl Magnifier {
+    pub fn new(settings_rc: &SettingsRc, captured: &CapturedScreen) -> Self {
+        Self {
+            start_x: Rc::new(Cell::new(0.0)),
+            start_y: Rc::new(Cell::new(0.0)),
+            end_x: Rc::new(Cell::new(0.0)),
+            end_y: Rc::new(Cell::new(0.0)),
+            dest: Rc::new(Cell::new(None)),
+            dest_origin: Rc::new(Cell::new((0.0, 0.0))),
+            picking_source: Rc::new(Cell::new(true)),
+            captured: captured.clone(),
+            cache: FilterCache::default(),
+            settings: settings_rc.clone(),
+            saved_settings: Rc::new(RefCell::new(None)),
+            layer: LayerState::default(),
+            shadow_cache: ShadowCache::default(),
+            drawing: Rc::new(Cell::new(false)),
         }
     }
-    fn draw_with_saved_settings(&self, cr: &cairo::Context, _pg: &gtk::pango::Layout) {
-        // Get the saved settings
-        let settings_hard_copy = self.saved_settings.borrow().clone();
-        if let Some(settings_to_unwrap) = settings_hard_copy {
-            let settings = settings_to_unwrap.arc;
-
-            if settings.get_value("fill").get_bool().expect("draw error") {
-                cr.arc(
-                    self.center_x.get(),
-                    self.center_y.get(),
-                    self.radius.get(),
-                    0.0,
-                    2.0 * f64::consts::PI,
-                );
-
-                cr.set_source_rgba(
-                    settings.get_value("fill_r").get_f64().expect("draw error"),
-                    settings.get_value("fill_g").get_f64().expect("draw error"),
-                    settings.get_value("fill_b").get_f64().expect("draw error"),
-                    settings.get_value("fill_a").get_f64().expect("draw error"),
-                );
-                cr.fill().expect("No arc fill to unwrap");
-                cr.stroke().unwrap();
-            }
 
-            if settings.get_value("border").get_bool().expect("draw error") {
-                cr.arc(
-                    self.center_x.get(),
-                    self.center_y.get(),
-                    self.radius.get(),
-                    0.0,
-                    2.0 * f64::consts::PI,
-                );
+    /// Source rectangle, normalized to positive sizes
+    fn source(&self) -> (f64, f64, f64, f64) {
+        let (x, y) = (self.start_x.get(), self.start_y.get());
+        let (w, h) = (self.end_x.get(), self.end_y.get());
+        (x.min(x + w), y.min(y + h), w.abs(), h.abs())
+    }
 
-                cr.set_source_rgba(
-                    settings
-                        .get_value("border_r")
-                        .get_f64()
-                        .expect("draw error"),
-                    settings
-                        .get_value("border_g")
-                        .get_f64()
-                        .expect("draw error"),
-                    settings
-                        .get_value("border_b")
-                        .get_f64()
-                        .expect("draw error"),
-                    settings
-                        .get_value("border_a")
-                        .get_f64()
-                        .expect("draw error"),
-                );
-                let border_size = settings
-                    .get_value("border_size")
-                    .get_f64()
-                    .expect("draw error");
-                cr.set_line_width(border_size);
-                cr.stroke().unwrap();
-            }
+    /// Source rectangle actually zoomed: for the circle it is the square around the source
+    fn zoomed_source(&self, circle: bool) -> (f64, f64, f64, f64) {
+        let (x, y, w, h) = self.source();
+        if circle {
+            let side = w.max(h);
+            (x + (w - side) / 2.0, y + (h - side) / 2.0, side, side)
         } else {
-            println!("Warning! saved_settings is None!");
+            (x, y, w, h)
         }
     }
-}
-impl Draw for AreaBox {
-    fn draw(&self, cr: &cairo::Context, _pg: &gtk::pango::Layout) {
-        let settings = self.settings.rect.clone();
 
-        if settings.get_value("fill").get_bool().expect("draw error") {
-            cr.rectangle(
-                self.start_x.get(),
-                self.start_y.get(),
-                self.end_x.get(),
-                self.end_y.get(),
-            );
+    /// Half sizes of the magnified view
+    fn half_size(&self, circle: bool, zoom: f64) -> (f64, f64) {
+        let (_, _, w, h) = self.zoomed_source(circle);
+        (w * zoom / 2.0, h * zoom / 2.0)
+    }
 
-            cr.set_source_rgba(
-                settings.get_value("fill_r").get_f64().expect("draw error"),
-                settings.get_value("fill_g").get_f64().expect("draw error"),
-                settings.get_value("fill_b").get_f64().expect("draw error"),
-                settings.get_value("fill_a").get_f64().expect("draw error"),
-            );
-            cr.fill().expect("No arc fill to unwrap");
-            cr.stroke().unwrap();
+    fn draw_magnifier<F>(&self, cr: &cairo::Context, get: F)
+    where
+        F: Fn(&str) -> SettingValue,
+    {
+        let (x, y, w, h) = self.source();
+        if w == 0.0 || h == 0.0 {
+            return;
         }
 
-        if settings.get_value("border").get_bool().expect("draw error") {
-            cr.rectangle(
-                self.start_x.get(),
-                self.start_y.get(),
-                self.end_x.get(),
-                self.end_y.get(),
-            );
+        let circle = get("shape").get_string().is_ok_and(|s| s == "circle");
+        let zoom = get("zoom").get_f64().unwrap_or(2.0).max(1.0);
+        let border_size = get("border_size").get_f64().unwrap_or(2.0);
+        let connector = get("connector").get_bool().unwrap_or(true);
+        let color = (
+            get("border_r").get_f64().unwrap_or(0.0),
+            get("border_g").get_f64().unwrap_or(0.0),
+            get("border_b").get_f64().unwrap_or(0.0),
+            get("border_a").get_f64().unwrap_or(1.0),
+        );
 
-            cr.set_source_rgba(
-                settings
-                    .get_value("border_r")
-                    .get_f64()
-                    .expect("draw error"),
-                settings
-                    .get_value("border_g")
-                    .get_f64()
-                    .expect("draw error"),
-                settings
-                    .get_value("border_b")
-                    .get_f64()
-                    .expect("draw error"),
-                settings
-                    .get_value("border_a")
-                    .get_f64()
-                    .expect("draw error"),
-            );
-            let border_size = settings
-                .get_value("border_size")
-                .get_f64()
-                .expect("draw error");
-            cr.set_line_width(border_size);
-            cr.stroke().unwrap();
-        }
-    }
-    fn draw_with_saved_settings(&self, cr: &cairo::Context, _pg: &gtk::pango::Layout) {
-        let settings_hard_copy = self.saved_settings.borrow().clone();
-        if let Some(settings_to_unwrap) = settings_hard_copy {
-            let settings = settings_to_unwrap.rect;
-            if settings.get_value("fill").get_bool().expect("draw error") {
-                cr.rectangle(
-                    self.start_x.get(),
-                    self.start_y.get(),
-                    self.end_x.get(),
-                    self.end_y.get(),
-                );
+        cr.save().expect("Failed to save the context");
+        cr.set_source_rgba(color.0, color.1, color.2, color.3);
+        cr.set_line_width(border_size);
 
-                cr.set_source_rgba(
-                    settings.get_value("fill_r").get_f64().expect("draw error"),
-                    settings.get_value("fill_g").get_f64().expect("draw error"),
-                    settings.get_value("fill_b").get_f64().expect("draw error"),
-                    settings.get_value("fill_a").get_f64().expect("draw error"),
-                );
-                cr.fill().expect("No arc fill to unwrap");
-                cr.stroke().unwrap();
+        // 1️⃣ Source rectangle
+        cr.rectangle(x, y, w, h);
+        cr.stroke().expect("Failed to draw the magnifier source");
+
+        let Some((dx, dy)) = self.dest.get() else {
+            cr.restore().expect("Failed to restore the context");
+            return;
+        };
+        let (hw, hh) = self.half_size(circle, zoom);
+
+        // 2️⃣ Connector, from the border of the source to the border of the view
+        let (sx, sy) = (x + w / 2.0, y + h / 2.0);
+        let (vx, vy) = (dx - sx, dy - sy);
+        let len = vx.hypot(vy);
+        if connector && len > 0.0 {
+            // distance from the center to the border of a rectangle, along (vx, vy)
+            let exit = |hw: f64, hh: f64| (hw / vx.abs()).min(hh / vy.abs());
+            let out = exit(w / 2.0, h / 2.0);
+            let inside = if circle { hw / len } else { exit(hw, hh) };
+            if out + inside < 1.0 {
+                cr.move_to(sx + vx * out, sy + vy * out);
+                cr.line_to(dx - vx * inside, dy - vy * inside);
+                cr.stroke().expect("Failed to draw the magnifier connector");
             }
+        }
 
-            if settings.get_value("border").get_bool().expect("draw error") {
-                cr.rectangle(
-                    self.start_x.get(),
-                    self.start_y.get(),
-                    self.end_x.get(),
-                    self.end_y.get(),
-                );
+        // 3️⃣ Zoomed pixels
+        if circle {
+            cr.arc(dx, dy, hw, 0.0, 2.0 * f64::consts::PI);
+        } else {
+            cr.rectangle(dx - hw, dy - hh, 2.0 * hw, 2.0 * hh);
+        }
+        let view = cr.copy_path().expect("Failed to copy the magnifier path");
+
+        let (zx, zy, zw, zh) = self.zoomed_source(circle);
+        match self.captured.region(zx, zy, zw, zh) {
+            Some(region) => {
+                let key = (region.x, region.y, region.width, region.height, 0);
+                let mut cached = self.cache.cached.borrow_mut();
+                let surface = match cached.as_ref() {
+                    Some((cached_key, surface)) if *cached_key == key => surface.clone(),
+                    _ => {
+                        let surface = region
+                            .to_surface()
+                            .expect("Failed to create the region surface");
+                        *cached = Some((key, surface.clone()));
+                        surface
+                    }
+                };
 
-                cr.set_source_rgba(
-                    settings
-                        .get_value("border_r")
-                        .get_f64()
-                        .expect("draw error"),
-                    settings
-                        .get_value("border_g")
-                        .get_f64()
-                        .expect("draw error"),
-                    settings
-                        .get_value("border_b")
-                        .get_f64()
-                        .expect("draw error"),
-                    settings
-                        .get_value("border_a")
-                        .get_f64()
-                        .expect("draw error"),
-                );
-                let border_size = settings
-                    .get_value("border_size")
-                    .get_f64()
-                    .expect("draw error");
-                cr.set_line_width(border_size);
-                cr.stroke().unwrap();
+                // Map the image pixels of the source onto the view
+                let scale = self.captured.scale();
+                cr.save().expect("Failed to save the context");
+                cr.clip();
+                cr.translate(dx - hw, dy - hh);
+                cr.scale(zoom / scale, zoom / scale);
+                cr.set_source_surface(
+                    &surface,
+                    region.x as f64 - zx * scale,
+                    region.y as f64 - zy * scale,
+                )
+                .expect("Failed to set the region surface");
+                cr.source().set_filter(cairo::Filter::Nearest);
+                cr.paint().expect("Failed to paint the magnified region");
+                cr.restore().expect("Failed to restore the context");
+            }
+            None => {
+                cr.set_source_rgba(0.0, 0.0, 0.0, 1.0);
+                cr.fill().expect("Failed to fill the magnifier");
+                cr.set_source_rgba(color.0, color.1, color.2, color.3);
             }
-        } else {
-            println!("AreaBox error, no save_settings!");
         }
+
+        // 4️⃣ Border of the view
+        cr.new_path();
+        cr.append_path(&view);
+        cr.stroke().expect("Failed to draw the magnifier border");
+        cr.restore().expect("Failed to restore the context");
     }
 }
-impl Draw for NumberedCircle {
-    fn draw(&self, cr: &cairo::Context, pg: &gtk::pango::Layout) {
-        let settings = self.settings.numbered_circle.clone();
-        let radius = settings
-            .get_value("radius")
-            .get_f64()
-            .expect("Missing setting 'radius'");
-
-        cr.arc(
-            self.x0.get(),
-            self.y0.get(),
-            radius,
-            0.0,
-            2.0 * f64::consts::PI,
-        );
-
-        if settings.get_value("fill").get_bool().expect("draw error") {
-            cr.set_source_rgba(
-                settings.get_value("fill_r").get_f64().expect("draw error"),
-                settings.get_value("fill_g").get_f64().expect("draw error"),
-                settings.get_value("fill_b").get_f64().expect("draw error"),
-                settings.get_value("fill_a").get_f64().expect("draw error"),
-            );
-            cr.fill_preserve()
-                .expect("Failed to fill the numbered_circle");
+impl Pixelate {
+    pub fn new(settings_rc: &SettingsRc, captured: &CapturedScreen) -> Self {
+        Self {
+            start_x: Rc::new(Cell::new(0.0)),
+            start_y: Rc::new(Cell::new(0.0)),
+            end_x: Rc::new(Cell::new(0.0)),
+            end_y: Rc::new(Cell::new(0.0)),
+            captured: captured.clone(),
+            cache: FilterCache::default(),
+            settings: settings_rc.clone(),
+            saved_settings: Rc::new(RefCell::new(None)),
+            layer: LayerState::default(),
+            shadow_cache: ShadowCache::default(),
+            drawing: Rc::new(Cell::new(false)),
         }
-
-        if settings.get_value("border").get_bool().expect("draw error") {
-            cr.set_source_rgba(
-                settings
+    }
+}
+impl Blur {
+    pub fn new(settings_rc: &SettingsRc, captured: &CapturedScreen) -> Self {
+        Self {
+            start_x: Rc::new(Cell::new(0.0)),
+            start_y: Rc::new(Cell::new(0.0)),
+            end_x: Rc::new(Cell::new(0.0)),
+            end_y: Rc::new(Cell::new(0.0)),
+            captured: captured.clone(),
+            cache: FilterCache::default(),
+            settings: settings_rc.clone(),
+            saved_settings: Rc::new(RefCell::new(None)),
+            layer: LayerState::default(),
+            shadow_cache: ShadowCache::default(),
+            drawing: Rc::new(Cell::new(false)),
+        }
+    }
+}
+/// Create the pixelate or blur drawable, depending on the obfuscate "tool" setting
+pub fn new_obfuscate_drawable(
+    settings_rc: &SettingsRc,
+    captured: &CapturedScreen,
+) -> DrawableCollection {
+    let tool = settings_rc
+        .obfuscate
+        .get_value("tool")
+        .get_string()
+        .unwrap_or_default();
+    match tool.as_str() {
+        "blur" => DrawableCollection::Blurs(Blur::new(settings_rc, captured)),
+        _ => DrawableCollection::Pixelates(Pixelate::new(settings_rc, captured)),
+    }
+}
+impl Group {
+    pub fn new(items: Vec<DrawableCollection>) -> Self {
+        Self {
+            items: Rc::new(RefCell::new(items)),
+            layer: LayerState::default(),
+        }
+    }
+}
+
+// Drag Begin trait
+pub trait DragBegin {
+    fn drag_begin(&self, x: f64, y: f64);
+}
+impl DragBegin for AreaBox {
+    fn drag_begin(&self, x: f64, y: f64) {
+        self.start_x.set(x);
+        self.start_y.set(y);
+        self.end_x.set(0.0);
+        self.end_y.set(0.0);
+        self.drawing.set(true);
+    }
+}
+impl DragBegin for Arc {
+    fn drag_begin(&self, x: f64, y: f64) {
+        self.center_x.set(x);
+        self.center_y.set(y);
+        self.radius.set(0.0);
+        self.width.set(0.0);
+        self.height.set(0.0);
+        self.drawing.set(true);
+    }
+}
+impl DragBegin for Arrow {
+    fn drag_begin(&self, x: f64, y: f64) {
+        self.x1.set(x);
+        self.y1.set(y);
+        self.x2.set(x);
+        self.y2.set(y);
+        self.cx.set(x);
+        self.cy.set(y);
+        self.drawing.set(true);
+    }
+}
+impl DragBegin for Line {
+    fn drag_begin(&self, x: f64, y: f64) {
+        self.x1.set(x);
+        self.y1.set(y);
+        self.x2.set(x);
+        self.y2.set(y);
+        self.drawing.set(true);
+    }
+}
+impl DragBegin for FreeHandDraw {
+    fn drag_begin(&self, x: f64, y: f64) {
+        self.x0.set(x);
+        self.y0.set(y);
+        self.drawing.set(true);
+    }
+}
+impl DragBegin for NumberedCircle {
+    fn drag_begin(&self, x: f64, y: f64) {
+        self.x0.set(x);
+        self.y0.set(y);
+    }
+}
+impl DragBegin for InputText {
+    fn drag_begin(&self, x: f64, y: f64) {
+        // Set the initial position
+        self.x0.set(x);
+        self.y0.set(y);
+
+        println!("begin");
+
+        // Se the initial height and width
+        let sz = self
+            .settings
+            .input_text
+            .get_value("font_size")
+            .get_f64()
+            .expect("");
+        self.x.set(30.0);
+        self.y.set(sz);
+
+        // Every time there is a drag_begin reset the settings text
+        self.settings
+            .input_text
+            .set_value("text", SettingValue::String("".to_string()))
+            .expect("Error in stop_controller_key");
+    }
+}
+impl DragBegin for Callout {
+    fn drag_begin(&self, x: f64, y: f64) {
+        // The drag goes from the pointed target to the bubble
+        self.tip_x.set(x);
+        self.tip_y.set(y);
+        self.x.set(x);
+        self.y.set(y);
+        self.drawing.set(true);
+
+        // Every time there is a drag_begin reset the settings text
+        self.settings
+            .input_text
+            .set_value("text", SettingValue::String("".to_string()))
+            .expect("Error in Callout drag_begin");
+    }
+}
+impl DragBegin for Polygon {
+    fn drag_begin(&self, x: f64, y: f64) {
+        self.drawing.set(true);
+
+        // The second click of a double click finishes the polygon instead of adding a vertex
+        if self.is_double_click(x, y) && self.finish() {
+            return;
+        }
+        self.last_click.set(Some((glib::monotonic_time(), x, y)));
+        self.x.borrow_mut().push(x);
+        self.y.borrow_mut().push(y);
+    }
+}
+impl DragBegin for Redact {
+    fn drag_begin(&self, x: f64, y: f64) {
+        self.start_x.set(x);
+        self.start_y.set(y);
+        self.end_x.set(0.0);
+        self.end_y.set(0.0);
+        self.drawing.set(true);
+    }
+}
+impl DragBegin for Stamp {
+    fn drag_begin(&self, x: f64, y: f64) {
+        self.x.set(x);
+        self.y.set(y);
+        self.scale.set(
+            self.settings
+                .stamp
+                .get_value("scale")
+                .get_f64()
+                .unwrap_or(1.0),
+        );
+        self.drawing.set(true);
+    }
+}
+impl DragBegin for Image {
+    fn drag_begin(&self, x: f64, y: f64) {
+        // Take the picture loaded in the tool, at its natural size
+        let surface = self.source.borrow().clone();
+        if let Some(surface) = surface.as_ref() {
+            self.width.set(surface.width() as f64);
+            self.height.set(surface.height() as f64);
+        }
+        *self.surface.borrow_mut() = surface;
+        self.x.set(x);
+        self.y.set(y);
+        self.drawing.set(true);
+    }
+}
+impl DragBegin for Measure {
+    fn drag_begin(&self, x: f64, y: f64) {
+        self.start_x.set(x);
+        self.start_y.set(y);
+        self.end_x.set(0.0);
+        self.end_y.set(0.0);
+        self.drawing.set(true);
+    }
+}
+impl DragBegin for Eyedropper {
+    fn drag_begin(&self, x: f64, y: f64) {
+        self.start_x.set(x);
+        self.start_y.set(y);
+        self.x.set(x);
+        self.y.set(y);
+        self.drawing.set(true);
+    }
+}
+impl DragBegin for Highlight {
+    fn drag_begin(&self, x: f64, y: f64) {
+        self.start_x.set(x);
+        self.start_y.set(y);
+        self.end_x.set(0.0);
+        self.end_y.set(0.0);
+        self.drawing.set(true);
+    }
+}
+impl DragBegin for Spotlight {
+    fn drag_begin(&self, x: f64, y: f64) {
+        let region = &self.region;
+        region.start_x.set(x);
+        region.start_y.set(y);
+        region.end_x.set(0.0);
+        region.end_y.set(0.0);
+        region.ellipse.set(
+            self.settings
+                .spotlight
+                .get_value("shape")
+                .get_string()
+                .is_ok_and(|shape| shape == "ellipse"),
+        );
+        region.dim.set(
+            self.settings
+                .spotlight
+                .get_value("dim")
+                .get_f64()
+                .unwrap_or(0.6),
+        );
+        self.drawing.set(true);
+
+        // Register the region, forgetting the deleted ones
+        let mut regions = self.regions.borrow_mut();
+        regions.retain(|r| r.strong_count() > 0);
+        if !regions.iter().any(|r| r.ptr_eq(&Rc::downgrade(region))) {
+            regions.push(Rc::downgrade(region));
+        }
+    }
+}
+impl DragBegin for Magnifier {
+    fn drag_begin(&self, x: f64, y: f64) {
+        if self.picking_source.get() {
+            self.start_x.set(x);
+            self.start_y.set(y);
+            self.end_x.set(0.0);
+            self.end_y.set(0.0);
+        } else {
+            self.dest.set(Some((x, y)));
+            self.dest_origin.set((x, y));
+        }
+        self.drawing.set(true);
+    }
+}
+impl DragBegin for Pixelate {
+    fn drag_begin(&self, x: f64, y: f64) {
+        self.start_x.set(x);
+        self.start_y.set(y);
+        self.end_x.set(0.0);
+        self.end_y.set(0.0);
+        self.drawing.set(true);
+    }
+}
+impl DragBegin for Blur {
+    fn drag_begin(&self, x: f64, y: f64) {
+        self.start_x.set(x);
+        self.start_y.set(y);
+        self.end_x.set(0.0);
+        self.end_y.set(0.0);
+        self.drawing.set(true);
+    }
+}
+impl DragBegin for DrawableCollection {
+    fn drag_begin(&self, x: f64, y: f64) {
+        match self {
+            DrawableCollection::FreeHands(state) => {
+                state.drag_begin(x, y);
+            }
+            DrawableCollection::Lines(state) => {
+                state.drag_begin(x, y);
+            }
+            DrawableCollection::AreaBoxes(state) => {
+                state.drag_begin(x, y);
+            }
+            DrawableCollection::Polygons(state) => {
+                state.drag_begin(x, y);
+            }
+            DrawableCollection::Redacts(state) => {
+                state.drag_begin(x, y);
+            }
+            DrawableCollection::Highlights(state) => {
+                state.drag_begin(x, y);
+            }
+            DrawableCollection::Stamps(state) => {
+                state.drag_begin(x, y);
+            }
+            DrawableCollection::Measures(state) => {
+                state.drag_begin(x, y);
+            }
+            DrawableCollection::Eyedroppers(state) => {
+                state.drag_begin(x, y);
+            }
+            DrawableCollection::Images(state) => {
+                state.drag_begin(x, y);
+            }
+            DrawableCollection::Spotlights(state) => {
+                state.drag_begin(x, y);
+            }
+            DrawableCollection::Magnifiers(state) => {
+                state.drag_begin(x, y);
+            }
+            DrawableCollection::Pixelates(state) => {
+                state.drag_begin(x, y);
+            }
+            DrawableCollection::Blurs(state) => {
+                state.drag_begin(x, y);
+            }
+            DrawableCollection::Arcs(state) => {
+                state.drag_begin(x, y);
+            }
+            DrawableCollection::Arrows(state) => {
+                state.drag_begin(x, y);
+            }
+            DrawableCollection::NumberedCircles(state) => {
+                state.drag_begin(x, y);
+            }
+            DrawableCollection::InputTexts(state) => {
+                state.drag_begin(x, y);
+            }
+            DrawableCollection::Callouts(state) => {
+                state.drag_begin(x, y);
+            }
+            DrawableCollection::Groups(_) => {}
+        }
+    }
+}
+
+// Drag Update trait
+pub trait DragUpdate {
+    fn drag_update(&self, x: f64, y: f64);
+}
+
+impl DragUpdate for AreaBox {
+    fn drag_update(&self, x: f64, y: f64) {
+        self.end_x.set(x);
+        self.end_y.set(y);
+    }
+}
+impl DragUpdate for Arc {
+    fn drag_update(&self, x: f64, y: f64) {
+        self.radius.set(f64::sqrt(x * x + y * y));
+        self.width.set(x);
+        self.height.set(y);
+    }
+}
+impl DragUpdate for Line {
+    fn drag_update(&self, x: f64, y: f64) {
+        self.x1.set(self.x2.get() + x);
+        self.y1.set(self.y2.get() + y);
+    }
+}
+impl DragUpdate for Arrow {
+    fn drag_update(&self, x: f64, y: f64) {
+        self.x2.set(self.x1.get() + x);
+        self.y2.set(self.y1.get() + y);
+
+        // The control point starts bent on the side of the segment, at a fifth of its length
+        self.cx.set(self.x1.get() + x / 2.0 + y / 5.0);
+        self.cy.set(self.y1.get() + y / 2.0 - x / 5.0);
+    }
+}
+impl DragUpdate for FreeHandDraw {
+    fn drag_update(&self, x: f64, y: f64) {
+        self.x.borrow_mut().push(self.x0.get() + x);
+        self.y.borrow_mut().push(self.y0.get() + y);
+    }
+}
+impl DragUpdate for InputText {
+    fn drag_update(&self, _x: f64, _y: f64) {
+        // self.x.set(x);
+        // self.y.set(y);
+    }
+}
+impl DragUpdate for Callout {
+    fn drag_update(&self, x: f64, y: f64) {
+        self.x.set(self.tip_x.get() + x);
+        self.y.set(self.tip_y.get() + y);
+    }
+}
+impl DragUpdate for Polygon {
+    fn drag_update(&self, x: f64, y: f64) {
+        // Dragging moves the vertex just placed
+        if self.finished.get() {
+            return;
+        }
+        let Some((x0, y0)) = self.last_click.get().map(|(_, x0, y0)| (x0, y0)) else {
+            return;
+        };
+        if let Some(last) = self.x.borrow_mut().last_mut() {
+            *last = x0 + x;
+        }
+        if let Some(last) = self.y.borrow_mut().last_mut() {
+            *last = y0 + y;
+        }
+    }
+}
+impl DragUpdate for Redact {
+    fn drag_update(&self, x: f64, y: f64) {
+        self.end_x.set(x);
+        self.end_y.set(y);
+    }
+}
+impl DragUpdate for Stamp {
+    fn drag_update(&self, x: f64, y: f64) {
+        // A click keeps the scale of the settings, a drag sizes the stamp up to the pointer
+        let distance = x.hypot(y);
+        if distance < 4.0 {
+            return;
+        }
+        if let Some(image) = self.image() {
+            let size = image.width().max(image.height()).max(1) as f64;
+            self.scale.set(2.0 * distance / size);
+        }
+    }
+}
+impl DragUpdate for Image {
+    fn drag_update(&self, x: f64, y: f64) {
+        // A click keeps the natural size, a drag sizes the picture up to the pointer
+        if x.abs() < 4.0 && y.abs() < 4.0 {
+            return;
+        }
+        self.resize(x, y);
+    }
+}
+impl DragUpdate for Measure {
+    fn drag_update(&self, x: f64, y: f64) {
+        self.end_x.set(x);
+        self.end_y.set(y);
+    }
+}
+impl DragUpdate for Eyedropper {
+    fn drag_update(&self, x: f64, y: f64) {
+        self.x.set(self.start_x.get() + x);
+        self.y.set(self.start_y.get() + y);
+    }
+}
+impl DragUpdate for Highlight {
+    fn drag_update(&self, x: f64, y: f64) {
+        self.end_x.set(x);
+        self.end_y.set(y);
+    }
+}
+impl DragUpdate for Spotlight {
+    fn drag_update(&self, x: f64, y: f64) {
+        self.region.end_x.set(x);
+        self.region.end_y.set(y);
+    }
+}
+impl DragUpdate for Magnifier {
+    fn drag_update(&self, x: f64, y: f64) {
+        if self.picking_source.get() {
+            self.end_x.set(x);
+            self.end_y.set(y);
+        } else {
+            let (ox, oy) = self.dest_origin.get();
+            self.dest.set(Some((ox + x, oy + y)));
+        }
+    }
+}
+impl DragUpdate for Pixelate {
+    fn drag_update(&self, x: f64, y: f64) {
+        self.end_x.set(x);
+        self.end_y.set(y);
+    }
+}
+impl DragUpdate for Blur {
+    fn drag_update(&self, x: f64, y: f64) {
+        self.end_x.set(x);
+        self.end_y.set(y);
+    }
+}
+impl DragUpdate for DrawableCollection {
+    fn drag_update(&self, x: f64, y: f64) {
+        match self {
+            DrawableCollection::FreeHands(state) => {
+                state.drag_update(x, y);
+            }
+            DrawableCollection::Lines(state) => {
+                state.drag_update(x, y);
+            }
+            DrawableCollection::AreaBoxes(state) => {
+                state.drag_update(x, y);
+            }
+            DrawableCollection::Polygons(state) => {
+                state.drag_update(x, y);
+            }
+            DrawableCollection::Redacts(state) => {
+                state.drag_update(x, y);
+            }
+            DrawableCollection::Highlights(state) => {
+                state.drag_update(x, y);
+            }
+            DrawableCollection::Stamps(state) => {
+                state.drag_update(x, y);
+            }
+            DrawableCollection::Measures(state) => {
+                state.drag_update(x, y);
+            }
+            DrawableCollection::Eyedroppers(state) => {
+                state.drag_update(x, y);
+            }
+            DrawableCollection::Images(state) => {
+                state.drag_update(x, y);
+            }
+            DrawableCollection::Spotlights(state) => {
+                state.drag_update(x, y);
+            }
+            DrawableCollection::Magnifiers(state) => {
+                state.drag_update(x, y);
+            }
+            DrawableCollection::Pixelates(state) => {
+                state.drag_update(x, y);
+            }
+            DrawableCollection::Blurs(state) => {
+                state.drag_update(x, y);
+            }
+            DrawableCollection::Arcs(state) => {
+                state.drag_update(x, y);
+            }
+            DrawableCollection::Arrows(state) => {
+                state.drag_update(x, y);
+            }
+            DrawableCollection::InputTexts(state) => {
+                state.drag_update(x, y);
+            }
+            DrawableCollection::Callouts(state) => {
+                state.drag_update(x, y);
+            }
+            _ => {}
+        }
+    }
+}
+
+// Drag End
+pub trait DragEnd {
+    fn drag_end(&self) -> Option<DrawableCollection>;
+}
+
+impl DragEnd for AreaBox {
+    fn drag_end(&self) -> Option<DrawableCollection> {
+        // Create an hard copy of the settings for future draws
+        *self.saved_settings.borrow_mut() = Some(self.settings.hard_copy());
+
+        // Shallow clone last settings and return the new element
+        let s = self.settings.clone();
+        Some(DrawableCollection::AreaBoxes(AreaBox::new(&s)))
+    }
+}
+impl DragEnd for Arc {
+    fn drag_end(&self) -> Option<DrawableCollection> {
+        // Create an hard copy of the settings for future draws
+        *self.saved_settings.borrow_mut() = Some(self.settings.hard_copy());
+
+        // Shallow clone last settings and return the new element
+        let s = self.settings.clone();
+        Some(DrawableCollection::Arcs(Arc::new(&s)))
+    }
+}
+impl DragEnd for Line {
+    fn drag_end(&self) -> Option<DrawableCollection> {
+        // Create an hard copy of the settings for future draws
+        *self.saved_settings.borrow_mut() = Some(self.settings.hard_copy());
+
+        // Shallow clone last settings and return the new element
+        let s = self.settings.clone();
+        Some(DrawableCollection::Lines(Line::new(&s)))
+    }
+}
+impl DragEnd for Arrow {
+    fn drag_end(&self) -> Option<DrawableCollection> {
+        // Create an hard copy of the settings for future draws
+        *self.saved_settings.borrow_mut() = Some(self.settings.hard_copy());
+
+        // Shallow clone last settings and return the new element
+        let s = self.settings.clone();
+        Some(DrawableCollection::Arrows(Arrow::new(&s)))
+    }
+}
+impl DragEnd for FreeHandDraw {
+    fn drag_end(&self) -> Option<DrawableCollection> {
+        // Create an hard copy of the settings for future draws
+        *self.saved_settings.borrow_mut() = Some(self.settings.hard_copy());
+
+        // Shallow clone last settings and return the new element
+        let s = self.settings.clone();
+        Some(DrawableCollection::FreeHands(FreeHandDraw::new(
+            &s,
+            &self.captured,
+        )))
+    }
+}
+impl DragEnd for NumberedCircle {
+    fn drag_end(&self) -> Option<DrawableCollection> {
+        // Create an hard copy of the settings for future draws
+        *self.saved_settings.borrow_mut() = Some(self.settings.hard_copy());
+
+        // Keep the number of this badge and set the next one
+        let number = self
+            .settings
+            .numbered_circle
+            .get_value("number")
+            .get_i32()
+            .expect("drag_end error");
+        self.number.set(number);
+        self.placed.set(glib::monotonic_time());
+        let num: i32 = number + 1_i32;
+        self.settings
+            .numbered_circle
+            .set_value(
+                "number",
+                crate::drawing_area_settings::SettingValue::I32(num),
+            )
+            .expect("NumberedCircle::new error");
+
+        // Shallow clone last settings and return the new element
+        let s = self.settings.clone();
+        Some(DrawableCollection::NumberedCircles(NumberedCircle::new(&s)))
+    }
+}
+impl DragEnd for InputText {
+    fn drag_end(&self) -> Option<DrawableCollection> {
+        println!("end");
+        // Create an hard copy of the settings for future draws
+        *self.saved_settings.borrow_mut() = Some(self.settings.hard_copy());
+
+        // Shallow clone last settings and return the new element
+        let s = self.settings.clone();
+        Some(DrawableCollection::InputTexts(InputText::new(&s)))
+    }
+}
+impl DragEnd for Callout {
+    fn drag_end(&self) -> Option<DrawableCollection> {
+        // Create an hard copy of the settings for future draws
+        *self.saved_settings.borrow_mut() = Some(self.settings.hard_copy());
+
+        // Shallow clone last settings and return the new element
+        let s = self.settings.clone();
+        Some(DrawableCollection::Callouts(Callout::new(&s)))
+    }
+}
+impl DragEnd for Polygon {
+    fn drag_end(&self) -> Option<DrawableCollection> {
+        // Keep adding vertices until the polygon is finished
+        if !self.finished.get() {
+            return None;
+        }
+
+        // Create an hard copy of the settings for future draws
+        *self.saved_settings.borrow_mut() = Some(self.settings.hard_copy());
+
+        // Shallow clone last settings and return the new element
+        let s = self.settings.clone();
+        Some(DrawableCollection::Polygons(Polygon::new(&s)))
+    }
+}
+impl DragEnd for Redact {
+    fn drag_end(&self) -> Option<DrawableCollection> {
+        // Create an hard copy of the settings for future draws
+        *self.saved_settings.borrow_mut() = Some(self.settings.hard_copy());
+
+        // Shallow clone last settings and return the new element
+        let s = self.settings.clone();
+        Some(DrawableCollection::Redacts(Redact::new(&s)))
+    }
+}
+impl DragEnd for Stamp {
+    fn drag_end(&self) -> Option<DrawableCollection> {
+        // Create an hard copy of the settings for future draws
+        *self.saved_settings.borrow_mut() = Some(self.settings.hard_copy());
+
+        // Shallow clone last settings and return the new element
+        let s = self.settings.clone();
+        Some(DrawableCollection::Stamps(Stamp::new(&s)))
+    }
+}
+impl DragEnd for Image {
+    fn drag_end(&self) -> Option<DrawableCollection> {
+        // Create an hard copy of the settings for future draws
+        *self.saved_settings.borrow_mut() = Some(self.settings.hard_copy());
+
+        // Shallow clone last settings and return the new element
+        let s = self.settings.clone();
+        Some(DrawableCollection::Images(Image::new(&s, &self.source)))
+    }
+}
+impl DragEnd for Measure {
+    fn drag_end(&self) -> Option<DrawableCollection> {
+        // Create an hard copy of the settings for future draws
+        *self.saved_settings.borrow_mut() = Some(self.settings.hard_copy());
+
+        // Shallow clone last settings and return the new element
+        let s = self.settings.clone();
+        Some(DrawableCollection::Measures(Measure::new(
+            &s,
+            &self.captured,
+        )))
+    }
+}
+impl DragEnd for Eyedropper {
+    fn drag_end(&self) -> Option<DrawableCollection> {
+        self.pick();
+        Some(DrawableCollection::Eyedroppers(Eyedropper::new(
+            &self.settings,
+            &self.captured,
+        )))
+    }
+}
+impl DragEnd for Highlight {
+    fn drag_end(&self) -> Option<DrawableCollection> {
+        // Create an hard copy of the settings for future draws
+        *self.saved_settings.borrow_mut() = Some(self.settings.hard_copy());
+
+        // Shallow clone last settings and return the new element
+        let s = self.settings.clone();
+        Some(DrawableCollection::Highlights(Highlight::new(
+            &s,
+            &self.captured,
+        )))
+    }
+}
+impl DragEnd for Spotlight {
+    fn drag_end(&self) -> Option<DrawableCollection> {
+        // Create an hard copy of the settings for future draws
+        *self.saved_settings.borrow_mut() = Some(self.settings.hard_copy());
+
+        // Shallow clone last settings and return the new element
+        let s = self.settings.clone();
+        Some(DrawableCollection::Spotlights(Spotlight::new(
+            &s,
+            &self.regions,
+        )))
+    }
+}
+impl DragEnd for Magnifier {
+    fn drag_end(&self) -> Option<DrawableCollection> {
+        // The first drag only picks the source, an empty one is ignored
+        if self.picking_source.get() {
+            if self.end_x.get() != 0.0 && self.end_y.get() != 0.0 {
+                self.picking_source.set(false);
+            }
+            return None;
+        }
+
+        // Create an hard copy of the settings for future draws
+        *self.saved_settings.borrow_mut() = Some(self.settings.hard_copy());
+
+        // Shallow clone last settings and return the new element
+        let s = self.settings.clone();
+        Some(DrawableCollection::Magnifiers(Magnifier::new(
+            &s,
+            &self.captured,
+        )))
+    }
+}
+impl DragEnd for Pixelate {
+    fn drag_end(&self) -> Option<DrawableCollection> {
+        // Create an hard copy of the settings for future draws
+        *self.saved_settings.borrow_mut() = Some(self.settings.hard_copy());
+
+        // The tool may have been changed in the meantime
+        Some(new_obfuscate_drawable(&self.settings, &self.captured))
+    }
+}
+impl DragEnd for Blur {
+    fn drag_end(&self) -> Option<DrawableCollection> {
+        // Create an hard copy of the settings for future draws
+        *self.saved_settings.borrow_mut() = Some(self.settings.hard_copy());
+
+        // The tool may have been changed in the meantime
+        Some(new_obfuscate_drawable(&self.settings, &self.captured))
+    }
+}
+impl DragEnd for DrawableCollection {
+    fn drag_end(&self) -> Option<DrawableCollection> {
+        match self {
+            DrawableCollection::AreaBoxes(state) => state.drag_end(),
+            DrawableCollection::Polygons(state) => state.drag_end(),
+            DrawableCollection::Redacts(state) => state.drag_end(),
+            DrawableCollection::Highlights(state) => state.drag_end(),
+            DrawableCollection::Stamps(state) => state.drag_end(),
+            DrawableCollection::Measures(state) => state.drag_end(),
+            DrawableCollection::Eyedroppers(state) => state.drag_end(),
+            DrawableCollection::Images(state) => state.drag_end(),
+            DrawableCollection::Spotlights(state) => state.drag_end(),
+            DrawableCollection::Magnifiers(state) => state.drag_end(),
+            DrawableCollection::Pixelates(state) => state.drag_end(),
+            DrawableCollection::Blurs(state) => state.drag_end(),
+            DrawableCollection::Arcs(state) => state.drag_end(),
+            DrawableCollection::Lines(state) => state.drag_end(),
+            DrawableCollection::Arrows(state) => state.drag_end(),
+            DrawableCollection::FreeHands(state) => state.drag_end(),
+            DrawableCollection::NumberedCircles(state) => state.drag_end(),
+            DrawableCollection::InputTexts(state) => state.drag_end(),
+            DrawableCollection::Callouts(state) => state.drag_end(),
+            DrawableCollection::Groups(_) => None,
+        }
+    }
+}
+
+// ControllerKey
+pub trait ControllerKey {
+    fn stop_controller_key(&self) -> Option<DrawableCollection>;
+}
+
+impl ControllerKey for AreaBox {
+    fn stop_controller_key(&self) -> Option<DrawableCollection> {
+        None
+    }
+}
+impl ControllerKey for Arc {
+    fn stop_controller_key(&self) -> Option<DrawableCollection> {
+        None
+    }
+}
+impl ControllerKey for Line {
+    fn stop_controller_key(&self) -> Option<DrawableCollection> {
+        None
+    }
+}
+impl ControllerKey for Arrow {
+    fn stop_controller_key(&self) -> Option<DrawableCollection> {
+        None
+    }
+}
+impl ControllerKey for FreeHandDraw {
+    fn stop_controller_key(&self) -> Option<DrawableCollection> {
+        None
+    }
+}
+impl ControllerKey for NumberedCircle {
+    fn stop_controller_key(&self) -> Option<DrawableCollection> {
+        None
+    }
+}
+impl ControllerKey for InputText {
+    fn stop_controller_key(&self) -> Option<DrawableCollection> {
+        println!("stopcontrollerkey");
+        // Create an hard copy of the settings for future draws
+        *self.saved_settings.borrow_mut() = Some(self.settings.hard_copy());
+
+        // Shallow clone last settings and return the new element
+        let s = self.settings.clone();
+
+        Some(DrawableCollection::InputTexts(InputText::new(&s)))
+    }
+}
+impl ControllerKey for Callout {
+    fn stop_controller_key(&self) -> Option<DrawableCollection> {
+        None
+    }
+}
+impl ControllerKey for Polygon {
+    fn stop_controller_key(&self) -> Option<DrawableCollection> {
+        None
+    }
+}
+impl ControllerKey for Redact {
+    fn stop_controller_key(&self) -> Option<DrawableCollection> {
+        None
+    }
+}
+impl ControllerKey for Stamp {
+    fn stop_controller_key(&self) -> Option<DrawableCollection> {
+        None
+    }
+}
+impl ControllerKey for Image {
+    fn stop_controller_key(&self) -> Option<DrawableCollection> {
+        None
+    }
+}
+impl ControllerKey for Measure {
+    fn stop_controller_key(&self) -> Option<DrawableCollection> {
+        None
+    }
+}
+impl ControllerKey for Eyedropper {
+    fn stop_controller_key(&self) -> Option<DrawableCollection> {
+        None
+    }
+}
+impl ControllerKey for Highlight {
+    fn stop_controller_key(&self) -> Option<DrawableCollection> {
+        None
+    }
+}
+impl ControllerKey for Spotlight {
+    fn stop_controller_key(&self) -> Option<DrawableCollection> {
+        None
+    }
+}
+impl ControllerKey for Magnifier {
+    fn stop_controller_key(&self) -> Option<DrawableCollection> {
+        None
+    }
+}
+impl ControllerKey for Pixelate {
+    fn stop_controller_key(&self) -> Option<DrawableCollection> {
+        None
+    }
+}
+impl ControllerKey for Blur {
+    fn stop_controller_key(&self) -> Option<DrawableCollection> {
+        None
+    }
+}
+impl ControllerKey for DrawableCollection {
+    fn stop_controller_key(&self) -> Option<DrawableCollection> {
+        match self {
+            DrawableCollection::AreaBoxes(state) => state.stop_controller_key(),
+            DrawableCollection::Polygons(state) => state.stop_controller_key(),
+            DrawableCollection::Redacts(state) => state.stop_controller_key(),
+            DrawableCollection::Highlights(state) => state.stop_controller_key(),
+            DrawableCollection::Stamps(state) => state.stop_controller_key(),
+            DrawableCollection::Measures(state) => state.stop_controller_key(),
+            DrawableCollection::Eyedroppers(state) => state.stop_controller_key(),
+            DrawableCollection::Images(state) => state.stop_controller_key(),
+            DrawableCollection::Spotlights(state) => state.stop_controller_key(),
+            DrawableCollection::Magnifiers(state) => state.stop_controller_key(),
+            DrawableCollection::Pixelates(state) => state.stop_controller_key(),
+            DrawableCollection::Blurs(state) => state.stop_controller_key(),
+            DrawableCollection::Arcs(state) => state.stop_controller_key(),
+            DrawableCollection::Lines(state) => state.stop_controller_key(),
+            DrawableCollection::Arrows(state) => state.stop_controller_key(),
+            DrawableCollection::FreeHands(state) => state.stop_controller_key(),
+            DrawableCollection::NumberedCircles(state) => state.stop_controller_key(),
+            DrawableCollection::InputTexts(state) => state.stop_controller_key(),
+            DrawableCollection::Callouts(state) => state.stop_controller_key(),
+            DrawableCollection::Groups(_) => None,
+        }
+    }
+}
+
+// Draw trait
+pub trait Draw {
+    fn draw(&self, cr: &cairo::Context, pg: &gtk::pango::Layout);
+    fn draw_with_saved_settings(&self, cr: &cairo::Context, pg: &gtk::pango::Layout);
+}
+
+impl Draw for FreeHandDraw {
+    fn draw(&self, cr: &cairo::Context, _pg: &gtk::pango::Layout) {
+        self.draw_freehand(cr, |key| self.settings.freehand.get_value(key));
+    }
+
+    fn draw_with_saved_settings(&self, cr: &cairo::Context, _pg: &gtk::pango::Layout) {
+        if let Some(settings) = self.saved_settings.borrow().as_ref() {
+            self.draw_freehand(cr, |key| settings.freehand.get_value(key));
+        } else {
+            println!("Warning! saved_settings is None!");
+        }
+    }
+}
+
+impl Draw for Line {
+    fn draw(&self, cr: &cairo::Context, _pg: &gtk::pango::Layout) {
+        let settings = self.settings.line.clone();
+        // Set color RGBA
+        cr.set_source_rgba(
+            settings
+                .get_value("color_r")
+                .get_f64()
+                .expect("draw line error"),
+            settings
+                .get_value("color_g")
+                .get_f64()
+                .expect("draw line error"),
+            settings
+                .get_value("color_b")
+                .get_f64()
+                .expect("draw line error"),
+            settings
+                .get_value("color_a")
+                .get_f64()
+                .expect("draw line error"),
+        );
+
+        // Draw the line
+        cr.move_to(self.x1.get(), self.y1.get());
+        cr.line_to(self.x2.get(), self.y2.get());
+
+        // Set line properties
+        cr.set_line_width(
+            settings
+                .get_value("size")
+                .get_f64()
+                .expect("draw line error"),
+        );
+
+        // Set line cap
+        match settings
+            .get_value("line_cap")
+            .get_string()
+            .expect("No line_cap value found")
+            .as_str()
+        {
+            "round" => cr.set_line_cap(cairo::LineCap::Round),
+            "butt" => cr.set_line_cap(cairo::LineCap::Butt),
+            "square" => cr.set_line_cap(cairo::LineCap::Square),
+            _ => println!("Error, no line_cap found"),
+        };
+
+        // Set line join
+        match settings
+            .get_value("line_join")
+            .get_string()
+            .expect("No line_join value found")
+            .as_str()
+        {
+            "round" => cr.set_line_join(cairo::LineJoin::Round),
+            "miter" => cr.set_line_join(cairo::LineJoin::Miter),
+            "bevel" => cr.set_line_join(cairo::LineJoin::Bevel),
+            _ => println!("Error, no line_join found"),
+        };
+
+        // Set dash pattern
+        set_dash_style(
+            cr,
+            &settings.get_value("dash").get_string().unwrap_or_default(),
+            &settings
+                .get_value("dash_pattern")
+                .get_string()
+                .unwrap_or_default(),
+            settings.get_value("size").get_f64().unwrap_or(1.0),
+        );
+        cr.stroke().unwrap();
+        cr.set_dash(&[], 0.0);
+    }
+    fn draw_with_saved_settings(&self, cr: &cairo::Context, _pg: &gtk::pango::Layout) {
+        let settings_hard_copy = self.saved_settings.borrow().clone();
+        if let Some(settings_to_unwrap) = settings_hard_copy {
+            let settings = settings_to_unwrap.line;
+            // Set color RGBA
+            cr.set_source_rgba(
+                settings
+                    .get_value("color_r")
+                    .get_f64()
+                    .expect("draw line error"),
+                settings
+                    .get_value("color_g")
+                    .get_f64()
+                    .expect("draw line error"),
+                settings
+                    .get_value("color_b")
+                    .get_f64()
+                    .expect("draw line error"),
+                settings
+                    .get_value("color_a")
+                    .get_f64()
+                    .expect("draw line error"),
+            );
+
+            // Draw the line
+            cr.move_to(self.x1.get(), self.y1.get());
+            cr.line_to(self.x2.get(), self.y2.get());
+
+            // Set line properties
+            cr.set_line_width(
+                settings
+                    .get_value("size")
+                    .get_f64()
+                    .expect("draw line error"),
+            );
+
+            // Set line cap
+            match settings
+                .get_value("line_cap")
+                .get_string()
+                .expect("No line_cap value found")
+                .as_str()
+            {
+                "round" => cr.set_line_cap(cairo::LineCap::Round),
+                "butt" => cr.set_line_cap(cairo::LineCap::Butt),
+                "square" => cr.set_line_cap(cairo::LineCap::Square),
+                _ => println!("Error, no line_cap found"),
+            };
+
+            // Set line join
+            match settings
+                .get_value("line_join")
+                .get_string()
+                .expect("No line_join value found")
+                .as_str()
+            {
+                "round" => cr.set_line_join(cairo::LineJoin::Round),
+                "miter" => cr.set_line_join(cairo::LineJoin::Miter),
+                "bevel" => cr.set_line_join(cairo::LineJoin::Bevel),
+                _ => println!("Error, no line_join found"),
+            };
+
+            // Set dash pattern
+            set_dash_style(
+                cr,
+                &settings.get_value("dash").get_string().unwrap_or_default(),
+                &settings
+                    .get_value("dash_pattern")
+                    .get_string()
+                    .unwrap_or_default(),
+                settings.get_value("size").get_f64().unwrap_or(1.0),
+            );
+            cr.stroke().unwrap();
+            cr.set_dash(&[], 0.0);
+        } else {
+            println!("Warning! saved_settings is None!");
+        }
+    }
+}
+impl Draw for Arrow {
+    fn draw(&self, cr: &cairo::Context, _pg: &gtk::pango::Layout) {
+        let settings = self.settings.arrow.clone();
+        self.draw_arrow(cr, |key| settings.get_value(key));
+    }
+    fn draw_with_saved_settings(&self, cr: &cairo::Context, _pg: &gtk::pango::Layout) {
+        let settings_hard_copy = self.saved_settings.borrow().clone();
+        if let Some(settings_to_unwrap) = settings_hard_copy {
+            let settings = settings_to_unwrap.arrow;
+            self.draw_arrow(cr, |key| settings.get_value(key));
+        } else {
+            println!("Warning! saved_settings is None!");
+        }
+    }
+}
+impl Draw for Arc {
+    fn draw(&self, cr: &cairo::Context, _pg: &gtk::pango::Layout) {
+        let settings = self.settings.arc.clone();
+
+        if settings.get_value("fill").get_bool().expect("draw error") {
+            self.build_path(cr, |key| settings.get_value(key));
+            set_fill_source(cr, |key| settings.get_value(key));
+            cr.fill().expect("No arc fill to unwrap");
+            cr.stroke().unwrap();
+        }
+
+        if settings.get_value("border").get_bool().expect("draw error") {
+            self.build_path(cr, |key| settings.get_value(key));
+
+            cr.set_source_rgba(
+                settings
                     .get_value("border_r")
                     .get_f64()
                     .expect("draw error"),
@@ -1335,388 +3421,1785 @@ impl Draw for NumberedCircle {
                 settings
                     .get_value("border_a")
                     .get_f64()
-                    .expect("draw error"),
+                    .expect("draw error"),
+            );
+            let border_size = settings
+                .get_value("border_size")
+                .get_f64()
+                .expect("draw error");
+            cr.set_line_width(border_size);
+            cr.stroke().unwrap();
+        }
+    }
+    fn draw_with_saved_settings(&self, cr: &cairo::Context, _pg: &gtk::pango::Layout) {
+        // Get the saved settings
+        let settings_hard_copy = self.saved_settings.borrow().clone();
+        if let Some(settings_to_unwrap) = settings_hard_copy {
+            let settings = settings_to_unwrap.arc;
+
+            if settings.get_value("fill").get_bool().expect("draw error") {
+                self.build_path(cr, |key| settings.get_value(key));
+                set_fill_source(cr, |key| settings.get_value(key));
+                cr.fill().expect("No arc fill to unwrap");
+                cr.stroke().unwrap();
+            }
+
+            if settings.get_value("border").get_bool().expect("draw error") {
+                self.build_path(cr, |key| settings.get_value(key));
+
+                cr.set_source_rgba(
+                    settings
+                        .get_value("border_r")
+                        .get_f64()
+                        .expect("draw error"),
+                    settings
+                        .get_value("border_g")
+                        .get_f64()
+                        .expect("draw error"),
+                    settings
+                        .get_value("border_b")
+                        .get_f64()
+                        .expect("draw error"),
+                    settings
+                        .get_value("border_a")
+                        .get_f64()
+                        .expect("draw error"),
+                );
+                let border_size = settings
+                    .get_value("border_size")
+                    .get_f64()
+                    .expect("draw error");
+                cr.set_line_width(border_size);
+                cr.stroke().unwrap();
+            }
+        } else {
+            println!("Warning! saved_settings is None!");
+        }
+    }
+}
+impl Draw for AreaBox {
+    fn draw(&self, cr: &cairo::Context, _pg: &gtk::pango::Layout) {
+        let settings = self.settings.rect.clone();
+        self.draw_box(cr, |key| settings.get_value(key));
+    }
+    fn draw_with_saved_settings(&self, cr: &cairo::Context, _pg: &gtk::pango::Layout) {
+        let settings_hard_copy = self.saved_settings.borrow().clone();
+        if let Some(settings_to_unwrap) = settings_hard_copy {
+            let settings = settings_to_unwrap.rect;
+            self.draw_box(cr, |key| settings.get_value(key));
+        } else {
+            println!("AreaBox error, no save_settings!");
+        }
+    }
+}
+impl Draw for NumberedCircle {
+    fn draw(&self, cr: &cairo::Context, pg: &gtk::pango::Layout) {
+        let get = |key: &str| self.settings.numbered_circle.get_value(key);
+        let number = get("number").get_i32().expect("NumberedCircle::new error");
+        self.draw_badge(cr, pg, number, get);
+    }
+    fn draw_with_saved_settings(&self, cr: &cairo::Context, pg: &gtk::pango::Layout) {
+        if let Some(settings) = self.saved_settings.borrow().as_ref() {
+            self.draw_badge(cr, pg, self.number.get(), |key| {
+                settings.numbered_circle.get_value(key)
+            });
+        } else {
+            println!("Warning! saved_settings is None!");
+        }
+    }
+}
+impl Draw for InputText {
+    fn draw(&self, cr: &cairo::Context, pg: &gtk::pango::Layout) {
+        self.draw_text(cr, pg, |key| self.settings.input_text.get_value(key));
+    }
+    fn draw_with_saved_settings(&self, cr: &cairo::Context, pg: &gtk::pango::Layout) {
+        if let Some(settings) = self.saved_settings.borrow().as_ref() {
+            self.draw_text(cr, pg, |key| settings.input_text.get_value(key));
+        } else {
+            println!("Warning! saved_settings is None!");
+        }
+    }
+}
+impl Draw for Callout {
+    fn draw(&self, cr: &cairo::Context, pg: &gtk::pango::Layout) {
+        self.draw_callout(
+            cr,
+            pg,
+            |key| self.settings.input_text.get_value(key),
+            |key| self.settings.callout.get_value(key),
+        );
+    }
+    fn draw_with_saved_settings(&self, cr: &cairo::Context, pg: &gtk::pango::Layout) {
+        if let Some(settings) = self.saved_settings.borrow().as_ref() {
+            self.draw_callout(
+                cr,
+                pg,
+                |key| settings.input_text.get_value(key),
+                |key| settings.callout.get_value(key),
             );
-            let border_size = settings
-                .get_value("border_size")
+        } else {
+            println!("Warning! saved_settings is None!");
+        }
+    }
+}
+impl Draw for Polygon {
+    fn draw(&self, cr: &cairo::Context, _pg: &gtk::pango::Layout) {
+        self.draw_polygon(cr, |key| self.settings.polygon.get_value(key));
+    }
+    fn draw_with_saved_settings(&self, cr: &cairo::Context, _pg: &gtk::pango::Layout) {
+        if let Some(settings) = self.saved_settings.borrow().as_ref() {
+            self.draw_polygon(cr, |key| settings.polygon.get_value(key));
+        } else {
+            println!("Warning! saved_settings is None!");
+        }
+    }
+}
+impl Draw for Redact {
+    fn draw(&self, cr: &cairo::Context, _pg: &gtk::pango::Layout) {
+        let ((x, y, w, h), [r, g, b]) = self.redaction();
+
+        // Always opaque and without antialiasing, so no pixel underneath is blended in
+        cr.save().expect("Failed to save the context");
+        cr.set_antialias(cairo::Antialias::None);
+        cr.set_operator(cairo::Operator::Source);
+        cr.rectangle(x, y, w, h);
+        cr.set_source_rgba(r as f64 / 255.0, g as f64 / 255.0, b as f64 / 255.0, 1.0);
+        cr.fill().expect("Failed to fill the redaction");
+        cr.restore().expect("Failed to restore the context");
+    }
+    fn draw_with_saved_settings(&self, cr: &cairo::Context, pg: &gtk::pango::Layout) {
+        // redaction() already reads the saved settings when present
+        self.draw(cr, pg);
+    }
+}
+
+impl Draw for Stamp {
+    fn draw(&self, cr: &cairo::Context, _pg: &gtk::pango::Layout) {
+        let Some(image) = self.image() else {
+            return;
+        };
+        // The painted image may be larger than the natural one, the stamp keeps its size
+        let painted = self.image_for(cr, &image);
+        let scale = self.scale.get() * image.width() as f64 / painted.width().max(1) as f64;
+        paint_image(
+            cr,
+            &painted,
+            self.x.get(),
+            self.y.get(),
+            scale,
+            self.rotation(),
+        );
+    }
+    fn draw_with_saved_settings(&self, cr: &cairo::Context, pg: &gtk::pango::Layout) {
+        // file and rotation are read from the saved settings when present
+        self.draw(cr, pg);
+    }
+}
+
+impl Draw for Image {
+    fn draw(&self, cr: &cairo::Context, _pg: &gtk::pango::Layout) {
+        let surface = self.surface.borrow();
+        let Some(surface) = surface.as_ref() else {
+            return;
+        };
+        let (x, y, w, h) = bounds_from_corners(
+            self.x.get(),
+            self.y.get(),
+            self.x.get() + self.width.get(),
+            self.y.get() + self.height.get(),
+        );
+        paint_image(
+            cr,
+            surface,
+            x + w / 2.0,
+            y + h / 2.0,
+            w / surface.width().max(1) as f64,
+            0.0,
+        );
+    }
+    fn draw_with_saved_settings(&self, cr: &cairo::Context, pg: &gtk::pango::Layout) {
+        // the picture has no settings
+        self.draw(cr, pg);
+    }
+}
+
+impl Draw for Measure {
+    fn draw(&self, cr: &cairo::Context, pg: &gtk::pango::Layout) {
+        self.draw_measure(cr, pg, |key| self.settings.measure.get_value(key));
+    }
+    fn draw_with_saved_settings(&self, cr: &cairo::Context, pg: &gtk::pango::Layout) {
+        if let Some(settings) = self.saved_settings.borrow().as_ref() {
+            self.draw_measure(cr, pg, |key| settings.measure.get_value(key));
+        } else {
+            println!("Warning! saved_settings is None!");
+        }
+    }
+}
+
+impl Draw for Eyedropper {
+    fn draw(&self, cr: &cairo::Context, pg: &gtk::pango::Layout) {
+        if self.drawing.get() {
+            self.draw_eyedropper(cr, pg);
+        }
+    }
+    fn draw_with_saved_settings(&self, _cr: &cairo::Context, _pg: &gtk::pango::Layout) {
+        // never stored in the drawn items
+    }
+}
+
+impl Draw for Highlight {
+    fn draw(&self, cr: &cairo::Context, _pg: &gtk::pango::Layout) {
+        self.draw_box(cr, |key| self.settings.highlight.get_value(key));
+    }
+    fn draw_with_saved_settings(&self, cr: &cairo::Context, _pg: &gtk::pango::Layout) {
+        if let Some(settings) = self.saved_settings.borrow().as_ref() {
+            self.draw_box(cr, |key| settings.highlight.get_value(key));
+        } else {
+            println!("Warning! saved_settings is None!");
+        }
+    }
+}
+
+impl Draw for Spotlight {
+    fn draw(&self, cr: &cairo::Context, _pg: &gtk::pango::Layout) {
+        let regions = self.visible_regions();
+
+        // Only the first visible spotlight paints the dimmed layer, with a hole for every one
+        let Some(first) = regions.first() else {
+            return;
+        };
+        if !Rc::ptr_eq(first, &self.region) {
+            return;
+        }
+
+        // A single dimmed layer, as dark as the strongest spotlight
+        let dim = regions
+            .iter()
+            .map(|region| region.dim.get().clamp(0.0, 1.0))
+            .fold(0.0, f64::max);
+        cr.save().expect("Failed to save the context");
+        cr.push_group();
+
+        cr.set_source_rgba(0.0, 0.0, 0.0, dim);
+        cr.paint().expect("Failed to dim the screen");
+
+        cr.set_operator(cairo::Operator::Clear);
+        for region in &regions {
+            let (x, y) = (region.start_x.get(), region.start_y.get());
+            let (w, h) = (region.end_x.get(), region.end_y.get());
+            if region.ellipse.get() {
+                cr.save().expect("Failed to save the context");
+                cr.translate(x + w / 2.0, y + h / 2.0);
+                cr.scale(w.abs() / 2.0, h.abs() / 2.0);
+                cr.arc(0.0, 0.0, 1.0, 0.0, 2.0 * f64::consts::PI);
+                cr.restore().expect("Failed to restore the context");
+            } else {
+                cr.rectangle(x, y, w, h);
+            }
+            cr.fill().expect("Failed to fill the spotlight");
+        }
+
+        cr.pop_group_to_source()
+            .expect("Failed to compose the spotlights");
+        cr.paint().expect("Failed to paint the spotlights");
+        cr.restore().expect("Failed to restore the context");
+    }
+    fn draw_with_saved_settings(&self, cr: &cairo::Context, pg: &gtk::pango::Layout) {
+        // shape and dim are stored in the region when the drag begins
+        self.draw(cr, pg);
+    }
+}
+
+impl Draw for Magnifier {
+    fn draw(&self, cr: &cairo::Context, _pg: &gtk::pango::Layout) {
+        self.draw_magnifier(cr, |key| self.settings.magnifier.get_value(key));
+    }
+    fn draw_with_saved_settings(&self, cr: &cairo::Context, _pg: &gtk::pango::Layout) {
+        if let Some(settings) = self.saved_settings.borrow().as_ref() {
+            self.draw_magnifier(cr, |key| settings.magnifier.get_value(key));
+        } else {
+            println!("Warning! saved_settings is None!");
+        }
+    }
+}
+
+/// Paint the (x, y, w, h) region of the captured screen processed by `filter`.
+/// If the screen could not be captured the region is painted black, so that its content stays
+/// hidden anyway.
+fn draw_filtered_region<F>(
+    cr: &cairo::Context,
+    captured: &CapturedScreen,
+    cache: &FilterCache,
+    rect: (f64, f64, f64, f64),
+    strength: f64,
+    filter: F,
+) where
+    F: Fn(&mut [u8], i32, i32, i32),
+{
+    let (x, y, w, h) = rect;
+    let Some(mut region) = captured.region(x, y, w, h) else {
+        if !captured.is_available() {
+            cr.rectangle(x, y, w, h);
+            cr.set_source_rgba(0.0, 0.0, 0.0, 1.0);
+            cr.fill().expect("Failed to fill the region");
+        }
+        return;
+    };
+
+    // The strength is expressed in window pixels
+    let scale = captured.scale();
+    let strength = (strength * scale).round() as i32;
+    let key = (region.x, region.y, region.width, region.height, strength);
+
+    let mut cached = cache.cached.borrow_mut();
+    let surface = match cached.as_ref() {
+        Some((cached_key, surface)) if *cached_key == key => surface.clone(),
+        _ => {
+            filter(&mut region.data, region.width, region.height, strength);
+            let surface = region
+                .to_surface()
+                .expect("Failed to create the region surface");
+            *cached = Some((key, surface.clone()));
+            surface
+        }
+    };
+
+    cr.save().expect("Failed to save the context");
+    cr.scale(1.0 / scale, 1.0 / scale);
+    cr.rectangle(
+        region.x as f64,
+        region.y as f64,
+        region.width as f64,
+        region.height as f64,
+    );
+    cr.set_source_surface(&surface, region.x as f64, region.y as f64)
+        .expect("Failed to set the region surface");
+    cr.fill().expect("Failed to fill the region");
+    cr.restore().expect("Failed to restore the context");
+}
+
+impl Draw for Pixelate {
+    fn draw(&self, cr: &cairo::Context, _pg: &gtk::pango::Layout) {
+        let pixel_size = self
+            .settings
+            .obfuscate
+            .get_value("pixel_size")
+            .get_f64()
+            .expect("draw error");
+        draw_filtered_region(
+            cr,
+            &self.captured,
+            &self.cache,
+            (
+                self.start_x.get(),
+                self.start_y.get(),
+                self.end_x.get(),
+                self.end_y.get(),
+            ),
+            pixel_size,
+            filters::pixelate,
+        );
+    }
+    fn draw_with_saved_settings(&self, cr: &cairo::Context, pg: &gtk::pango::Layout) {
+        let settings_hard_copy = self.saved_settings.borrow().clone();
+        if let Some(settings) = settings_hard_copy {
+            let pixel_size = settings
+                .obfuscate
+                .get_value("pixel_size")
                 .get_f64()
                 .expect("draw error");
-            cr.set_line_width(border_size);
-            cr.stroke().unwrap();
+            draw_filtered_region(
+                cr,
+                &self.captured,
+                &self.cache,
+                (
+                    self.start_x.get(),
+                    self.start_y.get(),
+                    self.end_x.get(),
+                    self.end_y.get(),
+                ),
+                pixel_size,
+                filters::pixelate,
+            );
         } else {
-            cr.new_path();
+            self.draw(cr, pg);
+        }
+    }
+}
+impl Draw for Blur {
+    fn draw(&self, cr: &cairo::Context, _pg: &gtk::pango::Layout) {
+        let blur_radius = self
+            .settings
+            .obfuscate
+            .get_value("blur_radius")
+            .get_f64()
+            .expect("draw error");
+        draw_filtered_region(
+            cr,
+            &self.captured,
+            &self.cache,
+            (
+                self.start_x.get(),
+                self.start_y.get(),
+                self.end_x.get(),
+                self.end_y.get(),
+            ),
+            blur_radius,
+            filters::gaussian_blur,
+        );
+    }
+    fn draw_with_saved_settings(&self, cr: &cairo::Context, pg: &gtk::pango::Layout) {
+        let settings_hard_copy = self.saved_settings.borrow().clone();
+        if let Some(settings) = settings_hard_copy {
+            let blur_radius = settings
+                .obfuscate
+                .get_value("blur_radius")
+                .get_f64()
+                .expect("draw error");
+            draw_filtered_region(
+                cr,
+                &self.captured,
+                &self.cache,
+                (
+                    self.start_x.get(),
+                    self.start_y.get(),
+                    self.end_x.get(),
+                    self.end_y.get(),
+                ),
+                blur_radius,
+                filters::gaussian_blur,
+            );
+        } else {
+            self.draw(cr, pg);
+        }
+    }
+}
+impl Draw for Group {
+    fn draw(&self, cr: &cairo::Context, pg: &gtk::pango::Layout) {
+        // A group only contains items that were already drawn, so they always use their own
+        // saved settings
+        self.draw_with_saved_settings(cr, pg);
+    }
+    fn draw_with_saved_settings(&self, cr: &cairo::Context, pg: &gtk::pango::Layout) {
+        for item in self.items.borrow().iter() {
+            item.draw_with_saved_settings(cr, pg);
+        }
+    }
+}
+impl Draw for DrawableCollection {
+    fn draw(&self, cr: &cairo::Context, pg: &gtk::pango::Layout) {
+        match self {
+            DrawableCollection::FreeHands(state) => {
+                state.draw(cr, pg);
+            }
+            DrawableCollection::AreaBoxes(state) => {
+                state.draw(cr, pg);
+            }
+            DrawableCollection::Polygons(state) => {
+                state.draw(cr, pg);
+            }
+            DrawableCollection::Redacts(state) => {
+                state.draw(cr, pg);
+            }
+            DrawableCollection::Highlights(state) => {
+                state.draw(cr, pg);
+            }
+            DrawableCollection::Stamps(state) => {
+                state.draw(cr, pg);
+            }
+            DrawableCollection::Measures(state) => {
+                state.draw(cr, pg);
+            }
+            DrawableCollection::Eyedroppers(state) => {
+                state.draw(cr, pg);
+            }
+            DrawableCollection::Images(state) => {
+                state.draw(cr, pg);
+            }
+            DrawableCollection::Spotlights(state) => {
+                state.draw(cr, pg);
+            }
+            DrawableCollection::Magnifiers(state) => {
+                state.draw(cr, pg);
+            }
+            DrawableCollection::Pixelates(state) => {
+                state.draw(cr, pg);
+            }
+            DrawableCollection::Blurs(state) => {
+                state.draw(cr, pg);
+            }
+            DrawableCollection::Lines(state) => {
+                state.draw(cr, pg);
+            }
+            DrawableCollection::Arcs(state) => {
+                state.draw(cr, pg);
+            }
+            DrawableCollection::Arrows(state) => {
+                state.draw(cr, pg);
+            }
+            DrawableCollection::NumberedCircles(state) => {
+                state.draw(cr, pg);
+            }
+            DrawableCollection::InputTexts(state) => {
+                state.draw(cr, pg);
+            }
+            DrawableCollection::Callouts(state) => {
+                state.draw(cr, pg);
+            }
+            DrawableCollection::Groups(state) => {
+                state.draw(cr, pg);
+            }
+        }
+    }
+    fn draw_with_saved_settings(&self, cr: &cairo::Context, pg: &gtk::pango::Layout) {
+        match self {
+            DrawableCollection::FreeHands(state) => {
+                state.draw_with_saved_settings(cr, pg);
+            }
+            DrawableCollection::AreaBoxes(state) => {
+                state.draw_with_saved_settings(cr, pg);
+            }
+            DrawableCollection::Polygons(state) => {
+                state.draw_with_saved_settings(cr, pg);
+            }
+            DrawableCollection::Redacts(state) => {
+                state.draw_with_saved_settings(cr, pg);
+            }
+            DrawableCollection::Highlights(state) => {
+                state.draw_with_saved_settings(cr, pg);
+            }
+            DrawableCollection::Stamps(state) => {
+                state.draw_with_saved_settings(cr, pg);
+            }
+            DrawableCollection::Measures(state) => {
+                state.draw_with_saved_settings(cr, pg);
+            }
+            DrawableCollection::Eyedroppers(state) => {
+                state.draw_with_saved_settings(cr, pg);
+            }
+            DrawableCollection::Images(state) => {
+                state.draw_with_saved_settings(cr, pg);
+            }
+            DrawableCollection::Spotlights(state) => {
+                state.draw_with_saved_settings(cr, pg);
+            }
+            DrawableCollection::Magnifiers(state) => {
+                state.draw_with_saved_settings(cr, pg);
+            }
+            DrawableCollection::Pixelates(state) => {
+                state.draw_with_saved_settings(cr, pg);
+            }
+            DrawableCollection::Blurs(state) => {
+                state.draw_with_saved_settings(cr, pg);
+            }
+            DrawableCollection::Lines(state) => {
+                state.draw_with_saved_settings(cr, pg);
+            }
+            DrawableCollection::Arcs(state) => {
+                state.draw_with_saved_settings(cr, pg);
+            }
+            DrawableCollection::Arrows(state) => {
+                state.draw_with_saved_settings(cr, pg);
+            }
+            DrawableCollection::NumberedCircles(state) => {
+                state.draw_with_saved_settings(cr, pg);
+            }
+            DrawableCollection::InputTexts(state) => {
+                state.draw(cr, pg);
+            }
+            DrawableCollection::Callouts(state) => {
+                state.draw_with_saved_settings(cr, pg);
+            }
+            DrawableCollection::Groups(state) => {
+                state.draw_with_saved_settings(cr, pg);
+            }
         }
+    }
+}
 
-        // Set text properties
-        cr.set_source_rgba(
-            settings.get_value("font_r").get_f64().expect("error"),
-            settings.get_value("font_g").get_f64().expect("error"),
-            settings.get_value("font_b").get_f64().expect("error"),
-            settings.get_value("font_a").get_f64().expect("error"),
-        );
-        // Font face
-        let font_face = settings
-            .get_value("font_face")
-            .get_string()
-            .expect("NumberedCircle font_face error");
-        let fd: gtk::pango::FontDescription =
-            gtk::pango::FontDescription::from_string(font_face.as_str());
-        pg.set_font_description(Some(&fd));
+// Bounds trait
+pub trait Bounds {
+    /// Bounding box of the drawable as (x, y, width, height), in drawing area coordinates.
+    fn bounds(&self) -> Option<(f64, f64, f64, f64)>;
+}
 
-        // Draw text in the center of the rectangle
-        let num = settings
-            .get_value("number")
-            .get_i32()
-            .expect("NumberedCircle::new error");
-        let text = num.to_string();
-        pg.set_text(&text);
+/// Build a bounding box from two opposite corners
+fn bounds_from_corners(x1: f64, y1: f64, x2: f64, y2: f64) -> (f64, f64, f64, f64) {
+    (x1.min(x2), y1.min(y2), (x2 - x1).abs(), (y2 - y1).abs())
+}
 
-        // Get logical extents (in Pango units) and convert to device units.
-        let (_ink_rect, logical_rect) = pg.extents();
-        let text_width = logical_rect.width() as f64 / gtk::pango::SCALE as f64;
-        let text_height = logical_rect.height() as f64 / gtk::pango::SCALE as f64;
+/// Smallest bounding box containing both the boxes
+fn bounds_union(a: (f64, f64, f64, f64), b: (f64, f64, f64, f64)) -> (f64, f64, f64, f64) {
+    bounds_from_corners(
+        a.0.min(b.0),
+        a.1.min(b.1),
+        (a.0 + a.2).max(b.0 + b.2),
+        (a.1 + a.3).max(b.1 + b.3),
+    )
+}
+
+impl Bounds for AreaBox {
+    fn bounds(&self) -> Option<(f64, f64, f64, f64)> {
+        // end_x and end_y are the width and the height of the rectangle
+        let (x, y) = (self.start_x.get(), self.start_y.get());
+        Some(bounds_from_corners(
+            x,
+            y,
+            x + self.end_x.get(),
+            y + self.end_y.get(),
+        ))
+    }
+}
+impl Bounds for Arc {
+    fn bounds(&self) -> Option<(f64, f64, f64, f64)> {
+        let shape = match self.saved_settings.borrow().as_ref() {
+            Some(settings) => settings.arc.get_value("shape"),
+            None => self.settings.arc.get_value("shape"),
+        }
+        .get_string()
+        .unwrap_or_default();
+        let (cx, cy, rx, ry) = self.geometry(&shape);
+        Some((cx - rx, cy - ry, 2.0 * rx, 2.0 * ry))
+    }
+}
+impl Bounds for Line {
+    fn bounds(&self) -> Option<(f64, f64, f64, f64)> {
+        Some(bounds_from_corners(
+            self.x1.get(),
+            self.y1.get(),
+            self.x2.get(),
+            self.y2.get(),
+        ))
+    }
+}
+impl Bounds for Arrow {
+    fn bounds(&self) -> Option<(f64, f64, f64, f64)> {
+        let segment =
+            bounds_from_corners(self.x1.get(), self.y1.get(), self.x2.get(), self.y2.get());
+        // The curve is inside the triangle of its end points and control point
+        Some(match self.control_point() {
+            Some((cx, cy)) => bounds_union(segment, bounds_from_corners(cx, cy, cx, cy)),
+            None => segment,
+        })
+    }
+}
+impl Bounds for FreeHandDraw {
+    fn bounds(&self) -> Option<(f64, f64, f64, f64)> {
+        let xs = self.x.borrow();
+        let ys = self.y.borrow();
+        if xs.is_empty() {
+            return None;
+        }
+        let x_min = xs.iter().cloned().fold(f64::INFINITY, f64::min);
+        let x_max = xs.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let y_min = ys.iter().cloned().fold(f64::INFINITY, f64::min);
+        let y_max = ys.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        Some(bounds_from_corners(x_min, y_min, x_max, y_max))
+    }
+}
+impl Bounds for NumberedCircle {
+    fn bounds(&self) -> Option<(f64, f64, f64, f64)> {
+        let radius = match self.saved_settings.borrow().as_ref() {
+            Some(settings) => settings.numbered_circle.get_value("radius").get_f64(),
+            None => self.settings.numbered_circle.get_value("radius").get_f64(),
+        }
+        .ok()?;
+        Some((
+            self.x0.get() - radius,
+            self.y0.get() - radius,
+            2.0 * radius,
+            2.0 * radius,
+        ))
+    }
+}
+impl Bounds for InputText {
+    fn bounds(&self) -> Option<(f64, f64, f64, f64)> {
+        // x and y store the size of the text box computed in the last draw
+        Some((self.x0.get(), self.y0.get(), self.x.get(), self.y.get()))
+    }
+}
+impl Bounds for Callout {
+    fn bounds(&self) -> Option<(f64, f64, f64, f64)> {
+        let (l, t, r, b) = self.bubble();
+        let (tip_x, tip_y) = (self.tip_x.get(), self.tip_y.get());
+        Some(bounds_union(
+            bounds_from_corners(l, t, r, b),
+            bounds_from_corners(tip_x, tip_y, tip_x, tip_y),
+        ))
+    }
+}
+impl Bounds for Polygon {
+    fn bounds(&self) -> Option<(f64, f64, f64, f64)> {
+        let xs = self.x.borrow();
+        let ys = self.y.borrow();
+        if xs.is_empty() {
+            return None;
+        }
+        let x_min = xs.iter().cloned().fold(f64::INFINITY, f64::min);
+        let x_max = xs.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let y_min = ys.iter().cloned().fold(f64::INFINITY, f64::min);
+        let y_max = ys.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        Some(bounds_from_corners(x_min, y_min, x_max, y_max))
+    }
+}
+impl Bounds for Redact {
+    fn bounds(&self) -> Option<(f64, f64, f64, f64)> {
+        let (x, y) = (self.start_x.get(), self.start_y.get());
+        Some(bounds_from_corners(
+            x,
+            y,
+            x + self.end_x.get(),
+            y + self.end_y.get(),
+        ))
+    }
+}
+impl Bounds for Stamp {
+    fn bounds(&self) -> Option<(f64, f64, f64, f64)> {
+        let image = self.image()?;
+        let scale = self.scale.get();
+        Some(rotated_bounds(
+            self.x.get(),
+            self.y.get(),
+            image.width() as f64 * scale,
+            image.height() as f64 * scale,
+            self.rotation(),
+        ))
+    }
+}
+impl Bounds for Image {
+    fn bounds(&self) -> Option<(f64, f64, f64, f64)> {
+        self.surface.borrow().as_ref()?;
+        let (x, y) = (self.x.get(), self.y.get());
+        Some(bounds_from_corners(
+            x,
+            y,
+            x + self.width.get(),
+            y + self.height.get(),
+        ))
+    }
+}
+impl Bounds for Measure {
+    fn bounds(&self) -> Option<(f64, f64, f64, f64)> {
+        let (x, y) = (self.start_x.get(), self.start_y.get());
+        Some(bounds_from_corners(
+            x,
+            y,
+            x + self.end_x.get(),
+            y + self.end_y.get(),
+        ))
+    }
+}
+impl Bounds for Eyedropper {
+    fn bounds(&self) -> Option<(f64, f64, f64, f64)> {
+        None
+    }
+}
+impl Bounds for Highlight {
+    fn bounds(&self) -> Option<(f64, f64, f64, f64)> {
+        let (x, y) = (self.start_x.get(), self.start_y.get());
+        Some(bounds_from_corners(
+            x,
+            y,
+            x + self.end_x.get(),
+            y + self.end_y.get(),
+        ))
+    }
+}
+impl Bounds for Spotlight {
+    fn bounds(&self) -> Option<(f64, f64, f64, f64)> {
+        let region = &self.region;
+        let (x, y) = (region.start_x.get(), region.start_y.get());
+        Some(bounds_from_corners(
+            x,
+            y,
+            x + region.end_x.get(),
+            y + region.end_y.get(),
+        ))
+    }
+}
+impl Bounds for Magnifier {
+    fn bounds(&self) -> Option<(f64, f64, f64, f64)> {
+        let source = self.source();
+        let Some((dx, dy)) = self.dest.get() else {
+            return Some(source);
+        };
+        let get = |key: &str| match self.saved_settings.borrow().as_ref() {
+            Some(settings) => settings.magnifier.get_value(key),
+            None => self.settings.magnifier.get_value(key),
+        };
+        let circle = get("shape").get_string().is_ok_and(|s| s == "circle");
+        let zoom = get("zoom").get_f64().unwrap_or(2.0).max(1.0);
+        let (hw, hh) = self.half_size(circle, zoom);
+        Some(bounds_union(
+            source,
+            bounds_from_corners(dx - hw, dy - hh, dx + hw, dy + hh),
+        ))
+    }
+}
+impl Bounds for Pixelate {
+    fn bounds(&self) -> Option<(f64, f64, f64, f64)> {
+        let (x, y) = (self.start_x.get(), self.start_y.get());
+        Some(bounds_from_corners(
+            x,
+            y,
+            x + self.end_x.get(),
+            y + self.end_y.get(),
+        ))
+    }
+}
+impl Bounds for Blur {
+    fn bounds(&self) -> Option<(f64, f64, f64, f64)> {
+        let (x, y) = (self.start_x.get(), self.start_y.get());
+        Some(bounds_from_corners(
+            x,
+            y,
+            x + self.end_x.get(),
+            y + self.end_y.get(),
+        ))
+    }
+}
+impl Bounds for Group {
+    fn bounds(&self) -> Option<(f64, f64, f64, f64)> {
+        self.items
+            .borrow()
+            .iter()
+            .filter_map(|item| item.bounds())
+            .reduce(bounds_union)
+    }
+}
+impl Bounds for DrawableCollection {
+    fn bounds(&self) -> Option<(f64, f64, f64, f64)> {
+        match self {
+            DrawableCollection::AreaBoxes(state) => state.bounds(),
+            DrawableCollection::Polygons(state) => state.bounds(),
+            DrawableCollection::Redacts(state) => state.bounds(),
+            DrawableCollection::Highlights(state) => state.bounds(),
+            DrawableCollection::Stamps(state) => state.bounds(),
+            DrawableCollection::Measures(state) => state.bounds(),
+            DrawableCollection::Eyedroppers(state) => state.bounds(),
+            DrawableCollection::Images(state) => state.bounds(),
+            DrawableCollection::Spotlights(state) => state.bounds(),
+            DrawableCollection::Magnifiers(state) => state.bounds(),
+            DrawableCollection::Pixelates(state) => state.bounds(),
+            DrawableCollection::Blurs(state) => state.bounds(),
+            DrawableCollection::Arcs(state) => state.bounds(),
+            DrawableCollection::Lines(state) => state.bounds(),
+            DrawableCollection::Arrows(state) => state.bounds(),
+            DrawableCollection::FreeHands(state) => state.bounds(),
+            DrawableCollection::NumberedCircles(state) => state.bounds(),
+            DrawableCollection::InputTexts(state) => state.bounds(),
+            DrawableCollection::Callouts(state) => state.bounds(),
+            DrawableCollection::Groups(state) => state.bounds(),
+        }
+    }
+}
 
-        // Position so the text is centred on (x0, y0).
-        let text_x = self.x0.get() - text_width / 2.0;
-        let text_y = self.y0.get() - text_height / 2.0;
+// Translate trait
+pub trait Translate {
+    /// Move the drawable by (dx, dy).
+    fn translate(&self, dx: f64, dy: f64);
+}
 
-        // Move the Cairo cursor (not strictly required for pangocairo, but keeps
-        // the state tidy) and render the layout.
-        cr.move_to(text_x, text_y);
-        pangocairo::functions::show_layout(cr, pg);
-        cr.stroke().expect("Failed to stroke the text");
+impl Translate for AreaBox {
+    fn translate(&self, dx: f64, dy: f64) {
+        self.start_x.set(self.start_x.get() + dx);
+        self.start_y.set(self.start_y.get() + dy);
     }
-    fn draw_with_saved_settings(&self, cr: &cairo::Context, pg: &gtk::pango::Layout) {
-        let settings_hard_copy = self.saved_settings.borrow().clone();
-        if let Some(settings_to_unwrap) = settings_hard_copy {
-            let settings = settings_to_unwrap.numbered_circle;
-            let radius = settings
-                .get_value("radius")
-                .get_f64()
-                .expect("Missing setting 'radius'");
-
-            cr.arc(
-                self.x0.get(),
-                self.y0.get(),
-                radius,
-                0.0,
-                2.0 * f64::consts::PI,
-            );
+}
+impl Translate for Arc {
+    fn translate(&self, dx: f64, dy: f64) {
+        self.center_x.set(self.center_x.get() + dx);
+        self.center_y.set(self.center_y.get() + dy);
+    }
+}
+impl Translate for Line {
+    fn translate(&self, dx: f64, dy: f64) {
+        self.x1.set(self.x1.get() + dx);
+        self.y1.set(self.y1.get() + dy);
+        self.x2.set(self.x2.get() + dx);
+        self.y2.set(self.y2.get() + dy);
+    }
+}
+impl Translate for Arrow {
+    fn translate(&self, dx: f64, dy: f64) {
+        self.x1.set(self.x1.get() + dx);
+        self.y1.set(self.y1.get() + dy);
+        self.x2.set(self.x2.get() + dx);
+        self.y2.set(self.y2.get() + dy);
+        self.move_control_point(dx, dy);
+    }
+}
+impl Translate for FreeHandDraw {
+    fn translate(&self, dx: f64, dy: f64) {
+        self.x0.set(self.x0.get() + dx);
+        self.y0.set(self.y0.get() + dy);
+        for x in self.x.borrow_mut().iter_mut() {
+            *x += dx;
+        }
+        for y in self.y.borrow_mut().iter_mut() {
+            *y += dy;
+        }
+    }
+}
+impl Translate for NumberedCircle {
+    fn translate(&self, dx: f64, dy: f64) {
+        self.x0.set(self.x0.get() + dx);
+        self.y0.set(self.y0.get() + dy);
+    }
+}
+impl Translate for InputText {
+    fn translate(&self, dx: f64, dy: f64) {
+        self.x0.set(self.x0.get() + dx);
+        self.y0.set(self.y0.get() + dy);
+    }
+}
+impl Translate for Callout {
+    fn translate(&self, dx: f64, dy: f64) {
+        self.x.set(self.x.get() + dx);
+        self.y.set(self.y.get() + dy);
+        self.move_tip(dx, dy);
+    }
+}
+impl Translate for Polygon {
+    fn translate(&self, dx: f64, dy: f64) {
+        for x in self.x.borrow_mut().iter_mut() {
+            *x += dx;
+        }
+        for y in self.y.borrow_mut().iter_mut() {
+            *y += dy;
+        }
+    }
+}
+impl Translate for Redact {
+    fn translate(&self, dx: f64, dy: f64) {
+        self.start_x.set(self.start_x.get() + dx);
+        self.start_y.set(self.start_y.get() + dy);
+    }
+}
+impl Translate for Stamp {
+    fn translate(&self, dx: f64, dy: f64) {
+        self.x.set(self.x.get() + dx);
+        self.y.set(self.y.get() + dy);
+    }
+}
+impl Translate for Image {
+    fn translate(&self, dx: f64, dy: f64) {
+        self.x.set(self.x.get() + dx);
+        self.y.set(self.y.get() + dy);
+    }
+}
+impl Translate for Measure {
+    fn translate(&self, dx: f64, dy: f64) {
+        self.start_x.set(self.start_x.get() + dx);
+        self.start_y.set(self.start_y.get() + dy);
+    }
+}
+impl Translate for Eyedropper {
+    fn translate(&self, _dx: f64, _dy: f64) {}
+}
+impl Translate for Highlight {
+    fn translate(&self, dx: f64, dy: f64) {
+        self.start_x.set(self.start_x.get() + dx);
+        self.start_y.set(self.start_y.get() + dy);
+    }
+}
+impl Translate for Spotlight {
+    fn translate(&self, dx: f64, dy: f64) {
+        let region = &self.region;
+        region.start_x.set(region.start_x.get() + dx);
+        region.start_y.set(region.start_y.get() + dy);
+    }
+}
+impl Translate for Magnifier {
+    fn translate(&self, dx: f64, dy: f64) {
+        self.start_x.set(self.start_x.get() + dx);
+        self.start_y.set(self.start_y.get() + dy);
+        if let Some((x, y)) = self.dest.get() {
+            self.dest.set(Some((x + dx, y + dy)));
+        }
+    }
+}
+impl Translate for Pixelate {
+    fn translate(&self, dx: f64, dy: f64) {
+        self.start_x.set(self.start_x.get() + dx);
+        self.start_y.set(self.start_y.get() + dy);
+    }
+}
+impl Translate for Blur {
+    fn translate(&self, dx: f64, dy: f64) {
+        self.start_x.set(self.start_x.get() + dx);
+        self.start_y.set(self.start_y.get() + dy);
+    }
+}
+impl Translate for Group {
+    fn translate(&self, dx: f64, dy: f64) {
+        for item in self.items.borrow().iter() {
+            item.translate(dx, dy);
+        }
+    }
+}
+impl Translate for DrawableCollection {
+    fn translate(&self, dx: f64, dy: f64) {
+        match self {
+            DrawableCollection::AreaBoxes(state) => state.translate(dx, dy),
+            DrawableCollection::Polygons(state) => state.translate(dx, dy),
+            DrawableCollection::Redacts(state) => state.translate(dx, dy),
+            DrawableCollection::Highlights(state) => state.translate(dx, dy),
+            DrawableCollection::Stamps(state) => state.translate(dx, dy),
+            DrawableCollection::Measures(state) => state.translate(dx, dy),
+            DrawableCollection::Eyedroppers(state) => state.translate(dx, dy),
+            DrawableCollection::Images(state) => state.translate(dx, dy),
+            DrawableCollection::Spotlights(state) => state.translate(dx, dy),
+            DrawableCollection::Magnifiers(state) => state.translate(dx, dy),
+            DrawableCollection::Pixelates(state) => state.translate(dx, dy),
+            DrawableCollection::Blurs(state) => state.translate(dx, dy),
+            DrawableCollection::Arcs(state) => state.translate(dx, dy),
+            DrawableCollection::Lines(state) => state.translate(dx, dy),
+            DrawableCollection::Arrows(state) => state.translate(dx, dy),
+            DrawableCollection::FreeHands(state) => state.translate(dx, dy),
+            DrawableCollection::NumberedCircles(state) => state.translate(dx, dy),
+            DrawableCollection::InputTexts(state) => state.translate(dx, dy),
+            DrawableCollection::Callouts(state) => state.translate(dx, dy),
+            DrawableCollection::Groups(state) => state.translate(dx, dy),
+        }
+    }
+}
 
-            if settings.get_value("fill").get_bool().expect("draw error") {
-                cr.set_source_rgba(
-                    settings.get_value("fill_r").get_f64().expect("draw error"),
-                    settings.get_value("fill_g").get_f64().expect("draw error"),
-                    settings.get_value("fill_b").get_f64().expect("draw error"),
-                    settings.get_value("fill_a").get_f64().expect("draw error"),
-                );
-                cr.fill_preserve()
-                    .expect("Failed to fill the numbered_circle");
+// Layer trait
+/// What the layers panel shows for a drawable
+pub struct LayerInfo {
+    pub icon: &'static str,
+    pub color: Option<(f64, f64, f64, f64)>,
+    pub label: String,
+}
+
+pub trait Layer {
+    fn layer(&self) -> &LayerState;
+    fn layer_info(&self) -> LayerInfo;
+}
+
+/// Shadow of a drawable: the tool "shadow" setting is "on", with the tool own offset, blur and
+/// color ("shadow_offset_x", "shadow_blur", "shadow_r", ...), "off", or "global", in which case
+/// the global [shadow] settings decide.
+fn resolve_shadow<F, G>(global: F, tool: G) -> Option<Shadow>
+where
+    F: Fn(&str) -> SettingValue,
+    G: Fn(&str) -> SettingValue,
+{
+    let shadow = |get: &dyn Fn(&str) -> SettingValue, prefix: &str, color: &str| {
+        Some(Shadow {
+            offset: (
+                get(&format!("{prefix}offset_x")).get_f64().ok()?,
+                get(&format!("{prefix}offset_y")).get_f64().ok()?,
+            ),
+            blur: get(&format!("{prefix}blur")).get_f64().ok()?.max(0.0),
+            rgba: rgba_setting(get, color)?,
+        })
+    };
+    match tool("shadow").get_string().unwrap_or_default().as_str() {
+        "on" => shadow(&tool, "shadow_", "shadow"),
+        "off" => None,
+        _ if global("enabled").get_bool().unwrap_or(false) => shadow(&global, "", "color"),
+        _ => None,
+    }
+}
+
+/// Paint the blurred silhouette of `draw`, its alpha in the shadow color, moved by the shadow
+/// offset. The drawing is recorded to measure its ink extents (line widths, arrow heads and
+/// labels go past the geometric `bounds`), then the silhouette is rendered off screen, at the
+/// device scale, over these extents plus the blur margin. With a `cache` it is kept until the
+/// bounds or the shadow change.
+fn draw_shadow<D>(
+    cr: &cairo::Context,
+    shadow: &Shadow,
+    bounds: (f64, f64, f64, f64),
+    cache: Option<&ShadowCache>,
+    draw: D,
+) where
+    D: Fn(&cairo::Context),
+{
+    let (bx, by, bw, bh) = bounds;
+    let (scale, _) = cr.target().device_scale();
+    let (r, g, b, a) = shadow.rgba;
+    let key = [bx, by, bw, bh, shadow.blur, r, g, b, a, scale].map(f64::to_bits);
+    let cached = cache.and_then(|cache| match cache.cached.borrow().as_ref() {
+        Some((cached_key, origin, surface)) if *cached_key == key => {
+            Some((*origin, surface.clone()))
+        }
+        _ => None,
+    });
+    let ((x, y), surface) = match cached {
+        Some(cached) => cached,
+        None => {
+            let Ok(recording) = cairo::RecordingSurface::create(cairo::Content::ColorAlpha, None)
+            else {
+                return;
+            };
+            {
+                let ctx = cairo::Context::new(&recording)
+                    .expect("Failed to create the shadow recording context");
+                draw(&ctx);
+            }
+            let (ix, iy, iw, ih) = recording.ink_extents();
+            if iw <= 0.0 || ih <= 0.0 {
+                return;
             }
 
-            if settings.get_value("border").get_bool().expect("draw error") {
-                cr.set_source_rgba(
-                    settings
-                        .get_value("border_r")
-                        .get_f64()
-                        .expect("draw error"),
-                    settings
-                        .get_value("border_g")
-                        .get_f64()
-                        .expect("draw error"),
-                    settings
-                        .get_value("border_b")
-                        .get_f64()
-                        .expect("draw error"),
-                    settings
-                        .get_value("border_a")
-                        .get_f64()
-                        .expect("draw error"),
-                );
-                let border_size = settings
-                    .get_value("border_size")
-                    .get_f64()
-                    .expect("draw error");
-                cr.set_line_width(border_size);
-                cr.stroke().unwrap();
+            let margin = (shadow.blur * 2.0).ceil() + 1.0;
+            let (x, y) = ((ix - margin).floor(), (iy - margin).floor());
+            let (w, h) = (iw + 2.0 * margin + 1.0, ih + 2.0 * margin + 1.0);
+            let (pw, ph) = ((w * scale).ceil() as i32, (h * scale).ceil() as i32);
+            if pw <= 0 || ph <= 0 || pw > MAX_SHADOW_SIZE || ph > MAX_SHADOW_SIZE {
+                return;
+            }
+            let Ok(mut surface) = cairo::ImageSurface::create(cairo::Format::ARgb32, pw, ph) else {
+                return;
+            };
+
+            // 1️⃣ Silhouette in the shadow color
+            {
+                let ctx =
+                    cairo::Context::new(&surface).expect("Failed to create the shadow context");
+                ctx.scale(scale, scale);
+                ctx.translate(-x, -y);
+                ctx.set_source_surface(&recording, 0.0, 0.0)
+                    .expect("Failed to set the shadow silhouette");
+                ctx.paint().expect("Failed to paint the shadow silhouette");
+                ctx.identity_matrix();
+                ctx.set_operator(cairo::Operator::In);
+                ctx.set_source_rgba(r, g, b, a);
+                ctx.paint().expect("Failed to paint the shadow");
+            }
+            surface.flush();
+
+            // 2️⃣ Blur, in device pixels
+            if let Ok(mut data) = surface.data() {
+                filters::gaussian_blur(&mut data, pw, ph, (shadow.blur * scale).round() as i32);
+            }
+            if let Some(cache) = cache {
+                *cache.cached.borrow_mut() = Some((key, (x, y), surface.clone()));
+            }
+            ((x, y), surface)
+        }
+    };
+
+    cr.save().expect("Failed to save the context");
+    cr.scale(1.0 / scale, 1.0 / scale);
+    cr.set_source_surface(
+        &surface,
+        (x + shadow.offset.0) * scale,
+        (y + shadow.offset.1) * scale,
+    )
+    .expect("Failed to set the shadow");
+    cr.paint().expect("Failed to paint the shadow");
+    cr.restore().expect("Failed to restore the context");
+}
+
+/// Read a setting from the saved settings if present, from the live ones otherwise
+macro_rules! saved_or_live {
+    ($self:ident, $tool:ident, $key:expr) => {
+        match $self.saved_settings.borrow().as_ref() {
+            Some(settings) => settings.$tool.get_value($key),
+            None => $self.settings.$tool.get_value($key),
+        }
+    };
+}
+
+/// Build an rgba tuple from the "<prefix>_r", "<prefix>_g", ... settings
+fn rgba_setting<F>(get: F, prefix: &str) -> Option<(f64, f64, f64, f64)>
+where
+    F: Fn(&str) -> SettingValue,
+{
+    Some((
+        get(&format!("{prefix}_r")).get_f64().ok()?,
+        get(&format!("{prefix}_g")).get_f64().ok()?,
+        get(&format!("{prefix}_b")).get_f64().ok()?,
+        get(&format!("{prefix}_a")).get_f64().ok()?,
+    ))
+}
+
+impl Layer for AreaBox {
+    fn layer(&self) -> &LayerState {
+        &self.layer
+    }
+    fn layer_info(&self) -> LayerInfo {
+        LayerInfo {
+            icon: "\u{f096}",
+            color: rgba_setting(|key: &str| saved_or_live!(self, rect, key), "fill"),
+            label: "Rectangle".to_string(),
+        }
+    }
+}
+impl Layer for Arc {
+    fn layer(&self) -> &LayerState {
+        &self.layer
+    }
+    fn layer_info(&self) -> LayerInfo {
+        LayerInfo {
+            icon: "\u{f111}",
+            color: rgba_setting(|key: &str| saved_or_live!(self, arc, key), "fill"),
+            label: match (
+                saved_or_live!(self, arc, "shape")
+                    .get_string()
+                    .unwrap_or_default()
+                    .as_str(),
+                saved_or_live!(self, arc, "style")
+                    .get_string()
+                    .unwrap_or_default()
+                    .as_str(),
+            ) {
+                (_, "pie") => "Pie slice",
+                (_, "arc") => "Arc",
+                ("ellipse", _) => "Ellipse",
+                _ => "Circle",
+            }
+            .to_string(),
+        }
+    }
+}
+impl Layer for Line {
+    fn layer(&self) -> &LayerState {
+        &self.layer
+    }
+    fn layer_info(&self) -> LayerInfo {
+        LayerInfo {
+            icon: "\u{f068}",
+            color: rgba_setting(|key: &str| saved_or_live!(self, line, key), "color"),
+            label: "Line".to_string(),
+        }
+    }
+}
+impl Layer for Arrow {
+    fn layer(&self) -> &LayerState {
+        &self.layer
+    }
+    fn layer_info(&self) -> LayerInfo {
+        LayerInfo {
+            icon: "\u{f061}",
+            color: rgba_setting(|key: &str| saved_or_live!(self, arrow, key), "color"),
+            label: if self.is_curved() {
+                "Curved arrow".to_string()
+            } else {
+                "Arrow".to_string()
+            },
+        }
+    }
+}
+impl Layer for FreeHandDraw {
+    fn layer(&self) -> &LayerState {
+        &self.layer
+    }
+    fn layer_info(&self) -> LayerInfo {
+        LayerInfo {
+            icon: "\u{f1fc}",
+            color: rgba_setting(|key: &str| saved_or_live!(self, freehand, key), "color"),
+            label: "Freehand".to_string(),
+        }
+    }
+}
+impl Layer for NumberedCircle {
+    fn layer(&self) -> &LayerState {
+        &self.layer
+    }
+    fn layer_info(&self) -> LayerInfo {
+        let get = |key: &str| saved_or_live!(self, numbered_circle, key);
+        let label = Self::label(self.number.get(), get);
+        LayerInfo {
+            icon: "\u{31}",
+            color: rgba_setting(get, "fill"),
+            label: format!("Number {label}"),
+        }
+    }
+}
+impl Layer for InputText {
+    fn layer(&self) -> &LayerState {
+        &self.layer
+    }
+    fn layer_info(&self) -> LayerInfo {
+        // Show only the beginning of the text
+        let text = self.text.borrow();
+        let mut label: String = text.chars().take(20).collect();
+        if text.chars().count() > 20 {
+            label.push('…');
+        }
+        LayerInfo {
+            icon: "\u{54}",
+            color: rgba_setting(|key: &str| saved_or_live!(self, input_text, key), "font"),
+            label,
+        }
+    }
+}
+impl Layer for Callout {
+    fn layer(&self) -> &LayerState {
+        &self.layer
+    }
+    fn layer_info(&self) -> LayerInfo {
+        let text = self.text.borrow();
+        LayerInfo {
+            icon: "\u{f075}",
+            color: rgba_setting(|key: &str| saved_or_live!(self, input_text, key), "fill"),
+            label: if text.is_empty() {
+                "Callout".to_string()
             } else {
-                cr.new_path();
-            }
-
-            // Set text properties
-            cr.set_source_rgba(
-                settings.get_value("font_r").get_f64().expect("error"),
-                settings.get_value("font_g").get_f64().expect("error"),
-                settings.get_value("font_b").get_f64().expect("error"),
-                settings.get_value("font_a").get_f64().expect("error"),
-            );
-            // Font face
-            let font_face = settings
-                .get_value("font_face")
-                .get_string()
-                .expect("NumberedCircle font_face error");
-            let fd: gtk::pango::FontDescription =
-                gtk::pango::FontDescription::from_string(font_face.as_str());
-            pg.set_font_description(Some(&fd));
-
-            // Draw text in the center of the rectangle
-            let num = settings
-                .get_value("number")
-                .get_i32()
-                .expect("NumberedCircle::new error");
-            let text = num.to_string();
-            pg.set_text(&text);
-
-            // Get logical extents (in Pango units) and convert to device units.
-            let (_ink_rect, logical_rect) = pg.extents();
-            let text_width = logical_rect.width() as f64 / gtk::pango::SCALE as f64;
-            let text_height = logical_rect.height() as f64 / gtk::pango::SCALE as f64;
-
-            // Position so the text is centred on (x0, y0).
-            let text_x = self.x0.get() - text_width / 2.0;
-            let text_y = self.y0.get() - text_height / 2.0;
-
-            // Move the Cairo cursor (not strictly required for pangocairo, but keeps
-            // the state tidy) and render the layout.
-            cr.move_to(text_x, text_y);
-            pangocairo::functions::show_layout(cr, pg);
-            cr.stroke().expect("Failed to stroke the text");
+                format!("Callout \"{}\"", text.chars().take(20).collect::<String>())
+            },
+        }
+    }
+}
+impl Layer for Polygon {
+    fn layer(&self) -> &LayerState {
+        &self.layer
+    }
+    fn layer_info(&self) -> LayerInfo {
+        let closed = saved_or_live!(self, polygon, "closed")
+            .get_bool()
+            .unwrap_or(true);
+        LayerInfo {
+            icon: "\u{f5ee}",
+            color: rgba_setting(|key: &str| saved_or_live!(self, polygon, key), "border"),
+            label: format!(
+                "{} ({} vertices)",
+                if closed { "Polygon" } else { "Polyline" },
+                self.x.borrow().len()
+            ),
+        }
+    }
+}
+impl Layer for Redact {
+    fn layer(&self) -> &LayerState {
+        &self.layer
+    }
+    fn layer_info(&self) -> LayerInfo {
+        let (_, [r, g, b]) = self.redaction();
+        LayerInfo {
+            icon: "\u{f070}",
+            color: Some((r as f64 / 255.0, g as f64 / 255.0, b as f64 / 255.0, 1.0)),
+            label: "Redaction".to_string(),
+        }
+    }
+}
+impl Layer for Stamp {
+    fn layer(&self) -> &LayerState {
+        &self.layer
+    }
+    fn layer_info(&self) -> LayerInfo {
+        let file = self.get_value("file").get_string().unwrap_or_default();
+        LayerInfo {
+            icon: "\u{f5bf}",
+            color: None,
+            label: format!("Stamp {}", file),
+        }
+    }
+}
+impl Layer for Image {
+    fn layer(&self) -> &LayerState {
+        &self.layer
+    }
+    fn layer_info(&self) -> LayerInfo {
+        LayerInfo {
+            icon: "\u{f03e}",
+            color: None,
+            label: format!(
+                "Image {:.0}x{:.0}",
+                self.width.get().abs(),
+                self.height.get().abs()
+            ),
+        }
+    }
+}
+impl Layer for Measure {
+    fn layer(&self) -> &LayerState {
+        &self.layer
+    }
+    fn layer_info(&self) -> LayerInfo {
+        let (len, _, _, _) = self.measurement();
+        LayerInfo {
+            icon: "\u{f545}",
+            color: None,
+            label: format!("Measure {:.0} px", len),
+        }
+    }
+}
+impl Layer for Eyedropper {
+    fn layer(&self) -> &LayerState {
+        &self.layer
+    }
+    fn layer_info(&self) -> LayerInfo {
+        LayerInfo {
+            icon: "\u{f1fb}",
+            color: self
+                .color()
+                .map(|rgb| rgb.map(|c| c as f64 / 255.0))
+                .map(|[r, g, b]| (r, g, b, 1.0)),
+            label: "Eyedropper".to_string(),
+        }
+    }
+}
+impl Layer for Highlight {
+    fn layer(&self) -> &LayerState {
+        &self.layer
+    }
+    fn layer_info(&self) -> LayerInfo {
+        let channel = |key: &str| {
+            saved_or_live!(self, highlight, key)
+                .get_f64()
+                .unwrap_or(0.0)
+        };
+        LayerInfo {
+            icon: "\u{f591}",
+            color: Some((
+                channel("color_r"),
+                channel("color_g"),
+                channel("color_b"),
+                1.0,
+            )),
+            label: "Highlight".to_string(),
+        }
+    }
+}
+impl Layer for Spotlight {
+    fn layer(&self) -> &LayerState {
+        &self.layer
+    }
+    fn layer_info(&self) -> LayerInfo {
+        let shape = if self.region.ellipse.get() {
+            "ellipse"
         } else {
-            println!("Warning! saved_settings is None!");
+            "rect"
+        };
+        LayerInfo {
+            icon: "\u{f0eb}",
+            color: None,
+            label: format!(
+                "Spotlight ({}, {:.0}%)",
+                shape,
+                self.region.dim.get() * 100.0
+            ),
+        }
+    }
+}
+impl Layer for Magnifier {
+    fn layer(&self) -> &LayerState {
+        &self.layer
+    }
+    fn layer_info(&self) -> LayerInfo {
+        LayerInfo {
+            icon: "\u{f00e}",
+            color: rgba_setting(|key: &str| saved_or_live!(self, magnifier, key), "border"),
+            label: format!(
+                "Magnifier x{}",
+                saved_or_live!(self, magnifier, "zoom")
+                    .get_f64()
+                    .unwrap_or(2.0)
+            ),
+        }
+    }
+}
+impl Layer for Pixelate {
+    fn layer(&self) -> &LayerState {
+        &self.layer
+    }
+    fn layer_info(&self) -> LayerInfo {
+        LayerInfo {
+            icon: "\u{f00a}",
+            color: None,
+            label: "Pixelate".to_string(),
+        }
+    }
+}
+impl Layer for Blur {
+    fn layer(&self) -> &LayerState {
+        &self.layer
+    }
+    fn layer_info(&self) -> LayerInfo {
+        LayerInfo {
+            icon: "\u{f0c2}",
+            color: None,
+            label: "Blur".to_string(),
+        }
+    }
+}
+impl Layer for Group {
+    fn layer(&self) -> &LayerState {
+        &self.layer
+    }
+    fn layer_info(&self) -> LayerInfo {
+        LayerInfo {
+            icon: "\u{f247}",
+            color: None,
+            label: format!("Group ({} items)", self.items.borrow().len()),
+        }
+    }
+}
+impl Layer for DrawableCollection {
+    fn layer(&self) -> &LayerState {
+        match self {
+            DrawableCollection::AreaBoxes(state) => state.layer(),
+            DrawableCollection::Polygons(state) => state.layer(),
+            DrawableCollection::Redacts(state) => state.layer(),
+            DrawableCollection::Highlights(state) => state.layer(),
+            DrawableCollection::Stamps(state) => state.layer(),
+            DrawableCollection::Measures(state) => state.layer(),
+            DrawableCollection::Eyedroppers(state) => state.layer(),
+            DrawableCollection::Images(state) => state.layer(),
+            DrawableCollection::Spotlights(state) => state.layer(),
+            DrawableCollection::Magnifiers(state) => state.layer(),
+            DrawableCollection::Pixelates(state) => state.layer(),
+            DrawableCollection::Blurs(state) => state.layer(),
+            DrawableCollection::Arcs(state) => state.layer(),
+            DrawableCollection::Lines(state) => state.layer(),
+            DrawableCollection::Arrows(state) => state.layer(),
+            DrawableCollection::FreeHands(state) => state.layer(),
+            DrawableCollection::NumberedCircles(state) => state.layer(),
+            DrawableCollection::InputTexts(state) => state.layer(),
+            DrawableCollection::Callouts(state) => state.layer(),
+            DrawableCollection::Groups(state) => state.layer(),
+        }
+    }
+    fn layer_info(&self) -> LayerInfo {
+        match self {
+            DrawableCollection::AreaBoxes(state) => state.layer_info(),
+            DrawableCollection::Polygons(state) => state.layer_info(),
+            DrawableCollection::Redacts(state) => state.layer_info(),
+            DrawableCollection::Highlights(state) => state.layer_info(),
+            DrawableCollection::Stamps(state) => state.layer_info(),
+            DrawableCollection::Measures(state) => state.layer_info(),
+            DrawableCollection::Eyedroppers(state) => state.layer_info(),
+            DrawableCollection::Images(state) => state.layer_info(),
+            DrawableCollection::Spotlights(state) => state.layer_info(),
+            DrawableCollection::Magnifiers(state) => state.layer_info(),
+            DrawableCollection::Pixelates(state) => state.layer_info(),
+            DrawableCollection::Blurs(state) => state.layer_info(),
+            DrawableCollection::Arcs(state) => state.layer_info(),
+            DrawableCollection::Lines(state) => state.layer_info(),
+            DrawableCollection::Arrows(state) => state.layer_info(),
+            DrawableCollection::FreeHands(state) => state.layer_info(),
+            DrawableCollection::NumberedCircles(state) => state.layer_info(),
+            DrawableCollection::InputTexts(state) => state.layer_info(),
+            DrawableCollection::Callouts(state) => state.layer_info(),
+            DrawableCollection::Groups(state) => state.layer_info(),
         }
     }
 }
-impl Draw for InputText {
-    fn draw(&self, cr: &cairo::Context, pg: &gtk::pango::Layout) {
-        println!("draw");
-        let settings = self.settings.input_text.clone();
-
-        // Draw text in the center of the rectangle
-        let text = self.text.borrow().clone();
-        pg.set_text(&text);
-
-        // Get logical extents (in Pango units) and convert to device units.
-        let (_ink_rect, logical_rect) = pg.extents();
-        let text_width = logical_rect.width() as f64 / gtk::pango::SCALE as f64;
-        let text_height = logical_rect.height() as f64 / gtk::pango::SCALE as f64;
-
-        // Font face
-        let font_face = settings
-            .get_value("font_face")
-            .get_string()
-            .expect("InputText font_face error");
-        let fd: gtk::pango::FontDescription =
-            gtk::pango::FontDescription::from_string(font_face.as_str());
-        pg.set_font_description(Some(&fd));
 
-        // Position so the text is centred on (x0, y0).
-        let text_x = self.x0.get(); // - text_width / 2.0;
-        let text_y = self.y0.get(); // - text_height / 2.0;
+impl DrawableCollection {
+    /// Redaction rectangles and colors of this item, groups included.
+    /// Hidden items are not exported, so they are skipped.
+    pub fn redactions(&self) -> Vec<Redaction> {
+        if self.layer().hidden.get() {
+            return Vec::new();
+        }
+        match self {
+            DrawableCollection::Redacts(state) => vec![state.redaction()],
+            DrawableCollection::Groups(state) => state
+                .items
+                .borrow()
+                .iter()
+                .flat_map(|item| item.redactions())
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
 
-        // Fill
-        if settings.get_value("fill").get_bool().expect("draw error") {
-            cr.rectangle(self.x0.get(), self.y0.get(), text_width, text_height);
+    /// True while the drawable waits for another drag before being complete
+    pub fn needs_another_drag(&self) -> bool {
+        match self {
+            DrawableCollection::Magnifiers(state) => state.picking_source.get(),
+            DrawableCollection::Polygons(state) => !state.finished.get(),
+            _ => false,
+        }
+    }
 
-            cr.set_source_rgba(
-                settings.get_value("fill_r").get_f64().expect("draw error"),
-                settings.get_value("fill_g").get_f64().expect("draw error"),
-                settings.get_value("fill_b").get_f64().expect("draw error"),
-                settings.get_value("fill_a").get_f64().expect("draw error"),
-            );
-            cr.fill().expect("No arc fill to unwrap");
-            cr.stroke().unwrap();
+    /// Point that can be dragged on its own: the callout tail, the control point of the
+    /// curved arrows
+    pub fn handle_point(&self) -> Option<(f64, f64)> {
+        match self {
+            DrawableCollection::Callouts(state) => Some((state.tip_x.get(), state.tip_y.get())),
+            DrawableCollection::Arrows(state) => state.control_point(),
+            DrawableCollection::Images(state) => state.corner(),
+            _ => None,
         }
+    }
 
-        // Border
-        if settings.get_value("border").get_bool().expect("draw error") {
-            cr.rectangle(self.x0.get(), self.y0.get(), text_width, text_height);
+    /// True if (x, y) grabs the handle point
+    pub fn handle_at(&self, x: f64, y: f64, tolerance: f64) -> bool {
+        self.handle_point()
+            .is_some_and(|(hx, hy)| (x - hx).hypot(y - hy) <= tolerance)
+    }
 
-            cr.set_source_rgba(
-                settings
-                    .get_value("border_r")
-                    .get_f64()
-                    .expect("draw error"),
-                settings
-                    .get_value("border_g")
-                    .get_f64()
-                    .expect("draw error"),
-                settings
-                    .get_value("border_b")
-                    .get_f64()
-                    .expect("draw error"),
-                settings
-                    .get_value("border_a")
-                    .get_f64()
-                    .expect("draw error"),
-            );
-            let border_size = settings
-                .get_value("border_size")
-                .get_f64()
-                .expect("draw error");
-            cr.set_line_width(border_size);
-            cr.stroke().unwrap();
+    /// Move the handle point grabbed with `handle_at`
+    pub fn move_handle(&self, dx: f64, dy: f64) {
+        match self {
+            DrawableCollection::Callouts(state) => state.move_tip(dx, dy),
+            DrawableCollection::Arrows(state) => state.move_control_point(dx, dy),
+            DrawableCollection::Images(state) => state.move_corner(dx, dy),
+            _ => {}
         }
+    }
 
-        // Set text properties
-        cr.set_source_rgba(
-            settings.get_value("font_r").get_f64().expect("error"),
-            settings.get_value("font_g").get_f64().expect("error"),
-            settings.get_value("font_b").get_f64().expect("error"),
-            settings.get_value("font_a").get_f64().expect("error"),
-        );
-
-        // Move the Cairo cursor (not strictly required for pangocairo, but keeps
-        // the state tidy) and render the layout.
-        cr.move_to(text_x, text_y);
-        pangocairo::functions::show_layout(cr, pg);
-        cr.stroke().expect("Failed to stroke the text");
+    /// Drop shadow of the drawable, if enabled for its tool or globally
+    pub fn shadow(&self) -> Option<Shadow> {
+        macro_rules! tool_shadow {
+            ($state:ident, $tool:ident) => {
+                match $state.saved_settings.borrow().as_ref() {
+                    Some(settings) => resolve_shadow(
+                        |key| settings.shadow.get_value(key),
+                        |key| settings.$tool.get_value(key),
+                    ),
+                    None => resolve_shadow(
+                        |key| $state.settings.shadow.get_value(key),
+                        |key| $state.settings.$tool.get_value(key),
+                    ),
+                }
+            };
+        }
+        match self {
+            DrawableCollection::InputTexts(state) => tool_shadow!(state, input_text),
+            DrawableCollection::Callouts(state) => tool_shadow!(state, callout),
+            DrawableCollection::NumberedCircles(state) => tool_shadow!(state, numbered_circle),
+            DrawableCollection::FreeHands(state) => tool_shadow!(state, freehand),
+            DrawableCollection::Lines(state) => tool_shadow!(state, line),
+            DrawableCollection::Arrows(state) => tool_shadow!(state, arrow),
+            DrawableCollection::Arcs(state) => tool_shadow!(state, arc),
+            DrawableCollection::AreaBoxes(state) => tool_shadow!(state, rect),
+            DrawableCollection::Polygons(state) => tool_shadow!(state, polygon),
+            DrawableCollection::Redacts(state) => tool_shadow!(state, redact),
+            DrawableCollection::Highlights(state) => tool_shadow!(state, highlight),
+            DrawableCollection::Stamps(state) => tool_shadow!(state, stamp),
+            DrawableCollection::Images(state) => tool_shadow!(state, image),
+            DrawableCollection::Measures(state) => tool_shadow!(state, measure),
+            DrawableCollection::Spotlights(state) => tool_shadow!(state, spotlight),
+            DrawableCollection::Magnifiers(state) => tool_shadow!(state, magnifier),
+            DrawableCollection::Pixelates(state) => tool_shadow!(state, obfuscate),
+            DrawableCollection::Blurs(state) => tool_shadow!(state, obfuscate),
+            // The eyedropper has no bounds, so it never casts a shadow, and the items of a
+            // group keep their own shadow
+            DrawableCollection::Eyedroppers(_) | DrawableCollection::Groups(_) => None,
+        }
     }
-    fn draw_with_saved_settings(&self, cr: &cairo::Context, pg: &gtk::pango::Layout) {
-        println!("draw saved");
-        let settings_hard_copy = self.saved_settings.borrow().clone();
-        if let Some(settings_to_unwrap) = settings_hard_copy {
-            let settings = settings_to_unwrap.input_text;
 
-            // Draw text in the center of the rectangle
-            let text = self.text.borrow().clone();
-            pg.set_text(&text);
+    /// Blurred shadow kept between the redraws, None for the eyedropper and the groups
+    pub fn shadow_cache(&self) -> Option<&ShadowCache> {
+        match self {
+            DrawableCollection::InputTexts(state) => Some(&state.shadow_cache),
+            DrawableCollection::Callouts(state) => Some(&state.shadow_cache),
+            DrawableCollection::NumberedCircles(state) => Some(&state.shadow_cache),
+            DrawableCollection::FreeHands(state) => Some(&state.shadow_cache),
+            DrawableCollection::Lines(state) => Some(&state.shadow_cache),
+            DrawableCollection::Arrows(state) => Some(&state.shadow_cache),
+            DrawableCollection::Arcs(state) => Some(&state.shadow_cache),
+            DrawableCollection::AreaBoxes(state) => Some(&state.shadow_cache),
+            DrawableCollection::Polygons(state) => Some(&state.shadow_cache),
+            DrawableCollection::Redacts(state) => Some(&state.shadow_cache),
+            DrawableCollection::Highlights(state) => Some(&state.shadow_cache),
+            DrawableCollection::Stamps(state) => Some(&state.shadow_cache),
+            DrawableCollection::Images(state) => Some(&state.shadow_cache),
+            DrawableCollection::Measures(state) => Some(&state.shadow_cache),
+            DrawableCollection::Spotlights(state) => Some(&state.shadow_cache),
+            DrawableCollection::Magnifiers(state) => Some(&state.shadow_cache),
+            DrawableCollection::Pixelates(state) => Some(&state.shadow_cache),
+            DrawableCollection::Blurs(state) => Some(&state.shadow_cache),
+            DrawableCollection::Eyedroppers(_) | DrawableCollection::Groups(_) => None,
+        }
+    }
 
-            // Get logical extents (in Pango units) and convert to device units.
-            let (_ink_rect, logical_rect) = pg.extents();
-            let text_width = logical_rect.width() as f64 / gtk::pango::SCALE as f64;
-            let text_height = logical_rect.height() as f64 / gtk::pango::SCALE as f64;
+    /// Typing state of the drawables edited on the keyboard, None for the others
+    pub fn typed_text(&self) -> Option<&TypedText> {
+        match self {
+            DrawableCollection::InputTexts(state) => Some(&state.typed),
+            DrawableCollection::Callouts(state) => Some(&state.typed),
+            _ => None,
+        }
+    }
 
-            // Font face
-            let font_face = settings
-                .get_value("font_face")
-                .get_string()
-                .expect("InputText font_face error");
-            let fd: gtk::pango::FontDescription =
-                gtk::pango::FontDescription::from_string(font_face.as_str());
-            pg.set_font_description(Some(&fd));
+    /// Apply an editing key to the typed text
+    pub fn edit_text(&self, action: &TextAction) {
+        match self {
+            DrawableCollection::InputTexts(state) => state.edit(action),
+            DrawableCollection::Callouts(state) => state.edit(action),
+            _ => {}
+        }
+    }
 
-            // Position so the text is centred on (x0, y0).
-            let text_x = self.x0.get(); // - text_width / 2.0;
-            let text_y = self.y0.get(); // - text_height / 2.0;
+    /// Show the input method text at the caret of the typed text
+    pub fn set_preedit(&self, text: &str, cursor: i32) {
+        match self {
+            DrawableCollection::InputTexts(state) => state.set_preedit(text, cursor),
+            DrawableCollection::Callouts(state) => state.set_preedit(text, cursor),
+            _ => {}
+        }
+    }
 
-            // Fill
-            if settings.get_value("fill").get_bool().expect("draw error") {
-                cr.rectangle(self.x0.get(), self.y0.get(), text_width, text_height);
+    /// Caret rectangle (x, y, height) of the typed text, in window coordinates
+    pub fn caret_area(&self, pg: &gtk::pango::Layout) -> Option<(f64, f64, f64)> {
+        match self {
+            DrawableCollection::InputTexts(state) => Some(state.caret_area(pg)),
+            DrawableCollection::Callouts(state) => Some(state.caret_area(pg)),
+            _ => None,
+        }
+    }
 
-                cr.set_source_rgba(
-                    settings.get_value("fill_r").get_f64().expect("draw error"),
-                    settings.get_value("fill_g").get_f64().expect("draw error"),
-                    settings.get_value("fill_b").get_f64().expect("draw error"),
-                    settings.get_value("fill_a").get_f64().expect("draw error"),
-                );
-                cr.fill().expect("No arc fill to unwrap");
-                cr.stroke().unwrap();
+    /// Draw the drawable over its drop shadow; `saved` draws with the saved settings
+    pub fn draw_with_shadow(&self, cr: &cairo::Context, pg: &gtk::pango::Layout, saved: bool) {
+        if let DrawableCollection::Groups(group) = self {
+            for item in group.items.borrow().iter() {
+                item.draw_with_shadow(cr, pg, true);
             }
+            return;
+        }
 
-            if settings.get_value("border").get_bool().expect("draw error") {
-                cr.rectangle(self.x0.get(), self.y0.get(), text_width, text_height);
-
-                cr.set_source_rgba(
-                    settings
-                        .get_value("border_r")
-                        .get_f64()
-                        .expect("draw error"),
-                    settings
-                        .get_value("border_g")
-                        .get_f64()
-                        .expect("draw error"),
-                    settings
-                        .get_value("border_b")
-                        .get_f64()
-                        .expect("draw error"),
-                    settings
-                        .get_value("border_a")
-                        .get_f64()
-                        .expect("draw error"),
-                );
-                let border_size = settings
-                    .get_value("border_size")
-                    .get_f64()
-                    .expect("draw error");
-                cr.set_line_width(border_size);
-                cr.stroke().unwrap();
+        let draw = |cr: &cairo::Context| {
+            if saved {
+                self.draw_with_saved_settings(cr, pg);
+            } else {
+                self.draw(cr, pg);
             }
+        };
+        if let (Some(shadow), Some(bounds)) = (self.shadow(), self.bounds()) {
+            // The item being drawn changes at every frame, only the stored ones are cached
+            let cache = self.shadow_cache().filter(|_| saved);
+            draw_shadow(cr, &shadow, bounds, cache, draw);
+        }
+        draw(cr);
+    }
 
-            // Set text properties
-            cr.set_source_rgba(
-                settings.get_value("font_r").get_f64().expect("error"),
-                settings.get_value("font_g").get_f64().expect("error"),
-                settings.get_value("font_b").get_f64().expect("error"),
-                settings.get_value("font_a").get_f64().expect("error"),
-            );
-            // Font face
-            let font_face = settings
-                .get_value("font_face")
-                .get_string()
-                .expect("NumberedCircle font_face error");
-            let fd: gtk::pango::FontDescription =
-                gtk::pango::FontDescription::from_string(font_face.as_str());
-            pg.set_font_description(Some(&fd));
-
-            // Move the Cairo cursor (not strictly required for pangocairo, but keeps
-            // the state tidy) and render the layout.
-            cr.move_to(text_x, text_y);
-            pangocairo::functions::show_layout(cr, pg);
-            cr.stroke().expect("Failed to stroke the text");
-        } else {
-            println!("Warning! saved_settings is None!");
+    /// True if there is nothing to draw, like an image placed before loading a picture
+    pub fn is_blank(&self) -> bool {
+        match self {
+            DrawableCollection::Images(state) => state.surface.borrow().is_none(),
+            DrawableCollection::Eyedroppers(_) => true,
+            DrawableCollection::FreeHands(state) => state.is_eraser(),
+            _ => false,
         }
     }
-}
-impl Draw for DrawableCollection {
-    fn draw(&self, cr: &cairo::Context, pg: &gtk::pango::Layout) {
+
+    /// Numbered badges of this drawable, including the ones inside a group
+    pub fn badges(&self) -> Vec<NumberedCircle> {
         match self {
-            DrawableCollection::FreeHands(state) => {
-                state.draw(cr, pg);
-            }
-            DrawableCollection::AreaBoxes(state) => {
-                state.draw(cr, pg);
-            }
-            DrawableCollection::Lines(state) => {
-                state.draw(cr, pg);
-            }
-            DrawableCollection::Arcs(state) => {
-                state.draw(cr, pg);
-            }
-            DrawableCollection::Arrows(state) => {
-                state.draw(cr, pg);
-            }
-            DrawableCollection::NumberedCircles(state) => {
-                state.draw(cr, pg);
-            }
-            DrawableCollection::InputTexts(state) => {
-                state.draw(cr, pg);
-            }
+            DrawableCollection::NumberedCircles(state) => vec![state.clone()],
+            DrawableCollection::Groups(state) => state
+                .items
+                .borrow()
+                .iter()
+                .flat_map(|item| item.badges())
+                .collect(),
+            _ => Vec::new(),
         }
     }
-    fn draw_with_saved_settings(&self, cr: &cairo::Context, pg: &gtk::pango::Layout) {
+
+    /// False for the drawables shown on screen only, left out of the screenshot
+    pub fn in_export(&self) -> bool {
         match self {
-            DrawableCollection::FreeHands(state) => {
-                state.draw_with_saved_settings(cr, pg);
-            }
-            DrawableCollection::AreaBoxes(state) => {
-                state.draw_with_saved_settings(cr, pg);
-            }
-            DrawableCollection::Lines(state) => {
-                state.draw_with_saved_settings(cr, pg);
-            }
-            DrawableCollection::Arcs(state) => {
-                state.draw_with_saved_settings(cr, pg);
-            }
-            DrawableCollection::Arrows(state) => {
-                state.draw_with_saved_settings(cr, pg);
-            }
-            DrawableCollection::NumberedCircles(state) => {
-                state.draw_with_saved_settings(cr, pg);
-            }
-            DrawableCollection::InputTexts(state) => {
-                state.draw(cr, pg);
-            }
+            DrawableCollection::Measures(state) => state.in_export(),
+            _ => true,
+        }
+    }
+
+    /// Complete a drawable that takes several clicks, true if it could be completed
+    pub fn finish(&self) -> bool {
+        match self {
+            DrawableCollection::Polygons(state) => state.finish(),
+            _ => false,
         }
     }
 }
@@ -0,0 +1,103 @@
+//! Geometry of the freehand eraser.
+//!
+//! The eraser does not paint: the points of the freehand polylines that fall under the eraser
+//! are removed, and every polyline is split where it was crossed, so the exported image never
+//! contains erased strokes hidden under something else.
+
+/// Polyline as parallel x and y coordinates, like `FreeHandDraw.x` and `FreeHandDraw.y`
+pub type Polyline = (Vec<f64>, Vec<f64>);
+
+/// Point in window coordinates
+pub type Point = (f64, f64);
+
+/// Distance of the point p from the segment a-b
+fn distance_to_segment(p: (f64, f64), a: (f64, f64), b: (f64, f64)) -> f64 {
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    let len2 = dx * dx + dy * dy;
+    let t = if len2 == 0.0 {
+        0.0
+    } else {
+        (((p.0 - a.0) * dx + (p.1 - a.1) * dy) / len2).clamp(0.0, 1.0)
+    };
+    (p.0 - a.0 - t * dx).hypot(p.1 - a.1 - t * dy)
+}
+
+/// Points of the polyline, with the long segments subdivided every `step` pixels, so that an
+/// eraser crossing a segment between two distant points still cuts it
+fn densify(xs: &[f64], ys: &[f64], step: f64) -> Vec<(f64, f64)> {
+    let mut points = Vec::with_capacity(xs.len());
+    for (i, (x, y)) in xs.iter().zip(ys.iter()).enumerate() {
+        if i > 0 {
+            let (px, py) = (xs[i - 1], ys[i - 1]);
+            let n = ((x - px).hypot(y - py) / step).ceil() as usize;
+            for k in 1..n {
+                let t = k as f64 / n as f64;
+                points.push((px + t * (x - px), py + t * (y - py)));
+            }
+        }
+        points.push((*x, *y));
+    }
+    points
+}
+
+/// Erase the polyline along the eraser segment a-b, `radius` pixels wide on each side.
+/// `None` if the eraser did not touch the polyline, the remaining pieces otherwise (an empty
+/// list when the whole polyline is erased). Pieces shorter than two points are dropped.
+pub fn erase(xs: &[f64], ys: &[f64], a: Point, b: Point, radius: f64) -> Option<Vec<Polyline>> {
+    let radius = radius.max(0.5);
+    let erased = |p: (f64, f64)| distance_to_segment(p, a, b) <= radius;
+
+    let points = densify(xs, ys, radius / 2.0);
+    if !points.iter().any(|p| erased(*p)) {
+        return None;
+    }
+
+    let mut pieces: Vec<Polyline> = Vec::new();
+    let mut piece: Polyline = (Vec::new(), Vec::new());
+    for p in points {
+        if erased(p) {
+            if piece.0.len() >= 2 {
+                pieces.push(std::mem::take(&mut piece));
+            } else {
+                piece = (Vec::new(), Vec::new());
+            }
+        } else {
+            piece.0.push(p.0);
+            piece.1.push(p.1);
+        }
+    }
+    if piece.0.len() >= 2 {
+        pieces.push(piece);
+    }
+    Some(pieces)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn untouched_polyline() {
+        let (xs, ys) = (vec![0.0, 10.0, 20.0], vec![0.0, 0.0, 0.0]);
+        assert_eq!(erase(&xs, &ys, (0.0, 50.0), (20.0, 50.0), 5.0), None);
+    }
+
+    #[test]
+    fn split_in_the_middle() {
+        // A vertical eraser crossing a horizontal line between two distant points
+        let (xs, ys) = (vec![0.0, 100.0], vec![0.0, 0.0]);
+        let pieces = erase(&xs, &ys, (50.0, -20.0), (50.0, 20.0), 5.0).unwrap();
+        assert_eq!(pieces.len(), 2);
+        assert_eq!(pieces[0].0.first(), Some(&0.0));
+        assert!(pieces[0].0.iter().all(|x| *x < 45.0));
+        assert!(pieces[1].0.iter().all(|x| *x > 55.0));
+        assert_eq!(pieces[1].0.last(), Some(&100.0));
+    }
+
+    #[test]
+    fn delete_whole_polyline() {
+        let (xs, ys) = (vec![0.0, 2.0, 4.0], vec![0.0, 1.0, 0.0]);
+        let pieces = erase(&xs, &ys, (-10.0, 0.0), (10.0, 0.0), 5.0).unwrap();
+        assert!(pieces.is_empty());
+    }
+}
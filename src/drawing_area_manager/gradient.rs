@@ -0,0 +1,99 @@
+//! Color stops of the gradient fills.
+//!
+//! The stops are kept in the settings as text, e.g. "#ffffff 0%, #3584e4cc 100%": a hex color
+//! (#rgb, #rrggbb or #rrggbbaa) optionally followed by its offset. Stops without an offset are
+//! spread evenly, like in CSS.
+
+/// Color stop, offset in [0, 1] and straight rgba color
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stop {
+    pub offset: f64,
+    pub rgba: (f64, f64, f64, f64),
+}
+
+/// Parse a hex color, "#" optional
+pub fn parse_hex(text: &str) -> Option<(f64, f64, f64, f64)> {
+    let hex = text.trim().trim_start_matches('#');
+    if !hex.is_ascii() {
+        return None;
+    }
+    let channel = |s: &str| u8::from_str_radix(s, 16).ok().map(|c| c as f64 / 255.0);
+    let short = |i: usize| channel(&hex[i..i + 1].repeat(2));
+    match hex.len() {
+        3 => Some((short(0)?, short(1)?, short(2)?, 1.0)),
+        6 | 8 => Some((
+            channel(&hex[0..2])?,
+            channel(&hex[2..4])?,
+            channel(&hex[4..6])?,
+            if hex.len() == 8 {
+                channel(&hex[6..8])?
+            } else {
+                1.0
+            },
+        )),
+        _ => None,
+    }
+}
+
+/// Parse the stops, `None` if a stop is invalid or there are less than two
+pub fn parse_stops(text: &str) -> Option<Vec<Stop>> {
+    let parsed = text
+        .split(',')
+        .map(|stop| {
+            let mut parts = stop.split_whitespace();
+            let rgba = parse_hex(parts.next()?)?;
+            let offset = match parts.next() {
+                Some(offset) => Some(offset.strip_suffix('%')?.parse::<f64>().ok()? / 100.0),
+                None => None,
+            };
+            parts.next().is_none().then_some((offset, rgba))
+        })
+        .collect::<Option<Vec<_>>>()?;
+    if parsed.len() < 2 {
+        return None;
+    }
+
+    let last = (parsed.len() - 1) as f64;
+    Some(
+        parsed
+            .into_iter()
+            .enumerate()
+            .map(|(i, (offset, rgba))| Stop {
+                offset: offset.unwrap_or(i as f64 / last).clamp(0.0, 1.0),
+                rgba,
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hex_colors() {
+        assert_eq!(parse_hex("#fff"), Some((1.0, 1.0, 1.0, 1.0)));
+        assert_eq!(parse_hex("000000"), Some((0.0, 0.0, 0.0, 1.0)));
+        assert_eq!(parse_hex("#ff000000"), Some((1.0, 0.0, 0.0, 0.0)));
+        assert_eq!(parse_hex("#ff00"), None);
+        assert_eq!(parse_hex("#gggggg"), None);
+    }
+
+    #[test]
+    fn stops_with_and_without_offsets() {
+        let stops = parse_stops("#000 0%, #fff 25%, #000").unwrap();
+        let offsets: Vec<f64> = stops.iter().map(|s| s.offset).collect();
+        assert_eq!(offsets, vec![0.0, 0.25, 1.0]);
+
+        let stops = parse_stops("#000, #fff, #000").unwrap();
+        assert_eq!(stops[1].offset, 0.5);
+        assert_eq!(stops[1].rgba, (1.0, 1.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn invalid_stops() {
+        assert_eq!(parse_stops("#000"), None);
+        assert_eq!(parse_stops("#000 0%, nope 100%"), None);
+        assert_eq!(parse_stops("#000 0, #fff 100%"), None);
+    }
+}
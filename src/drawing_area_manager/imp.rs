@@ -13,6 +13,21 @@ pub struct DrawingAreaManager {
     pub drawn_items: RefCell<Vec<DrawableCollection>>,
     pub current_item: RefCell<Option<DrawableCollection>>,
     pub is_drawing: Rc<Cell<bool>>,
+
+    // Selection
+    pub is_selecting: Rc<Cell<bool>>,
+    pub selected_items: RefCell<Vec<usize>>,
+    pub rubber_band: RefCell<Option<(f64, f64, f64, f64)>>,
+    pub moving_selection: Cell<bool>,
+    pub last_offset: Cell<(f64, f64)>,
+    pub moving_handle: RefCell<Option<DrawableCollection>>,
+
+    // Screenshot in progress, the screen-only items are not drawn
+    pub exporting: Cell<bool>,
+
+    // Text annotation receiving the keys, and the input method composing its text
+    pub typing: RefCell<Option<DrawableCollection>>,
+    pub im_context: RefCell<Option<gtk::IMContext>>,
 }
 
 #[glib::object_subclass]
@@ -32,6 +47,15 @@ impl ObjectSubclass for DrawingAreaManager {
             drawn_items: Vec::new().into(),
             // numbered_circle_idx: 1.into(),
             is_drawing: Rc::new(Cell::new(false)),
+            is_selecting: Rc::new(Cell::new(false)),
+            selected_items: Vec::new().into(),
+            rubber_band: None.into(),
+            moving_selection: false.into(),
+            last_offset: (0.0, 0.0).into(),
+            moving_handle: None.into(),
+            exporting: false.into(),
+            typing: None.into(),
+            im_context: None.into(),
         }
     }
 }
@@ -1,11 +1,23 @@
+pub mod dash;
 pub mod drawables;
+pub mod eraser;
+pub mod gradient;
 mod imp;
+pub mod numbering;
+pub mod text_editor;
 
+use crate::captured_screen::redact;
 use crate::drawing_area_manager::drawables::{
-    ControllerKey, DragBegin, DragEnd, DragUpdate, Draw, DrawableCollection,
+    Bounds, ControllerKey, DragBegin, DragEnd, DragUpdate, DrawableCollection, Group, Layer,
+    NumberedCircle, Redaction, Translate,
 };
+use crate::drawing_area_manager::text_editor::TextAction;
+use crate::drawing_area_settings::{SettingValue, SettingsRc};
 
-use gtk::{cairo, glib, pango, prelude::WidgetExt, subclass::prelude::*};
+use gtk::prelude::{Cast, IMContextExt, IsA, WidgetExt};
+use gtk::{cairo, gdk, glib, pango, subclass::prelude::*};
+use rustshot_gtk::constants::SELECTION_TOLERANCE_PX;
+use std::collections::BTreeMap;
 
 glib::wrapper! {
     pub struct DrawingAreaManager(ObjectSubclass<imp::DrawingAreaManager>)
@@ -28,6 +40,8 @@ impl DrawingAreaManager {
         let imp = self.imp();
         *imp.current_item.borrow_mut() = Some(drawable.clone());
         imp.is_drawing.set(true);
+        self.stop_selection();
+        self.set_typing(None);
     }
 
     pub fn is_drawing(&self) -> bool {
@@ -37,14 +51,636 @@ impl DrawingAreaManager {
 
     pub fn set_drawing(&self, flag: bool) {
         let imp = self.imp();
-        imp.is_drawing.set(flag)
+        imp.is_drawing.set(flag);
+        if !flag {
+            self.stop_selection();
+            self.set_typing(None);
+        }
+    }
+
+    /// Enter the selection mode: drags select and move the drawn items instead of creating
+    /// new ones.
+    pub fn start_selection(&self) {
+        let imp = self.imp();
+        *imp.current_item.borrow_mut() = None;
+        imp.is_drawing.set(true);
+        imp.is_selecting.set(true);
+        self.set_typing(None);
+    }
+
+    /// Leave the selection mode and clear the current selection
+    pub fn stop_selection(&self) {
+        let imp = self.imp();
+        imp.is_selecting.set(false);
+        self.clear_selection();
+    }
+
+    pub fn is_selecting(&self) -> bool {
+        let imp = self.imp();
+        imp.is_selecting.get()
+    }
+
+    pub fn has_selection(&self) -> bool {
+        let imp = self.imp();
+        !imp.selected_items.borrow().is_empty()
+    }
+
+    pub fn clear_selection(&self) {
+        let imp = self.imp();
+        imp.selected_items.borrow_mut().clear();
+        *imp.rubber_band.borrow_mut() = None;
+        imp.moving_selection.set(false);
+        self.queue_draw();
+    }
+
+    /// Toggle the screenshot mode: the selection and the screen-only items are not drawn
+    pub fn set_exporting(&self, exporting: bool) {
+        self.imp().exporting.set(exporting);
+        if exporting {
+            // The caret is not part of the screenshot
+            self.set_typing(None);
+            self.clear_selection();
+        } else {
+            self.queue_draw();
+        }
+    }
+
+    /// Hidden and locked items can't be picked on the canvas
+    fn is_selectable(item: &DrawableCollection) -> bool {
+        !item.layer().hidden.get() && !item.layer().locked.get()
+    }
+
+    /// Shallow copy of the drawn items, in drawing order
+    pub fn drawn_items(&self) -> Vec<DrawableCollection> {
+        let imp = self.imp();
+        imp.drawn_items.borrow().clone()
+    }
+
+    pub fn is_selected(&self, idx: usize) -> bool {
+        let imp = self.imp();
+        imp.selected_items.borrow().contains(&idx)
+    }
+
+    /// Select the item at position `idx` of the drawn items; `extend` toggles it in the
+    /// current selection instead
+    pub fn select_index(&self, idx: usize, extend: bool) {
+        let imp = self.imp();
+        let mut selected = imp.selected_items.borrow_mut();
+        if extend {
+            if let Some(pos) = selected.iter().position(|&i| i == idx) {
+                selected.remove(pos);
+            } else {
+                selected.push(idx);
+            }
+        } else {
+            *selected = vec![idx];
+        }
+        drop(selected);
+
+        self.queue_draw();
+    }
+
+    /// Remove a single item from the drawing
+    pub fn delete_item(&self, idx: usize) {
+        let imp = self.imp();
+        self.clear_selection();
+        let mut items = imp.drawn_items.borrow_mut();
+        let removed = (idx < items.len()).then(|| items.remove(idx));
+        drop(items);
+
+        self.items_removed(removed.as_slice());
+        self.queue_draw();
+    }
+
+    /// Move the item at position `from` to position `to` of the drawing order
+    pub fn move_item(&self, from: usize, to: usize) {
+        let imp = self.imp();
+        let mut items = imp.drawn_items.borrow_mut();
+        if from >= items.len() || to >= items.len() || from == to {
+            return;
+        }
+        let item = items.remove(from);
+        items.insert(to, item);
+        drop(items);
+
+        self.clear_selection();
+        self.queue_draw();
+    }
+
+    /// Hide or show an item; hidden items are also removed from the selection
+    pub fn set_item_hidden(&self, idx: usize, hidden: bool) {
+        let imp = self.imp();
+        if let Some(item) = imp.drawn_items.borrow().get(idx) {
+            item.layer().hidden.set(hidden);
+        }
+        if hidden {
+            imp.selected_items.borrow_mut().retain(|&i| i != idx);
+        }
+        self.queue_draw();
+    }
+
+    /// Lock or unlock an item; locked items are also removed from the selection
+    pub fn set_item_locked(&self, idx: usize, locked: bool) {
+        let imp = self.imp();
+        if let Some(item) = imp.drawn_items.borrow().get(idx) {
+            item.layer().locked.set(locked);
+        }
+        if locked {
+            imp.selected_items.borrow_mut().retain(|&i| i != idx);
+        }
+        self.queue_draw();
+    }
+
+    /// Index of the topmost drawn item under the (x, y) point
+    fn item_at(&self, x: f64, y: f64) -> Option<usize> {
+        let imp = self.imp();
+        let t = SELECTION_TOLERANCE_PX;
+        imp.drawn_items
+            .borrow()
+            .iter()
+            .enumerate()
+            .rev()
+            .filter(|(_, item)| Self::is_selectable(item))
+            .find(|(_, item)| match item.bounds() {
+                Some((bx, by, bw, bh)) => {
+                    x >= bx - t && x <= bx + bw + t && y >= by - t && y <= by + bh + t
+                }
+                None => false,
+            })
+            .map(|(idx, _)| idx)
+    }
+
+    /// Start a selection drag.
+    /// Clicking on an item selects it (and starts moving the selection), clicking on an empty
+    /// spot starts a rubber band. When `extend` is true (Shift pressed) the clicked item is
+    /// toggled in the current selection.
+    pub fn selection_begin(&self, x: f64, y: f64, extend: bool) {
+        let imp = self.imp();
+        imp.last_offset.set((0.0, 0.0));
+        imp.moving_selection.set(false);
+
+        // A handle (e.g. the tail of a callout) is dragged on its own
+        let handle = imp
+            .drawn_items
+            .borrow()
+            .iter()
+            .rev()
+            .find(|item| {
+                Self::is_selectable(item) && item.handle_at(x, y, 2.0 * SELECTION_TOLERANCE_PX)
+            })
+            .cloned();
+        if handle.is_some() {
+            *imp.moving_handle.borrow_mut() = handle;
+            return;
+        }
+
+        match self.item_at(x, y) {
+            Some(idx) => {
+                let mut selected = imp.selected_items.borrow_mut();
+                if extend {
+                    if let Some(pos) = selected.iter().position(|&i| i == idx) {
+                        selected.remove(pos);
+                        return;
+                    }
+                    selected.push(idx);
+                } else if !selected.contains(&idx) {
+                    *selected = vec![idx];
+                }
+                imp.moving_selection.set(true);
+            }
+            None => {
+                if !extend {
+                    imp.selected_items.borrow_mut().clear();
+                }
+                *imp.rubber_band.borrow_mut() = Some((x, y, 0.0, 0.0));
+            }
+        }
+    }
+
+    /// Select the item under (x, y) unless it is already part of the selection.
+    /// Used before opening the context menu.
+    pub fn select_at(&self, x: f64, y: f64) {
+        let imp = self.imp();
+        if let Some(idx) = self.item_at(x, y)
+            && !imp.selected_items.borrow().contains(&idx)
+        {
+            *imp.selected_items.borrow_mut() = vec![idx];
+            self.queue_draw();
+        }
+    }
+
+    /// Move the selected items or resize the rubber band; (x, y) is the offset from the
+    /// drag start
+    pub fn selection_update(&self, x: f64, y: f64) {
+        let imp = self.imp();
+
+        if let Some(item) = imp.moving_handle.borrow().as_ref() {
+            let (last_x, last_y) = imp.last_offset.get();
+            item.move_handle(x - last_x, y - last_y);
+            imp.last_offset.set((x, y));
+        } else if imp.moving_selection.get() {
+            let (last_x, last_y) = imp.last_offset.get();
+            let items = imp.drawn_items.borrow();
+            for &idx in imp.selected_items.borrow().iter() {
+                if let Some(item) = items.get(idx) {
+                    item.translate(x - last_x, y - last_y);
+                }
+            }
+            imp.last_offset.set((x, y));
+        } else if let Some((x0, y0, _, _)) = *imp.rubber_band.borrow() {
+            *imp.rubber_band.borrow_mut() = Some((x0, y0, x, y));
+        }
+    }
+
+    /// Close the selection drag: all the items fully inside the rubber band are selected
+    pub fn selection_end(&self) {
+        let imp = self.imp();
+        imp.moving_selection.set(false);
+        imp.moving_handle.borrow_mut().take();
+
+        let Some((x0, y0, w, h)) = imp.rubber_band.borrow_mut().take() else {
+            return;
+        };
+        let (rx, ry) = (x0.min(x0 + w), y0.min(y0 + h));
+        let (rw, rh) = (w.abs(), h.abs());
+
+        let mut selected = imp.selected_items.borrow_mut();
+        for (idx, item) in imp.drawn_items.borrow().iter().enumerate() {
+            if Self::is_selectable(item)
+                && let Some((bx, by, bw, bh)) = item.bounds()
+                && bx >= rx
+                && by >= ry
+                && bx + bw <= rx + rw
+                && by + bh <= ry + rh
+                && !selected.contains(&idx)
+            {
+                selected.push(idx);
+            }
+        }
+    }
+
+    /// Replace the selected items with a single group, placed at the z-position of the
+    /// topmost selected item
+    pub fn group_selection(&self) {
+        let imp = self.imp();
+        let mut selected = imp.selected_items.borrow_mut();
+        if selected.len() < 2 {
+            return;
+        }
+        selected.sort_unstable();
+
+        let mut items = imp.drawn_items.borrow_mut();
+        let mut group_items: Vec<DrawableCollection> = Vec::new();
+        for &idx in selected.iter().rev() {
+            group_items.insert(0, items.remove(idx));
+        }
+
+        let position = selected[selected.len() - 1] + 1 - selected.len();
+        items.insert(
+            position,
+            DrawableCollection::Groups(Group::new(group_items)),
+        );
+        *selected = vec![position];
+        drop(items);
+        drop(selected);
+
+        self.queue_draw();
+    }
+
+    /// Split the selected groups back into their items, keeping them selected
+    pub fn ungroup_selection(&self) {
+        let imp = self.imp();
+        let mut selected = imp.selected_items.borrow_mut();
+        let mut items = imp.drawn_items.borrow_mut();
+
+        let mut new_items: Vec<DrawableCollection> = Vec::new();
+        let mut new_selected: Vec<usize> = Vec::new();
+        for (idx, item) in items.drain(..).enumerate() {
+            let is_selected = selected.contains(&idx);
+            match item {
+                DrawableCollection::Groups(group) if is_selected => {
+                    for inner in group.items.borrow().iter() {
+                        new_selected.push(new_items.len());
+                        new_items.push(inner.clone());
+                    }
+                }
+                item => {
+                    if is_selected {
+                        new_selected.push(new_items.len());
+                    }
+                    new_items.push(item);
+                }
+            }
+        }
+
+        *items = new_items;
+        *selected = new_selected;
+        drop(items);
+        drop(selected);
+
+        self.queue_draw();
+    }
+
+    /// Sorted copy of the selected indices, without the stale ones past the drawn items
+    fn sorted_selection(&self) -> Vec<usize> {
+        let imp = self.imp();
+        let n_items = imp.drawn_items.borrow().len();
+        let mut selected: Vec<usize> = imp
+            .selected_items
+            .borrow()
+            .iter()
+            .copied()
+            .filter(|&idx| idx < n_items)
+            .collect();
+        selected.sort_unstable();
+        selected.dedup();
+        selected
+    }
+
+    /// Move the selected items on top of all the others, keeping their relative order
+    pub fn bring_selection_to_front(&self) {
+        let imp = self.imp();
+        let selected = self.sorted_selection();
+        let mut items = imp.drawn_items.borrow_mut();
+
+        let mut moved: Vec<DrawableCollection> = Vec::new();
+        for &idx in selected.iter().rev() {
+            moved.insert(0, items.remove(idx));
+        }
+        let start = items.len();
+        items.extend(moved);
+        *imp.selected_items.borrow_mut() = (start..items.len()).collect();
+        drop(items);
+
+        self.queue_draw();
+    }
+
+    /// Move the selected items below all the others, keeping their relative order
+    pub fn send_selection_to_back(&self) {
+        let imp = self.imp();
+        let selected = self.sorted_selection();
+        let mut items = imp.drawn_items.borrow_mut();
+
+        let mut moved: Vec<DrawableCollection> = Vec::new();
+        for &idx in selected.iter().rev() {
+            moved.insert(0, items.remove(idx));
+        }
+        let n = moved.len();
+        items.splice(0..0, moved);
+        *imp.selected_items.borrow_mut() = (0..n).collect();
+        drop(items);
+
+        self.queue_draw();
+    }
+
+    /// Move each selected item one step up in the drawing order
+    pub fn raise_selection(&self) {
+        let imp = self.imp();
+        let mut selected = self.sorted_selection();
+        let mut items = imp.drawn_items.borrow_mut();
+
+        for i in (0..selected.len()).rev() {
+            let idx = selected[i];
+            if idx + 1 < items.len() && !selected.contains(&(idx + 1)) {
+                items.swap(idx, idx + 1);
+                selected[i] = idx + 1;
+            }
+        }
+        *imp.selected_items.borrow_mut() = selected;
+        drop(items);
+
+        self.queue_draw();
+    }
+
+    /// Move each selected item one step down in the drawing order
+    pub fn lower_selection(&self) {
+        let imp = self.imp();
+        let mut selected = self.sorted_selection();
+        let mut items = imp.drawn_items.borrow_mut();
+
+        for i in 0..selected.len() {
+            let idx = selected[i];
+            if idx > 0 && !selected.contains(&(idx - 1)) {
+                items.swap(idx, idx - 1);
+                selected[i] = idx - 1;
+            }
+        }
+        *imp.selected_items.borrow_mut() = selected;
+        drop(items);
+
+        self.queue_draw();
+    }
+
+    /// Remove the selected items from the drawing
+    pub fn delete_selection(&self) {
+        let imp = self.imp();
+        let mut selected = imp.selected_items.borrow_mut();
+        selected.sort_unstable();
+
+        let mut items = imp.drawn_items.borrow_mut();
+        let mut removed = Vec::new();
+        for &idx in selected.iter().rev() {
+            if idx < items.len() {
+                removed.push(items.remove(idx));
+            }
+        }
+        selected.clear();
+        drop(items);
+        drop(selected);
+
+        self.items_removed(&removed);
+        self.queue_draw();
+    }
+
+    /// Renumber the remaining badges when a numbered badge is removed, if enabled
+    fn items_removed(&self, removed: &[DrawableCollection]) {
+        let Some(badge) = removed.iter().flat_map(|item| item.badges()).next() else {
+            return;
+        };
+        let renumber = badge
+            .settings
+            .numbered_circle
+            .get_value("renumber")
+            .get_bool()
+            .unwrap_or(false);
+        if renumber {
+            self.renumber_badges(&badge.settings);
+        }
+    }
+
+    /// Number the badges again in placement order, every restarted sequence from its own
+    /// first number, so that no gap is left. The next badge follows the last one.
+    pub fn renumber_badges(&self, settings: &SettingsRc) {
+        let imp = self.imp();
+        let mut sequences: BTreeMap<i32, (i32, Vec<NumberedCircle>)> = BTreeMap::new();
+        for badge in imp
+            .drawn_items
+            .borrow()
+            .iter()
+            .flat_map(|item| item.badges())
+        {
+            let (sequence, start) = badge.sequence();
+            sequences
+                .entry(sequence)
+                .or_insert_with(|| (start, Vec::new()))
+                .1
+                .push(badge);
+        }
+
+        for (start, badges) in sequences.values_mut() {
+            badges.sort_by_key(|badge| badge.placed.get());
+            for (number, badge) in (*start..).zip(badges.iter()) {
+                badge.number.set(number);
+                badge.shadow_cache.clear();
+            }
+        }
+
+        // Continue the current sequence after its last badge, or from its start if it is empty
+        let get = |key: &str| {
+            settings
+                .numbered_circle
+                .get_value(key)
+                .get_i32()
+                .unwrap_or_default()
+        };
+        let next = match sequences.get(&get("sequence")) {
+            Some((start, badges)) => start + badges.len() as i32,
+            None => get("sequence_start"),
+        };
+        settings
+            .numbered_circle
+            .set_value("number", SettingValue::I32(next))
+            .expect("Error in renumber_badges");
+
+        self.queue_draw();
+    }
+
+    /// Redaction rectangles, in drawing order, that must be burnt into the exported image
+    pub fn redactions(&self) -> Vec<Redaction> {
+        let imp = self.imp();
+        imp.drawn_items
+            .borrow()
+            .iter()
+            .flat_map(|item| item.redactions())
+            .collect()
+    }
+
+    /// Exported items bottom to top, groups flattened, for an image of `width` x `height`
+    /// pixels whose top left corner is at `origin` in the window, with `scale` pixels per
+    /// window pixel. The redactions are kept as rectangles, the other items as the pixels
+    /// they paint, so that the items above a redaction are not burnt.
+    pub fn redaction_stack(
+        &self,
+        origin: (f64, f64),
+        scale: f64,
+        width: i32,
+        height: i32,
+    ) -> Vec<redact::Stacked> {
+        let imp = self.imp();
+        let pg: pango::Layout = self.create_pango_layout(None);
+        let mut stack = Vec::new();
+        for item in imp.drawn_items.borrow().iter() {
+            Self::stack_item(item, &pg, origin, scale, (width, height), &mut stack);
+        }
+        stack
+    }
+
+    fn stack_item(
+        item: &DrawableCollection,
+        pg: &pango::Layout,
+        origin: (f64, f64),
+        scale: f64,
+        (width, height): (i32, i32),
+        stack: &mut Vec<redact::Stacked>,
+    ) {
+        if item.layer().hidden.get() || !item.in_export() {
+            return;
+        }
+        match item {
+            DrawableCollection::Groups(group) => {
+                for item in group.items.borrow().iter() {
+                    Self::stack_item(item, pg, origin, scale, (width, height), stack);
+                }
+            }
+            DrawableCollection::Redacts(state) => {
+                let (rect, rgb) = state.redaction();
+                if let Some(rect) =
+                    redact::PixelRect::from_window(rect, origin, scale, width, height)
+                {
+                    stack.push(redact::Stacked::Redaction(rect, rgb));
+                }
+            }
+            _ => {
+                // Only the alpha of what the item paints, shadow included
+                let Ok(mut surface) = cairo::ImageSurface::create(cairo::Format::A8, width, height)
+                else {
+                    return;
+                };
+                surface.set_device_scale(scale, scale);
+                {
+                    let cr = cairo::Context::new(&surface).expect("Failed to create the context");
+                    cr.translate(-origin.0, -origin.1);
+                    item.draw_with_shadow(&cr, pg, true);
+                }
+                surface.flush();
+                let stride = surface.stride();
+                let Ok(data) = surface.data() else {
+                    return;
+                };
+                let mut painted = Vec::with_capacity((width * height) as usize);
+                for y in 0..height {
+                    for x in 0..width {
+                        painted.push(data[(y * stride + x) as usize] != 0);
+                    }
+                }
+                stack.push(redact::Stacked::Item(painted));
+            }
+        }
+    }
+
+    /// Draw a dashed box around the selected items and the rubber band
+    fn draw_selection(&self, cr: &cairo::Context) {
+        let imp = self.imp();
+        let t = SELECTION_TOLERANCE_PX;
+
+        cr.save().unwrap();
+        cr.set_line_width(1.5);
+        cr.set_dash(&[6.0, 4.0], 0.0);
+        cr.set_source_rgba(0.54, 0.71, 0.98, 1.0);
+        let items = imp.drawn_items.borrow();
+        for &idx in imp.selected_items.borrow().iter() {
+            if let Some((x, y, w, h)) = items.get(idx).and_then(|item| item.bounds()) {
+                cr.rectangle(x - t, y - t, w + 2.0 * t, h + 2.0 * t);
+                cr.stroke().unwrap();
+            }
+        }
+
+        // Handles that can be dragged on their own
+        for &idx in imp.selected_items.borrow().iter() {
+            if let Some((x, y)) = items.get(idx).and_then(|item| item.handle_point()) {
+                cr.arc(x, y, t, 0.0, 2.0 * std::f64::consts::PI);
+                cr.fill().unwrap();
+            }
+        }
+
+        if let Some((x0, y0, w, h)) = *imp.rubber_band.borrow() {
+            cr.rectangle(x0, y0, w, h);
+            cr.set_source_rgba(0.54, 0.71, 0.98, 0.15);
+            cr.fill_preserve().unwrap();
+            cr.set_source_rgba(0.54, 0.71, 0.98, 1.0);
+            cr.stroke().unwrap();
+        }
+        cr.restore().unwrap();
     }
 
     fn draw_elements(&self, cr: &cairo::Context) {
         let imp = self.imp();
         let pg: pango::Layout = self.create_pango_layout(None);
         for element in &*imp.drawn_items.borrow() {
-            element.draw_with_saved_settings(cr, &pg);
+            if element.layer().hidden.get() || (imp.exporting.get() && !element.in_export()) {
+                continue;
+            }
+            element.draw_with_shadow(cr, &pg, true);
         }
     }
 
@@ -52,7 +688,7 @@ impl DrawingAreaManager {
         let imp = self.imp();
         let pg: pango::Layout = self.create_pango_layout(None);
         if let Some(ref item) = *imp.current_item.borrow() {
-            item.draw(cr, &pg);
+            item.draw_with_shadow(cr, &pg, false);
         }
     }
 
@@ -62,6 +698,7 @@ impl DrawingAreaManager {
 
         self.draw_elements(cr);
         self.draw_current_element(cr);
+        self.draw_selection(cr);
     }
 
     pub fn drag_begin(&self, x: f64, y: f64) {
@@ -71,27 +708,18 @@ impl DrawingAreaManager {
             Some(drawable) => match drawable {
                 DrawableCollection::InputTexts(item) => {
                     // BUG: when changing the color, the same applies to all text...
-                    // See the "event_controller_key" function for the first part of the logic explanation.
-                    //
-                    // The user has pressed "esc" because he has finished typing. He is actually typing on the
-                    // drawn_items.last() element. So, to continue with the second InputText (which is actually the
-                    // current_item), we need to:
-                    //     1. create a new InputText instance via the drawable.stop_controller_key() function;
-                    //     2. push the current_item into the drawn_items so that event_controller_key can access
-                    //        and change its text;
-                    //     3. make the new InputText the new current_item.
-                    //
-                    // Finally, note that if another drawable (like Arc, Box, Freehand, ...) is created, the
-                    // current_item is normally filled, and the transition is smooth.
-                    let mut items = imp.drawn_items.borrow_mut();
-                    if let Some(last_drawable) = items.last() {
+                    // The user has finished typing on the drawn_items.last() element, its settings
+                    // are frozen via the drawable.stop_controller_key() function. The new InputText
+                    // (the current_item) is stored by drag_end, like the other drawables.
+                    if let Some(last_drawable) = imp.drawn_items.borrow().last() {
                         let _ = last_drawable.stop_controller_key();
                     }
-
-                    items.push(drawable.clone());
+                    self.set_typing(None);
                     item.drag_begin(x, y);
                 }
                 _ => {
+                    // A new callout takes the keys on drag end, the others leave the text
+                    self.set_typing(None);
                     drawable.drag_begin(x, y);
                 }
             },
@@ -103,23 +731,95 @@ impl DrawingAreaManager {
 
     pub fn drag_update(&self, x: f64, y: f64) {
         let imp = self.imp();
+        if imp.is_selecting.get() {
+            self.selection_update(x, y);
+            return;
+        }
+        let mut eraser_step = None;
         if let Some(drawable) = imp.current_item.borrow_mut().as_mut() {
             drawable.drag_update(x, y);
+            if let DrawableCollection::FreeHands(stroke) = drawable
+                && stroke.is_eraser()
+            {
+                eraser_step = stroke.eraser_step();
+            }
         } else {
             eprintln!("⚠️ drag_update called but no current_item is set");
         }
+
+        if let Some((from, to, radius)) = eraser_step {
+            self.erase_freehands(from, to, radius);
+        }
+    }
+
+    /// Remove the parts of the freehand strokes under the eraser segment from-to, splitting
+    /// the strokes it crosses. Hidden, locked and grouped strokes are left untouched.
+    fn erase_freehands(&self, from: eraser::Point, to: eraser::Point, radius: f64) {
+        let imp = self.imp();
+        let mut items = imp.drawn_items.borrow_mut();
+        let mut erased = false;
+        let mut idx = 0;
+        while idx < items.len() {
+            let pieces = match &items[idx] {
+                DrawableCollection::FreeHands(stroke) if Self::is_selectable(&items[idx]) => {
+                    eraser::erase(&stroke.x.borrow(), &stroke.y.borrow(), from, to, radius).map(
+                        |pieces| {
+                            pieces
+                                .into_iter()
+                                .map(|(x, y)| {
+                                    DrawableCollection::FreeHands(stroke.with_points(x, y))
+                                })
+                                .collect::<Vec<_>>()
+                        },
+                    )
+                }
+                _ => None,
+            };
+            match pieces {
+                Some(pieces) => {
+                    let len = pieces.len();
+                    items.splice(idx..=idx, pieces);
+                    idx += len;
+                    erased = true;
+                }
+                None => idx += 1,
+            }
+        }
+        drop(items);
+
+        // The indices of the selected items are no longer valid
+        if erased {
+            self.clear_selection();
+        }
     }
 
     pub fn drag_end(&self) {
         // Borrow the current item
         let imp = self.imp();
+        if imp.is_selecting.get() {
+            self.selection_end();
+            return;
+        }
 
         // Create the next drawable
         let mut new_drawable: Option<DrawableCollection> = None;
 
         if let Some(drawable) = imp.current_item.borrow_mut().as_mut() {
+            // The drawable is not complete yet, keep it as the current item
+            if drawable.needs_another_drag() {
+                let _ = drawable.drag_end();
+                return;
+            }
+
             // Store it in the drawn items list
-            imp.drawn_items.borrow_mut().push(drawable.clone());
+            if !drawable.is_blank() {
+                imp.drawn_items.borrow_mut().push(drawable.clone());
+            }
+
+            // The keys now go to the placed text
+            if drawable.typed_text().is_some() {
+                self.set_typing(Some(drawable.clone()));
+            }
 
             // Create new drawable
             new_drawable = drawable.drag_end();
@@ -131,32 +831,99 @@ impl DrawingAreaManager {
         *imp.current_item.borrow_mut() = new_drawable.clone();
     }
 
-    /// This function is useful only for the InputText drawables.
-    /// The logic for InputText items is the following.
-    ///     1. Press the button and create the item;
-    ///     2. Drag_begin instatiate the position of the box;
-    ///     3. Drag_end stores the current InputText and returns a new InputText that becomes the
-    ///        new current_item.
-    ///
-    /// Up to now, there is: the first InputText as drawn_items.last(), and the next InputText is
-    /// the current_item.
-    ///
-    /// A listener is create in geometry::mod::attach_gestures(), so whenever the user writes on
-    /// the keyboard, the settings.input_text["text"] is filled.
-    /// If the key pressed is not "esc", the text increases and the "event_controller_key()"
-    /// function is called. So, this function needs to get drawn_items.last() and modify its
-    /// self.text and then queue a new redraw.
-    ///
-    /// See the "stop_controller_key" function for a continuation.
-    pub fn event_controller_key(&self) {
-        let imp = self.imp();
-
-        // The last element that needs to be written is InputText
-        if let Some(drawable) = imp.drawn_items.borrow_mut().last() {
-            drawable.event_controller_key();
-            self.queue_draw();
-        } else {
-            eprintln!("⚠️ event_controller_key called but no current_item is set");
+    /// Complete the current drawable when it takes several clicks (e.g. with Enter).
+    /// Returns false if the current drawable does not support it.
+    pub fn finish_current_item(&self) -> bool {
+        let imp = self.imp();
+        let mut current = imp.current_item.borrow_mut();
+        let Some(drawable) = current.as_ref() else {
+            return false;
+        };
+        if !drawable.needs_another_drag() || !drawable.finish() {
+            return false;
+        }
+
+        // Store it in the drawn items list and create the next drawable
+        imp.drawn_items.borrow_mut().push(drawable.clone());
+        *current = drawable.drag_end();
+        drop(current);
+
+        self.queue_draw();
+        true
+    }
+
+    /// Change the text annotation or callout receiving the keys, its caret is shown until
+    /// another one is placed, the tool changes or the screenshot is taken.
+    fn set_typing(&self, text: Option<DrawableCollection>) {
+        let imp = self.imp();
+        if let Some(previous) = imp.typing.replace(text.clone())
+            && let Some(typed) = previous.typed_text()
+        {
+            typed.caret.set(false);
+            previous.set_preedit("", 0);
+        }
+
+        // Drop the sequence being composed for the previous text
+        if let Some(im_context) = imp.im_context.borrow().as_ref() {
+            im_context.reset();
+            if text.is_some() {
+                im_context.focus_in();
+            } else {
+                im_context.focus_out();
+            }
+        }
+        if let Some(typed) = text.as_ref().and_then(|text| text.typed_text()) {
+            typed.caret.set(true);
+        }
+        self.update_im_cursor();
+        self.queue_draw();
+    }
+
+    /// Input method used for the text annotations: dead keys, compose sequences, CJK...
+    pub fn set_im_context(&self, im_context: &impl IsA<gtk::IMContext>) {
+        *self.imp().im_context.borrow_mut() = Some(im_context.clone().upcast());
+    }
+
+    /// Show the string being composed by the input method in the text being typed
+    pub fn set_preedit(&self, preedit: &str, cursor: i32) {
+        if let Some(text) = self.imp().typing.borrow().as_ref() {
+            text.set_preedit(preedit, cursor);
+        }
+        self.update_im_cursor();
+        self.queue_draw();
+    }
+
+    /// Move the input method popups next to the caret
+    fn update_im_cursor(&self) {
+        let imp = self.imp();
+        let (Some(text), Some(im_context)) =
+            (imp.typing.borrow().clone(), imp.im_context.borrow().clone())
+        else {
+            return;
+        };
+        let pg = self.create_pango_layout(None);
+        let Some((x, y, height)) = text.caret_area(&pg) else {
+            return;
+        };
+        im_context.set_cursor_location(&gdk::Rectangle::new(
+            x as i32,
+            y as i32,
+            1,
+            height.ceil() as i32,
+        ));
+    }
+
+    /// True while a text annotation or a callout is being typed
+    pub fn is_typing_text(&self) -> bool {
+        self.imp().typing.borrow().is_some()
+    }
+
+    /// Apply an editing key to the text being typed
+    pub fn edit_text(&self, action: &TextAction) {
+        if let Some(text) = self.imp().typing.borrow().as_ref() {
+            text.edit_text(action);
         }
+        self.update_im_cursor();
+        self.queue_draw();
     }
 }
@@ -0,0 +1,89 @@
+//! Labels of the numbered annotations.
+//!
+//! A badge stores a plain integer, written as a number (1, 2, 3), a letter (A, B, C) or a
+//! roman numeral (I, II, III), optionally after a custom prefix such as "Step ".
+
+/// Label of the `number`-th badge of a sequence in the given style ("numbers", "letters" or
+/// "roman"). Numbers that can't be written in the style fall back to plain digits.
+pub fn format_number(number: i32, style: &str, prefix: &str) -> String {
+    let label = match style {
+        "letters" => letters(number),
+        "roman" => roman(number),
+        _ => None,
+    }
+    .unwrap_or_else(|| number.to_string());
+    format!("{prefix}{label}")
+}
+
+/// A..Z, then AA, AB, ... like spreadsheet columns
+fn letters(number: i32) -> Option<String> {
+    if number <= 0 {
+        return None;
+    }
+    let mut n = number as u32;
+    let mut label = Vec::new();
+    while n > 0 {
+        n -= 1;
+        label.push(char::from(b'A' + (n % 26) as u8));
+        n /= 26;
+    }
+    Some(label.iter().rev().collect())
+}
+
+/// Roman numerals, from 1 to 3999
+fn roman(number: i32) -> Option<String> {
+    if !(1..4000).contains(&number) {
+        return None;
+    }
+    const SYMBOLS: [(i32, &str); 13] = [
+        (1000, "M"),
+        (900, "CM"),
+        (500, "D"),
+        (400, "CD"),
+        (100, "C"),
+        (90, "XC"),
+        (50, "L"),
+        (40, "XL"),
+        (10, "X"),
+        (9, "IX"),
+        (5, "V"),
+        (4, "IV"),
+        (1, "I"),
+    ];
+    let mut n = number;
+    let mut label = String::new();
+    for (value, symbol) in SYMBOLS {
+        while n >= value {
+            label.push_str(symbol);
+            n -= value;
+        }
+    }
+    Some(label)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers_and_prefix() {
+        assert_eq!(format_number(7, "numbers", ""), "7");
+        assert_eq!(format_number(3, "numbers", "Step "), "Step 3");
+    }
+
+    #[test]
+    fn letters_sequence() {
+        assert_eq!(format_number(1, "letters", ""), "A");
+        assert_eq!(format_number(26, "letters", ""), "Z");
+        assert_eq!(format_number(27, "letters", ""), "AA");
+        assert_eq!(format_number(703, "letters", ""), "AAA");
+        assert_eq!(format_number(0, "letters", ""), "0");
+    }
+
+    #[test]
+    fn roman_numerals() {
+        assert_eq!(format_number(4, "roman", ""), "IV");
+        assert_eq!(format_number(1994, "roman", ""), "MCMXCIV");
+        assert_eq!(format_number(4000, "roman", "#"), "#4000");
+    }
+}
@@ -0,0 +1,238 @@
+//! Editing state of the text annotations.
+//!
+//! The text is a plain string with a caret and an optional selection anchor, both byte
+//! indices on char boundaries, as expected by the Pango layout that draws it. The layout has
+//! no width, so the lines on screen are the lines separated by '\n' and up/down keep the
+//! column in chars.
+
+/// Caret movements
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Motion {
+    Left,
+    Right,
+    Up,
+    Down,
+    /// Start of the line
+    Home,
+    /// End of the line
+    End,
+}
+
+/// Edits coming from the keyboard
+#[derive(Clone, Debug, PartialEq)]
+pub enum TextAction {
+    /// Type a string over the selection
+    Insert(String),
+    /// Remove the selection or the char before the caret
+    Backspace,
+    /// Remove the selection or the char after the caret
+    Delete,
+    /// Move the caret, extending the selection if true
+    Move(Motion, bool),
+    SelectAll,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TextEditor {
+    text: String,
+    caret: usize,
+    anchor: Option<usize>,
+}
+
+impl TextEditor {
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Caret position, as a byte index
+    pub fn caret(&self) -> usize {
+        self.caret
+    }
+
+    /// Selected byte range, None if nothing is selected
+    pub fn selection(&self) -> Option<(usize, usize)> {
+        let anchor = self.anchor?;
+        (anchor != self.caret).then(|| (anchor.min(self.caret), anchor.max(self.caret)))
+    }
+
+    pub fn apply(&mut self, action: &TextAction) {
+        match action {
+            TextAction::Insert(text) => self.insert(text),
+            TextAction::Backspace => {
+                if !self.delete_selection() {
+                    let start = self.prev_boundary(self.caret);
+                    self.text.replace_range(start..self.caret, "");
+                    self.caret = start;
+                }
+            }
+            TextAction::Delete => {
+                if !self.delete_selection() {
+                    let end = self.next_boundary(self.caret);
+                    self.text.replace_range(self.caret..end, "");
+                }
+            }
+            TextAction::Move(motion, extend) => self.move_caret(*motion, *extend),
+            TextAction::SelectAll => {
+                self.anchor = Some(0);
+                self.caret = self.text.len();
+            }
+        }
+    }
+
+    fn insert(&mut self, text: &str) {
+        self.delete_selection();
+        self.text.insert_str(self.caret, text);
+        self.caret += text.len();
+    }
+
+    /// Remove the selected text, false if nothing was selected
+    fn delete_selection(&mut self) -> bool {
+        let selection = self.selection();
+        self.anchor = None;
+        let Some((start, end)) = selection else {
+            return false;
+        };
+        self.text.replace_range(start..end, "");
+        self.caret = start;
+        true
+    }
+
+    fn move_caret(&mut self, motion: Motion, extend: bool) {
+        // Without shift, left and right collapse the selection on its side
+        let selection = self.selection();
+        if extend {
+            self.anchor.get_or_insert(self.caret);
+        } else {
+            self.anchor = None;
+        }
+
+        self.caret = match (motion, selection, extend) {
+            (Motion::Left, Some((start, _)), false) => start,
+            (Motion::Right, Some((_, end)), false) => end,
+            (Motion::Left, _, _) => self.prev_boundary(self.caret),
+            (Motion::Right, _, _) => self.next_boundary(self.caret),
+            (Motion::Home, _, _) => self.line_start(self.caret),
+            (Motion::End, _, _) => self.line_end(self.caret),
+            (Motion::Up, _, _) => {
+                let start = self.line_start(self.caret);
+                if start == 0 {
+                    0
+                } else {
+                    let column = self.text[start..self.caret].chars().count();
+                    self.at_column(self.line_start(start - 1), column)
+                }
+            }
+            (Motion::Down, _, _) => {
+                let end = self.line_end(self.caret);
+                if end == self.text.len() {
+                    end
+                } else {
+                    let column = self.text[self.line_start(self.caret)..self.caret]
+                        .chars()
+                        .count();
+                    self.at_column(end + 1, column)
+                }
+            }
+        };
+    }
+
+    fn prev_boundary(&self, idx: usize) -> usize {
+        self.text[..idx]
+            .char_indices()
+            .next_back()
+            .map_or(0, |(i, _)| i)
+    }
+
+    fn next_boundary(&self, idx: usize) -> usize {
+        self.text[idx..]
+            .chars()
+            .next()
+            .map_or(idx, |c| idx + c.len_utf8())
+    }
+
+    fn line_start(&self, idx: usize) -> usize {
+        self.text[..idx].rfind('\n').map_or(0, |i| i + 1)
+    }
+
+    fn line_end(&self, idx: usize) -> usize {
+        self.text[idx..]
+            .find('\n')
+            .map_or(self.text.len(), |i| idx + i)
+    }
+
+    /// Index of the char `column` of the line starting at `start`, or of the line end
+    fn at_column(&self, start: usize, column: usize) -> usize {
+        let end = self.line_end(start);
+        self.text[start..end]
+            .char_indices()
+            .nth(column)
+            .map_or(end, |(i, _)| start + i)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn typed(text: &str) -> TextEditor {
+        let mut editor = TextEditor::default();
+        editor.apply(&TextAction::Insert(text.to_string()));
+        editor
+    }
+
+    #[test]
+    fn insert_and_delete_around_the_caret() {
+        let mut editor = typed("héllo");
+        editor.apply(&TextAction::Move(Motion::Left, false));
+        editor.apply(&TextAction::Move(Motion::Left, false));
+        editor.apply(&TextAction::Backspace);
+        assert_eq!(editor.text(), "hélo");
+        editor.apply(&TextAction::Delete);
+        assert_eq!(editor.text(), "héo");
+        editor.apply(&TextAction::Move(Motion::Home, false));
+        editor.apply(&TextAction::Insert("¡".to_string()));
+        assert_eq!(editor.text(), "¡héo");
+        assert_eq!(editor.caret(), "¡".len());
+    }
+
+    #[test]
+    fn lines_keep_the_column() {
+        let mut editor = typed("first line\nab\nthird");
+        editor.apply(&TextAction::Move(Motion::Up, false));
+        // "third" caret at column 5, the middle line is shorter
+        assert_eq!(editor.caret(), "first line\nab".len());
+        editor.apply(&TextAction::Move(Motion::Up, false));
+        assert_eq!(editor.caret(), "fi".len());
+        editor.apply(&TextAction::Move(Motion::End, false));
+        assert_eq!(editor.caret(), "first line".len());
+        editor.apply(&TextAction::Move(Motion::Down, false));
+        editor.apply(&TextAction::Move(Motion::Down, false));
+        assert_eq!(editor.caret(), "first line\nab\nth".len());
+        editor.apply(&TextAction::Move(Motion::Down, false));
+        assert_eq!(editor.caret(), editor.text().len());
+    }
+
+    #[test]
+    fn shift_selection_is_replaced() {
+        let mut editor = typed("one two");
+        for _ in 0..3 {
+            editor.apply(&TextAction::Move(Motion::Left, true));
+        }
+        assert_eq!(editor.selection(), Some((4, 7)));
+        editor.apply(&TextAction::Insert("2".to_string()));
+        assert_eq!(editor.text(), "one 2");
+        assert_eq!(editor.selection(), None);
+    }
+
+    #[test]
+    fn select_all_and_collapse() {
+        let mut editor = typed("abc\ndef");
+        editor.apply(&TextAction::SelectAll);
+        assert_eq!(editor.selection(), Some((0, 7)));
+        editor.apply(&TextAction::Move(Motion::Left, false));
+        assert_eq!((editor.caret(), editor.selection()), (0, None));
+        editor.apply(&TextAction::SelectAll);
+        editor.apply(&TextAction::Backspace);
+        assert_eq!(editor.text(), "");
+    }
+}
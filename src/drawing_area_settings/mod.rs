@@ -95,6 +95,18 @@ pub struct Settings {
     pub freehand: FreehandSettings,
     pub numbered_circle: NumberedCircleSettings,
     pub input_text: InputTextSettings,
+    pub obfuscate: ObfuscateSettings,
+    pub redact: RedactSettings,
+    pub spotlight: SpotlightSettings,
+    pub magnifier: MagnifierSettings,
+    pub polygon: PolygonSettings,
+    pub callout: CalloutSettings,
+    pub stamp: StampSettings,
+    pub measure: MeasureSettings,
+    pub eyedropper: EyedropperSettings,
+    pub highlight: HighlightSettings,
+    pub shadow: ShadowSettings,
+    pub image: ImageSettings,
 }
 
 #[derive(Default, Deserialize, Serialize, Clone)]
@@ -110,6 +122,21 @@ pub struct ArcSettings {
     border_g: f64,
     border_b: f64,
     border_a: f64,
+    shape: String,
+    style: String,
+    start_angle: f64,
+    end_angle: f64,
+    fill_mode: String,
+    fill_stops: String,
+    fill_angle: f64,
+    shadow: String,
+    shadow_offset_x: f64,
+    shadow_offset_y: f64,
+    shadow_blur: f64,
+    shadow_r: f64,
+    shadow_g: f64,
+    shadow_b: f64,
+    shadow_a: f64,
 }
 
 impl ArcSettings {
@@ -127,6 +154,21 @@ impl ArcSettings {
             "border_g" => SettingValue::F64(self.border_g),
             "border_b" => SettingValue::F64(self.border_b),
             "border_a" => SettingValue::F64(self.border_a),
+            "shape" => SettingValue::String(self.shape.clone()),
+            "style" => SettingValue::String(self.style.clone()),
+            "start_angle" => SettingValue::F64(self.start_angle),
+            "end_angle" => SettingValue::F64(self.end_angle),
+            "fill_mode" => SettingValue::String(self.fill_mode.clone()),
+            "fill_stops" => SettingValue::String(self.fill_stops.clone()),
+            "fill_angle" => SettingValue::F64(self.fill_angle),
+            "shadow" => SettingValue::String(self.shadow.clone()),
+            "shadow_offset_x" => SettingValue::F64(self.shadow_offset_x),
+            "shadow_offset_y" => SettingValue::F64(self.shadow_offset_y),
+            "shadow_blur" => SettingValue::F64(self.shadow_blur),
+            "shadow_r" => SettingValue::F64(self.shadow_r),
+            "shadow_g" => SettingValue::F64(self.shadow_g),
+            "shadow_b" => SettingValue::F64(self.shadow_b),
+            "shadow_a" => SettingValue::F64(self.shadow_a),
             other => {
                 eprintln!("ArcSettings::get_value: unknown key '{}'", other);
                 // Default – you can change this to whatever makes sense.
@@ -149,6 +191,20 @@ pub struct RectSettings {
     border_g: f64,
     border_b: f64,
     border_a: f64,
+    corner_radius: f64,
+    dash: String,
+    style: String,
+    fill_mode: String,
+    fill_stops: String,
+    fill_angle: f64,
+    shadow: String,
+    shadow_offset_x: f64,
+    shadow_offset_y: f64,
+    shadow_blur: f64,
+    shadow_r: f64,
+    shadow_g: f64,
+    shadow_b: f64,
+    shadow_a: f64,
 }
 
 impl RectSettings {
@@ -166,6 +222,20 @@ impl RectSettings {
             "border_g" => SettingValue::F64(self.border_g),
             "border_b" => SettingValue::F64(self.border_b),
             "border_a" => SettingValue::F64(self.border_a),
+            "corner_radius" => SettingValue::F64(self.corner_radius),
+            "dash" => SettingValue::String(self.dash.clone()),
+            "style" => SettingValue::String(self.style.clone()),
+            "fill_mode" => SettingValue::String(self.fill_mode.clone()),
+            "fill_stops" => SettingValue::String(self.fill_stops.clone()),
+            "fill_angle" => SettingValue::F64(self.fill_angle),
+            "shadow" => SettingValue::String(self.shadow.clone()),
+            "shadow_offset_x" => SettingValue::F64(self.shadow_offset_x),
+            "shadow_offset_y" => SettingValue::F64(self.shadow_offset_y),
+            "shadow_blur" => SettingValue::F64(self.shadow_blur),
+            "shadow_r" => SettingValue::F64(self.shadow_r),
+            "shadow_g" => SettingValue::F64(self.shadow_g),
+            "shadow_b" => SettingValue::F64(self.shadow_b),
+            "shadow_a" => SettingValue::F64(self.shadow_a),
             other => {
                 eprintln!("RectSettings::get_value: unknown key '{}'", other);
                 // Default – you can change this to whatever makes sense.
@@ -184,6 +254,16 @@ pub struct LineSettings {
     size: f64,
     line_cap: String,
     line_join: String,
+    dash: String,
+    dash_pattern: String,
+    shadow: String,
+    shadow_offset_x: f64,
+    shadow_offset_y: f64,
+    shadow_blur: f64,
+    shadow_r: f64,
+    shadow_g: f64,
+    shadow_b: f64,
+    shadow_a: f64,
 }
 
 impl LineSettings {
@@ -197,6 +277,16 @@ impl LineSettings {
             "size" => SettingValue::F64(self.size),
             "line_cap" => SettingValue::String(self.line_cap.clone()),
             "line_join" => SettingValue::String(self.line_join.clone()),
+            "dash" => SettingValue::String(self.dash.clone()),
+            "dash_pattern" => SettingValue::String(self.dash_pattern.clone()),
+            "shadow" => SettingValue::String(self.shadow.clone()),
+            "shadow_offset_x" => SettingValue::F64(self.shadow_offset_x),
+            "shadow_offset_y" => SettingValue::F64(self.shadow_offset_y),
+            "shadow_blur" => SettingValue::F64(self.shadow_blur),
+            "shadow_r" => SettingValue::F64(self.shadow_r),
+            "shadow_g" => SettingValue::F64(self.shadow_g),
+            "shadow_b" => SettingValue::F64(self.shadow_b),
+            "shadow_a" => SettingValue::F64(self.shadow_a),
             other => {
                 eprintln!("LineSettings::get_value: unknown key '{}'", other);
                 // Default – you can change this to whatever makes sense.
@@ -216,6 +306,25 @@ pub struct ArrowSettings {
     line_cap: String,
     line_join: String,
     arrow_size: f64,
+    curved: bool,
+    double_headed: bool,
+    head_style: String,
+    outline: bool,
+    outline_size: f64,
+    outline_r: f64,
+    outline_g: f64,
+    outline_b: f64,
+    outline_a: f64,
+    dash: String,
+    dash_pattern: String,
+    shadow: String,
+    shadow_offset_x: f64,
+    shadow_offset_y: f64,
+    shadow_blur: f64,
+    shadow_r: f64,
+    shadow_g: f64,
+    shadow_b: f64,
+    shadow_a: f64,
 }
 
 impl ArrowSettings {
@@ -230,6 +339,25 @@ impl ArrowSettings {
             "line_cap" => SettingValue::String(self.line_cap.clone()),
             "line_join" => SettingValue::String(self.line_join.clone()),
             "arrow_size" => SettingValue::F64(self.arrow_size),
+            "curved" => SettingValue::Bool(self.curved),
+            "double_headed" => SettingValue::Bool(self.double_headed),
+            "head_style" => SettingValue::String(self.head_style.clone()),
+            "outline" => SettingValue::Bool(self.outline),
+            "outline_size" => SettingValue::F64(self.outline_size),
+            "outline_r" => SettingValue::F64(self.outline_r),
+            "outline_g" => SettingValue::F64(self.outline_g),
+            "outline_b" => SettingValue::F64(self.outline_b),
+            "outline_a" => SettingValue::F64(self.outline_a),
+            "dash" => SettingValue::String(self.dash.clone()),
+            "dash_pattern" => SettingValue::String(self.dash_pattern.clone()),
+            "shadow" => SettingValue::String(self.shadow.clone()),
+            "shadow_offset_x" => SettingValue::F64(self.shadow_offset_x),
+            "shadow_offset_y" => SettingValue::F64(self.shadow_offset_y),
+            "shadow_blur" => SettingValue::F64(self.shadow_blur),
+            "shadow_r" => SettingValue::F64(self.shadow_r),
+            "shadow_g" => SettingValue::F64(self.shadow_g),
+            "shadow_b" => SettingValue::F64(self.shadow_b),
+            "shadow_a" => SettingValue::F64(self.shadow_a),
             other => {
                 eprintln!("LineSettings::get_value: unknown key '{}'", other);
                 // Default – you can change this to whatever makes sense.
@@ -247,6 +375,15 @@ pub struct FreehandSettings {
     color_a: f64,
     size: f64,
     tool: String,
+    shadow: String,
+    shadow_offset_x: f64,
+    shadow_offset_y: f64,
+    shadow_blur: f64,
+    shadow_r: f64,
+    shadow_g: f64,
+    shadow_b: f64,
+    shadow_a: f64,
+    eraser_size: f64,
 }
 
 impl FreehandSettings {
@@ -259,6 +396,15 @@ impl FreehandSettings {
             "color_a" => SettingValue::F64(self.color_a),
             "size" => SettingValue::F64(self.size),
             "tool" => SettingValue::String(self.tool.clone()),
+            "shadow" => SettingValue::String(self.shadow.clone()),
+            "shadow_offset_x" => SettingValue::F64(self.shadow_offset_x),
+            "shadow_offset_y" => SettingValue::F64(self.shadow_offset_y),
+            "shadow_blur" => SettingValue::F64(self.shadow_blur),
+            "shadow_r" => SettingValue::F64(self.shadow_r),
+            "shadow_g" => SettingValue::F64(self.shadow_g),
+            "shadow_b" => SettingValue::F64(self.shadow_b),
+            "shadow_a" => SettingValue::F64(self.shadow_a),
+            "eraser_size" => SettingValue::F64(self.eraser_size),
             other => {
                 eprintln!("FreehandSettings::get_value: unknown key '{}'", other);
                 // Default – you can change this to whatever makes sense.
@@ -292,6 +438,23 @@ pub struct NumberedCircleSettings {
     font_b: f64,
     font_a: f64,
     radius: f64,
+    style: String,
+    prefix: String,
+    shape: String,
+    sequence: i32,
+    sequence_start: i32,
+    renumber: bool,
+    fill_mode: String,
+    fill_stops: String,
+    fill_angle: f64,
+    shadow: String,
+    shadow_offset_x: f64,
+    shadow_offset_y: f64,
+    shadow_blur: f64,
+    shadow_r: f64,
+    shadow_g: f64,
+    shadow_b: f64,
+    shadow_a: f64,
 }
 
 impl NumberedCircleSettings {
@@ -319,6 +482,23 @@ impl NumberedCircleSettings {
             "font_b" => SettingValue::F64(self.font_b),
             "font_a" => SettingValue::F64(self.font_a),
             "radius" => SettingValue::F64(self.radius),
+            "style" => SettingValue::String(self.style.clone()),
+            "prefix" => SettingValue::String(self.prefix.clone()),
+            "shape" => SettingValue::String(self.shape.clone()),
+            "sequence" => SettingValue::I32(self.sequence),
+            "sequence_start" => SettingValue::I32(self.sequence_start),
+            "renumber" => SettingValue::Bool(self.renumber),
+            "fill_mode" => SettingValue::String(self.fill_mode.clone()),
+            "fill_stops" => SettingValue::String(self.fill_stops.clone()),
+            "fill_angle" => SettingValue::F64(self.fill_angle),
+            "shadow" => SettingValue::String(self.shadow.clone()),
+            "shadow_offset_x" => SettingValue::F64(self.shadow_offset_x),
+            "shadow_offset_y" => SettingValue::F64(self.shadow_offset_y),
+            "shadow_blur" => SettingValue::F64(self.shadow_blur),
+            "shadow_r" => SettingValue::F64(self.shadow_r),
+            "shadow_g" => SettingValue::F64(self.shadow_g),
+            "shadow_b" => SettingValue::F64(self.shadow_b),
+            "shadow_a" => SettingValue::F64(self.shadow_a),
             other => {
                 eprintln!("NumberedCircleSettings::get_value: unknown key '{}'", other);
                 // Default – you can change this to whatever makes sense.
@@ -350,6 +530,17 @@ pub struct InputTextSettings {
     font_g: f64,
     font_b: f64,
     font_a: f64,
+    fill_mode: String,
+    fill_stops: String,
+    fill_angle: f64,
+    shadow: String,
+    shadow_offset_x: f64,
+    shadow_offset_y: f64,
+    shadow_blur: f64,
+    shadow_r: f64,
+    shadow_g: f64,
+    shadow_b: f64,
+    shadow_a: f64,
 }
 
 impl InputTextSettings {
@@ -376,6 +567,17 @@ impl InputTextSettings {
             "font_g" => SettingValue::F64(self.font_g),
             "font_b" => SettingValue::F64(self.font_b),
             "font_a" => SettingValue::F64(self.font_a),
+            "fill_mode" => SettingValue::String(self.fill_mode.clone()),
+            "fill_stops" => SettingValue::String(self.fill_stops.clone()),
+            "fill_angle" => SettingValue::F64(self.fill_angle),
+            "shadow" => SettingValue::String(self.shadow.clone()),
+            "shadow_offset_x" => SettingValue::F64(self.shadow_offset_x),
+            "shadow_offset_y" => SettingValue::F64(self.shadow_offset_y),
+            "shadow_blur" => SettingValue::F64(self.shadow_blur),
+            "shadow_r" => SettingValue::F64(self.shadow_r),
+            "shadow_g" => SettingValue::F64(self.shadow_g),
+            "shadow_b" => SettingValue::F64(self.shadow_b),
+            "shadow_a" => SettingValue::F64(self.shadow_a),
             other => {
                 eprintln!("NumberedCircleSettings::get_value: unknown key '{}'", other);
                 // Default – you can change this to whatever makes sense.
@@ -385,6 +587,482 @@ impl InputTextSettings {
     }
 }
 
+#[derive(Default, Deserialize, Serialize, Clone)]
+pub struct ObfuscateSettings {
+    tool: String,
+    pixel_size: f64,
+    blur_radius: f64,
+    shadow: String,
+    shadow_offset_x: f64,
+    shadow_offset_y: f64,
+    shadow_blur: f64,
+    shadow_r: f64,
+    shadow_g: f64,
+    shadow_b: f64,
+    shadow_a: f64,
+}
+
+impl ObfuscateSettings {
+    /// Get a setting by name, returning a `SettingValue`.
+    pub fn get_value(&self, setting_name: &str) -> SettingValue {
+        match setting_name {
+            "tool" => SettingValue::String(self.tool.clone()),
+            "pixel_size" => SettingValue::F64(self.pixel_size),
+            "blur_radius" => SettingValue::F64(self.blur_radius),
+            "shadow" => SettingValue::String(self.shadow.clone()),
+            "shadow_offset_x" => SettingValue::F64(self.shadow_offset_x),
+            "shadow_offset_y" => SettingValue::F64(self.shadow_offset_y),
+            "shadow_blur" => SettingValue::F64(self.shadow_blur),
+            "shadow_r" => SettingValue::F64(self.shadow_r),
+            "shadow_g" => SettingValue::F64(self.shadow_g),
+            "shadow_b" => SettingValue::F64(self.shadow_b),
+            "shadow_a" => SettingValue::F64(self.shadow_a),
+            other => {
+                eprintln!("ObfuscateSettings::get_value: unknown key '{}'", other);
+                // Default – you can change this to whatever makes sense.
+                SettingValue::Bool(false)
+            }
+        }
+    }
+}
+
+#[derive(Default, Deserialize, Serialize, Clone)]
+pub struct RedactSettings {
+    color_r: f64,
+    color_g: f64,
+    color_b: f64,
+    shadow: String,
+    shadow_offset_x: f64,
+    shadow_offset_y: f64,
+    shadow_blur: f64,
+    shadow_r: f64,
+    shadow_g: f64,
+    shadow_b: f64,
+    shadow_a: f64,
+}
+
+impl RedactSettings {
+    /// Get a setting by name, returning a `SettingValue`.
+    pub fn get_value(&self, setting_name: &str) -> SettingValue {
+        match setting_name {
+            "color_r" => SettingValue::F64(self.color_r),
+            "color_g" => SettingValue::F64(self.color_g),
+            "color_b" => SettingValue::F64(self.color_b),
+            "shadow" => SettingValue::String(self.shadow.clone()),
+            "shadow_offset_x" => SettingValue::F64(self.shadow_offset_x),
+            "shadow_offset_y" => SettingValue::F64(self.shadow_offset_y),
+            "shadow_blur" => SettingValue::F64(self.shadow_blur),
+            "shadow_r" => SettingValue::F64(self.shadow_r),
+            "shadow_g" => SettingValue::F64(self.shadow_g),
+            "shadow_b" => SettingValue::F64(self.shadow_b),
+            "shadow_a" => SettingValue::F64(self.shadow_a),
+            other => {
+                eprintln!("RedactSettings::get_value: unknown key '{}'", other);
+                // Default – you can change this to whatever makes sense.
+                SettingValue::Bool(false)
+            }
+        }
+    }
+}
+
+#[derive(Default, Deserialize, Serialize, Clone)]
+pub struct SpotlightSettings {
+    shape: String,
+    dim: f64,
+    shadow: String,
+    shadow_offset_x: f64,
+    shadow_offset_y: f64,
+    shadow_blur: f64,
+    shadow_r: f64,
+    shadow_g: f64,
+    shadow_b: f64,
+    shadow_a: f64,
+}
+
+impl SpotlightSettings {
+    /// Get a setting by name, returning a `SettingValue`.
+    pub fn get_value(&self, setting_name: &str) -> SettingValue {
+        match setting_name {
+            "shape" => SettingValue::String(self.shape.clone()),
+            "dim" => SettingValue::F64(self.dim),
+            "shadow" => SettingValue::String(self.shadow.clone()),
+            "shadow_offset_x" => SettingValue::F64(self.shadow_offset_x),
+            "shadow_offset_y" => SettingValue::F64(self.shadow_offset_y),
+            "shadow_blur" => SettingValue::F64(self.shadow_blur),
+            "shadow_r" => SettingValue::F64(self.shadow_r),
+            "shadow_g" => SettingValue::F64(self.shadow_g),
+            "shadow_b" => SettingValue::F64(self.shadow_b),
+            "shadow_a" => SettingValue::F64(self.shadow_a),
+            other => {
+                eprintln!("SpotlightSettings::get_value: unknown key '{}'", other);
+                // Default – you can change this to whatever makes sense.
+                SettingValue::Bool(false)
+            }
+        }
+    }
+}
+
+#[derive(Default, Deserialize, Serialize, Clone)]
+pub struct MagnifierSettings {
+    shape: String,
+    zoom: f64,
+    border_size: f64,
+    border_r: f64,
+    border_g: f64,
+    border_b: f64,
+    border_a: f64,
+    connector: bool,
+    shadow: String,
+    shadow_offset_x: f64,
+    shadow_offset_y: f64,
+    shadow_blur: f64,
+    shadow_r: f64,
+    shadow_g: f64,
+    shadow_b: f64,
+    shadow_a: f64,
+}
+
+impl MagnifierSettings {
+    /// Get a setting by name, returning a `SettingValue`.
+    pub fn get_value(&self, setting_name: &str) -> SettingValue {
+        match setting_name {
+            "shape" => SettingValue::String(self.shape.clone()),
+            "zoom" => SettingValue::F64(self.zoom),
+            "border_size" => SettingValue::F64(self.border_size),
+            "border_r" => SettingValue::F64(self.border_r),
+            "border_g" => SettingValue::F64(self.border_g),
+            "border_b" => SettingValue::F64(self.border_b),
+            "border_a" => SettingValue::F64(self.border_a),
+            "connector" => SettingValue::Bool(self.connector),
+            "shadow" => SettingValue::String(self.shadow.clone()),
+            "shadow_offset_x" => SettingValue::F64(self.shadow_offset_x),
+            "shadow_offset_y" => SettingValue::F64(self.shadow_offset_y),
+            "shadow_blur" => SettingValue::F64(self.shadow_blur),
+            "shadow_r" => SettingValue::F64(self.shadow_r),
+            "shadow_g" => SettingValue::F64(self.shadow_g),
+            "shadow_b" => SettingValue::F64(self.shadow_b),
+            "shadow_a" => SettingValue::F64(self.shadow_a),
+            other => {
+                eprintln!("MagnifierSettings::get_value: unknown key '{}'", other);
+                // Default – you can change this to whatever makes sense.
+                SettingValue::Bool(false)
+            }
+        }
+    }
+}
+
+#[derive(Default, Deserialize, Serialize, Clone)]
+pub struct PolygonSettings {
+    closed: bool,
+    fill: bool,
+    fill_r: f64,
+    fill_g: f64,
+    fill_b: f64,
+    fill_a: f64,
+    border: bool,
+    border_size: f64,
+    border_r: f64,
+    border_g: f64,
+    border_b: f64,
+    border_a: f64,
+    shadow: String,
+    shadow_offset_x: f64,
+    shadow_offset_y: f64,
+    shadow_blur: f64,
+    shadow_r: f64,
+    shadow_g: f64,
+    shadow_b: f64,
+    shadow_a: f64,
+}
+
+impl PolygonSettings {
+    /// Get a setting by name, returning a `SettingValue`.
+    pub fn get_value(&self, setting_name: &str) -> SettingValue {
+        match setting_name {
+            "closed" => SettingValue::Bool(self.closed),
+            "fill" => SettingValue::Bool(self.fill),
+            "fill_r" => SettingValue::F64(self.fill_r),
+            "fill_g" => SettingValue::F64(self.fill_g),
+            "fill_b" => SettingValue::F64(self.fill_b),
+            "fill_a" => SettingValue::F64(self.fill_a),
+            "border" => SettingValue::Bool(self.border),
+            "border_size" => SettingValue::F64(self.border_size),
+            "border_r" => SettingValue::F64(self.border_r),
+            "border_g" => SettingValue::F64(self.border_g),
+            "border_b" => SettingValue::F64(self.border_b),
+            "border_a" => SettingValue::F64(self.border_a),
+            "shadow" => SettingValue::String(self.shadow.clone()),
+            "shadow_offset_x" => SettingValue::F64(self.shadow_offset_x),
+            "shadow_offset_y" => SettingValue::F64(self.shadow_offset_y),
+            "shadow_blur" => SettingValue::F64(self.shadow_blur),
+            "shadow_r" => SettingValue::F64(self.shadow_r),
+            "shadow_g" => SettingValue::F64(self.shadow_g),
+            "shadow_b" => SettingValue::F64(self.shadow_b),
+            "shadow_a" => SettingValue::F64(self.shadow_a),
+            other => {
+                eprintln!("PolygonSettings::get_value: unknown key '{}'", other);
+                // Default – you can change this to whatever makes sense.
+                SettingValue::Bool(false)
+            }
+        }
+    }
+}
+
+#[derive(Default, Deserialize, Serialize, Clone)]
+pub struct CalloutSettings {
+    wrap_width: f64,
+    corner_radius: f64,
+    padding: f64,
+    shadow: String,
+    shadow_offset_x: f64,
+    shadow_offset_y: f64,
+    shadow_blur: f64,
+    shadow_r: f64,
+    shadow_g: f64,
+    shadow_b: f64,
+    shadow_a: f64,
+}
+
+impl CalloutSettings {
+    /// Get a setting by name, returning a `SettingValue`.
+    pub fn get_value(&self, setting_name: &str) -> SettingValue {
+        match setting_name {
+            "wrap_width" => SettingValue::F64(self.wrap_width),
+            "corner_radius" => SettingValue::F64(self.corner_radius),
+            "padding" => SettingValue::F64(self.padding),
+            "shadow" => SettingValue::String(self.shadow.clone()),
+            "shadow_offset_x" => SettingValue::F64(self.shadow_offset_x),
+            "shadow_offset_y" => SettingValue::F64(self.shadow_offset_y),
+            "shadow_blur" => SettingValue::F64(self.shadow_blur),
+            "shadow_r" => SettingValue::F64(self.shadow_r),
+            "shadow_g" => SettingValue::F64(self.shadow_g),
+            "shadow_b" => SettingValue::F64(self.shadow_b),
+            "shadow_a" => SettingValue::F64(self.shadow_a),
+            other => {
+                eprintln!("CalloutSettings::get_value: unknown key '{}'", other);
+                // Default – you can change this to whatever makes sense.
+                SettingValue::Bool(false)
+            }
+        }
+    }
+}
+
+#[derive(Default, Deserialize, Serialize, Clone)]
+pub struct StampSettings {
+    file: String,
+    scale: f64,
+    rotation: f64,
+    shadow: String,
+    shadow_offset_x: f64,
+    shadow_offset_y: f64,
+    shadow_blur: f64,
+    shadow_r: f64,
+    shadow_g: f64,
+    shadow_b: f64,
+    shadow_a: f64,
+}
+
+impl StampSettings {
+    /// Get a setting by name, returning a `SettingValue`.
+    pub fn get_value(&self, setting_name: &str) -> SettingValue {
+        match setting_name {
+            "file" => SettingValue::String(self.file.clone()),
+            "scale" => SettingValue::F64(self.scale),
+            "rotation" => SettingValue::F64(self.rotation),
+            "shadow" => SettingValue::String(self.shadow.clone()),
+            "shadow_offset_x" => SettingValue::F64(self.shadow_offset_x),
+            "shadow_offset_y" => SettingValue::F64(self.shadow_offset_y),
+            "shadow_blur" => SettingValue::F64(self.shadow_blur),
+            "shadow_r" => SettingValue::F64(self.shadow_r),
+            "shadow_g" => SettingValue::F64(self.shadow_g),
+            "shadow_b" => SettingValue::F64(self.shadow_b),
+            "shadow_a" => SettingValue::F64(self.shadow_a),
+            other => {
+                eprintln!("StampSettings::get_value: unknown key '{}'", other);
+                // Default – you can change this to whatever makes sense.
+                SettingValue::Bool(false)
+            }
+        }
+    }
+}
+
+#[derive(Default, Deserialize, Serialize, Clone)]
+pub struct MeasureSettings {
+    size: f64,
+    color_r: f64,
+    color_g: f64,
+    color_b: f64,
+    color_a: f64,
+    font_size: f64,
+    export: bool,
+    shadow: String,
+    shadow_offset_x: f64,
+    shadow_offset_y: f64,
+    shadow_blur: f64,
+    shadow_r: f64,
+    shadow_g: f64,
+    shadow_b: f64,
+    shadow_a: f64,
+}
+
+impl MeasureSettings {
+    /// Get a setting by name, returning a `SettingValue`.
+    pub fn get_value(&self, setting_name: &str) -> SettingValue {
+        match setting_name {
+            "size" => SettingValue::F64(self.size),
+            "color_r" => SettingValue::F64(self.color_r),
+            "color_g" => SettingValue::F64(self.color_g),
+            "color_b" => SettingValue::F64(self.color_b),
+            "color_a" => SettingValue::F64(self.color_a),
+            "font_size" => SettingValue::F64(self.font_size),
+            "export" => SettingValue::Bool(self.export),
+            "shadow" => SettingValue::String(self.shadow.clone()),
+            "shadow_offset_x" => SettingValue::F64(self.shadow_offset_x),
+            "shadow_offset_y" => SettingValue::F64(self.shadow_offset_y),
+            "shadow_blur" => SettingValue::F64(self.shadow_blur),
+            "shadow_r" => SettingValue::F64(self.shadow_r),
+            "shadow_g" => SettingValue::F64(self.shadow_g),
+            "shadow_b" => SettingValue::F64(self.shadow_b),
+            "shadow_a" => SettingValue::F64(self.shadow_a),
+            other => {
+                eprintln!("MeasureSettings::get_value: unknown key '{}'", other);
+                // Default – you can change this to whatever makes sense.
+                SettingValue::Bool(false)
+            }
+        }
+    }
+}
+
+#[derive(Default, Deserialize, Serialize, Clone)]
+pub struct EyedropperSettings {
+    format: String,
+    action: String,
+    target: String,
+}
+
+impl EyedropperSettings {
+    /// Get a setting by name, returning a `SettingValue`.
+    pub fn get_value(&self, setting_name: &str) -> SettingValue {
+        match setting_name {
+            "format" => SettingValue::String(self.format.clone()),
+            "action" => SettingValue::String(self.action.clone()),
+            "target" => SettingValue::String(self.target.clone()),
+            other => {
+                eprintln!("EyedropperSettings::get_value: unknown key '{}'", other);
+                // Default – you can change this to whatever makes sense.
+                SettingValue::Bool(false)
+            }
+        }
+    }
+}
+
+#[derive(Default, Deserialize, Serialize, Clone)]
+pub struct HighlightSettings {
+    color_r: f64,
+    color_g: f64,
+    color_b: f64,
+    corner_radius: f64,
+    shadow: String,
+    shadow_offset_x: f64,
+    shadow_offset_y: f64,
+    shadow_blur: f64,
+    shadow_r: f64,
+    shadow_g: f64,
+    shadow_b: f64,
+    shadow_a: f64,
+}
+
+impl HighlightSettings {
+    /// Get a setting by name, returning a `SettingValue`.
+    pub fn get_value(&self, setting_name: &str) -> SettingValue {
+        match setting_name {
+            "color_r" => SettingValue::F64(self.color_r),
+            "color_g" => SettingValue::F64(self.color_g),
+            "color_b" => SettingValue::F64(self.color_b),
+            "corner_radius" => SettingValue::F64(self.corner_radius),
+            "shadow" => SettingValue::String(self.shadow.clone()),
+            "shadow_offset_x" => SettingValue::F64(self.shadow_offset_x),
+            "shadow_offset_y" => SettingValue::F64(self.shadow_offset_y),
+            "shadow_blur" => SettingValue::F64(self.shadow_blur),
+            "shadow_r" => SettingValue::F64(self.shadow_r),
+            "shadow_g" => SettingValue::F64(self.shadow_g),
+            "shadow_b" => SettingValue::F64(self.shadow_b),
+            "shadow_a" => SettingValue::F64(self.shadow_a),
+            other => {
+                eprintln!("HighlightSettings::get_value: unknown key '{}'", other);
+                // Default – you can change this to whatever makes sense.
+                SettingValue::Bool(false)
+            }
+        }
+    }
+}
+
+#[derive(Default, Deserialize, Serialize, Clone)]
+pub struct ShadowSettings {
+    enabled: bool,
+    offset_x: f64,
+    offset_y: f64,
+    blur: f64,
+    color_r: f64,
+    color_g: f64,
+    color_b: f64,
+    color_a: f64,
+}
+
+impl ShadowSettings {
+    /// Get a setting by name, returning a `SettingValue`.
+    pub fn get_value(&self, setting_name: &str) -> SettingValue {
+        match setting_name {
+            "enabled" => SettingValue::Bool(self.enabled),
+            "offset_x" => SettingValue::F64(self.offset_x),
+            "offset_y" => SettingValue::F64(self.offset_y),
+            "blur" => SettingValue::F64(self.blur),
+            "color_r" => SettingValue::F64(self.color_r),
+            "color_g" => SettingValue::F64(self.color_g),
+            "color_b" => SettingValue::F64(self.color_b),
+            "color_a" => SettingValue::F64(self.color_a),
+            other => {
+                eprintln!("ShadowSettings::get_value: unknown key '{}'", other);
+                // Default – you can change this to whatever makes sense.
+                SettingValue::Bool(false)
+            }
+        }
+    }
+}
+
+#[derive(Default, Deserialize, Serialize, Clone)]
+pub struct ImageSettings {
+    shadow: String,
+    shadow_offset_x: f64,
+    shadow_offset_y: f64,
+    shadow_blur: f64,
+    shadow_r: f64,
+    shadow_g: f64,
+    shadow_b: f64,
+    shadow_a: f64,
+}
+
+impl ImageSettings {
+    /// Get a setting by name, returning a `SettingValue`.
+    pub fn get_value(&self, setting_name: &str) -> SettingValue {
+        match setting_name {
+            "shadow" => SettingValue::String(self.shadow.clone()),
+            "shadow_offset_x" => SettingValue::F64(self.shadow_offset_x),
+            "shadow_offset_y" => SettingValue::F64(self.shadow_offset_y),
+            "shadow_blur" => SettingValue::F64(self.shadow_blur),
+            "shadow_r" => SettingValue::F64(self.shadow_r),
+            "shadow_g" => SettingValue::F64(self.shadow_g),
+            "shadow_b" => SettingValue::F64(self.shadow_b),
+            "shadow_a" => SettingValue::F64(self.shadow_a),
+            other => {
+                eprintln!("ImageSettings::get_value: unknown key '{}'", other);
+                // Default – you can change this to whatever makes sense.
+                SettingValue::Bool(false)
+            }
+        }
+    }
+}
+
 // Top level struct to hold the TOML data.
 
 #[derive(Default, Clone)]
@@ -396,6 +1074,18 @@ pub struct SettingsRc {
     pub freehand: FreehandSettingsRc,
     pub numbered_circle: NumberedCircleSettingsRc,
     pub input_text: InputTextSettingsRc,
+    pub obfuscate: ObfuscateSettingsRc,
+    pub redact: RedactSettingsRc,
+    pub spotlight: SpotlightSettingsRc,
+    pub magnifier: MagnifierSettingsRc,
+    pub polygon: PolygonSettingsRc,
+    pub callout: CalloutSettingsRc,
+    pub stamp: StampSettingsRc,
+    pub measure: MeasureSettingsRc,
+    pub eyedropper: EyedropperSettingsRc,
+    pub highlight: HighlightSettingsRc,
+    pub shadow: ShadowSettingsRc,
+    pub image: ImageSettingsRc,
 }
 
 #[derive(Default, Clone)]
@@ -411,6 +1101,21 @@ pub struct ArcSettingsRc {
     border_g: Rc<Cell<f64>>,
     border_b: Rc<Cell<f64>>,
     border_a: Rc<Cell<f64>>,
+    shape: Rc<RefCell<String>>,
+    style: Rc<RefCell<String>>,
+    start_angle: Rc<Cell<f64>>,
+    end_angle: Rc<Cell<f64>>,
+    fill_mode: Rc<RefCell<String>>,
+    fill_stops: Rc<RefCell<String>>,
+    fill_angle: Rc<Cell<f64>>,
+    shadow: Rc<RefCell<String>>,
+    shadow_offset_x: Rc<Cell<f64>>,
+    shadow_offset_y: Rc<Cell<f64>>,
+    shadow_blur: Rc<Cell<f64>>,
+    shadow_r: Rc<Cell<f64>>,
+    shadow_g: Rc<Cell<f64>>,
+    shadow_b: Rc<Cell<f64>>,
+    shadow_a: Rc<Cell<f64>>,
 }
 
 impl ArcSettingsRc {
@@ -428,6 +1133,21 @@ impl ArcSettingsRc {
             "border_g" => SettingValue::F64(self.border_g.get()),
             "border_b" => SettingValue::F64(self.border_b.get()),
             "border_a" => SettingValue::F64(self.border_a.get()),
+            "shape" => SettingValue::String(self.shape.borrow().to_string()),
+            "style" => SettingValue::String(self.style.borrow().to_string()),
+            "start_angle" => SettingValue::F64(self.start_angle.get()),
+            "end_angle" => SettingValue::F64(self.end_angle.get()),
+            "fill_mode" => SettingValue::String(self.fill_mode.borrow().to_string()),
+            "fill_stops" => SettingValue::String(self.fill_stops.borrow().to_string()),
+            "fill_angle" => SettingValue::F64(self.fill_angle.get()),
+            "shadow" => SettingValue::String(self.shadow.borrow().to_string()),
+            "shadow_offset_x" => SettingValue::F64(self.shadow_offset_x.get()),
+            "shadow_offset_y" => SettingValue::F64(self.shadow_offset_y.get()),
+            "shadow_blur" => SettingValue::F64(self.shadow_blur.get()),
+            "shadow_r" => SettingValue::F64(self.shadow_r.get()),
+            "shadow_g" => SettingValue::F64(self.shadow_g.get()),
+            "shadow_b" => SettingValue::F64(self.shadow_b.get()),
+            "shadow_a" => SettingValue::F64(self.shadow_a.get()),
             other => {
                 eprintln!("ArcSettings::get_value: unknown key '{}'", other);
                 // Default – you can change this to whatever makes sense.
@@ -455,48 +1175,1652 @@ impl ArcSettingsRc {
                 self.fill_b.set(v);
                 Ok(())
             }
-            ("fill_a", SettingValue::F64(v)) => {
-                self.fill_a.set(v);
+            ("fill_a", SettingValue::F64(v)) => {
+                self.fill_a.set(v);
+                Ok(())
+            }
+
+            // ---- border -----------------------------------------------------
+            ("border", SettingValue::Bool(v)) => {
+                self.border.set(v);
+                Ok(())
+            }
+            ("border_size", SettingValue::F64(v)) => {
+                self.border_size.set(v);
+                Ok(())
+            }
+            ("border_r", SettingValue::F64(v)) => {
+                self.border_r.set(v);
+                Ok(())
+            }
+            ("border_g", SettingValue::F64(v)) => {
+                self.border_g.set(v);
+                Ok(())
+            }
+            ("border_b", SettingValue::F64(v)) => {
+                self.border_b.set(v);
+                Ok(())
+            }
+            ("border_a", SettingValue::F64(v)) => {
+                self.border_a.set(v);
+                Ok(())
+            }
+
+            ("shape", SettingValue::String(v)) => {
+                *self.shape.borrow_mut() = v;
+                Ok(())
+            }
+            ("style", SettingValue::String(v)) => {
+                *self.style.borrow_mut() = v;
+                Ok(())
+            }
+            ("start_angle", SettingValue::F64(v)) => {
+                self.start_angle.set(v);
+                Ok(())
+            }
+            ("end_angle", SettingValue::F64(v)) => {
+                self.end_angle.set(v);
+                Ok(())
+            }
+
+            ("fill_mode", SettingValue::String(v)) => {
+                *self.fill_mode.borrow_mut() = v;
+                Ok(())
+            }
+            ("fill_stops", SettingValue::String(v)) => {
+                *self.fill_stops.borrow_mut() = v;
+                Ok(())
+            }
+            ("fill_angle", SettingValue::F64(v)) => {
+                self.fill_angle.set(v);
+                Ok(())
+            }
+
+            ("shadow", SettingValue::String(v)) => {
+                *self.shadow.borrow_mut() = v;
+                Ok(())
+            }
+
+            ("shadow_offset_x", SettingValue::F64(v)) => {
+                self.shadow_offset_x.set(v);
+                Ok(())
+            }
+            ("shadow_offset_y", SettingValue::F64(v)) => {
+                self.shadow_offset_y.set(v);
+                Ok(())
+            }
+            ("shadow_blur", SettingValue::F64(v)) => {
+                self.shadow_blur.set(v);
+                Ok(())
+            }
+            ("shadow_r", SettingValue::F64(v)) => {
+                self.shadow_r.set(v);
+                Ok(())
+            }
+            ("shadow_g", SettingValue::F64(v)) => {
+                self.shadow_g.set(v);
+                Ok(())
+            }
+            ("shadow_b", SettingValue::F64(v)) => {
+                self.shadow_b.set(v);
+                Ok(())
+            }
+            ("shadow_a", SettingValue::F64(v)) => {
+                self.shadow_a.set(v);
+                Ok(())
+            }
+
+            // ---- mismatched type -------------------------------------------------
+            (key, _wrong_type) => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("ArcSettings::get_value: unknown key '{}'", key),
+            )),
+        }
+    }
+}
+
+#[derive(Default, Clone)]
+pub struct RectSettingsRc {
+    fill: Rc<Cell<bool>>,
+    fill_r: Rc<Cell<f64>>,
+    fill_g: Rc<Cell<f64>>,
+    fill_b: Rc<Cell<f64>>,
+    fill_a: Rc<Cell<f64>>,
+    border: Rc<Cell<bool>>,
+    border_size: Rc<Cell<f64>>,
+    border_r: Rc<Cell<f64>>,
+    border_g: Rc<Cell<f64>>,
+    border_b: Rc<Cell<f64>>,
+    border_a: Rc<Cell<f64>>,
+    corner_radius: Rc<Cell<f64>>,
+    dash: Rc<RefCell<String>>,
+    style: Rc<RefCell<String>>,
+    fill_mode: Rc<RefCell<String>>,
+    fill_stops: Rc<RefCell<String>>,
+    fill_angle: Rc<Cell<f64>>,
+    shadow: Rc<RefCell<String>>,
+    shadow_offset_x: Rc<Cell<f64>>,
+    shadow_offset_y: Rc<Cell<f64>>,
+    shadow_blur: Rc<Cell<f64>>,
+    shadow_r: Rc<Cell<f64>>,
+    shadow_g: Rc<Cell<f64>>,
+    shadow_b: Rc<Cell<f64>>,
+    shadow_a: Rc<Cell<f64>>,
+}
+
+impl RectSettingsRc {
+    /// Get a setting by name, returning a `SettingValue`.
+    pub fn get_value(&self, setting_name: &str) -> SettingValue {
+        match setting_name {
+            "fill" => SettingValue::Bool(self.fill.get()),
+            "fill_r" => SettingValue::F64(self.fill_r.get()),
+            "fill_g" => SettingValue::F64(self.fill_g.get()),
+            "fill_b" => SettingValue::F64(self.fill_b.get()),
+            "fill_a" => SettingValue::F64(self.fill_a.get()),
+            "border" => SettingValue::Bool(self.border.get()),
+            "border_size" => SettingValue::F64(self.border_size.get()),
+            "border_r" => SettingValue::F64(self.border_r.get()),
+            "border_g" => SettingValue::F64(self.border_g.get()),
+            "border_b" => SettingValue::F64(self.border_b.get()),
+            "border_a" => SettingValue::F64(self.border_a.get()),
+            "corner_radius" => SettingValue::F64(self.corner_radius.get()),
+            "dash" => SettingValue::String(self.dash.borrow().to_string()),
+            "style" => SettingValue::String(self.style.borrow().to_string()),
+            "fill_mode" => SettingValue::String(self.fill_mode.borrow().to_string()),
+            "fill_stops" => SettingValue::String(self.fill_stops.borrow().to_string()),
+            "fill_angle" => SettingValue::F64(self.fill_angle.get()),
+            "shadow" => SettingValue::String(self.shadow.borrow().to_string()),
+            "shadow_offset_x" => SettingValue::F64(self.shadow_offset_x.get()),
+            "shadow_offset_y" => SettingValue::F64(self.shadow_offset_y.get()),
+            "shadow_blur" => SettingValue::F64(self.shadow_blur.get()),
+            "shadow_r" => SettingValue::F64(self.shadow_r.get()),
+            "shadow_g" => SettingValue::F64(self.shadow_g.get()),
+            "shadow_b" => SettingValue::F64(self.shadow_b.get()),
+            "shadow_a" => SettingValue::F64(self.shadow_a.get()),
+            other => {
+                eprintln!("RectSettings::get_value: unknown key '{}'", other);
+                // Default – you can change this to whatever makes sense.
+                SettingValue::Bool(false)
+            }
+        }
+    }
+    /// Set a setting by name, returning a `SettingValue`.
+    pub fn set_value(&self, setting_name: &str, value: SettingValue) -> std::io::Result<()> {
+        match (setting_name, value) {
+            // ---- fill -------------------------------------------------------
+            ("fill", SettingValue::Bool(v)) => {
+                self.fill.set(v);
+                Ok(())
+            }
+            ("fill_r", SettingValue::F64(v)) => {
+                self.fill_r.set(v);
+                Ok(())
+            }
+            ("fill_g", SettingValue::F64(v)) => {
+                self.fill_g.set(v);
+                Ok(())
+            }
+            ("fill_b", SettingValue::F64(v)) => {
+                self.fill_b.set(v);
+                Ok(())
+            }
+            ("fill_a", SettingValue::F64(v)) => {
+                self.fill_a.set(v);
+                Ok(())
+            }
+
+            // ---- border -----------------------------------------------------
+            ("border", SettingValue::Bool(v)) => {
+                self.border.set(v);
+                Ok(())
+            }
+            ("border_size", SettingValue::F64(v)) => {
+                self.border_size.set(v);
+                Ok(())
+            }
+            ("border_r", SettingValue::F64(v)) => {
+                self.border_r.set(v);
+                Ok(())
+            }
+            ("border_g", SettingValue::F64(v)) => {
+                self.border_g.set(v);
+                Ok(())
+            }
+            ("border_b", SettingValue::F64(v)) => {
+                self.border_b.set(v);
+                Ok(())
+            }
+            ("border_a", SettingValue::F64(v)) => {
+                self.border_a.set(v);
+                Ok(())
+            }
+
+            ("corner_radius", SettingValue::F64(v)) => {
+                self.corner_radius.set(v);
+                Ok(())
+            }
+            ("dash", SettingValue::String(v)) => {
+                *self.dash.borrow_mut() = v;
+                Ok(())
+            }
+            ("style", SettingValue::String(v)) => {
+                *self.style.borrow_mut() = v;
+                Ok(())
+            }
+
+            ("fill_mode", SettingValue::String(v)) => {
+                *self.fill_mode.borrow_mut() = v;
+                Ok(())
+            }
+            ("fill_stops", SettingValue::String(v)) => {
+                *self.fill_stops.borrow_mut() = v;
+                Ok(())
+            }
+            ("fill_angle", SettingValue::F64(v)) => {
+                self.fill_angle.set(v);
+                Ok(())
+            }
+
+            ("shadow", SettingValue::String(v)) => {
+                *self.shadow.borrow_mut() = v;
+                Ok(())
+            }
+
+            ("shadow_offset_x", SettingValue::F64(v)) => {
+                self.shadow_offset_x.set(v);
+                Ok(())
+            }
+            ("shadow_offset_y", SettingValue::F64(v)) => {
+                self.shadow_offset_y.set(v);
+                Ok(())
+            }
+            ("shadow_blur", SettingValue::F64(v)) => {
+                self.shadow_blur.set(v);
+                Ok(())
+            }
+            ("shadow_r", SettingValue::F64(v)) => {
+                self.shadow_r.set(v);
+                Ok(())
+            }
+            ("shadow_g", SettingValue::F64(v)) => {
+                self.shadow_g.set(v);
+                Ok(())
+            }
+            ("shadow_b", SettingValue::F64(v)) => {
+                self.shadow_b.set(v);
+                Ok(())
+            }
+            ("shadow_a", SettingValue::F64(v)) => {
+                self.shadow_a.set(v);
+                Ok(())
+            }
+
+            // ---- mismatched type -------------------------------------------------
+            (key, _wrong_type) => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("RectSettings::get_value: unknown key '{}'", key),
+            )),
+        }
+    }
+}
+
+#[derive(Default, Clone)]
+pub struct LineSettingsRc {
+    color_r: Rc<Cell<f64>>,
+    color_g: Rc<Cell<f64>>,
+    color_b: Rc<Cell<f64>>,
+    color_a: Rc<Cell<f64>>,
+    size: Rc<Cell<f64>>,
+    line_cap: Rc<RefCell<String>>,
+    line_join: Rc<RefCell<String>>,
+    dash: Rc<RefCell<String>>,
+    dash_pattern: Rc<RefCell<String>>,
+    shadow: Rc<RefCell<String>>,
+    shadow_offset_x: Rc<Cell<f64>>,
+    shadow_offset_y: Rc<Cell<f64>>,
+    shadow_blur: Rc<Cell<f64>>,
+    shadow_r: Rc<Cell<f64>>,
+    shadow_g: Rc<Cell<f64>>,
+    shadow_b: Rc<Cell<f64>>,
+    shadow_a: Rc<Cell<f64>>,
+}
+
+impl LineSettingsRc {
+    /// Get a setting by name, returning a `SettingValue`.
+    pub fn get_value(&self, setting_name: &str) -> SettingValue {
+        match setting_name {
+            "color_r" => SettingValue::F64(self.color_r.get()),
+            "color_g" => SettingValue::F64(self.color_g.get()),
+            "color_b" => SettingValue::F64(self.color_b.get()),
+            "color_a" => SettingValue::F64(self.color_a.get()),
+            "size" => SettingValue::F64(self.size.get()),
+            "line_cap" => SettingValue::String(self.line_cap.borrow().to_string()),
+            "line_join" => SettingValue::String(self.line_join.borrow().to_string()),
+            "dash" => SettingValue::String(self.dash.borrow().to_string()),
+            "dash_pattern" => SettingValue::String(self.dash_pattern.borrow().to_string()),
+            "shadow" => SettingValue::String(self.shadow.borrow().to_string()),
+            "shadow_offset_x" => SettingValue::F64(self.shadow_offset_x.get()),
+            "shadow_offset_y" => SettingValue::F64(self.shadow_offset_y.get()),
+            "shadow_blur" => SettingValue::F64(self.shadow_blur.get()),
+            "shadow_r" => SettingValue::F64(self.shadow_r.get()),
+            "shadow_g" => SettingValue::F64(self.shadow_g.get()),
+            "shadow_b" => SettingValue::F64(self.shadow_b.get()),
+            "shadow_a" => SettingValue::F64(self.shadow_a.get()),
+            other => {
+                eprintln!("LineSettings::get_value: unknown key '{}'", other);
+                // Default – you can change this to whatever makes sense.
+                SettingValue::Bool(false)
+            }
+        }
+    }
+    /// Set a setting by name, returning a `SettingValue`.
+    pub fn set_value(&self, setting_name: &str, value: SettingValue) -> std::io::Result<()> {
+        match (setting_name, value) {
+            // ---- fill -------------------------------------------------------
+            ("color_r", SettingValue::F64(v)) => {
+                self.color_r.set(v);
+                Ok(())
+            }
+            ("color_g", SettingValue::F64(v)) => {
+                self.color_g.set(v);
+                Ok(())
+            }
+            ("color_b", SettingValue::F64(v)) => {
+                self.color_b.set(v);
+                Ok(())
+            }
+            ("color_a", SettingValue::F64(v)) => {
+                self.color_a.set(v);
+                Ok(())
+            }
+            ("size", SettingValue::F64(v)) => {
+                self.size.set(v);
+                Ok(())
+            }
+
+            // ---- junctions -------------------------------------------------------
+            ("line_cap", SettingValue::String(v)) => {
+                *self.line_cap.borrow_mut() = v;
+                Ok(())
+            }
+            ("line_join", SettingValue::String(v)) => {
+                *self.line_join.borrow_mut() = v;
+                Ok(())
+            }
+
+            ("dash", SettingValue::String(v)) => {
+                *self.dash.borrow_mut() = v;
+                Ok(())
+            }
+            ("dash_pattern", SettingValue::String(v)) => {
+                *self.dash_pattern.borrow_mut() = v;
+                Ok(())
+            }
+
+            ("shadow", SettingValue::String(v)) => {
+                *self.shadow.borrow_mut() = v;
+                Ok(())
+            }
+
+            ("shadow_offset_x", SettingValue::F64(v)) => {
+                self.shadow_offset_x.set(v);
+                Ok(())
+            }
+            ("shadow_offset_y", SettingValue::F64(v)) => {
+                self.shadow_offset_y.set(v);
+                Ok(())
+            }
+            ("shadow_blur", SettingValue::F64(v)) => {
+                self.shadow_blur.set(v);
+                Ok(())
+            }
+            ("shadow_r", SettingValue::F64(v)) => {
+                self.shadow_r.set(v);
+                Ok(())
+            }
+            ("shadow_g", SettingValue::F64(v)) => {
+                self.shadow_g.set(v);
+                Ok(())
+            }
+            ("shadow_b", SettingValue::F64(v)) => {
+                self.shadow_b.set(v);
+                Ok(())
+            }
+            ("shadow_a", SettingValue::F64(v)) => {
+                self.shadow_a.set(v);
+                Ok(())
+            }
+
+            // ---- mismatched type -------------------------------------------------
+            (key, _wrong_type) => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("LineSettings::get_value: unknown key '{}'", key),
+            )),
+        }
+    }
+}
+
+#[derive(Default, Clone)]
+pub struct ArrowSettingsRc {
+    color_r: Rc<Cell<f64>>,
+    color_g: Rc<Cell<f64>>,
+    color_b: Rc<Cell<f64>>,
+    color_a: Rc<Cell<f64>>,
+    size: Rc<Cell<f64>>,
+    line_cap: Rc<RefCell<String>>,
+    line_join: Rc<RefCell<String>>,
+    arrow_size: Rc<Cell<f64>>,
+    curved: Rc<Cell<bool>>,
+    double_headed: Rc<Cell<bool>>,
+    head_style: Rc<RefCell<String>>,
+    outline: Rc<Cell<bool>>,
+    outline_size: Rc<Cell<f64>>,
+    outline_r: Rc<Cell<f64>>,
+    outline_g: Rc<Cell<f64>>,
+    outline_b: Rc<Cell<f64>>,
+    outline_a: Rc<Cell<f64>>,
+    dash: Rc<RefCell<String>>,
+    dash_pattern: Rc<RefCell<String>>,
+    shadow: Rc<RefCell<String>>,
+    shadow_offset_x: Rc<Cell<f64>>,
+    shadow_offset_y: Rc<Cell<f64>>,
+    shadow_blur: Rc<Cell<f64>>,
+    shadow_r: Rc<Cell<f64>>,
+    shadow_g: Rc<Cell<f64>>,
+    shadow_b: Rc<Cell<f64>>,
+    shadow_a: Rc<Cell<f64>>,
+}
+
+impl ArrowSettingsRc {
+    /// Get a setting by name, returning a `SettingValue`.
+    pub fn get_value(&self, setting_name: &str) -> SettingValue {
+        match setting_name {
+            "color_r" => SettingValue::F64(self.color_r.get()),
+            "color_g" => SettingValue::F64(self.color_g.get()),
+            "color_b" => SettingValue::F64(self.color_b.get()),
+            "color_a" => SettingValue::F64(self.color_a.get()),
+            "size" => SettingValue::F64(self.size.get()),
+            "line_cap" => SettingValue::String(self.line_cap.borrow().to_string()),
+            "line_join" => SettingValue::String(self.line_join.borrow().to_string()),
+            "arrow_size" => SettingValue::F64(self.arrow_size.get()),
+            "curved" => SettingValue::Bool(self.curved.get()),
+            "double_headed" => SettingValue::Bool(self.double_headed.get()),
+            "head_style" => SettingValue::String(self.head_style.borrow().to_string()),
+            "outline" => SettingValue::Bool(self.outline.get()),
+            "outline_size" => SettingValue::F64(self.outline_size.get()),
+            "outline_r" => SettingValue::F64(self.outline_r.get()),
+            "outline_g" => SettingValue::F64(self.outline_g.get()),
+            "outline_b" => SettingValue::F64(self.outline_b.get()),
+            "outline_a" => SettingValue::F64(self.outline_a.get()),
+            "dash" => SettingValue::String(self.dash.borrow().to_string()),
+            "dash_pattern" => SettingValue::String(self.dash_pattern.borrow().to_string()),
+            "shadow" => SettingValue::String(self.shadow.borrow().to_string()),
+            "shadow_offset_x" => SettingValue::F64(self.shadow_offset_x.get()),
+            "shadow_offset_y" => SettingValue::F64(self.shadow_offset_y.get()),
+            "shadow_blur" => SettingValue::F64(self.shadow_blur.get()),
+            "shadow_r" => SettingValue::F64(self.shadow_r.get()),
+            "shadow_g" => SettingValue::F64(self.shadow_g.get()),
+            "shadow_b" => SettingValue::F64(self.shadow_b.get()),
+            "shadow_a" => SettingValue::F64(self.shadow_a.get()),
+            other => {
+                eprintln!("LineSettings::get_value: unknown key '{}'", other);
+                // Default – you can change this to whatever makes sense.
+                SettingValue::Bool(false)
+            }
+        }
+    }
+    /// Set a setting by name, returning a `SettingValue`.
+    pub fn set_value(&self, setting_name: &str, value: SettingValue) -> std::io::Result<()> {
+        match (setting_name, value) {
+            // ---- fill -------------------------------------------------------
+            ("color_r", SettingValue::F64(v)) => {
+                self.color_r.set(v);
+                Ok(())
+            }
+            ("color_g", SettingValue::F64(v)) => {
+                self.color_g.set(v);
+                Ok(())
+            }
+            ("color_b", SettingValue::F64(v)) => {
+                self.color_b.set(v);
+                Ok(())
+            }
+            ("color_a", SettingValue::F64(v)) => {
+                self.color_a.set(v);
+                Ok(())
+            }
+            ("size", SettingValue::F64(v)) => {
+                self.size.set(v);
+                Ok(())
+            }
+
+            // ---- junctions -------------------------------------------------------
+            ("line_cap", SettingValue::String(v)) => {
+                *self.line_cap.borrow_mut() = v;
+                Ok(())
+            }
+            ("line_join", SettingValue::String(v)) => {
+                *self.line_join.borrow_mut() = v;
+                Ok(())
+            }
+
+            ("curved", SettingValue::Bool(v)) => {
+                self.curved.set(v);
+                Ok(())
+            }
+            ("double_headed", SettingValue::Bool(v)) => {
+                self.double_headed.set(v);
+                Ok(())
+            }
+            ("head_style", SettingValue::String(v)) => {
+                *self.head_style.borrow_mut() = v;
+                Ok(())
+            }
+            ("outline", SettingValue::Bool(v)) => {
+                self.outline.set(v);
+                Ok(())
+            }
+            ("outline_size", SettingValue::F64(v)) => {
+                self.outline_size.set(v);
+                Ok(())
+            }
+            ("outline_r", SettingValue::F64(v)) => {
+                self.outline_r.set(v);
+                Ok(())
+            }
+            ("outline_g", SettingValue::F64(v)) => {
+                self.outline_g.set(v);
+                Ok(())
+            }
+            ("outline_b", SettingValue::F64(v)) => {
+                self.outline_b.set(v);
+                Ok(())
+            }
+            ("outline_a", SettingValue::F64(v)) => {
+                self.outline_a.set(v);
+                Ok(())
+            }
+
+            ("dash", SettingValue::String(v)) => {
+                *self.dash.borrow_mut() = v;
+                Ok(())
+            }
+            ("dash_pattern", SettingValue::String(v)) => {
+                *self.dash_pattern.borrow_mut() = v;
+                Ok(())
+            }
+
+            ("shadow", SettingValue::String(v)) => {
+                *self.shadow.borrow_mut() = v;
+                Ok(())
+            }
+
+            ("shadow_offset_x", SettingValue::F64(v)) => {
+                self.shadow_offset_x.set(v);
+                Ok(())
+            }
+            ("shadow_offset_y", SettingValue::F64(v)) => {
+                self.shadow_offset_y.set(v);
+                Ok(())
+            }
+            ("shadow_blur", SettingValue::F64(v)) => {
+                self.shadow_blur.set(v);
+                Ok(())
+            }
+            ("shadow_r", SettingValue::F64(v)) => {
+                self.shadow_r.set(v);
+                Ok(())
+            }
+            ("shadow_g", SettingValue::F64(v)) => {
+                self.shadow_g.set(v);
+                Ok(())
+            }
+            ("shadow_b", SettingValue::F64(v)) => {
+                self.shadow_b.set(v);
+                Ok(())
+            }
+            ("shadow_a", SettingValue::F64(v)) => {
+                self.shadow_a.set(v);
+                Ok(())
+            }
+
+            // ---- mismatched type -------------------------------------------------
+            (key, _wrong_type) => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("LineSettings::get_value: unknown key '{}'", key),
+            )),
+        }
+    }
+}
+
+#[derive(Default, Clone)]
+pub struct FreehandSettingsRc {
+    color_r: Rc<Cell<f64>>,
+    color_g: Rc<Cell<f64>>,
+    color_b: Rc<Cell<f64>>,
+    color_a: Rc<Cell<f64>>,
+    size: Rc<Cell<f64>>,
+    tool: Rc<RefCell<String>>,
+    shadow: Rc<RefCell<String>>,
+    shadow_offset_x: Rc<Cell<f64>>,
+    shadow_offset_y: Rc<Cell<f64>>,
+    shadow_blur: Rc<Cell<f64>>,
+    shadow_r: Rc<Cell<f64>>,
+    shadow_g: Rc<Cell<f64>>,
+    shadow_b: Rc<Cell<f64>>,
+    shadow_a: Rc<Cell<f64>>,
+    eraser_size: Rc<Cell<f64>>,
+}
+
+impl FreehandSettingsRc {
+    /// Get a setting by name, returning a `SettingValue`.
+    pub fn get_value(&self, setting_name: &str) -> SettingValue {
+        match setting_name {
+            "color_r" => SettingValue::F64(self.color_r.get()),
+            "color_g" => SettingValue::F64(self.color_g.get()),
+            "color_b" => SettingValue::F64(self.color_b.get()),
+            "color_a" => SettingValue::F64(self.color_a.get()),
+            "size" => SettingValue::F64(self.size.get()),
+            "tool" => SettingValue::String(self.tool.borrow().to_string()),
+            "shadow" => SettingValue::String(self.shadow.borrow().to_string()),
+            "shadow_offset_x" => SettingValue::F64(self.shadow_offset_x.get()),
+            "shadow_offset_y" => SettingValue::F64(self.shadow_offset_y.get()),
+            "shadow_blur" => SettingValue::F64(self.shadow_blur.get()),
+            "shadow_r" => SettingValue::F64(self.shadow_r.get()),
+            "shadow_g" => SettingValue::F64(self.shadow_g.get()),
+            "shadow_b" => SettingValue::F64(self.shadow_b.get()),
+            "shadow_a" => SettingValue::F64(self.shadow_a.get()),
+            "eraser_size" => SettingValue::F64(self.eraser_size.get()),
+            other => {
+                eprintln!("FreehandSettings::get_value: unknown key '{}'", other);
+                // Default – you can change this to whatever makes sense.
+                SettingValue::Bool(false)
+            }
+        }
+    }
+    /// Set a setting by name, returning a `SettingValue`.
+    pub fn set_value(&self, setting_name: &str, value: SettingValue) -> std::io::Result<()> {
+        match (setting_name, value) {
+            // ---- fill -------------------------------------------------------
+            ("color_r", SettingValue::F64(v)) => {
+                self.color_r.set(v);
+                Ok(())
+            }
+            ("color_g", SettingValue::F64(v)) => {
+                self.color_g.set(v);
+                Ok(())
+            }
+            ("color_b", SettingValue::F64(v)) => {
+                self.color_b.set(v);
+                Ok(())
+            }
+            ("color_a", SettingValue::F64(v)) => {
+                self.color_a.set(v);
+                Ok(())
+            }
+            ("size", SettingValue::F64(v)) => {
+                self.size.set(v);
+                Ok(())
+            }
+
+            // ---- junctions -------------------------------------------------------
+            ("tool", SettingValue::String(v)) => {
+                *self.tool.borrow_mut() = v;
+                Ok(())
+            }
+
+            ("shadow", SettingValue::String(v)) => {
+                *self.shadow.borrow_mut() = v;
+                Ok(())
+            }
+
+            ("shadow_offset_x", SettingValue::F64(v)) => {
+                self.shadow_offset_x.set(v);
+                Ok(())
+            }
+            ("shadow_offset_y", SettingValue::F64(v)) => {
+                self.shadow_offset_y.set(v);
+                Ok(())
+            }
+            ("shadow_blur", SettingValue::F64(v)) => {
+                self.shadow_blur.set(v);
+                Ok(())
+            }
+            ("shadow_r", SettingValue::F64(v)) => {
+                self.shadow_r.set(v);
+                Ok(())
+            }
+            ("shadow_g", SettingValue::F64(v)) => {
+                self.shadow_g.set(v);
+                Ok(())
+            }
+            ("shadow_b", SettingValue::F64(v)) => {
+                self.shadow_b.set(v);
+                Ok(())
+            }
+            ("shadow_a", SettingValue::F64(v)) => {
+                self.shadow_a.set(v);
+                Ok(())
+            }
+
+            ("eraser_size", SettingValue::F64(v)) => {
+                self.eraser_size.set(v);
+                Ok(())
+            }
+
+            // ---- mismatched type -------------------------------------------------
+            (key, _wrong_type) => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("FreehandSettings::get_value: unknown key '{}'", key),
+            )),
+        }
+    }
+}
+
+#[derive(Default, Clone)]
+pub struct NumberedCircleSettingsRc {
+    fill: Rc<Cell<bool>>,
+    fill_r: Rc<Cell<f64>>,
+    fill_g: Rc<Cell<f64>>,
+    fill_b: Rc<Cell<f64>>,
+    fill_a: Rc<Cell<f64>>,
+    border: Rc<Cell<bool>>,
+    border_size: Rc<Cell<f64>>,
+    border_r: Rc<Cell<f64>>,
+    border_g: Rc<Cell<f64>>,
+    border_b: Rc<Cell<f64>>,
+    border_a: Rc<Cell<f64>>,
+    number: Rc<Cell<i32>>,
+    font_size: Rc<Cell<f64>>,
+    font_face: Rc<RefCell<String>>,
+    font_weight: Rc<RefCell<String>>,
+    font_slant: Rc<RefCell<String>>,
+    font_r: Rc<Cell<f64>>,
+    font_g: Rc<Cell<f64>>,
+    font_b: Rc<Cell<f64>>,
+    font_a: Rc<Cell<f64>>,
+    radius: Rc<Cell<f64>>,
+    style: Rc<RefCell<String>>,
+    prefix: Rc<RefCell<String>>,
+    shape: Rc<RefCell<String>>,
+    sequence: Rc<Cell<i32>>,
+    sequence_start: Rc<Cell<i32>>,
+    renumber: Rc<Cell<bool>>,
+    fill_mode: Rc<RefCell<String>>,
+    fill_stops: Rc<RefCell<String>>,
+    fill_angle: Rc<Cell<f64>>,
+    shadow: Rc<RefCell<String>>,
+    shadow_offset_x: Rc<Cell<f64>>,
+    shadow_offset_y: Rc<Cell<f64>>,
+    shadow_blur: Rc<Cell<f64>>,
+    shadow_r: Rc<Cell<f64>>,
+    shadow_g: Rc<Cell<f64>>,
+    shadow_b: Rc<Cell<f64>>,
+    shadow_a: Rc<Cell<f64>>,
+}
+
+impl NumberedCircleSettingsRc {
+    /// Get a setting by name, returning a `SettingValue`.
+    pub fn get_value(&self, setting_name: &str) -> SettingValue {
+        match setting_name {
+            "fill" => SettingValue::Bool(self.fill.get()),
+            "fill_r" => SettingValue::F64(self.fill_r.get()),
+            "fill_g" => SettingValue::F64(self.fill_g.get()),
+            "fill_b" => SettingValue::F64(self.fill_b.get()),
+            "fill_a" => SettingValue::F64(self.fill_a.get()),
+            "border" => SettingValue::Bool(self.border.get()),
+            "border_size" => SettingValue::F64(self.border_size.get()),
+            "border_r" => SettingValue::F64(self.border_r.get()),
+            "border_g" => SettingValue::F64(self.border_g.get()),
+            "border_b" => SettingValue::F64(self.border_b.get()),
+            "border_a" => SettingValue::F64(self.border_a.get()),
+            "number" => SettingValue::I32(self.number.get()),
+            "font_size" => SettingValue::F64(self.font_size.get()),
+            "font_face" => SettingValue::String(self.font_face.borrow().clone()),
+            "font_weight" => SettingValue::String(self.font_weight.borrow().clone()),
+            "font_slant" => SettingValue::String(self.font_slant.borrow().clone()),
+            "font_r" => SettingValue::F64(self.font_r.get()),
+            "font_g" => SettingValue::F64(self.font_g.get()),
+            "font_b" => SettingValue::F64(self.font_b.get()),
+            "font_a" => SettingValue::F64(self.font_a.get()),
+            "radius" => SettingValue::F64(self.radius.get()),
+            "style" => SettingValue::String(self.style.borrow().to_string()),
+            "prefix" => SettingValue::String(self.prefix.borrow().to_string()),
+            "shape" => SettingValue::String(self.shape.borrow().to_string()),
+            "sequence" => SettingValue::I32(self.sequence.get()),
+            "sequence_start" => SettingValue::I32(self.sequence_start.get()),
+            "renumber" => SettingValue::Bool(self.renumber.get()),
+            "fill_mode" => SettingValue::String(self.fill_mode.borrow().to_string()),
+            "fill_stops" => SettingValue::String(self.fill_stops.borrow().to_string()),
+            "fill_angle" => SettingValue::F64(self.fill_angle.get()),
+            "shadow" => SettingValue::String(self.shadow.borrow().to_string()),
+            "shadow_offset_x" => SettingValue::F64(self.shadow_offset_x.get()),
+            "shadow_offset_y" => SettingValue::F64(self.shadow_offset_y.get()),
+            "shadow_blur" => SettingValue::F64(self.shadow_blur.get()),
+            "shadow_r" => SettingValue::F64(self.shadow_r.get()),
+            "shadow_g" => SettingValue::F64(self.shadow_g.get()),
+            "shadow_b" => SettingValue::F64(self.shadow_b.get()),
+            "shadow_a" => SettingValue::F64(self.shadow_a.get()),
+            other => {
+                eprintln!("NumberedCircleSettings::get_value: unknown key '{}'", other);
+                // Default – you can change this to whatever makes sense.
+                SettingValue::Bool(false)
+            }
+        }
+    }
+    /// Set a setting by name, returning a `SettingValue`.
+    pub fn set_value(&self, setting_name: &str, value: SettingValue) -> std::io::Result<()> {
+        match (setting_name, value) {
+            // ---- fill -------------------------------------------------------
+            ("fill", SettingValue::Bool(v)) => {
+                self.fill.set(v);
+                Ok(())
+            }
+            ("fill_r", SettingValue::F64(v)) => {
+                self.fill_r.set(v);
+                Ok(())
+            }
+            ("fill_g", SettingValue::F64(v)) => {
+                self.fill_g.set(v);
+                Ok(())
+            }
+            ("fill_b", SettingValue::F64(v)) => {
+                self.fill_b.set(v);
+                Ok(())
+            }
+            ("fill_a", SettingValue::F64(v)) => {
+                self.fill_a.set(v);
+                Ok(())
+            }
+
+            // ---- border -----------------------------------------------------
+            ("border", SettingValue::Bool(v)) => {
+                self.border.set(v);
+                Ok(())
+            }
+            ("border_size", SettingValue::F64(v)) => {
+                self.border_size.set(v);
+                Ok(())
+            }
+            ("border_r", SettingValue::F64(v)) => {
+                self.border_r.set(v);
+                Ok(())
+            }
+            ("border_g", SettingValue::F64(v)) => {
+                self.border_g.set(v);
+                Ok(())
+            }
+            ("border_b", SettingValue::F64(v)) => {
+                self.border_b.set(v);
+                Ok(())
+            }
+            ("border_a", SettingValue::F64(v)) => {
+                self.border_a.set(v);
+                Ok(())
+            }
+
+            // ---- font -----------------------------------------------------
+            ("font_size", SettingValue::F64(v)) => {
+                self.font_size.set(v);
+                Ok(())
+            }
+            ("font_face", SettingValue::String(v)) => {
+                *self.font_face.borrow_mut() = v;
+                Ok(())
+            }
+            ("font_weight", SettingValue::String(v)) => {
+                *self.font_weight.borrow_mut() = v;
+                Ok(())
+            }
+            ("font_slant", SettingValue::String(v)) => {
+                *self.font_slant.borrow_mut() = v;
+                Ok(())
+            }
+            ("font_r", SettingValue::F64(v)) => {
+                self.font_r.set(v);
+                Ok(())
+            }
+            ("font_g", SettingValue::F64(v)) => {
+                self.font_g.set(v);
+                Ok(())
+            }
+            ("font_b", SettingValue::F64(v)) => {
+                self.font_b.set(v);
+                Ok(())
+            }
+            ("font_a", SettingValue::F64(v)) => {
+                self.font_a.set(v);
+                Ok(())
+            }
+
+            // ---- number -----------------------------------------------------
+            ("number", SettingValue::I32(v)) => {
+                self.number.set(v);
+                Ok(())
+            }
+
+            // ---- radius -----------------------------------------------------
+            ("radius", SettingValue::F64(v)) => {
+                self.radius.set(v);
+                Ok(())
+            }
+
+            ("style", SettingValue::String(v)) => {
+                *self.style.borrow_mut() = v;
+                Ok(())
+            }
+            ("prefix", SettingValue::String(v)) => {
+                *self.prefix.borrow_mut() = v;
+                Ok(())
+            }
+            ("shape", SettingValue::String(v)) => {
+                *self.shape.borrow_mut() = v;
+                Ok(())
+            }
+            ("sequence", SettingValue::I32(v)) => {
+                self.sequence.set(v);
+                Ok(())
+            }
+            ("sequence_start", SettingValue::I32(v)) => {
+                self.sequence_start.set(v);
+                Ok(())
+            }
+            ("renumber", SettingValue::Bool(v)) => {
+                self.renumber.set(v);
+                Ok(())
+            }
+
+            ("fill_mode", SettingValue::String(v)) => {
+                *self.fill_mode.borrow_mut() = v;
+                Ok(())
+            }
+            ("fill_stops", SettingValue::String(v)) => {
+                *self.fill_stops.borrow_mut() = v;
+                Ok(())
+            }
+            ("fill_angle", SettingValue::F64(v)) => {
+                self.fill_angle.set(v);
+                Ok(())
+            }
+
+            ("shadow", SettingValue::String(v)) => {
+                *self.shadow.borrow_mut() = v;
+                Ok(())
+            }
+
+            ("shadow_offset_x", SettingValue::F64(v)) => {
+                self.shadow_offset_x.set(v);
+                Ok(())
+            }
+            ("shadow_offset_y", SettingValue::F64(v)) => {
+                self.shadow_offset_y.set(v);
+                Ok(())
+            }
+            ("shadow_blur", SettingValue::F64(v)) => {
+                self.shadow_blur.set(v);
+                Ok(())
+            }
+            ("shadow_r", SettingValue::F64(v)) => {
+                self.shadow_r.set(v);
+                Ok(())
+            }
+            ("shadow_g", SettingValue::F64(v)) => {
+                self.shadow_g.set(v);
+                Ok(())
+            }
+            ("shadow_b", SettingValue::F64(v)) => {
+                self.shadow_b.set(v);
+                Ok(())
+            }
+            ("shadow_a", SettingValue::F64(v)) => {
+                self.shadow_a.set(v);
+                Ok(())
+            }
+
+            // ---- mismatched type -------------------------------------------------
+            (key, _wrong_type) => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("NumberedCircleSettings::get_value: unknown key '{}'", key),
+            )),
+        }
+    }
+}
+
+#[derive(Default, Clone)]
+pub struct InputTextSettingsRc {
+    fill: Rc<Cell<bool>>,
+    fill_r: Rc<Cell<f64>>,
+    fill_g: Rc<Cell<f64>>,
+    fill_b: Rc<Cell<f64>>,
+    fill_a: Rc<Cell<f64>>,
+    border: Rc<Cell<bool>>,
+    border_size: Rc<Cell<f64>>,
+    border_r: Rc<Cell<f64>>,
+    border_g: Rc<Cell<f64>>,
+    border_b: Rc<Cell<f64>>,
+    border_a: Rc<Cell<f64>>,
+    text: Rc<RefCell<String>>,
+    font_size: Rc<Cell<f64>>,
+    font_face: Rc<RefCell<String>>,
+    font_weight: Rc<RefCell<String>>,
+    font_slant: Rc<RefCell<String>>,
+    font_r: Rc<Cell<f64>>,
+    font_g: Rc<Cell<f64>>,
+    font_b: Rc<Cell<f64>>,
+    font_a: Rc<Cell<f64>>,
+    fill_mode: Rc<RefCell<String>>,
+    fill_stops: Rc<RefCell<String>>,
+    fill_angle: Rc<Cell<f64>>,
+    shadow: Rc<RefCell<String>>,
+    shadow_offset_x: Rc<Cell<f64>>,
+    shadow_offset_y: Rc<Cell<f64>>,
+    shadow_blur: Rc<Cell<f64>>,
+    shadow_r: Rc<Cell<f64>>,
+    shadow_g: Rc<Cell<f64>>,
+    shadow_b: Rc<Cell<f64>>,
+    shadow_a: Rc<Cell<f64>>,
+}
+
+impl InputTextSettingsRc {
+    /// Get a setting by name, returning a `SettingValue`.
+    pub fn get_value(&self, setting_name: &str) -> SettingValue {
+        match setting_name {
+            "fill" => SettingValue::Bool(self.fill.get()),
+            "fill_r" => SettingValue::F64(self.fill_r.get()),
+            "fill_g" => SettingValue::F64(self.fill_g.get()),
+            "fill_b" => SettingValue::F64(self.fill_b.get()),
+            "fill_a" => SettingValue::F64(self.fill_a.get()),
+            "border" => SettingValue::Bool(self.border.get()),
+            "border_size" => SettingValue::F64(self.border_size.get()),
+            "border_r" => SettingValue::F64(self.border_r.get()),
+            "border_g" => SettingValue::F64(self.border_g.get()),
+            "border_b" => SettingValue::F64(self.border_b.get()),
+            "border_a" => SettingValue::F64(self.border_a.get()),
+            "text" => SettingValue::String(self.text.borrow().clone()),
+            "font_size" => SettingValue::F64(self.font_size.get()),
+            "font_face" => SettingValue::String(self.font_face.borrow().clone()),
+            "font_weight" => SettingValue::String(self.font_weight.borrow().clone()),
+            "font_slant" => SettingValue::String(self.font_slant.borrow().clone()),
+            "font_r" => SettingValue::F64(self.font_r.get()),
+            "font_g" => SettingValue::F64(self.font_g.get()),
+            "font_b" => SettingValue::F64(self.font_b.get()),
+            "font_a" => SettingValue::F64(self.font_a.get()),
+            "fill_mode" => SettingValue::String(self.fill_mode.borrow().to_string()),
+            "fill_stops" => SettingValue::String(self.fill_stops.borrow().to_string()),
+            "fill_angle" => SettingValue::F64(self.fill_angle.get()),
+            "shadow" => SettingValue::String(self.shadow.borrow().to_string()),
+            "shadow_offset_x" => SettingValue::F64(self.shadow_offset_x.get()),
+            "shadow_offset_y" => SettingValue::F64(self.shadow_offset_y.get()),
+            "shadow_blur" => SettingValue::F64(self.shadow_blur.get()),
+            "shadow_r" => SettingValue::F64(self.shadow_r.get()),
+            "shadow_g" => SettingValue::F64(self.shadow_g.get()),
+            "shadow_b" => SettingValue::F64(self.shadow_b.get()),
+            "shadow_a" => SettingValue::F64(self.shadow_a.get()),
+            other => {
+                eprintln!("NumberedCircleSettings::get_value: unknown key '{}'", other);
+                // Default – you can change this to whatever makes sense.
+                SettingValue::Bool(false)
+            }
+        }
+    }
+    /// Set a setting by name, returning a `SettingValue`.
+    pub fn set_value(&self, setting_name: &str, value: SettingValue) -> std::io::Result<()> {
+        match (setting_name, value) {
+            // ---- fill -------------------------------------------------------
+            ("fill", SettingValue::Bool(v)) => {
+                self.fill.set(v);
+                Ok(())
+            }
+            ("fill_r", SettingValue::F64(v)) => {
+                self.fill_r.set(v);
+                Ok(())
+            }
+            ("fill_g", SettingValue::F64(v)) => {
+                self.fill_g.set(v);
+                Ok(())
+            }
+            ("fill_b", SettingValue::F64(v)) => {
+                self.fill_b.set(v);
+                Ok(())
+            }
+            ("fill_a", SettingValue::F64(v)) => {
+                self.fill_a.set(v);
+                Ok(())
+            }
+
+            // ---- border -----------------------------------------------------
+            ("border", SettingValue::Bool(v)) => {
+                self.border.set(v);
+                Ok(())
+            }
+            ("border_size", SettingValue::F64(v)) => {
+                self.border_size.set(v);
+                Ok(())
+            }
+            ("border_r", SettingValue::F64(v)) => {
+                self.border_r.set(v);
+                Ok(())
+            }
+            ("border_g", SettingValue::F64(v)) => {
+                self.border_g.set(v);
+                Ok(())
+            }
+            ("border_b", SettingValue::F64(v)) => {
+                self.border_b.set(v);
+                Ok(())
+            }
+            ("border_a", SettingValue::F64(v)) => {
+                self.border_a.set(v);
+                Ok(())
+            }
+
+            // ---- text -----------------------------------------------------
+            ("text", SettingValue::String(v)) => {
+                *self.text.borrow_mut() = v;
+                Ok(())
+            }
+
+            // ---- font -----------------------------------------------------
+            ("font_size", SettingValue::F64(v)) => {
+                self.font_size.set(v);
+                Ok(())
+            }
+            ("font_face", SettingValue::String(v)) => {
+                *self.font_face.borrow_mut() = v;
+                Ok(())
+            }
+            ("font_weight", SettingValue::String(v)) => {
+                *self.font_weight.borrow_mut() = v;
+                Ok(())
+            }
+            ("font_slant", SettingValue::String(v)) => {
+                *self.font_slant.borrow_mut() = v;
+                Ok(())
+            }
+            ("font_r", SettingValue::F64(v)) => {
+                self.font_r.set(v);
+                Ok(())
+            }
+            ("font_g", SettingValue::F64(v)) => {
+                self.font_g.set(v);
+                Ok(())
+            }
+            ("font_b", SettingValue::F64(v)) => {
+                self.font_b.set(v);
+                Ok(())
+            }
+            ("font_a", SettingValue::F64(v)) => {
+                self.font_a.set(v);
+                Ok(())
+            }
+
+            ("fill_mode", SettingValue::String(v)) => {
+                *self.fill_mode.borrow_mut() = v;
+                Ok(())
+            }
+            ("fill_stops", SettingValue::String(v)) => {
+                *self.fill_stops.borrow_mut() = v;
+                Ok(())
+            }
+            ("fill_angle", SettingValue::F64(v)) => {
+                self.fill_angle.set(v);
+                Ok(())
+            }
+
+            ("shadow", SettingValue::String(v)) => {
+                *self.shadow.borrow_mut() = v;
+                Ok(())
+            }
+
+            ("shadow_offset_x", SettingValue::F64(v)) => {
+                self.shadow_offset_x.set(v);
+                Ok(())
+            }
+            ("shadow_offset_y", SettingValue::F64(v)) => {
+                self.shadow_offset_y.set(v);
+                Ok(())
+            }
+            ("shadow_blur", SettingValue::F64(v)) => {
+                self.shadow_blur.set(v);
+                Ok(())
+            }
+            ("shadow_r", SettingValue::F64(v)) => {
+                self.shadow_r.set(v);
+                Ok(())
+            }
+            ("shadow_g", SettingValue::F64(v)) => {
+                self.shadow_g.set(v);
+                Ok(())
+            }
+            ("shadow_b", SettingValue::F64(v)) => {
+                self.shadow_b.set(v);
+                Ok(())
+            }
+            ("shadow_a", SettingValue::F64(v)) => {
+                self.shadow_a.set(v);
+                Ok(())
+            }
+
+            // ---- mismatched type -------------------------------------------------
+            (key, _wrong_type) => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("NumberedCircleSettings::get_value: unknown key '{}'", key),
+            )),
+        }
+    }
+}
+
+#[derive(Default, Clone)]
+pub struct ObfuscateSettingsRc {
+    tool: Rc<RefCell<String>>,
+    pixel_size: Rc<Cell<f64>>,
+    blur_radius: Rc<Cell<f64>>,
+    shadow: Rc<RefCell<String>>,
+    shadow_offset_x: Rc<Cell<f64>>,
+    shadow_offset_y: Rc<Cell<f64>>,
+    shadow_blur: Rc<Cell<f64>>,
+    shadow_r: Rc<Cell<f64>>,
+    shadow_g: Rc<Cell<f64>>,
+    shadow_b: Rc<Cell<f64>>,
+    shadow_a: Rc<Cell<f64>>,
+}
+
+impl ObfuscateSettingsRc {
+    /// Get a setting by name, returning a `SettingValue`.
+    pub fn get_value(&self, setting_name: &str) -> SettingValue {
+        match setting_name {
+            "tool" => SettingValue::String(self.tool.borrow().to_string()),
+            "pixel_size" => SettingValue::F64(self.pixel_size.get()),
+            "blur_radius" => SettingValue::F64(self.blur_radius.get()),
+            "shadow" => SettingValue::String(self.shadow.borrow().to_string()),
+            "shadow_offset_x" => SettingValue::F64(self.shadow_offset_x.get()),
+            "shadow_offset_y" => SettingValue::F64(self.shadow_offset_y.get()),
+            "shadow_blur" => SettingValue::F64(self.shadow_blur.get()),
+            "shadow_r" => SettingValue::F64(self.shadow_r.get()),
+            "shadow_g" => SettingValue::F64(self.shadow_g.get()),
+            "shadow_b" => SettingValue::F64(self.shadow_b.get()),
+            "shadow_a" => SettingValue::F64(self.shadow_a.get()),
+            other => {
+                eprintln!("ObfuscateSettings::get_value: unknown key '{}'", other);
+                // Default – you can change this to whatever makes sense.
+                SettingValue::Bool(false)
+            }
+        }
+    }
+    /// Set a setting by name, returning a `SettingValue`.
+    pub fn set_value(&self, setting_name: &str, value: SettingValue) -> std::io::Result<()> {
+        match (setting_name, value) {
+            ("tool", SettingValue::String(v)) => {
+                *self.tool.borrow_mut() = v;
+                Ok(())
+            }
+            ("pixel_size", SettingValue::F64(v)) => {
+                self.pixel_size.set(v);
+                Ok(())
+            }
+            ("blur_radius", SettingValue::F64(v)) => {
+                self.blur_radius.set(v);
+                Ok(())
+            }
+
+            ("shadow", SettingValue::String(v)) => {
+                *self.shadow.borrow_mut() = v;
+                Ok(())
+            }
+
+            ("shadow_offset_x", SettingValue::F64(v)) => {
+                self.shadow_offset_x.set(v);
+                Ok(())
+            }
+            ("shadow_offset_y", SettingValue::F64(v)) => {
+                self.shadow_offset_y.set(v);
+                Ok(())
+            }
+            ("shadow_blur", SettingValue::F64(v)) => {
+                self.shadow_blur.set(v);
+                Ok(())
+            }
+            ("shadow_r", SettingValue::F64(v)) => {
+                self.shadow_r.set(v);
+                Ok(())
+            }
+            ("shadow_g", SettingValue::F64(v)) => {
+                self.shadow_g.set(v);
+                Ok(())
+            }
+            ("shadow_b", SettingValue::F64(v)) => {
+                self.shadow_b.set(v);
+                Ok(())
+            }
+            ("shadow_a", SettingValue::F64(v)) => {
+                self.shadow_a.set(v);
+                Ok(())
+            }
+
+            // ---- mismatched type -------------------------------------------------
+            (key, _wrong_type) => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("ObfuscateSettings::get_value: unknown key '{}'", key),
+            )),
+        }
+    }
+}
+
+#[derive(Default, Clone)]
+pub struct RedactSettingsRc {
+    color_r: Rc<Cell<f64>>,
+    color_g: Rc<Cell<f64>>,
+    color_b: Rc<Cell<f64>>,
+    shadow: Rc<RefCell<String>>,
+    shadow_offset_x: Rc<Cell<f64>>,
+    shadow_offset_y: Rc<Cell<f64>>,
+    shadow_blur: Rc<Cell<f64>>,
+    shadow_r: Rc<Cell<f64>>,
+    shadow_g: Rc<Cell<f64>>,
+    shadow_b: Rc<Cell<f64>>,
+    shadow_a: Rc<Cell<f64>>,
+}
+
+impl RedactSettingsRc {
+    /// Get a setting by name, returning a `SettingValue`.
+    pub fn get_value(&self, setting_name: &str) -> SettingValue {
+        match setting_name {
+            "color_r" => SettingValue::F64(self.color_r.get()),
+            "color_g" => SettingValue::F64(self.color_g.get()),
+            "color_b" => SettingValue::F64(self.color_b.get()),
+            "shadow" => SettingValue::String(self.shadow.borrow().to_string()),
+            "shadow_offset_x" => SettingValue::F64(self.shadow_offset_x.get()),
+            "shadow_offset_y" => SettingValue::F64(self.shadow_offset_y.get()),
+            "shadow_blur" => SettingValue::F64(self.shadow_blur.get()),
+            "shadow_r" => SettingValue::F64(self.shadow_r.get()),
+            "shadow_g" => SettingValue::F64(self.shadow_g.get()),
+            "shadow_b" => SettingValue::F64(self.shadow_b.get()),
+            "shadow_a" => SettingValue::F64(self.shadow_a.get()),
+            other => {
+                eprintln!("RedactSettings::get_value: unknown key '{}'", other);
+                // Default – you can change this to whatever makes sense.
+                SettingValue::Bool(false)
+            }
+        }
+    }
+    /// Set a setting by name, returning a `SettingValue`.
+    pub fn set_value(&self, setting_name: &str, value: SettingValue) -> std::io::Result<()> {
+        match (setting_name, value) {
+            ("color_r", SettingValue::F64(v)) => {
+                self.color_r.set(v);
+                Ok(())
+            }
+            ("color_g", SettingValue::F64(v)) => {
+                self.color_g.set(v);
+                Ok(())
+            }
+            ("color_b", SettingValue::F64(v)) => {
+                self.color_b.set(v);
+                Ok(())
+            }
+
+            ("shadow", SettingValue::String(v)) => {
+                *self.shadow.borrow_mut() = v;
+                Ok(())
+            }
+
+            ("shadow_offset_x", SettingValue::F64(v)) => {
+                self.shadow_offset_x.set(v);
+                Ok(())
+            }
+            ("shadow_offset_y", SettingValue::F64(v)) => {
+                self.shadow_offset_y.set(v);
+                Ok(())
+            }
+            ("shadow_blur", SettingValue::F64(v)) => {
+                self.shadow_blur.set(v);
+                Ok(())
+            }
+            ("shadow_r", SettingValue::F64(v)) => {
+                self.shadow_r.set(v);
+                Ok(())
+            }
+            ("shadow_g", SettingValue::F64(v)) => {
+                self.shadow_g.set(v);
+                Ok(())
+            }
+            ("shadow_b", SettingValue::F64(v)) => {
+                self.shadow_b.set(v);
+                Ok(())
+            }
+            ("shadow_a", SettingValue::F64(v)) => {
+                self.shadow_a.set(v);
+                Ok(())
+            }
+
+            // ---- mismatched type -------------------------------------------------
+            (key, _wrong_type) => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("RedactSettings::get_value: unknown key '{}'", key),
+            )),
+        }
+    }
+}
+
+#[derive(Default, Clone)]
+pub struct SpotlightSettingsRc {
+    shape: Rc<RefCell<String>>,
+    dim: Rc<Cell<f64>>,
+    shadow: Rc<RefCell<String>>,
+    shadow_offset_x: Rc<Cell<f64>>,
+    shadow_offset_y: Rc<Cell<f64>>,
+    shadow_blur: Rc<Cell<f64>>,
+    shadow_r: Rc<Cell<f64>>,
+    shadow_g: Rc<Cell<f64>>,
+    shadow_b: Rc<Cell<f64>>,
+    shadow_a: Rc<Cell<f64>>,
+}
+
+impl SpotlightSettingsRc {
+    /// Get a setting by name, returning a `SettingValue`.
+    pub fn get_value(&self, setting_name: &str) -> SettingValue {
+        match setting_name {
+            "shape" => SettingValue::String(self.shape.borrow().to_string()),
+            "dim" => SettingValue::F64(self.dim.get()),
+            "shadow" => SettingValue::String(self.shadow.borrow().to_string()),
+            "shadow_offset_x" => SettingValue::F64(self.shadow_offset_x.get()),
+            "shadow_offset_y" => SettingValue::F64(self.shadow_offset_y.get()),
+            "shadow_blur" => SettingValue::F64(self.shadow_blur.get()),
+            "shadow_r" => SettingValue::F64(self.shadow_r.get()),
+            "shadow_g" => SettingValue::F64(self.shadow_g.get()),
+            "shadow_b" => SettingValue::F64(self.shadow_b.get()),
+            "shadow_a" => SettingValue::F64(self.shadow_a.get()),
+            other => {
+                eprintln!("SpotlightSettings::get_value: unknown key '{}'", other);
+                // Default – you can change this to whatever makes sense.
+                SettingValue::Bool(false)
+            }
+        }
+    }
+    /// Set a setting by name, returning a `SettingValue`.
+    pub fn set_value(&self, setting_name: &str, value: SettingValue) -> std::io::Result<()> {
+        match (setting_name, value) {
+            ("shape", SettingValue::String(v)) => {
+                *self.shape.borrow_mut() = v;
+                Ok(())
+            }
+            ("dim", SettingValue::F64(v)) => {
+                self.dim.set(v);
+                Ok(())
+            }
+
+            ("shadow", SettingValue::String(v)) => {
+                *self.shadow.borrow_mut() = v;
+                Ok(())
+            }
+
+            ("shadow_offset_x", SettingValue::F64(v)) => {
+                self.shadow_offset_x.set(v);
+                Ok(())
+            }
+            ("shadow_offset_y", SettingValue::F64(v)) => {
+                self.shadow_offset_y.set(v);
+                Ok(())
+            }
+            ("shadow_blur", SettingValue::F64(v)) => {
+                self.shadow_blur.set(v);
+                Ok(())
+            }
+            ("shadow_r", SettingValue::F64(v)) => {
+                self.shadow_r.set(v);
+                Ok(())
+            }
+            ("shadow_g", SettingValue::F64(v)) => {
+                self.shadow_g.set(v);
+                Ok(())
+            }
+            ("shadow_b", SettingValue::F64(v)) => {
+                self.shadow_b.set(v);
+                Ok(())
+            }
+            ("shadow_a", SettingValue::F64(v)) => {
+                self.shadow_a.set(v);
+                Ok(())
+            }
+
+            // ---- mismatched type -------------------------------------------------
+            (key, _wrong_type) => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("SpotlightSettings::get_value: unknown key '{}'", key),
+            )),
+        }
+    }
+}
+
+#[derive(Default, Clone)]
+pub struct MagnifierSettingsRc {
+    shape: Rc<RefCell<String>>,
+    zoom: Rc<Cell<f64>>,
+    border_size: Rc<Cell<f64>>,
+    border_r: Rc<Cell<f64>>,
+    border_g: Rc<Cell<f64>>,
+    border_b: Rc<Cell<f64>>,
+    border_a: Rc<Cell<f64>>,
+    connector: Rc<Cell<bool>>,
+    shadow: Rc<RefCell<String>>,
+    shadow_offset_x: Rc<Cell<f64>>,
+    shadow_offset_y: Rc<Cell<f64>>,
+    shadow_blur: Rc<Cell<f64>>,
+    shadow_r: Rc<Cell<f64>>,
+    shadow_g: Rc<Cell<f64>>,
+    shadow_b: Rc<Cell<f64>>,
+    shadow_a: Rc<Cell<f64>>,
+}
+
+impl MagnifierSettingsRc {
+    /// Get a setting by name, returning a `SettingValue`.
+    pub fn get_value(&self, setting_name: &str) -> SettingValue {
+        match setting_name {
+            "shape" => SettingValue::String(self.shape.borrow().to_string()),
+            "zoom" => SettingValue::F64(self.zoom.get()),
+            "border_size" => SettingValue::F64(self.border_size.get()),
+            "border_r" => SettingValue::F64(self.border_r.get()),
+            "border_g" => SettingValue::F64(self.border_g.get()),
+            "border_b" => SettingValue::F64(self.border_b.get()),
+            "border_a" => SettingValue::F64(self.border_a.get()),
+            "connector" => SettingValue::Bool(self.connector.get()),
+            "shadow" => SettingValue::String(self.shadow.borrow().to_string()),
+            "shadow_offset_x" => SettingValue::F64(self.shadow_offset_x.get()),
+            "shadow_offset_y" => SettingValue::F64(self.shadow_offset_y.get()),
+            "shadow_blur" => SettingValue::F64(self.shadow_blur.get()),
+            "shadow_r" => SettingValue::F64(self.shadow_r.get()),
+            "shadow_g" => SettingValue::F64(self.shadow_g.get()),
+            "shadow_b" => SettingValue::F64(self.shadow_b.get()),
+            "shadow_a" => SettingValue::F64(self.shadow_a.get()),
+            other => {
+                eprintln!("MagnifierSettings::get_value: unknown key '{}'", other);
+                // Default – you can change this to whatever makes sense.
+                SettingValue::Bool(false)
+            }
+        }
+    }
+    /// Set a setting by name, returning a `SettingValue`.
+    pub fn set_value(&self, setting_name: &str, value: SettingValue) -> std::io::Result<()> {
+        match (setting_name, value) {
+            ("shape", SettingValue::String(v)) => {
+                *self.shape.borrow_mut() = v;
+                Ok(())
+            }
+            ("zoom", SettingValue::F64(v)) => {
+                self.zoom.set(v);
+                Ok(())
+            }
+            ("border_size", SettingValue::F64(v)) => {
+                self.border_size.set(v);
+                Ok(())
+            }
+            ("border_r", SettingValue::F64(v)) => {
+                self.border_r.set(v);
+                Ok(())
+            }
+            ("border_g", SettingValue::F64(v)) => {
+                self.border_g.set(v);
+                Ok(())
+            }
+            ("border_b", SettingValue::F64(v)) => {
+                self.border_b.set(v);
+                Ok(())
+            }
+            ("border_a", SettingValue::F64(v)) => {
+                self.border_a.set(v);
+                Ok(())
+            }
+            ("connector", SettingValue::Bool(v)) => {
+                self.connector.set(v);
+                Ok(())
+            }
+
+            ("shadow", SettingValue::String(v)) => {
+                *self.shadow.borrow_mut() = v;
                 Ok(())
             }
 
-            // ---- border -----------------------------------------------------
-            ("border", SettingValue::Bool(v)) => {
-                self.border.set(v);
+            ("shadow_offset_x", SettingValue::F64(v)) => {
+                self.shadow_offset_x.set(v);
                 Ok(())
             }
-            ("border_size", SettingValue::F64(v)) => {
-                self.border_size.set(v);
+            ("shadow_offset_y", SettingValue::F64(v)) => {
+                self.shadow_offset_y.set(v);
                 Ok(())
             }
-            ("border_r", SettingValue::F64(v)) => {
-                self.border_r.set(v);
+            ("shadow_blur", SettingValue::F64(v)) => {
+                self.shadow_blur.set(v);
                 Ok(())
             }
-            ("border_g", SettingValue::F64(v)) => {
-                self.border_g.set(v);
+            ("shadow_r", SettingValue::F64(v)) => {
+                self.shadow_r.set(v);
                 Ok(())
             }
-            ("border_b", SettingValue::F64(v)) => {
-                self.border_b.set(v);
+            ("shadow_g", SettingValue::F64(v)) => {
+                self.shadow_g.set(v);
                 Ok(())
             }
-            ("border_a", SettingValue::F64(v)) => {
-                self.border_a.set(v);
+            ("shadow_b", SettingValue::F64(v)) => {
+                self.shadow_b.set(v);
+                Ok(())
+            }
+            ("shadow_a", SettingValue::F64(v)) => {
+                self.shadow_a.set(v);
                 Ok(())
             }
 
             // ---- mismatched type -------------------------------------------------
             (key, _wrong_type) => Err(std::io::Error::new(
                 std::io::ErrorKind::InvalidInput,
-                format!("ArcSettings::get_value: unknown key '{}'", key),
+                format!("MagnifierSettings::get_value: unknown key '{}'", key),
             )),
         }
     }
 }
 
 #[derive(Default, Clone)]
-pub struct RectSettingsRc {
+pub struct PolygonSettingsRc {
+    closed: Rc<Cell<bool>>,
     fill: Rc<Cell<bool>>,
     fill_r: Rc<Cell<f64>>,
     fill_g: Rc<Cell<f64>>,
@@ -508,12 +2832,21 @@ pub struct RectSettingsRc {
     border_g: Rc<Cell<f64>>,
     border_b: Rc<Cell<f64>>,
     border_a: Rc<Cell<f64>>,
+    shadow: Rc<RefCell<String>>,
+    shadow_offset_x: Rc<Cell<f64>>,
+    shadow_offset_y: Rc<Cell<f64>>,
+    shadow_blur: Rc<Cell<f64>>,
+    shadow_r: Rc<Cell<f64>>,
+    shadow_g: Rc<Cell<f64>>,
+    shadow_b: Rc<Cell<f64>>,
+    shadow_a: Rc<Cell<f64>>,
 }
 
-impl RectSettingsRc {
+impl PolygonSettingsRc {
     /// Get a setting by name, returning a `SettingValue`.
     pub fn get_value(&self, setting_name: &str) -> SettingValue {
         match setting_name {
+            "closed" => SettingValue::Bool(self.closed.get()),
             "fill" => SettingValue::Bool(self.fill.get()),
             "fill_r" => SettingValue::F64(self.fill_r.get()),
             "fill_g" => SettingValue::F64(self.fill_g.get()),
@@ -525,8 +2858,16 @@ impl RectSettingsRc {
             "border_g" => SettingValue::F64(self.border_g.get()),
             "border_b" => SettingValue::F64(self.border_b.get()),
             "border_a" => SettingValue::F64(self.border_a.get()),
+            "shadow" => SettingValue::String(self.shadow.borrow().to_string()),
+            "shadow_offset_x" => SettingValue::F64(self.shadow_offset_x.get()),
+            "shadow_offset_y" => SettingValue::F64(self.shadow_offset_y.get()),
+            "shadow_blur" => SettingValue::F64(self.shadow_blur.get()),
+            "shadow_r" => SettingValue::F64(self.shadow_r.get()),
+            "shadow_g" => SettingValue::F64(self.shadow_g.get()),
+            "shadow_b" => SettingValue::F64(self.shadow_b.get()),
+            "shadow_a" => SettingValue::F64(self.shadow_a.get()),
             other => {
-                eprintln!("RectSettings::get_value: unknown key '{}'", other);
+                eprintln!("PolygonSettings::get_value: unknown key '{}'", other);
                 // Default – you can change this to whatever makes sense.
                 SettingValue::Bool(false)
             }
@@ -535,7 +2876,10 @@ impl RectSettingsRc {
     /// Set a setting by name, returning a `SettingValue`.
     pub fn set_value(&self, setting_name: &str, value: SettingValue) -> std::io::Result<()> {
         match (setting_name, value) {
-            // ---- fill -------------------------------------------------------
+            ("closed", SettingValue::Bool(v)) => {
+                self.closed.set(v);
+                Ok(())
+            }
             ("fill", SettingValue::Bool(v)) => {
                 self.fill.set(v);
                 Ok(())
@@ -556,8 +2900,6 @@ impl RectSettingsRc {
                 self.fill_a.set(v);
                 Ok(())
             }
-
-            // ---- border -----------------------------------------------------
             ("border", SettingValue::Bool(v)) => {
                 self.border.set(v);
                 Ok(())
@@ -583,39 +2925,81 @@ impl RectSettingsRc {
                 Ok(())
             }
 
+            ("shadow", SettingValue::String(v)) => {
+                *self.shadow.borrow_mut() = v;
+                Ok(())
+            }
+
+            ("shadow_offset_x", SettingValue::F64(v)) => {
+                self.shadow_offset_x.set(v);
+                Ok(())
+            }
+            ("shadow_offset_y", SettingValue::F64(v)) => {
+                self.shadow_offset_y.set(v);
+                Ok(())
+            }
+            ("shadow_blur", SettingValue::F64(v)) => {
+                self.shadow_blur.set(v);
+                Ok(())
+            }
+            ("shadow_r", SettingValue::F64(v)) => {
+                self.shadow_r.set(v);
+                Ok(())
+            }
+            ("shadow_g", SettingValue::F64(v)) => {
+                self.shadow_g.set(v);
+                Ok(())
+            }
+            ("shadow_b", SettingValue::F64(v)) => {
+                self.shadow_b.set(v);
+                Ok(())
+            }
+            ("shadow_a", SettingValue::F64(v)) => {
+                self.shadow_a.set(v);
+                Ok(())
+            }
+
             // ---- mismatched type -------------------------------------------------
             (key, _wrong_type) => Err(std::io::Error::new(
                 std::io::ErrorKind::InvalidInput,
-                format!("RectSettings::get_value: unknown key '{}'", key),
+                format!("PolygonSettings::get_value: unknown key '{}'", key),
             )),
         }
     }
 }
 
 #[derive(Default, Clone)]
-pub struct LineSettingsRc {
-    color_r: Rc<Cell<f64>>,
-    color_g: Rc<Cell<f64>>,
-    color_b: Rc<Cell<f64>>,
-    color_a: Rc<Cell<f64>>,
-    size: Rc<Cell<f64>>,
-    line_cap: Rc<RefCell<String>>,
-    line_join: Rc<RefCell<String>>,
+pub struct CalloutSettingsRc {
+    wrap_width: Rc<Cell<f64>>,
+    corner_radius: Rc<Cell<f64>>,
+    padding: Rc<Cell<f64>>,
+    shadow: Rc<RefCell<String>>,
+    shadow_offset_x: Rc<Cell<f64>>,
+    shadow_offset_y: Rc<Cell<f64>>,
+    shadow_blur: Rc<Cell<f64>>,
+    shadow_r: Rc<Cell<f64>>,
+    shadow_g: Rc<Cell<f64>>,
+    shadow_b: Rc<Cell<f64>>,
+    shadow_a: Rc<Cell<f64>>,
 }
 
-impl LineSettingsRc {
+impl CalloutSettingsRc {
     /// Get a setting by name, returning a `SettingValue`.
     pub fn get_value(&self, setting_name: &str) -> SettingValue {
         match setting_name {
-            "color_r" => SettingValue::F64(self.color_r.get()),
-            "color_g" => SettingValue::F64(self.color_g.get()),
-            "color_b" => SettingValue::F64(self.color_b.get()),
-            "color_a" => SettingValue::F64(self.color_a.get()),
-            "size" => SettingValue::F64(self.size.get()),
-            "line_cap" => SettingValue::String(self.line_cap.borrow().to_string()),
-            "line_join" => SettingValue::String(self.line_join.borrow().to_string()),
+            "wrap_width" => SettingValue::F64(self.wrap_width.get()),
+            "corner_radius" => SettingValue::F64(self.corner_radius.get()),
+            "padding" => SettingValue::F64(self.padding.get()),
+            "shadow" => SettingValue::String(self.shadow.borrow().to_string()),
+            "shadow_offset_x" => SettingValue::F64(self.shadow_offset_x.get()),
+            "shadow_offset_y" => SettingValue::F64(self.shadow_offset_y.get()),
+            "shadow_blur" => SettingValue::F64(self.shadow_blur.get()),
+            "shadow_r" => SettingValue::F64(self.shadow_r.get()),
+            "shadow_g" => SettingValue::F64(self.shadow_g.get()),
+            "shadow_b" => SettingValue::F64(self.shadow_b.get()),
+            "shadow_a" => SettingValue::F64(self.shadow_a.get()),
             other => {
-                eprintln!("LineSettings::get_value: unknown key '{}'", other);
+                eprintln!("CalloutSettings::get_value: unknown key '{}'", other);
                 // Default – you can change this to whatever makes sense.
                 SettingValue::Bool(false)
             }
@@ -624,73 +3008,94 @@ impl LineSettingsRc {
     /// Set a setting by name, returning a `SettingValue`.
     pub fn set_value(&self, setting_name: &str, value: SettingValue) -> std::io::Result<()> {
         match (setting_name, value) {
-            // ---- fill -------------------------------------------------------
-            ("color_r", SettingValue::F64(v)) => {
-                self.color_r.set(v);
+            ("wrap_width", SettingValue::F64(v)) => {
+                self.wrap_width.set(v);
                 Ok(())
             }
-            ("color_g", SettingValue::F64(v)) => {
-                self.color_g.set(v);
+            ("corner_radius", SettingValue::F64(v)) => {
+                self.corner_radius.set(v);
                 Ok(())
             }
-            ("color_b", SettingValue::F64(v)) => {
-                self.color_b.set(v);
+            ("padding", SettingValue::F64(v)) => {
+                self.padding.set(v);
                 Ok(())
             }
-            ("color_a", SettingValue::F64(v)) => {
-                self.color_a.set(v);
+
+            ("shadow", SettingValue::String(v)) => {
+                *self.shadow.borrow_mut() = v;
                 Ok(())
             }
-            ("size", SettingValue::F64(v)) => {
-                self.size.set(v);
+
+            ("shadow_offset_x", SettingValue::F64(v)) => {
+                self.shadow_offset_x.set(v);
                 Ok(())
             }
-
-            // ---- junctions -------------------------------------------------------
-            ("line_cap", SettingValue::String(v)) => {
-                *self.line_cap.borrow_mut() = v;
+            ("shadow_offset_y", SettingValue::F64(v)) => {
+                self.shadow_offset_y.set(v);
                 Ok(())
             }
-            ("line_join", SettingValue::String(v)) => {
-                *self.line_join.borrow_mut() = v;
+            ("shadow_blur", SettingValue::F64(v)) => {
+                self.shadow_blur.set(v);
+                Ok(())
+            }
+            ("shadow_r", SettingValue::F64(v)) => {
+                self.shadow_r.set(v);
+                Ok(())
+            }
+            ("shadow_g", SettingValue::F64(v)) => {
+                self.shadow_g.set(v);
+                Ok(())
+            }
+            ("shadow_b", SettingValue::F64(v)) => {
+                self.shadow_b.set(v);
+                Ok(())
+            }
+            ("shadow_a", SettingValue::F64(v)) => {
+                self.shadow_a.set(v);
                 Ok(())
             }
 
             // ---- mismatched type -------------------------------------------------
             (key, _wrong_type) => Err(std::io::Error::new(
                 std::io::ErrorKind::InvalidInput,
-                format!("LineSettings::get_value: unknown key '{}'", key),
+                format!("CalloutSettings::get_value: unknown key '{}'", key),
             )),
         }
     }
 }
 
 #[derive(Default, Clone)]
-pub struct ArrowSettingsRc {
-    color_r: Rc<Cell<f64>>,
-    color_g: Rc<Cell<f64>>,
-    color_b: Rc<Cell<f64>>,
-    color_a: Rc<Cell<f64>>,
-    size: Rc<Cell<f64>>,
-    line_cap: Rc<RefCell<String>>,
-    line_join: Rc<RefCell<String>>,
-    arrow_size: Rc<Cell<f64>>,
+pub struct StampSettingsRc {
+    file: Rc<RefCell<String>>,
+    scale: Rc<Cell<f64>>,
+    rotation: Rc<Cell<f64>>,
+    shadow: Rc<RefCell<String>>,
+    shadow_offset_x: Rc<Cell<f64>>,
+    shadow_offset_y: Rc<Cell<f64>>,
+    shadow_blur: Rc<Cell<f64>>,
+    shadow_r: Rc<Cell<f64>>,
+    shadow_g: Rc<Cell<f64>>,
+    shadow_b: Rc<Cell<f64>>,
+    shadow_a: Rc<Cell<f64>>,
 }
 
-impl ArrowSettingsRc {
+impl StampSettingsRc {
     /// Get a setting by name, returning a `SettingValue`.
     pub fn get_value(&self, setting_name: &str) -> SettingValue {
         match setting_name {
-            "color_r" => SettingValue::F64(self.color_r.get()),
-            "color_g" => SettingValue::F64(self.color_g.get()),
-            "color_b" => SettingValue::F64(self.color_b.get()),
-            "color_a" => SettingValue::F64(self.color_a.get()),
-            "size" => SettingValue::F64(self.size.get()),
-            "line_cap" => SettingValue::String(self.line_cap.borrow().to_string()),
-            "line_join" => SettingValue::String(self.line_join.borrow().to_string()),
-            "arrow_size" => SettingValue::F64(self.arrow_size.get()),
+            "file" => SettingValue::String(self.file.borrow().to_string()),
+            "scale" => SettingValue::F64(self.scale.get()),
+            "rotation" => SettingValue::F64(self.rotation.get()),
+            "shadow" => SettingValue::String(self.shadow.borrow().to_string()),
+            "shadow_offset_x" => SettingValue::F64(self.shadow_offset_x.get()),
+            "shadow_offset_y" => SettingValue::F64(self.shadow_offset_y.get()),
+            "shadow_blur" => SettingValue::F64(self.shadow_blur.get()),
+            "shadow_r" => SettingValue::F64(self.shadow_r.get()),
+            "shadow_g" => SettingValue::F64(self.shadow_g.get()),
+            "shadow_b" => SettingValue::F64(self.shadow_b.get()),
+            "shadow_a" => SettingValue::F64(self.shadow_a.get()),
             other => {
-                eprintln!("LineSettings::get_value: unknown key '{}'", other);
+                eprintln!("StampSettings::get_value: unknown key '{}'", other);
                 // Default – you can change this to whatever makes sense.
                 SettingValue::Bool(false)
             }
@@ -699,69 +3104,102 @@ impl ArrowSettingsRc {
     /// Set a setting by name, returning a `SettingValue`.
     pub fn set_value(&self, setting_name: &str, value: SettingValue) -> std::io::Result<()> {
         match (setting_name, value) {
-            // ---- fill -------------------------------------------------------
-            ("color_r", SettingValue::F64(v)) => {
-                self.color_r.set(v);
+            ("file", SettingValue::String(v)) => {
+                *self.file.borrow_mut() = v;
                 Ok(())
             }
-            ("color_g", SettingValue::F64(v)) => {
-                self.color_g.set(v);
+            ("scale", SettingValue::F64(v)) => {
+                self.scale.set(v);
                 Ok(())
             }
-            ("color_b", SettingValue::F64(v)) => {
-                self.color_b.set(v);
+            ("rotation", SettingValue::F64(v)) => {
+                self.rotation.set(v);
                 Ok(())
             }
-            ("color_a", SettingValue::F64(v)) => {
-                self.color_a.set(v);
+
+            ("shadow", SettingValue::String(v)) => {
+                *self.shadow.borrow_mut() = v;
                 Ok(())
             }
-            ("size", SettingValue::F64(v)) => {
-                self.size.set(v);
+
+            ("shadow_offset_x", SettingValue::F64(v)) => {
+                self.shadow_offset_x.set(v);
                 Ok(())
             }
-
-            // ---- junctions -------------------------------------------------------
-            ("line_cap", SettingValue::String(v)) => {
-                *self.line_cap.borrow_mut() = v;
+            ("shadow_offset_y", SettingValue::F64(v)) => {
+                self.shadow_offset_y.set(v);
                 Ok(())
             }
-            ("line_join", SettingValue::String(v)) => {
-                *self.line_join.borrow_mut() = v;
+            ("shadow_blur", SettingValue::F64(v)) => {
+                self.shadow_blur.set(v);
+                Ok(())
+            }
+            ("shadow_r", SettingValue::F64(v)) => {
+                self.shadow_r.set(v);
+                Ok(())
+            }
+            ("shadow_g", SettingValue::F64(v)) => {
+                self.shadow_g.set(v);
+                Ok(())
+            }
+            ("shadow_b", SettingValue::F64(v)) => {
+                self.shadow_b.set(v);
+                Ok(())
+            }
+            ("shadow_a", SettingValue::F64(v)) => {
+                self.shadow_a.set(v);
                 Ok(())
             }
 
             // ---- mismatched type -------------------------------------------------
             (key, _wrong_type) => Err(std::io::Error::new(
                 std::io::ErrorKind::InvalidInput,
-                format!("LineSettings::get_value: unknown key '{}'", key),
+                format!("StampSettings::get_value: unknown key '{}'", key),
             )),
         }
     }
 }
 
 #[derive(Default, Clone)]
-pub struct FreehandSettingsRc {
+pub struct MeasureSettingsRc {
+    size: Rc<Cell<f64>>,
     color_r: Rc<Cell<f64>>,
     color_g: Rc<Cell<f64>>,
     color_b: Rc<Cell<f64>>,
     color_a: Rc<Cell<f64>>,
-    size: Rc<Cell<f64>>,
-    tool: Rc<RefCell<String>>,
+    font_size: Rc<Cell<f64>>,
+    export: Rc<Cell<bool>>,
+    shadow: Rc<RefCell<String>>,
+    shadow_offset_x: Rc<Cell<f64>>,
+    shadow_offset_y: Rc<Cell<f64>>,
+    shadow_blur: Rc<Cell<f64>>,
+    shadow_r: Rc<Cell<f64>>,
+    shadow_g: Rc<Cell<f64>>,
+    shadow_b: Rc<Cell<f64>>,
+    shadow_a: Rc<Cell<f64>>,
 }
 
-impl FreehandSettingsRc {
+impl MeasureSettingsRc {
     /// Get a setting by name, returning a `SettingValue`.
     pub fn get_value(&self, setting_name: &str) -> SettingValue {
         match setting_name {
+            "size" => SettingValue::F64(self.size.get()),
             "color_r" => SettingValue::F64(self.color_r.get()),
             "color_g" => SettingValue::F64(self.color_g.get()),
             "color_b" => SettingValue::F64(self.color_b.get()),
             "color_a" => SettingValue::F64(self.color_a.get()),
-            "size" => SettingValue::F64(self.size.get()),
-            "tool" => SettingValue::String(self.tool.borrow().to_string()),
+            "font_size" => SettingValue::F64(self.font_size.get()),
+            "export" => SettingValue::Bool(self.export.get()),
+            "shadow" => SettingValue::String(self.shadow.borrow().to_string()),
+            "shadow_offset_x" => SettingValue::F64(self.shadow_offset_x.get()),
+            "shadow_offset_y" => SettingValue::F64(self.shadow_offset_y.get()),
+            "shadow_blur" => SettingValue::F64(self.shadow_blur.get()),
+            "shadow_r" => SettingValue::F64(self.shadow_r.get()),
+            "shadow_g" => SettingValue::F64(self.shadow_g.get()),
+            "shadow_b" => SettingValue::F64(self.shadow_b.get()),
+            "shadow_a" => SettingValue::F64(self.shadow_a.get()),
             other => {
-                eprintln!("FreehandSettings::get_value: unknown key '{}'", other);
+                eprintln!("MeasureSettings::get_value: unknown key '{}'", other);
                 // Default – you can change this to whatever makes sense.
                 SettingValue::Bool(false)
             }
@@ -770,7 +3208,10 @@ impl FreehandSettingsRc {
     /// Set a setting by name, returning a `SettingValue`.
     pub fn set_value(&self, setting_name: &str, value: SettingValue) -> std::io::Result<()> {
         match (setting_name, value) {
-            // ---- fill -------------------------------------------------------
+            ("size", SettingValue::F64(v)) => {
+                self.size.set(v);
+                Ok(())
+            }
             ("color_r", SettingValue::F64(v)) => {
                 self.color_r.set(v);
                 Ok(())
@@ -787,78 +3228,74 @@ impl FreehandSettingsRc {
                 self.color_a.set(v);
                 Ok(())
             }
-            ("size", SettingValue::F64(v)) => {
-                self.size.set(v);
+            ("font_size", SettingValue::F64(v)) => {
+                self.font_size.set(v);
+                Ok(())
+            }
+            ("export", SettingValue::Bool(v)) => {
+                self.export.set(v);
                 Ok(())
             }
 
-            // ---- junctions -------------------------------------------------------
-            ("tool", SettingValue::String(v)) => {
-                *self.tool.borrow_mut() = v;
+            ("shadow", SettingValue::String(v)) => {
+                *self.shadow.borrow_mut() = v;
+                Ok(())
+            }
+
+            ("shadow_offset_x", SettingValue::F64(v)) => {
+                self.shadow_offset_x.set(v);
+                Ok(())
+            }
+            ("shadow_offset_y", SettingValue::F64(v)) => {
+                self.shadow_offset_y.set(v);
+                Ok(())
+            }
+            ("shadow_blur", SettingValue::F64(v)) => {
+                self.shadow_blur.set(v);
+                Ok(())
+            }
+            ("shadow_r", SettingValue::F64(v)) => {
+                self.shadow_r.set(v);
+                Ok(())
+            }
+            ("shadow_g", SettingValue::F64(v)) => {
+                self.shadow_g.set(v);
+                Ok(())
+            }
+            ("shadow_b", SettingValue::F64(v)) => {
+                self.shadow_b.set(v);
+                Ok(())
+            }
+            ("shadow_a", SettingValue::F64(v)) => {
+                self.shadow_a.set(v);
                 Ok(())
             }
 
             // ---- mismatched type -------------------------------------------------
             (key, _wrong_type) => Err(std::io::Error::new(
                 std::io::ErrorKind::InvalidInput,
-                format!("FreehandSettings::get_value: unknown key '{}'", key),
+                format!("MeasureSettings::get_value: unknown key '{}'", key),
             )),
         }
     }
 }
 
 #[derive(Default, Clone)]
-pub struct NumberedCircleSettingsRc {
-    fill: Rc<Cell<bool>>,
-    fill_r: Rc<Cell<f64>>,
-    fill_g: Rc<Cell<f64>>,
-    fill_b: Rc<Cell<f64>>,
-    fill_a: Rc<Cell<f64>>,
-    border: Rc<Cell<bool>>,
-    border_size: Rc<Cell<f64>>,
-    border_r: Rc<Cell<f64>>,
-    border_g: Rc<Cell<f64>>,
-    border_b: Rc<Cell<f64>>,
-    border_a: Rc<Cell<f64>>,
-    number: Rc<Cell<i32>>,
-    font_size: Rc<Cell<f64>>,
-    font_face: Rc<RefCell<String>>,
-    font_weight: Rc<RefCell<String>>,
-    font_slant: Rc<RefCell<String>>,
-    font_r: Rc<Cell<f64>>,
-    font_g: Rc<Cell<f64>>,
-    font_b: Rc<Cell<f64>>,
-    font_a: Rc<Cell<f64>>,
-    radius: Rc<Cell<f64>>,
+pub struct EyedropperSettingsRc {
+    format: Rc<RefCell<String>>,
+    action: Rc<RefCell<String>>,
+    target: Rc<RefCell<String>>,
 }
 
-impl NumberedCircleSettingsRc {
+impl EyedropperSettingsRc {
     /// Get a setting by name, returning a `SettingValue`.
     pub fn get_value(&self, setting_name: &str) -> SettingValue {
         match setting_name {
-            "fill" => SettingValue::Bool(self.fill.get()),
-            "fill_r" => SettingValue::F64(self.fill_r.get()),
-            "fill_g" => SettingValue::F64(self.fill_g.get()),
-            "fill_b" => SettingValue::F64(self.fill_b.get()),
-            "fill_a" => SettingValue::F64(self.fill_a.get()),
-            "border" => SettingValue::Bool(self.border.get()),
-            "border_size" => SettingValue::F64(self.border_size.get()),
-            "border_r" => SettingValue::F64(self.border_r.get()),
-            "border_g" => SettingValue::F64(self.border_g.get()),
-            "border_b" => SettingValue::F64(self.border_b.get()),
-            "border_a" => SettingValue::F64(self.border_a.get()),
-            "number" => SettingValue::I32(self.number.get()),
-            "font_size" => SettingValue::F64(self.font_size.get()),
-            "font_face" => SettingValue::String(self.font_face.borrow().clone()),
-            "font_weight" => SettingValue::String(self.font_weight.borrow().clone()),
-            "font_slant" => SettingValue::String(self.font_slant.borrow().clone()),
-            "font_r" => SettingValue::F64(self.font_r.get()),
-            "font_g" => SettingValue::F64(self.font_g.get()),
-            "font_b" => SettingValue::F64(self.font_b.get()),
-            "font_a" => SettingValue::F64(self.font_a.get()),
-            "radius" => SettingValue::F64(self.radius.get()),
+            "format" => SettingValue::String(self.format.borrow().to_string()),
+            "action" => SettingValue::String(self.action.borrow().to_string()),
+            "target" => SettingValue::String(self.target.borrow().to_string()),
             other => {
-                eprintln!("NumberedCircleSettings::get_value: unknown key '{}'", other);
+                eprintln!("EyedropperSettings::get_value: unknown key '{}'", other);
                 // Default – you can change this to whatever makes sense.
                 SettingValue::Bool(false)
             }
@@ -867,159 +3304,156 @@ impl NumberedCircleSettingsRc {
     /// Set a setting by name, returning a `SettingValue`.
     pub fn set_value(&self, setting_name: &str, value: SettingValue) -> std::io::Result<()> {
         match (setting_name, value) {
-            // ---- fill -------------------------------------------------------
-            ("fill", SettingValue::Bool(v)) => {
-                self.fill.set(v);
-                Ok(())
-            }
-            ("fill_r", SettingValue::F64(v)) => {
-                self.fill_r.set(v);
+            ("format", SettingValue::String(v)) => {
+                *self.format.borrow_mut() = v;
                 Ok(())
             }
-            ("fill_g", SettingValue::F64(v)) => {
-                self.fill_g.set(v);
-                Ok(())
-            }
-            ("fill_b", SettingValue::F64(v)) => {
-                self.fill_b.set(v);
+            ("action", SettingValue::String(v)) => {
+                *self.action.borrow_mut() = v;
                 Ok(())
             }
-            ("fill_a", SettingValue::F64(v)) => {
-                self.fill_a.set(v);
+            ("target", SettingValue::String(v)) => {
+                *self.target.borrow_mut() = v;
                 Ok(())
             }
 
-            // ---- border -----------------------------------------------------
-            ("border", SettingValue::Bool(v)) => {
-                self.border.set(v);
-                Ok(())
-            }
-            ("border_size", SettingValue::F64(v)) => {
-                self.border_size.set(v);
-                Ok(())
+            // ---- mismatched type -------------------------------------------------
+            (key, _wrong_type) => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("EyedropperSettings::get_value: unknown key '{}'", key),
+            )),
+        }
+    }
+}
+
+#[derive(Default, Clone)]
+pub struct HighlightSettingsRc {
+    color_r: Rc<Cell<f64>>,
+    color_g: Rc<Cell<f64>>,
+    color_b: Rc<Cell<f64>>,
+    corner_radius: Rc<Cell<f64>>,
+    shadow: Rc<RefCell<String>>,
+    shadow_offset_x: Rc<Cell<f64>>,
+    shadow_offset_y: Rc<Cell<f64>>,
+    shadow_blur: Rc<Cell<f64>>,
+    shadow_r: Rc<Cell<f64>>,
+    shadow_g: Rc<Cell<f64>>,
+    shadow_b: Rc<Cell<f64>>,
+    shadow_a: Rc<Cell<f64>>,
+}
+
+impl HighlightSettingsRc {
+    /// Get a setting by name, returning a `SettingValue`.
+    pub fn get_value(&self, setting_name: &str) -> SettingValue {
+        match setting_name {
+            "color_r" => SettingValue::F64(self.color_r.get()),
+            "color_g" => SettingValue::F64(self.color_g.get()),
+            "color_b" => SettingValue::F64(self.color_b.get()),
+            "corner_radius" => SettingValue::F64(self.corner_radius.get()),
+            "shadow" => SettingValue::String(self.shadow.borrow().to_string()),
+            "shadow_offset_x" => SettingValue::F64(self.shadow_offset_x.get()),
+            "shadow_offset_y" => SettingValue::F64(self.shadow_offset_y.get()),
+            "shadow_blur" => SettingValue::F64(self.shadow_blur.get()),
+            "shadow_r" => SettingValue::F64(self.shadow_r.get()),
+            "shadow_g" => SettingValue::F64(self.shadow_g.get()),
+            "shadow_b" => SettingValue::F64(self.shadow_b.get()),
+            "shadow_a" => SettingValue::F64(self.shadow_a.get()),
+            other => {
+                eprintln!("HighlightSettings::get_value: unknown key '{}'", other);
+                // Default – you can change this to whatever makes sense.
+                SettingValue::Bool(false)
             }
-            ("border_r", SettingValue::F64(v)) => {
-                self.border_r.set(v);
+        }
+    }
+    /// Set a setting by name, returning a `SettingValue`.
+    pub fn set_value(&self, setting_name: &str, value: SettingValue) -> std::io::Result<()> {
+        match (setting_name, value) {
+            ("color_r", SettingValue::F64(v)) => {
+                self.color_r.set(v);
                 Ok(())
             }
-            ("border_g", SettingValue::F64(v)) => {
-                self.border_g.set(v);
+            ("color_g", SettingValue::F64(v)) => {
+                self.color_g.set(v);
                 Ok(())
             }
-            ("border_b", SettingValue::F64(v)) => {
-                self.border_b.set(v);
+            ("color_b", SettingValue::F64(v)) => {
+                self.color_b.set(v);
                 Ok(())
             }
-            ("border_a", SettingValue::F64(v)) => {
-                self.border_a.set(v);
+            ("corner_radius", SettingValue::F64(v)) => {
+                self.corner_radius.set(v);
                 Ok(())
             }
 
-            // ---- font -----------------------------------------------------
-            ("font_size", SettingValue::F64(v)) => {
-                self.font_size.set(v);
-                Ok(())
-            }
-            ("font_face", SettingValue::String(v)) => {
-                *self.font_face.borrow_mut() = v;
-                Ok(())
-            }
-            ("font_weight", SettingValue::String(v)) => {
-                *self.font_weight.borrow_mut() = v;
+            ("shadow", SettingValue::String(v)) => {
+                *self.shadow.borrow_mut() = v;
                 Ok(())
             }
-            ("font_slant", SettingValue::String(v)) => {
-                *self.font_slant.borrow_mut() = v;
+
+            ("shadow_offset_x", SettingValue::F64(v)) => {
+                self.shadow_offset_x.set(v);
                 Ok(())
             }
-            ("font_r", SettingValue::F64(v)) => {
-                self.font_r.set(v);
+            ("shadow_offset_y", SettingValue::F64(v)) => {
+                self.shadow_offset_y.set(v);
                 Ok(())
             }
-            ("font_g", SettingValue::F64(v)) => {
-                self.font_g.set(v);
+            ("shadow_blur", SettingValue::F64(v)) => {
+                self.shadow_blur.set(v);
                 Ok(())
             }
-            ("font_b", SettingValue::F64(v)) => {
-                self.font_b.set(v);
+            ("shadow_r", SettingValue::F64(v)) => {
+                self.shadow_r.set(v);
                 Ok(())
             }
-            ("font_a", SettingValue::F64(v)) => {
-                self.font_a.set(v);
+            ("shadow_g", SettingValue::F64(v)) => {
+                self.shadow_g.set(v);
                 Ok(())
             }
-
-            // ---- number -----------------------------------------------------
-            ("number", SettingValue::I32(v)) => {
-                self.number.set(v);
+            ("shadow_b", SettingValue::F64(v)) => {
+                self.shadow_b.set(v);
                 Ok(())
             }
-
-            // ---- radius -----------------------------------------------------
-            ("radius", SettingValue::F64(v)) => {
-                self.radius.set(v);
+            ("shadow_a", SettingValue::F64(v)) => {
+                self.shadow_a.set(v);
                 Ok(())
             }
 
             // ---- mismatched type -------------------------------------------------
             (key, _wrong_type) => Err(std::io::Error::new(
                 std::io::ErrorKind::InvalidInput,
-                format!("NumberedCircleSettings::get_value: unknown key '{}'", key),
+                format!("HighlightSettings::get_value: unknown key '{}'", key),
             )),
         }
     }
 }
 
 #[derive(Default, Clone)]
-pub struct InputTextSettingsRc {
-    fill: Rc<Cell<bool>>,
-    fill_r: Rc<Cell<f64>>,
-    fill_g: Rc<Cell<f64>>,
-    fill_b: Rc<Cell<f64>>,
-    fill_a: Rc<Cell<f64>>,
-    border: Rc<Cell<bool>>,
-    border_size: Rc<Cell<f64>>,
-    border_r: Rc<Cell<f64>>,
-    border_g: Rc<Cell<f64>>,
-    border_b: Rc<Cell<f64>>,
-    border_a: Rc<Cell<f64>>,
-    text: Rc<RefCell<String>>,
-    font_size: Rc<Cell<f64>>,
-    font_face: Rc<RefCell<String>>,
-    font_weight: Rc<RefCell<String>>,
-    font_slant: Rc<RefCell<String>>,
-    font_r: Rc<Cell<f64>>,
-    font_g: Rc<Cell<f64>>,
-    font_b: Rc<Cell<f64>>,
-    font_a: Rc<Cell<f64>>,
+pub struct ShadowSettingsRc {
+    enabled: Rc<Cell<bool>>,
+    offset_x: Rc<Cell<f64>>,
+    offset_y: Rc<Cell<f64>>,
+    blur: Rc<Cell<f64>>,
+    color_r: Rc<Cell<f64>>,
+    color_g: Rc<Cell<f64>>,
+    color_b: Rc<Cell<f64>>,
+    color_a: Rc<Cell<f64>>,
 }
 
-impl InputTextSettingsRc {
+impl ShadowSettingsRc {
     /// Get a setting by name, returning a `SettingValue`.
     pub fn get_value(&self, setting_name: &str) -> SettingValue {
         match setting_name {
-            "fill" => SettingValue::Bool(self.fill.get()),
-            "fill_r" => SettingValue::F64(self.fill_r.get()),
-            "fill_g" => SettingValue::F64(self.fill_g.get()),
-            "fill_b" => SettingValue::F64(self.fill_b.get()),
-            "fill_a" => SettingValue::F64(self.fill_a.get()),
-            "border" => SettingValue::Bool(self.border.get()),
-            "border_size" => SettingValue::F64(self.border_size.get()),
-            "border_r" => SettingValue::F64(self.border_r.get()),
-            "border_g" => SettingValue::F64(self.border_g.get()),
-            "border_b" => SettingValue::F64(self.border_b.get()),
-            "border_a" => SettingValue::F64(self.border_a.get()),
-            "text" => SettingValue::String(self.text.borrow().clone()),
-            "font_size" => SettingValue::F64(self.font_size.get()),
-            "font_face" => SettingValue::String(self.font_face.borrow().clone()),
-            "font_weight" => SettingValue::String(self.font_weight.borrow().clone()),
-            "font_slant" => SettingValue::String(self.font_slant.borrow().clone()),
-            "font_r" => SettingValue::F64(self.font_r.get()),
-            "font_g" => SettingValue::F64(self.font_g.get()),
-            "font_b" => SettingValue::F64(self.font_b.get()),
-            "font_a" => SettingValue::F64(self.font_a.get()),
+            "enabled" => SettingValue::Bool(self.enabled.get()),
+            "offset_x" => SettingValue::F64(self.offset_x.get()),
+            "offset_y" => SettingValue::F64(self.offset_y.get()),
+            "blur" => SettingValue::F64(self.blur.get()),
+            "color_r" => SettingValue::F64(self.color_r.get()),
+            "color_g" => SettingValue::F64(self.color_g.get()),
+            "color_b" => SettingValue::F64(self.color_b.get()),
+            "color_a" => SettingValue::F64(self.color_a.get()),
             other => {
-                eprintln!("NumberedCircleSettings::get_value: unknown key '{}'", other);
+                eprintln!("ShadowSettings::get_value: unknown key '{}'", other);
                 // Default – you can change this to whatever makes sense.
                 SettingValue::Bool(false)
             }
@@ -1028,98 +3462,120 @@ impl InputTextSettingsRc {
     /// Set a setting by name, returning a `SettingValue`.
     pub fn set_value(&self, setting_name: &str, value: SettingValue) -> std::io::Result<()> {
         match (setting_name, value) {
-            // ---- fill -------------------------------------------------------
-            ("fill", SettingValue::Bool(v)) => {
-                self.fill.set(v);
-                Ok(())
-            }
-            ("fill_r", SettingValue::F64(v)) => {
-                self.fill_r.set(v);
-                Ok(())
-            }
-            ("fill_g", SettingValue::F64(v)) => {
-                self.fill_g.set(v);
-                Ok(())
-            }
-            ("fill_b", SettingValue::F64(v)) => {
-                self.fill_b.set(v);
+            ("enabled", SettingValue::Bool(v)) => {
+                self.enabled.set(v);
                 Ok(())
             }
-            ("fill_a", SettingValue::F64(v)) => {
-                self.fill_a.set(v);
+            ("offset_x", SettingValue::F64(v)) => {
+                self.offset_x.set(v);
                 Ok(())
             }
-
-            // ---- border -----------------------------------------------------
-            ("border", SettingValue::Bool(v)) => {
-                self.border.set(v);
+            ("offset_y", SettingValue::F64(v)) => {
+                self.offset_y.set(v);
                 Ok(())
             }
-            ("border_size", SettingValue::F64(v)) => {
-                self.border_size.set(v);
+            ("blur", SettingValue::F64(v)) => {
+                self.blur.set(v);
                 Ok(())
             }
-            ("border_r", SettingValue::F64(v)) => {
-                self.border_r.set(v);
+            ("color_r", SettingValue::F64(v)) => {
+                self.color_r.set(v);
                 Ok(())
             }
-            ("border_g", SettingValue::F64(v)) => {
-                self.border_g.set(v);
+            ("color_g", SettingValue::F64(v)) => {
+                self.color_g.set(v);
                 Ok(())
             }
-            ("border_b", SettingValue::F64(v)) => {
-                self.border_b.set(v);
+            ("color_b", SettingValue::F64(v)) => {
+                self.color_b.set(v);
                 Ok(())
             }
-            ("border_a", SettingValue::F64(v)) => {
-                self.border_a.set(v);
+            ("color_a", SettingValue::F64(v)) => {
+                self.color_a.set(v);
                 Ok(())
             }
 
-            // ---- text -----------------------------------------------------
-            ("text", SettingValue::String(v)) => {
-                *self.text.borrow_mut() = v;
-                Ok(())
-            }
+            // ---- mismatched type -------------------------------------------------
+            (key, _wrong_type) => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("ShadowSettings::get_value: unknown key '{}'", key),
+            )),
+        }
+    }
+}
 
-            // ---- font -----------------------------------------------------
-            ("font_size", SettingValue::F64(v)) => {
-                self.font_size.set(v);
+#[derive(Default, Clone)]
+pub struct ImageSettingsRc {
+    shadow: Rc<RefCell<String>>,
+    shadow_offset_x: Rc<Cell<f64>>,
+    shadow_offset_y: Rc<Cell<f64>>,
+    shadow_blur: Rc<Cell<f64>>,
+    shadow_r: Rc<Cell<f64>>,
+    shadow_g: Rc<Cell<f64>>,
+    shadow_b: Rc<Cell<f64>>,
+    shadow_a: Rc<Cell<f64>>,
+}
+
+impl ImageSettingsRc {
+    /// Get a setting by name, returning a `SettingValue`.
+    pub fn get_value(&self, setting_name: &str) -> SettingValue {
+        match setting_name {
+            "shadow" => SettingValue::String(self.shadow.borrow().to_string()),
+            "shadow_offset_x" => SettingValue::F64(self.shadow_offset_x.get()),
+            "shadow_offset_y" => SettingValue::F64(self.shadow_offset_y.get()),
+            "shadow_blur" => SettingValue::F64(self.shadow_blur.get()),
+            "shadow_r" => SettingValue::F64(self.shadow_r.get()),
+            "shadow_g" => SettingValue::F64(self.shadow_g.get()),
+            "shadow_b" => SettingValue::F64(self.shadow_b.get()),
+            "shadow_a" => SettingValue::F64(self.shadow_a.get()),
+            other => {
+                eprintln!("ImageSettings::get_value: unknown key '{}'", other);
+                // Default – you can change this to whatever makes sense.
+                SettingValue::Bool(false)
+            }
+        }
+    }
+    /// Set a setting by name, returning a `SettingValue`.
+    pub fn set_value(&self, setting_name: &str, value: SettingValue) -> std::io::Result<()> {
+        match (setting_name, value) {
+            ("shadow", SettingValue::String(v)) => {
+                *self.shadow.borrow_mut() = v;
                 Ok(())
             }
-            ("font_face", SettingValue::String(v)) => {
-                *self.font_face.borrow_mut() = v;
+
+            ("shadow_offset_x", SettingValue::F64(v)) => {
+                self.shadow_offset_x.set(v);
                 Ok(())
             }
-            ("font_weight", SettingValue::String(v)) => {
-                *self.font_weight.borrow_mut() = v;
+            ("shadow_offset_y", SettingValue::F64(v)) => {
+                self.shadow_offset_y.set(v);
                 Ok(())
             }
-            ("font_slant", SettingValue::String(v)) => {
-                *self.font_slant.borrow_mut() = v;
+            ("shadow_blur", SettingValue::F64(v)) => {
+                self.shadow_blur.set(v);
                 Ok(())
             }
-            ("font_r", SettingValue::F64(v)) => {
-                self.font_r.set(v);
+            ("shadow_r", SettingValue::F64(v)) => {
+                self.shadow_r.set(v);
                 Ok(())
             }
-            ("font_g", SettingValue::F64(v)) => {
-                self.font_g.set(v);
+            ("shadow_g", SettingValue::F64(v)) => {
+                self.shadow_g.set(v);
                 Ok(())
             }
-            ("font_b", SettingValue::F64(v)) => {
-                self.font_b.set(v);
+            ("shadow_b", SettingValue::F64(v)) => {
+                self.shadow_b.set(v);
                 Ok(())
             }
-            ("font_a", SettingValue::F64(v)) => {
-                self.font_a.set(v);
+            ("shadow_a", SettingValue::F64(v)) => {
+                self.shadow_a.set(v);
                 Ok(())
             }
 
             // ---- mismatched type -------------------------------------------------
             (key, _wrong_type) => Err(std::io::Error::new(
                 std::io::ErrorKind::InvalidInput,
-                format!("NumberedCircleSettings::get_value: unknown key '{}'", key),
+                format!("ImageSettings::get_value: unknown key '{}'", key),
             )),
         }
     }
@@ -1142,6 +3598,21 @@ impl SettingsRc {
             border_g: Rc::new(Cell::new(raw.arc.border_g)),
             border_b: Rc::new(Cell::new(raw.arc.border_b)),
             border_a: Rc::new(Cell::new(raw.arc.border_a)),
+            shape: Rc::new(RefCell::new(raw.arc.shape)),
+            style: Rc::new(RefCell::new(raw.arc.style)),
+            start_angle: Rc::new(Cell::new(raw.arc.start_angle)),
+            end_angle: Rc::new(Cell::new(raw.arc.end_angle)),
+            fill_mode: Rc::new(RefCell::new(raw.arc.fill_mode)),
+            fill_stops: Rc::new(RefCell::new(raw.arc.fill_stops)),
+            fill_angle: Rc::new(Cell::new(raw.arc.fill_angle)),
+            shadow: Rc::new(RefCell::new(raw.arc.shadow)),
+            shadow_offset_x: Rc::new(Cell::new(raw.arc.shadow_offset_x)),
+            shadow_offset_y: Rc::new(Cell::new(raw.arc.shadow_offset_y)),
+            shadow_blur: Rc::new(Cell::new(raw.arc.shadow_blur)),
+            shadow_r: Rc::new(Cell::new(raw.arc.shadow_r)),
+            shadow_g: Rc::new(Cell::new(raw.arc.shadow_g)),
+            shadow_b: Rc::new(Cell::new(raw.arc.shadow_b)),
+            shadow_a: Rc::new(Cell::new(raw.arc.shadow_a)),
         };
 
         let rect_rc = RectSettingsRc {
@@ -1156,6 +3627,20 @@ impl SettingsRc {
             border_g: Rc::new(Cell::new(raw.rect.border_g)),
             border_b: Rc::new(Cell::new(raw.rect.border_b)),
             border_a: Rc::new(Cell::new(raw.rect.border_a)),
+            corner_radius: Rc::new(Cell::new(raw.rect.corner_radius)),
+            dash: Rc::new(RefCell::new(raw.rect.dash)),
+            style: Rc::new(RefCell::new(raw.rect.style)),
+            fill_mode: Rc::new(RefCell::new(raw.rect.fill_mode)),
+            fill_stops: Rc::new(RefCell::new(raw.rect.fill_stops)),
+            fill_angle: Rc::new(Cell::new(raw.rect.fill_angle)),
+            shadow: Rc::new(RefCell::new(raw.rect.shadow)),
+            shadow_offset_x: Rc::new(Cell::new(raw.rect.shadow_offset_x)),
+            shadow_offset_y: Rc::new(Cell::new(raw.rect.shadow_offset_y)),
+            shadow_blur: Rc::new(Cell::new(raw.rect.shadow_blur)),
+            shadow_r: Rc::new(Cell::new(raw.rect.shadow_r)),
+            shadow_g: Rc::new(Cell::new(raw.rect.shadow_g)),
+            shadow_b: Rc::new(Cell::new(raw.rect.shadow_b)),
+            shadow_a: Rc::new(Cell::new(raw.rect.shadow_a)),
         };
 
         let line_rc = LineSettingsRc {
@@ -1166,6 +3651,16 @@ impl SettingsRc {
             size: Rc::new(Cell::new(raw.line.size)),
             line_cap: Rc::new(RefCell::new(raw.line.line_cap)),
             line_join: Rc::new(RefCell::new(raw.line.line_join)),
+            dash: Rc::new(RefCell::new(raw.line.dash)),
+            dash_pattern: Rc::new(RefCell::new(raw.line.dash_pattern)),
+            shadow: Rc::new(RefCell::new(raw.line.shadow)),
+            shadow_offset_x: Rc::new(Cell::new(raw.line.shadow_offset_x)),
+            shadow_offset_y: Rc::new(Cell::new(raw.line.shadow_offset_y)),
+            shadow_blur: Rc::new(Cell::new(raw.line.shadow_blur)),
+            shadow_r: Rc::new(Cell::new(raw.line.shadow_r)),
+            shadow_g: Rc::new(Cell::new(raw.line.shadow_g)),
+            shadow_b: Rc::new(Cell::new(raw.line.shadow_b)),
+            shadow_a: Rc::new(Cell::new(raw.line.shadow_a)),
         };
 
         let arrow_rc = ArrowSettingsRc {
@@ -1177,6 +3672,25 @@ impl SettingsRc {
             line_cap: Rc::new(RefCell::new(raw.arrow.line_cap)),
             line_join: Rc::new(RefCell::new(raw.arrow.line_join)),
             arrow_size: Rc::new(Cell::new(raw.arrow.arrow_size)),
+            curved: Rc::new(Cell::new(raw.arrow.curved)),
+            double_headed: Rc::new(Cell::new(raw.arrow.double_headed)),
+            head_style: Rc::new(RefCell::new(raw.arrow.head_style)),
+            outline: Rc::new(Cell::new(raw.arrow.outline)),
+            outline_size: Rc::new(Cell::new(raw.arrow.outline_size)),
+            outline_r: Rc::new(Cell::new(raw.arrow.outline_r)),
+            outline_g: Rc::new(Cell::new(raw.arrow.outline_g)),
+            outline_b: Rc::new(Cell::new(raw.arrow.outline_b)),
+            outline_a: Rc::new(Cell::new(raw.arrow.outline_a)),
+            dash: Rc::new(RefCell::new(raw.arrow.dash)),
+            dash_pattern: Rc::new(RefCell::new(raw.arrow.dash_pattern)),
+            shadow: Rc::new(RefCell::new(raw.arrow.shadow)),
+            shadow_offset_x: Rc::new(Cell::new(raw.arrow.shadow_offset_x)),
+            shadow_offset_y: Rc::new(Cell::new(raw.arrow.shadow_offset_y)),
+            shadow_blur: Rc::new(Cell::new(raw.arrow.shadow_blur)),
+            shadow_r: Rc::new(Cell::new(raw.arrow.shadow_r)),
+            shadow_g: Rc::new(Cell::new(raw.arrow.shadow_g)),
+            shadow_b: Rc::new(Cell::new(raw.arrow.shadow_b)),
+            shadow_a: Rc::new(Cell::new(raw.arrow.shadow_a)),
         };
 
         let freehand_rc = FreehandSettingsRc {
@@ -1186,6 +3700,15 @@ impl SettingsRc {
             color_a: Rc::new(Cell::new(raw.freehand.color_a)),
             size: Rc::new(Cell::new(raw.freehand.size)),
             tool: Rc::new(RefCell::new(raw.freehand.tool)),
+            shadow: Rc::new(RefCell::new(raw.freehand.shadow)),
+            shadow_offset_x: Rc::new(Cell::new(raw.freehand.shadow_offset_x)),
+            shadow_offset_y: Rc::new(Cell::new(raw.freehand.shadow_offset_y)),
+            shadow_blur: Rc::new(Cell::new(raw.freehand.shadow_blur)),
+            shadow_r: Rc::new(Cell::new(raw.freehand.shadow_r)),
+            shadow_g: Rc::new(Cell::new(raw.freehand.shadow_g)),
+            shadow_b: Rc::new(Cell::new(raw.freehand.shadow_b)),
+            shadow_a: Rc::new(Cell::new(raw.freehand.shadow_a)),
+            eraser_size: Rc::new(Cell::new(raw.freehand.eraser_size)),
         };
 
         let numbered_circle_rc = NumberedCircleSettingsRc {
@@ -1210,6 +3733,23 @@ impl SettingsRc {
             font_b: Rc::new(Cell::new(raw.numbered_circle.font_b)),
             font_a: Rc::new(Cell::new(raw.numbered_circle.font_a)),
             radius: Rc::new(Cell::new(raw.numbered_circle.radius)),
+            style: Rc::new(RefCell::new(raw.numbered_circle.style)),
+            prefix: Rc::new(RefCell::new(raw.numbered_circle.prefix)),
+            shape: Rc::new(RefCell::new(raw.numbered_circle.shape)),
+            sequence: Rc::new(Cell::new(raw.numbered_circle.sequence)),
+            sequence_start: Rc::new(Cell::new(raw.numbered_circle.sequence_start)),
+            renumber: Rc::new(Cell::new(raw.numbered_circle.renumber)),
+            fill_mode: Rc::new(RefCell::new(raw.numbered_circle.fill_mode)),
+            fill_stops: Rc::new(RefCell::new(raw.numbered_circle.fill_stops)),
+            fill_angle: Rc::new(Cell::new(raw.numbered_circle.fill_angle)),
+            shadow: Rc::new(RefCell::new(raw.numbered_circle.shadow)),
+            shadow_offset_x: Rc::new(Cell::new(raw.numbered_circle.shadow_offset_x)),
+            shadow_offset_y: Rc::new(Cell::new(raw.numbered_circle.shadow_offset_y)),
+            shadow_blur: Rc::new(Cell::new(raw.numbered_circle.shadow_blur)),
+            shadow_r: Rc::new(Cell::new(raw.numbered_circle.shadow_r)),
+            shadow_g: Rc::new(Cell::new(raw.numbered_circle.shadow_g)),
+            shadow_b: Rc::new(Cell::new(raw.numbered_circle.shadow_b)),
+            shadow_a: Rc::new(Cell::new(raw.numbered_circle.shadow_a)),
         };
 
         let input_text_rc = InputTextSettingsRc {
@@ -1233,6 +3773,189 @@ impl SettingsRc {
             font_g: Rc::new(Cell::new(raw.input_text.font_g)),
             font_b: Rc::new(Cell::new(raw.input_text.font_b)),
             font_a: Rc::new(Cell::new(raw.input_text.font_a)),
+            fill_mode: Rc::new(RefCell::new(raw.input_text.fill_mode)),
+            fill_stops: Rc::new(RefCell::new(raw.input_text.fill_stops)),
+            fill_angle: Rc::new(Cell::new(raw.input_text.fill_angle)),
+            shadow: Rc::new(RefCell::new(raw.input_text.shadow)),
+            shadow_offset_x: Rc::new(Cell::new(raw.input_text.shadow_offset_x)),
+            shadow_offset_y: Rc::new(Cell::new(raw.input_text.shadow_offset_y)),
+            shadow_blur: Rc::new(Cell::new(raw.input_text.shadow_blur)),
+            shadow_r: Rc::new(Cell::new(raw.input_text.shadow_r)),
+            shadow_g: Rc::new(Cell::new(raw.input_text.shadow_g)),
+            shadow_b: Rc::new(Cell::new(raw.input_text.shadow_b)),
+            shadow_a: Rc::new(Cell::new(raw.input_text.shadow_a)),
+        };
+
+        let obfuscate_rc = ObfuscateSettingsRc {
+            tool: Rc::new(RefCell::new(raw.obfuscate.tool)),
+            pixel_size: Rc::new(Cell::new(raw.obfuscate.pixel_size)),
+            blur_radius: Rc::new(Cell::new(raw.obfuscate.blur_radius)),
+            shadow: Rc::new(RefCell::new(raw.obfuscate.shadow)),
+            shadow_offset_x: Rc::new(Cell::new(raw.obfuscate.shadow_offset_x)),
+            shadow_offset_y: Rc::new(Cell::new(raw.obfuscate.shadow_offset_y)),
+            shadow_blur: Rc::new(Cell::new(raw.obfuscate.shadow_blur)),
+            shadow_r: Rc::new(Cell::new(raw.obfuscate.shadow_r)),
+            shadow_g: Rc::new(Cell::new(raw.obfuscate.shadow_g)),
+            shadow_b: Rc::new(Cell::new(raw.obfuscate.shadow_b)),
+            shadow_a: Rc::new(Cell::new(raw.obfuscate.shadow_a)),
+        };
+
+        let redact_rc = RedactSettingsRc {
+            color_r: Rc::new(Cell::new(raw.redact.color_r)),
+            color_g: Rc::new(Cell::new(raw.redact.color_g)),
+            color_b: Rc::new(Cell::new(raw.redact.color_b)),
+            shadow: Rc::new(RefCell::new(raw.redact.shadow)),
+            shadow_offset_x: Rc::new(Cell::new(raw.redact.shadow_offset_x)),
+            shadow_offset_y: Rc::new(Cell::new(raw.redact.shadow_offset_y)),
+            shadow_blur: Rc::new(Cell::new(raw.redact.shadow_blur)),
+            shadow_r: Rc::new(Cell::new(raw.redact.shadow_r)),
+            shadow_g: Rc::new(Cell::new(raw.redact.shadow_g)),
+            shadow_b: Rc::new(Cell::new(raw.redact.shadow_b)),
+            shadow_a: Rc::new(Cell::new(raw.redact.shadow_a)),
+        };
+
+        let spotlight_rc = SpotlightSettingsRc {
+            shape: Rc::new(RefCell::new(raw.spotlight.shape)),
+            dim: Rc::new(Cell::new(raw.spotlight.dim)),
+            shadow: Rc::new(RefCell::new(raw.spotlight.shadow)),
+            shadow_offset_x: Rc::new(Cell::new(raw.spotlight.shadow_offset_x)),
+            shadow_offset_y: Rc::new(Cell::new(raw.spotlight.shadow_offset_y)),
+            shadow_blur: Rc::new(Cell::new(raw.spotlight.shadow_blur)),
+            shadow_r: Rc::new(Cell::new(raw.spotlight.shadow_r)),
+            shadow_g: Rc::new(Cell::new(raw.spotlight.shadow_g)),
+            shadow_b: Rc::new(Cell::new(raw.spotlight.shadow_b)),
+            shadow_a: Rc::new(Cell::new(raw.spotlight.shadow_a)),
+        };
+
+        let magnifier_rc = MagnifierSettingsRc {
+            shape: Rc::new(RefCell::new(raw.magnifier.shape)),
+            zoom: Rc::new(Cell::new(raw.magnifier.zoom)),
+            border_size: Rc::new(Cell::new(raw.magnifier.border_size)),
+            border_r: Rc::new(Cell::new(raw.magnifier.border_r)),
+            border_g: Rc::new(Cell::new(raw.magnifier.border_g)),
+            border_b: Rc::new(Cell::new(raw.magnifier.border_b)),
+            border_a: Rc::new(Cell::new(raw.magnifier.border_a)),
+            connector: Rc::new(Cell::new(raw.magnifier.connector)),
+            shadow: Rc::new(RefCell::new(raw.magnifier.shadow)),
+            shadow_offset_x: Rc::new(Cell::new(raw.magnifier.shadow_offset_x)),
+            shadow_offset_y: Rc::new(Cell::new(raw.magnifier.shadow_offset_y)),
+            shadow_blur: Rc::new(Cell::new(raw.magnifier.shadow_blur)),
+            shadow_r: Rc::new(Cell::new(raw.magnifier.shadow_r)),
+            shadow_g: Rc::new(Cell::new(raw.magnifier.shadow_g)),
+            shadow_b: Rc::new(Cell::new(raw.magnifier.shadow_b)),
+            shadow_a: Rc::new(Cell::new(raw.magnifier.shadow_a)),
+        };
+
+        let polygon_rc = PolygonSettingsRc {
+            closed: Rc::new(Cell::new(raw.polygon.closed)),
+            fill: Rc::new(Cell::new(raw.polygon.fill)),
+            fill_r: Rc::new(Cell::new(raw.polygon.fill_r)),
+            fill_g: Rc::new(Cell::new(raw.polygon.fill_g)),
+            fill_b: Rc::new(Cell::new(raw.polygon.fill_b)),
+            fill_a: Rc::new(Cell::new(raw.polygon.fill_a)),
+            border: Rc::new(Cell::new(raw.polygon.border)),
+            border_size: Rc::new(Cell::new(raw.polygon.border_size)),
+            border_r: Rc::new(Cell::new(raw.polygon.border_r)),
+            border_g: Rc::new(Cell::new(raw.polygon.border_g)),
+            border_b: Rc::new(Cell::new(raw.polygon.border_b)),
+            border_a: Rc::new(Cell::new(raw.polygon.border_a)),
+            shadow: Rc::new(RefCell::new(raw.polygon.shadow)),
+            shadow_offset_x: Rc::new(Cell::new(raw.polygon.shadow_offset_x)),
+            shadow_offset_y: Rc::new(Cell::new(raw.polygon.shadow_offset_y)),
+            shadow_blur: Rc::new(Cell::new(raw.polygon.shadow_blur)),
+            shadow_r: Rc::new(Cell::new(raw.polygon.shadow_r)),
+            shadow_g: Rc::new(Cell::new(raw.polygon.shadow_g)),
+            shadow_b: Rc::new(Cell::new(raw.polygon.shadow_b)),
+            shadow_a: Rc::new(Cell::new(raw.polygon.shadow_a)),
+        };
+
+        let callout_rc = CalloutSettingsRc {
+            wrap_width: Rc::new(Cell::new(raw.callout.wrap_width)),
+            corner_radius: Rc::new(Cell::new(raw.callout.corner_radius)),
+            padding: Rc::new(Cell::new(raw.callout.padding)),
+            shadow: Rc::new(RefCell::new(raw.callout.shadow)),
+            shadow_offset_x: Rc::new(Cell::new(raw.callout.shadow_offset_x)),
+            shadow_offset_y: Rc::new(Cell::new(raw.callout.shadow_offset_y)),
+            shadow_blur: Rc::new(Cell::new(raw.callout.shadow_blur)),
+            shadow_r: Rc::new(Cell::new(raw.callout.shadow_r)),
+            shadow_g: Rc::new(Cell::new(raw.callout.shadow_g)),
+            shadow_b: Rc::new(Cell::new(raw.callout.shadow_b)),
+            shadow_a: Rc::new(Cell::new(raw.callout.shadow_a)),
+        };
+
+        let stamp_rc = StampSettingsRc {
+            file: Rc::new(RefCell::new(raw.stamp.file)),
+            scale: Rc::new(Cell::new(raw.stamp.scale)),
+            rotation: Rc::new(Cell::new(raw.stamp.rotation)),
+            shadow: Rc::new(RefCell::new(raw.stamp.shadow)),
+            shadow_offset_x: Rc::new(Cell::new(raw.stamp.shadow_offset_x)),
+            shadow_offset_y: Rc::new(Cell::new(raw.stamp.shadow_offset_y)),
+            shadow_blur: Rc::new(Cell::new(raw.stamp.shadow_blur)),
+            shadow_r: Rc::new(Cell::new(raw.stamp.shadow_r)),
+            shadow_g: Rc::new(Cell::new(raw.stamp.shadow_g)),
+            shadow_b: Rc::new(Cell::new(raw.stamp.shadow_b)),
+            shadow_a: Rc::new(Cell::new(raw.stamp.shadow_a)),
+        };
+
+        let measure_rc = MeasureSettingsRc {
+            size: Rc::new(Cell::new(raw.measure.size)),
+            color_r: Rc::new(Cell::new(raw.measure.color_r)),
+            color_g: Rc::new(Cell::new(raw.measure.color_g)),
+            color_b: Rc::new(Cell::new(raw.measure.color_b)),
+            color_a: Rc::new(Cell::new(raw.measure.color_a)),
+            font_size: Rc::new(Cell::new(raw.measure.font_size)),
+            export: Rc::new(Cell::new(raw.measure.export)),
+            shadow: Rc::new(RefCell::new(raw.measure.shadow)),
+            shadow_offset_x: Rc::new(Cell::new(raw.measure.shadow_offset_x)),
+            shadow_offset_y: Rc::new(Cell::new(raw.measure.shadow_offset_y)),
+            shadow_blur: Rc::new(Cell::new(raw.measure.shadow_blur)),
+            shadow_r: Rc::new(Cell::new(raw.measure.shadow_r)),
+            shadow_g: Rc::new(Cell::new(raw.measure.shadow_g)),
+            shadow_b: Rc::new(Cell::new(raw.measure.shadow_b)),
+            shadow_a: Rc::new(Cell::new(raw.measure.shadow_a)),
+        };
+
+        let eyedropper_rc = EyedropperSettingsRc {
+            format: Rc::new(RefCell::new(raw.eyedropper.format)),
+            action: Rc::new(RefCell::new(raw.eyedropper.action)),
+            target: Rc::new(RefCell::new(raw.eyedropper.target)),
+        };
+
+        let highlight_rc = HighlightSettingsRc {
+            color_r: Rc::new(Cell::new(raw.highlight.color_r)),
+            color_g: Rc::new(Cell::new(raw.highlight.color_g)),
+            color_b: Rc::new(Cell::new(raw.highlight.color_b)),
+            corner_radius: Rc::new(Cell::new(raw.highlight.corner_radius)),
+            shadow: Rc::new(RefCell::new(raw.highlight.shadow)),
+            shadow_offset_x: Rc::new(Cell::new(raw.highlight.shadow_offset_x)),
+            shadow_offset_y: Rc::new(Cell::new(raw.highlight.shadow_offset_y)),
+            shadow_blur: Rc::new(Cell::new(raw.highlight.shadow_blur)),
+            shadow_r: Rc::new(Cell::new(raw.highlight.shadow_r)),
+            shadow_g: Rc::new(Cell::new(raw.highlight.shadow_g)),
+            shadow_b: Rc::new(Cell::new(raw.highlight.shadow_b)),
+            shadow_a: Rc::new(Cell::new(raw.highlight.shadow_a)),
+        };
+
+        let shadow_rc = ShadowSettingsRc {
+            enabled: Rc::new(Cell::new(raw.shadow.enabled)),
+            offset_x: Rc::new(Cell::new(raw.shadow.offset_x)),
+            offset_y: Rc::new(Cell::new(raw.shadow.offset_y)),
+            blur: Rc::new(Cell::new(raw.shadow.blur)),
+            color_r: Rc::new(Cell::new(raw.shadow.color_r)),
+            color_g: Rc::new(Cell::new(raw.shadow.color_g)),
+            color_b: Rc::new(Cell::new(raw.shadow.color_b)),
+            color_a: Rc::new(Cell::new(raw.shadow.color_a)),
+        };
+
+        let image_rc = ImageSettingsRc {
+            shadow: Rc::new(RefCell::new(raw.image.shadow)),
+            shadow_offset_x: Rc::new(Cell::new(raw.image.shadow_offset_x)),
+            shadow_offset_y: Rc::new(Cell::new(raw.image.shadow_offset_y)),
+            shadow_blur: Rc::new(Cell::new(raw.image.shadow_blur)),
+            shadow_r: Rc::new(Cell::new(raw.image.shadow_r)),
+            shadow_g: Rc::new(Cell::new(raw.image.shadow_g)),
+            shadow_b: Rc::new(Cell::new(raw.image.shadow_b)),
+            shadow_a: Rc::new(Cell::new(raw.image.shadow_a)),
         };
 
         SettingsRc {
@@ -1243,6 +3966,46 @@ impl SettingsRc {
             freehand: freehand_rc,
             numbered_circle: numbered_circle_rc,
             input_text: input_text_rc,
+            obfuscate: obfuscate_rc,
+            redact: redact_rc,
+            spotlight: spotlight_rc,
+            magnifier: magnifier_rc,
+            polygon: polygon_rc,
+            callout: callout_rc,
+            stamp: stamp_rc,
+            measure: measure_rc,
+            eyedropper: eyedropper_rc,
+            highlight: highlight_rc,
+            shadow: shadow_rc,
+            image: image_rc,
+        }
+    }
+
+    /// Set the (r, g, b) color of a tool, with `target` written as "section.prefix", like
+    /// "rect.fill" or "line.color". The alpha of the tool is kept.
+    pub fn set_color(&self, target: &str, r: f64, g: f64, b: f64) -> std::io::Result<()> {
+        let set = |set_value: &dyn Fn(&str, SettingValue) -> std::io::Result<()>, prefix: &str| {
+            set_value(&format!("{}_r", prefix), SettingValue::F64(r))?;
+            set_value(&format!("{}_g", prefix), SettingValue::F64(g))?;
+            set_value(&format!("{}_b", prefix), SettingValue::F64(b))
+        };
+        match target.split_once('.') {
+            Some(("arc", prefix)) => set(&|k, v| self.arc.set_value(k, v), prefix),
+            Some(("rect", prefix)) => set(&|k, v| self.rect.set_value(k, v), prefix),
+            Some(("line", prefix)) => set(&|k, v| self.line.set_value(k, v), prefix),
+            Some(("arrow", prefix)) => set(&|k, v| self.arrow.set_value(k, v), prefix),
+            Some(("freehand", prefix)) => set(&|k, v| self.freehand.set_value(k, v), prefix),
+            Some(("numbered_circle", prefix)) => {
+                set(&|k, v| self.numbered_circle.set_value(k, v), prefix)
+            }
+            Some(("input_text", prefix)) => set(&|k, v| self.input_text.set_value(k, v), prefix),
+            Some(("redact", prefix)) => set(&|k, v| self.redact.set_value(k, v), prefix),
+            Some(("polygon", prefix)) => set(&|k, v| self.polygon.set_value(k, v), prefix),
+            Some(("measure", prefix)) => set(&|k, v| self.measure.set_value(k, v), prefix),
+            _ => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("SettingsRc::set_color: unknown target '{}'", target),
+            )),
         }
     }
 
@@ -1259,6 +4022,21 @@ impl SettingsRc {
             border_g: self.arc.border_g.get(),
             border_b: self.arc.border_b.get(),
             border_a: self.arc.border_a.get(),
+            shape: self.arc.shape.borrow().clone(),
+            style: self.arc.style.borrow().clone(),
+            start_angle: self.arc.start_angle.get(),
+            end_angle: self.arc.end_angle.get(),
+            fill_mode: self.arc.fill_mode.borrow().clone(),
+            fill_stops: self.arc.fill_stops.borrow().clone(),
+            fill_angle: self.arc.fill_angle.get(),
+            shadow: self.arc.shadow.borrow().clone(),
+            shadow_offset_x: self.arc.shadow_offset_x.get(),
+            shadow_offset_y: self.arc.shadow_offset_y.get(),
+            shadow_blur: self.arc.shadow_blur.get(),
+            shadow_r: self.arc.shadow_r.get(),
+            shadow_g: self.arc.shadow_g.get(),
+            shadow_b: self.arc.shadow_b.get(),
+            shadow_a: self.arc.shadow_a.get(),
         };
 
         let re: RectSettings = RectSettings {
@@ -1273,6 +4051,20 @@ impl SettingsRc {
             border_g: self.rect.border_g.get(),
             border_b: self.rect.border_b.get(),
             border_a: self.rect.border_a.get(),
+            corner_radius: self.rect.corner_radius.get(),
+            dash: self.rect.dash.borrow().clone(),
+            style: self.rect.style.borrow().clone(),
+            fill_mode: self.rect.fill_mode.borrow().clone(),
+            fill_stops: self.rect.fill_stops.borrow().clone(),
+            fill_angle: self.rect.fill_angle.get(),
+            shadow: self.rect.shadow.borrow().clone(),
+            shadow_offset_x: self.rect.shadow_offset_x.get(),
+            shadow_offset_y: self.rect.shadow_offset_y.get(),
+            shadow_blur: self.rect.shadow_blur.get(),
+            shadow_r: self.rect.shadow_r.get(),
+            shadow_g: self.rect.shadow_g.get(),
+            shadow_b: self.rect.shadow_b.get(),
+            shadow_a: self.rect.shadow_a.get(),
         };
 
         let li = LineSettings {
@@ -1283,6 +4075,16 @@ impl SettingsRc {
             size: self.line.size.get(),
             line_cap: self.line.line_cap.borrow().to_string(),
             line_join: self.line.line_join.borrow().to_string(),
+            dash: self.line.dash.borrow().clone(),
+            dash_pattern: self.line.dash_pattern.borrow().clone(),
+            shadow: self.line.shadow.borrow().clone(),
+            shadow_offset_x: self.line.shadow_offset_x.get(),
+            shadow_offset_y: self.line.shadow_offset_y.get(),
+            shadow_blur: self.line.shadow_blur.get(),
+            shadow_r: self.line.shadow_r.get(),
+            shadow_g: self.line.shadow_g.get(),
+            shadow_b: self.line.shadow_b.get(),
+            shadow_a: self.line.shadow_a.get(),
         };
 
         let aw = ArrowSettings {
@@ -1294,6 +4096,25 @@ impl SettingsRc {
             line_cap: self.arrow.line_cap.borrow().to_string(),
             line_join: self.arrow.line_join.borrow().to_string(),
             arrow_size: self.arrow.arrow_size.get(),
+            curved: self.arrow.curved.get(),
+            double_headed: self.arrow.double_headed.get(),
+            head_style: self.arrow.head_style.borrow().clone(),
+            outline: self.arrow.outline.get(),
+            outline_size: self.arrow.outline_size.get(),
+            outline_r: self.arrow.outline_r.get(),
+            outline_g: self.arrow.outline_g.get(),
+            outline_b: self.arrow.outline_b.get(),
+            outline_a: self.arrow.outline_a.get(),
+            dash: self.arrow.dash.borrow().clone(),
+            dash_pattern: self.arrow.dash_pattern.borrow().clone(),
+            shadow: self.arrow.shadow.borrow().clone(),
+            shadow_offset_x: self.arrow.shadow_offset_x.get(),
+            shadow_offset_y: self.arrow.shadow_offset_y.get(),
+            shadow_blur: self.arrow.shadow_blur.get(),
+            shadow_r: self.arrow.shadow_r.get(),
+            shadow_g: self.arrow.shadow_g.get(),
+            shadow_b: self.arrow.shadow_b.get(),
+            shadow_a: self.arrow.shadow_a.get(),
         };
 
         let fh = FreehandSettings {
@@ -1303,6 +4124,15 @@ impl SettingsRc {
             color_a: self.freehand.color_a.get(),
             size: self.freehand.size.get(),
             tool: self.freehand.tool.borrow().to_string(),
+            shadow: self.freehand.shadow.borrow().clone(),
+            shadow_offset_x: self.freehand.shadow_offset_x.get(),
+            shadow_offset_y: self.freehand.shadow_offset_y.get(),
+            shadow_blur: self.freehand.shadow_blur.get(),
+            shadow_r: self.freehand.shadow_r.get(),
+            shadow_g: self.freehand.shadow_g.get(),
+            shadow_b: self.freehand.shadow_b.get(),
+            shadow_a: self.freehand.shadow_a.get(),
+            eraser_size: self.freehand.eraser_size.get(),
         };
 
         let nc: NumberedCircleSettings = NumberedCircleSettings {
@@ -1327,6 +4157,23 @@ impl SettingsRc {
             font_b: self.numbered_circle.font_b.get(),
             font_a: self.numbered_circle.font_a.get(),
             radius: self.numbered_circle.radius.get(),
+            style: self.numbered_circle.style.borrow().clone(),
+            prefix: self.numbered_circle.prefix.borrow().clone(),
+            shape: self.numbered_circle.shape.borrow().clone(),
+            sequence: self.numbered_circle.sequence.get(),
+            sequence_start: self.numbered_circle.sequence_start.get(),
+            renumber: self.numbered_circle.renumber.get(),
+            fill_mode: self.numbered_circle.fill_mode.borrow().clone(),
+            fill_stops: self.numbered_circle.fill_stops.borrow().clone(),
+            fill_angle: self.numbered_circle.fill_angle.get(),
+            shadow: self.numbered_circle.shadow.borrow().clone(),
+            shadow_offset_x: self.numbered_circle.shadow_offset_x.get(),
+            shadow_offset_y: self.numbered_circle.shadow_offset_y.get(),
+            shadow_blur: self.numbered_circle.shadow_blur.get(),
+            shadow_r: self.numbered_circle.shadow_r.get(),
+            shadow_g: self.numbered_circle.shadow_g.get(),
+            shadow_b: self.numbered_circle.shadow_b.get(),
+            shadow_a: self.numbered_circle.shadow_a.get(),
         };
 
         let it: InputTextSettings = InputTextSettings {
@@ -1350,6 +4197,189 @@ impl SettingsRc {
             font_g: self.input_text.font_g.get(),
             font_b: self.input_text.font_b.get(),
             font_a: self.input_text.font_a.get(),
+            fill_mode: self.input_text.fill_mode.borrow().clone(),
+            fill_stops: self.input_text.fill_stops.borrow().clone(),
+            fill_angle: self.input_text.fill_angle.get(),
+            shadow: self.input_text.shadow.borrow().clone(),
+            shadow_offset_x: self.input_text.shadow_offset_x.get(),
+            shadow_offset_y: self.input_text.shadow_offset_y.get(),
+            shadow_blur: self.input_text.shadow_blur.get(),
+            shadow_r: self.input_text.shadow_r.get(),
+            shadow_g: self.input_text.shadow_g.get(),
+            shadow_b: self.input_text.shadow_b.get(),
+            shadow_a: self.input_text.shadow_a.get(),
+        };
+
+        let ob = ObfuscateSettings {
+            tool: self.obfuscate.tool.borrow().clone(),
+            pixel_size: self.obfuscate.pixel_size.get(),
+            blur_radius: self.obfuscate.blur_radius.get(),
+            shadow: self.obfuscate.shadow.borrow().clone(),
+            shadow_offset_x: self.obfuscate.shadow_offset_x.get(),
+            shadow_offset_y: self.obfuscate.shadow_offset_y.get(),
+            shadow_blur: self.obfuscate.shadow_blur.get(),
+            shadow_r: self.obfuscate.shadow_r.get(),
+            shadow_g: self.obfuscate.shadow_g.get(),
+            shadow_b: self.obfuscate.shadow_b.get(),
+            shadow_a: self.obfuscate.shadow_a.get(),
+        };
+
+        let rd = RedactSettings {
+            color_r: self.redact.color_r.get(),
+            color_g: self.redact.color_g.get(),
+            color_b: self.redact.color_b.get(),
+            shadow: self.redact.shadow.borrow().clone(),
+            shadow_offset_x: self.redact.shadow_offset_x.get(),
+            shadow_offset_y: self.redact.shadow_offset_y.get(),
+            shadow_blur: self.redact.shadow_blur.get(),
+            shadow_r: self.redact.shadow_r.get(),
+            shadow_g: self.redact.shadow_g.get(),
+            shadow_b: self.redact.shadow_b.get(),
+            shadow_a: self.redact.shadow_a.get(),
+        };
+
+        let sp = SpotlightSettings {
+            shape: self.spotlight.shape.borrow().clone(),
+            dim: self.spotlight.dim.get(),
+            shadow: self.spotlight.shadow.borrow().clone(),
+            shadow_offset_x: self.spotlight.shadow_offset_x.get(),
+            shadow_offset_y: self.spotlight.shadow_offset_y.get(),
+            shadow_blur: self.spotlight.shadow_blur.get(),
+            shadow_r: self.spotlight.shadow_r.get(),
+            shadow_g: self.spotlight.shadow_g.get(),
+            shadow_b: self.spotlight.shadow_b.get(),
+            shadow_a: self.spotlight.shadow_a.get(),
+        };
+
+        let mg = MagnifierSettings {
+            shape: self.magnifier.shape.borrow().clone(),
+            zoom: self.magnifier.zoom.get(),
+            border_size: self.magnifier.border_size.get(),
+            border_r: self.magnifier.border_r.get(),
+            border_g: self.magnifier.border_g.get(),
+            border_b: self.magnifier.border_b.get(),
+            border_a: self.magnifier.border_a.get(),
+            connector: self.magnifier.connector.get(),
+            shadow: self.magnifier.shadow.borrow().clone(),
+            shadow_offset_x: self.magnifier.shadow_offset_x.get(),
+            shadow_offset_y: self.magnifier.shadow_offset_y.get(),
+            shadow_blur: self.magnifier.shadow_blur.get(),
+            shadow_r: self.magnifier.shadow_r.get(),
+            shadow_g: self.magnifier.shadow_g.get(),
+            shadow_b: self.magnifier.shadow_b.get(),
+            shadow_a: self.magnifier.shadow_a.get(),
+        };
+
+        let po = PolygonSettings {
+            closed: self.polygon.closed.get(),
+            fill: self.polygon.fill.get(),
+            fill_r: self.polygon.fill_r.get(),
+            fill_g: self.polygon.fill_g.get(),
+            fill_b: self.polygon.fill_b.get(),
+            fill_a: self.polygon.fill_a.get(),
+            border: self.polygon.border.get(),
+            border_size: self.polygon.border_size.get(),
+            border_r: self.polygon.border_r.get(),
+            border_g: self.polygon.border_g.get(),
+            border_b: self.polygon.border_b.get(),
+            border_a: self.polygon.border_a.get(),
+            shadow: self.polygon.shadow.borrow().clone(),
+            shadow_offset_x: self.polygon.shadow_offset_x.get(),
+            shadow_offset_y: self.polygon.shadow_offset_y.get(),
+            shadow_blur: self.polygon.shadow_blur.get(),
+            shadow_r: self.polygon.shadow_r.get(),
+            shadow_g: self.polygon.shadow_g.get(),
+            shadow_b: self.polygon.shadow_b.get(),
+            shadow_a: self.polygon.shadow_a.get(),
+        };
+
+        let co = CalloutSettings {
+            wrap_width: self.callout.wrap_width.get(),
+            corner_radius: self.callout.corner_radius.get(),
+            padding: self.callout.padding.get(),
+            shadow: self.callout.shadow.borrow().clone(),
+            shadow_offset_x: self.callout.shadow_offset_x.get(),
+            shadow_offset_y: self.callout.shadow_offset_y.get(),
+            shadow_blur: self.callout.shadow_blur.get(),
+            shadow_r: self.callout.shadow_r.get(),
+            shadow_g: self.callout.shadow_g.get(),
+            shadow_b: self.callout.shadow_b.get(),
+            shadow_a: self.callout.shadow_a.get(),
+        };
+
+        let st = StampSettings {
+            file: self.stamp.file.borrow().clone(),
+            scale: self.stamp.scale.get(),
+            rotation: self.stamp.rotation.get(),
+            shadow: self.stamp.shadow.borrow().clone(),
+            shadow_offset_x: self.stamp.shadow_offset_x.get(),
+            shadow_offset_y: self.stamp.shadow_offset_y.get(),
+            shadow_blur: self.stamp.shadow_blur.get(),
+            shadow_r: self.stamp.shadow_r.get(),
+            shadow_g: self.stamp.shadow_g.get(),
+            shadow_b: self.stamp.shadow_b.get(),
+            shadow_a: self.stamp.shadow_a.get(),
+        };
+
+        let me = MeasureSettings {
+            size: self.measure.size.get(),
+            color_r: self.measure.color_r.get(),
+            color_g: self.measure.color_g.get(),
+            color_b: self.measure.color_b.get(),
+            color_a: self.measure.color_a.get(),
+            font_size: self.measure.font_size.get(),
+            export: self.measure.export.get(),
+            shadow: self.measure.shadow.borrow().clone(),
+            shadow_offset_x: self.measure.shadow_offset_x.get(),
+            shadow_offset_y: self.measure.shadow_offset_y.get(),
+            shadow_blur: self.measure.shadow_blur.get(),
+            shadow_r: self.measure.shadow_r.get(),
+            shadow_g: self.measure.shadow_g.get(),
+            shadow_b: self.measure.shadow_b.get(),
+            shadow_a: self.measure.shadow_a.get(),
+        };
+
+        let ey = EyedropperSettings {
+            format: self.eyedropper.format.borrow().clone(),
+            action: self.eyedropper.action.borrow().clone(),
+            target: self.eyedropper.target.borrow().clone(),
+        };
+
+        let hl = HighlightSettings {
+            color_r: self.highlight.color_r.get(),
+            color_g: self.highlight.color_g.get(),
+            color_b: self.highlight.color_b.get(),
+            corner_radius: self.highlight.corner_radius.get(),
+            shadow: self.highlight.shadow.borrow().clone(),
+            shadow_offset_x: self.highlight.shadow_offset_x.get(),
+            shadow_offset_y: self.highlight.shadow_offset_y.get(),
+            shadow_blur: self.highlight.shadow_blur.get(),
+            shadow_r: self.highlight.shadow_r.get(),
+            shadow_g: self.highlight.shadow_g.get(),
+            shadow_b: self.highlight.shadow_b.get(),
+            shadow_a: self.highlight.shadow_a.get(),
+        };
+
+        let sh = ShadowSettings {
+            enabled: self.shadow.enabled.get(),
+            offset_x: self.shadow.offset_x.get(),
+            offset_y: self.shadow.offset_y.get(),
+            blur: self.shadow.blur.get(),
+            color_r: self.shadow.color_r.get(),
+            color_g: self.shadow.color_g.get(),
+            color_b: self.shadow.color_b.get(),
+            color_a: self.shadow.color_a.get(),
+        };
+
+        let im = ImageSettings {
+            shadow: self.image.shadow.borrow().clone(),
+            shadow_offset_x: self.image.shadow_offset_x.get(),
+            shadow_offset_y: self.image.shadow_offset_y.get(),
+            shadow_blur: self.image.shadow_blur.get(),
+            shadow_r: self.image.shadow_r.get(),
+            shadow_g: self.image.shadow_g.get(),
+            shadow_b: self.image.shadow_b.get(),
+            shadow_a: self.image.shadow_a.get(),
         };
 
         Settings {
@@ -1360,6 +4390,18 @@ impl SettingsRc {
             freehand: fh,
             numbered_circle: nc,
             input_text: it,
+            obfuscate: ob,
+            redact: rd,
+            spotlight: sp,
+            magnifier: mg,
+            polygon: po,
+            callout: co,
+            stamp: st,
+            measure: me,
+            eyedropper: ey,
+            highlight: hl,
+            shadow: sh,
+            image: im,
         }
     }
 }
@@ -1380,6 +4422,18 @@ impl Settings {
             freehand: data.freehand,
             numbered_circle: data.numbered_circle,
             input_text: data.input_text,
+            obfuscate: data.obfuscate,
+            redact: data.redact,
+            spotlight: data.spotlight,
+            magnifier: data.magnifier,
+            polygon: data.polygon,
+            callout: data.callout,
+            stamp: data.stamp,
+            measure: data.measure,
+            eyedropper: data.eyedropper,
+            highlight: data.highlight,
+            shadow: data.shadow,
+            image: data.image,
         }
     }
 
@@ -1,6 +1,9 @@
+use crate::captured_screen::redact;
+use crate::captured_screen::{CapturedScreen, encode_redacted_png};
 use crate::drawing_area_manager::DrawingAreaManager;
-use crate::drawing_area_settings::SettingValue;
+use crate::drawing_area_manager::text_editor::{Motion, TextAction};
 use crate::edge::GrayEdge;
+use crate::layers_panel::LayersPanel;
 use rustshot_gtk::constants::{CSS_CLASS_PRESSED, CSS_FILE_PATH};
 use std::io::Write;
 // use crate::handles::Handles;
@@ -11,7 +14,12 @@ use crate::toolbox::Toolbox;
 use gtk::prelude::*;
 use gtk::{gdk, gio, glib};
 use rustshot_gtk::constants::CSS_CLASS_TRANSPARENT;
+use std::cell::Cell;
 use std::env;
+use std::rc::Rc;
+
+/// Callback applied to the selected items by the context menu
+type SelectionAction = fn(&DrawingAreaManager);
 
 /// Stores the mutable geometry values used by the drag callbacks.
 #[derive(Clone)]
@@ -29,12 +37,17 @@ pub struct GeometryState {
     pub screenshot_box: ScreenshotBox,
 
     pub settings: SettingsRc,
+    pub captured: CapturedScreen,
     pub toolbox: Toolbox,
+    pub layers_panel: LayersPanel,
 
     // Layout
     layout: gtk::Box,
     pub drawing: DrawingAreaManager,
 
+    // Drag on the gray edges to draw the screenshot box again, attached in crop mode
+    crop_gesture: gtk::GestureDrag,
+
     // Full window size – constant for the life of the widget
     pub full_w: i32,
     pub full_h: i32,
@@ -151,9 +164,15 @@ impl GeometryState {
         // Create Toolbox object
         let toolbox: Toolbox = Toolbox::default();
 
+        // Create the layers panel, placed by the toolbox
+        let layers_panel: LayersPanel = LayersPanel::default();
+
         // let stop = st.elapsed().as_millis();
         //println!("Elapsed: {}", stop);
 
+        // Capture the screen before the window covers it
+        let captured: CapturedScreen = CapturedScreen::capture(w);
+
         // Set main window size and present
         window.present();
 
@@ -169,9 +188,12 @@ impl GeometryState {
             central_overlay: overlay,
             screenshot_box: screenshot_b,
             settings: settings_rc,
-            toolbox: toolbox,
+            captured,
+            toolbox,
+            layers_panel,
             layout,
             drawing: draw,
+            crop_gesture: gtk::GestureDrag::new(),
             full_w: w,
             full_h: h,
         }
@@ -202,8 +224,39 @@ impl GeometryState {
         keyboard_ctrl.connect_key_released(glib::clone!(
             #[strong]
             geom,
-            move |_, key, _keycode, _state| {
-                geom.handle_key_event(key);
+            move |_, key, _keycode, state| {
+                geom.handle_key_event(key, state);
+            }
+        ));
+        // The text annotations are edited on key press, so held keys repeat
+        keyboard_ctrl.connect_key_pressed(glib::clone!(
+            #[strong]
+            geom,
+            move |_, key, _keycode, state| geom.handle_text_key(key, state)
+        ));
+
+        // The typed text goes through the input method, so dead keys, compose sequences and
+        // CJK input work. The keys it doesn't use reach connect_key_pressed.
+        let im_context = gtk::IMMulticontext::new();
+        im_context.set_client_widget(Some(&geom.drawing));
+        keyboard_ctrl.set_im_context(Some(&im_context));
+        geom.drawing.set_im_context(&im_context);
+        im_context.connect_commit(glib::clone!(
+            #[strong]
+            geom,
+            move |_, text| {
+                if geom.drawing.is_typing_text() {
+                    geom.drawing
+                        .edit_text(&TextAction::Insert(text.to_string()));
+                }
+            }
+        ));
+        im_context.connect_preedit_changed(glib::clone!(
+            #[strong]
+            geom,
+            move |im_context| {
+                let (preedit, _attrs, cursor) = im_context.preedit_string();
+                geom.drawing.set_preedit(&preedit, cursor);
             }
         ));
 
@@ -245,6 +298,56 @@ impl GeometryState {
             }
         ));
 
+        // ---------------------------------------
+        // Crop mode: draw the screenshot box again, the drawn items are kept
+        // ---------------------------------------
+        let cropping = Rc::new(Cell::new(false));
+        self.crop_gesture.connect_drag_begin(glib::clone!(
+            #[strong]
+            geom,
+            #[strong]
+            cropping,
+            move |gest, x, y| {
+                // Inside the screenshot box the handles move and resize it
+                let (bx, by, bw, bh) = geom.screenshot_rect();
+                let inside = x >= bx && x <= bx + bw && y >= by && y <= by + bh;
+                cropping.set(!inside);
+                if inside {
+                    gest.set_state(gtk::EventSequenceState::Denied);
+                    return;
+                }
+                geom.toolbox.stop_toolbox(&geom);
+                geom.drag_begin(x, y);
+            }
+        ));
+
+        self.crop_gesture.connect_drag_update(glib::clone!(
+            #[strong]
+            geom,
+            #[strong]
+            cropping,
+            move |_, x, y| {
+                if cropping.get() {
+                    geom.drag_update(x, y);
+                }
+            }
+        ));
+
+        self.crop_gesture.connect_drag_end(glib::clone!(
+            #[strong]
+            geom,
+            #[strong]
+            cropping,
+            move |_, x, y| {
+                if cropping.replace(false) {
+                    geom.drag_end(x, y);
+                    geom.toolbox
+                        .draw_toolbox(&geom)
+                        .expect("GeometryState crop drag_end error");
+                }
+            }
+        ));
+
         // draw boxes on screenshot_box box
         let draw_gesture = gtk::GestureDrag::new();
         self.screenshot_box.add_controller(draw_gesture.clone());
@@ -259,8 +362,21 @@ impl GeometryState {
             drawing,
             #[strong]
             geom,
-            move |_, x, y| {
-                if drawing.is_drawing() {
+            move |gesture, x, y| {
+                if drawing.is_selecting() {
+                    // Shift extends the current selection
+                    let extend = gesture
+                        .current_event_state()
+                        .contains(gdk::ModifierType::SHIFT_MASK);
+
+                    geom.toolbox.stop_toolbox(&geom);
+                    drawing.selection_begin(
+                        left.get_edge_f64() + x,
+                        top.get_edge_f64() + y,
+                        extend,
+                    );
+                    drawing.queue_draw(); // Request a redraw
+                } else if drawing.is_drawing() {
                     // Stop toolbox in order to prevent toolbox superimposition in fullscreen
                     // NOTE: some toolbox button settings will rely on this stop/start behavior
                     //       If we don't stop the toolbox and redraw it at drag_end, some items will
@@ -308,45 +424,159 @@ impl GeometryState {
             }
         ));
 
+        // ---------------------------------------
+        // Context menu for the selected items
+        // ---------------------------------------
+        self.attach_selection_menu();
+
         Ok(())
     }
 
+    /// Right click on the screenshot box, while selecting, opens a menu acting on the selection.
+    fn attach_selection_menu(&self) {
+        // Actions, reachable as "selection.<name>"
+        let actions = gio::SimpleActionGroup::new();
+        let entries: [(&str, SelectionAction); 7] = [
+            ("front", DrawingAreaManager::bring_selection_to_front),
+            ("raise", DrawingAreaManager::raise_selection),
+            ("lower", DrawingAreaManager::lower_selection),
+            ("back", DrawingAreaManager::send_selection_to_back),
+            ("group", DrawingAreaManager::group_selection),
+            ("ungroup", DrawingAreaManager::ungroup_selection),
+            ("delete", DrawingAreaManager::delete_selection),
+        ];
+        for (name, callback) in entries {
+            let action = gio::SimpleAction::new(name, None);
+            action.connect_activate(glib::clone!(
+                #[strong(rename_to = geom)]
+                self,
+                move |_, _| {
+                    callback(&geom.drawing);
+                    geom.layers_panel.refresh(&geom);
+                }
+            ));
+            actions.add_action(&action);
+        }
+        self.window.insert_action_group("selection", Some(&actions));
+
+        // Menu
+        let order = gio::Menu::new();
+        order.append(Some("Bring to front (Home)"), Some("selection.front"));
+        order.append(Some("Raise (Page Up)"), Some("selection.raise"));
+        order.append(Some("Lower (Page Down)"), Some("selection.lower"));
+        order.append(Some("Send to back (End)"), Some("selection.back"));
+        let edit = gio::Menu::new();
+        edit.append(Some("Group (Ctrl+G)"), Some("selection.group"));
+        edit.append(Some("Ungroup (Ctrl+Shift+G)"), Some("selection.ungroup"));
+        edit.append(Some("Delete (Delete)"), Some("selection.delete"));
+        let menu = gio::Menu::new();
+        menu.append_section(None, &order);
+        menu.append_section(None, &edit);
+
+        let popover = gtk::PopoverMenu::from_model(Some(&menu));
+        popover.set_parent(&self.screenshot_box);
+        popover.set_has_arrow(false);
+
+        // Right click
+        let gesture = gtk::GestureClick::new();
+        gesture.set_button(gdk::BUTTON_SECONDARY);
+        self.screenshot_box.add_controller(gesture.clone());
+
+        let left = self.left_box.clone();
+        let top = self.top_box.clone();
+        gesture.connect_pressed(glib::clone!(
+            #[weak(rename_to = drawing)]
+            self.drawing,
+            #[weak]
+            popover,
+            move |_, _, x, y| {
+                if !drawing.is_selecting() {
+                    return;
+                }
+
+                drawing.select_at(left.get_edge_f64() + x, top.get_edge_f64() + y);
+                if drawing.has_selection() {
+                    popover.set_pointing_to(Some(&gdk::Rectangle::new(x as i32, y as i32, 1, 1)));
+                    popover.popup();
+                }
+            }
+        ));
+    }
+
     /// Entry point for a key event coming from the controller.
-    fn handle_key_event(&self, key: gdk::Key) {
+    fn handle_key_event(&self, key: gdk::Key, state: gdk::ModifierType) {
         // println!("{:?}", key.name());
         // 1️⃣  Escape key – special handling
         if key == gdk::Key::Escape {
             self.handle_escape();
             return; // nothing else to do for Esc
         }
-        if key == gdk::Key::BackSpace {
-            println!("delete");
-            self.handle_delete();
-            self.drawing.event_controller_key();
+        if self.drawing.is_selecting() {
+            self.handle_selection_key(key, state);
             return;
         }
+        // The text being typed already got the key when it was pressed
+        if self.drawing.is_typing_text() {
+            return;
+        }
+        // Enter finishes the drawables made of several clicks (polygons)
+        if matches!(key, gdk::Key::Return | gdk::Key::KP_Enter)
+            && self.drawing.finish_current_item()
+        {
+            self.toolbox
+                .draw_toolbox(self)
+                .expect("Impossible to draw toolbox");
+        }
+    }
 
-        // 2️⃣  All other keys – normal text entry
-        self.append_key_to_input(key);
-        self.drawing.event_controller_key();
+    /* ---------------------------------------------------------------- *
+     *  Text annotation editing
+     * ---------------------------------------------------------------- */
+    fn handle_text_key(&self, key: gdk::Key, state: gdk::ModifierType) -> glib::Propagation {
+        if !self.drawing.is_typing_text() {
+            return glib::Propagation::Proceed;
+        }
+        let ctrl = state.contains(gdk::ModifierType::CONTROL_MASK);
+        let shift = state.contains(gdk::ModifierType::SHIFT_MASK);
+
+        let action = match key {
+            gdk::Key::Return | gdk::Key::KP_Enter => TextAction::Insert("\n".to_string()),
+            gdk::Key::BackSpace => TextAction::Backspace,
+            gdk::Key::Delete | gdk::Key::KP_Delete => TextAction::Delete,
+            gdk::Key::Left | gdk::Key::KP_Left => TextAction::Move(Motion::Left, shift),
+            gdk::Key::Right | gdk::Key::KP_Right => TextAction::Move(Motion::Right, shift),
+            gdk::Key::Up | gdk::Key::KP_Up => TextAction::Move(Motion::Up, shift),
+            gdk::Key::Down | gdk::Key::KP_Down => TextAction::Move(Motion::Down, shift),
+            gdk::Key::Home | gdk::Key::KP_Home => TextAction::Move(Motion::Home, shift),
+            gdk::Key::End | gdk::Key::KP_End => TextAction::Move(Motion::End, shift),
+            _ if ctrl && key.to_lower() == gdk::Key::a => TextAction::SelectAll,
+            // The characters come from the input method commits
+            _ => return glib::Propagation::Proceed,
+        };
+        self.drawing.edit_text(&action);
+        glib::Propagation::Stop
     }
 
     /* ---------------------------------------------------------------- *
-     *  Delete‑key handling
+     *  Selection shortcuts
      * ---------------------------------------------------------------- */
-    fn handle_delete(&self) {
-        let mut text = self
-            .settings
-            .input_text
-            .get_value("text")
-            .get_string()
-            .expect("Impossible to get text");
-
-        text.pop();
-        self.settings
-            .input_text
-            .set_value("text", SettingValue::String(text))
-            .expect("Impossible to get text");
+    fn handle_selection_key(&self, key: gdk::Key, state: gdk::ModifierType) {
+        let ctrl = state.contains(gdk::ModifierType::CONTROL_MASK);
+        let shift = state.contains(gdk::ModifierType::SHIFT_MASK);
+
+        match key.to_lower() {
+            // Ctrl+G groups, Ctrl+Shift+G ungroups
+            gdk::Key::g if ctrl && shift => self.drawing.ungroup_selection(),
+            gdk::Key::g if ctrl => self.drawing.group_selection(),
+            gdk::Key::Delete | gdk::Key::BackSpace => self.drawing.delete_selection(),
+            // Drawing order
+            gdk::Key::Page_Up => self.drawing.raise_selection(),
+            gdk::Key::Page_Down => self.drawing.lower_selection(),
+            gdk::Key::Home => self.drawing.bring_selection_to_front(),
+            gdk::Key::End => self.drawing.send_selection_to_back(),
+            _ => return,
+        }
+        self.layers_panel.refresh(self);
     }
 
     /* ---------------------------------------------------------------- *
@@ -366,6 +596,20 @@ impl GeometryState {
     fn enable_screenshot_overlay(&self) {
         self.screenshot_box.set_screenshot_box_sensitivity(true);
         self.drawing.set_drawing(false);
+        self.set_cropping(false);
+    }
+
+    /// In crop mode the screenshot box can be drawn again on the gray edges, or adjusted with
+    /// its handles. The drawn items keep their place on the screen, so the export only shows
+    /// the part of them inside the new box.
+    pub fn set_cropping(&self, cropping: bool) {
+        let attached = self.crop_gesture.widget().is_some();
+        if cropping && !attached {
+            self.screenshot_box.set_screenshot_box_sensitivity(true);
+            self.layout.add_controller(self.crop_gesture.clone());
+        } else if !cropping && attached {
+            self.layout.remove_controller(&self.crop_gesture);
+        }
     }
 
     /// Reset the toolbox UI state after the button is released
@@ -374,35 +618,6 @@ impl GeometryState {
         self.toolbox.remove_css_class(CSS_CLASS_PRESSED);
     }
 
-    /* ---------------------------------------------------------------- *
-     *  Append a printable character to the stored input text
-     * ---------------------------------------------------------------- */
-    fn append_key_to_input(&self, key: gdk::Key) {
-        // 1️⃣ Retrieve the current text; bail out on error
-        let mut text = match self.settings.input_text.get_value("text").get_string() {
-            Ok(t) => t,
-            Err(e) => {
-                eprintln!("Failed to read input text: {e}");
-                return;
-            }
-        };
-
-        // 2️⃣ Convert the GDK key into a Unicode character, if possible
-        if let Some(name) = key.into()
-            && let Some(ch) = name.to_unicode()
-        {
-            text.push(ch);
-
-            // 3️⃣ Store the updated text back into the setting
-            // Again we ignore the Result; replace `_` with proper handling
-            // if you need to react to a failure.
-            let _ = self
-                .settings
-                .input_text
-                .set_value("text", SettingValue::String(text));
-        }
-    }
-
     pub fn set_new_geometry_f64(&self, top: f64, left: f64, bottom: f64, right: f64) {
         self.top_box.set_edge_f64(top);
         self.left_box.set_edge_f64(left);
@@ -464,6 +679,15 @@ impl GeometryState {
         self.screenshot_box.set_visible(true);
     }
 
+    /// Screenshot box (x, y, w, h) in window coordinates
+    pub fn screenshot_rect(&self) -> (f64, f64, f64, f64) {
+        let x = self.left_box.get_edge_f64();
+        let y = self.top_box.get_edge_f64();
+        let w = self.full_w as f64 - x - self.right_box.get_edge_f64();
+        let h = self.full_h as f64 - y - self.bottom_box.get_edge_f64();
+        (x, y, w, h)
+    }
+
     /// Get the screenshot box (x, y, w, h)
     /// x: screenshot box x position
     /// y: screenshot box y position
@@ -478,7 +702,40 @@ impl GeometryState {
         format!("{},{} {}x{}", x, y, w, h)
     }
 
-    pub fn take_screenshot(&self) {
+    /// Overwrite the redacted rectangles in the png produced by grim, except under the items
+    /// stacked above them, and check the exported png.
+    /// The png is returned untouched when there is nothing to redact.
+    fn apply_redactions(&self, png: &[u8]) -> Result<Vec<u8>, String> {
+        let redactions = self.drawing.redactions();
+        if redactions.is_empty() {
+            return Ok(png.to_vec());
+        }
+
+        let x = self.left_box.get_edge();
+        let y = self.top_box.get_edge();
+        let w = self.full_w - x - self.right_box.get_edge();
+
+        let mut image = CapturedScreen::decode_png(png, w)
+            .map_err(|e| format!("impossible to decode the capture: {}", e))?;
+
+        // The items drawn above a redaction keep their captured pixels
+        let stack = self.drawing.redaction_stack(
+            (x as f64, y as f64),
+            image.scale,
+            image.width,
+            image.height,
+        );
+        let kept = redact::kept_pixels(image.width, image.height, &stack);
+        let rects = redact::redaction_rects(&stack);
+
+        // The check is done on the decoded png, exactly what goes to wl-copy
+        encode_redacted_png(&mut image.data, image.width, image.height, &rects, &kept)
+            .map(|png| png.to_vec())
+    }
+
+    /// Copy the screenshot to the clipboard. On error the clipboard is cleared, so that an old
+    /// capture can not be saved in its place.
+    pub fn take_screenshot(&self) -> Result<(), String> {
         // build the grim string like "10,20 400x900"
         // we need to subtract the border of the screenbox (which is 2px, see style.css)
         let grim_string = &self.get_grim_cmd();
@@ -494,6 +751,18 @@ impl GeometryState {
 
         // Check if the grim command was successful
         if grim_output.status.success() {
+            // Burn the redactions into the image, never export it if this fails
+            let png = match self.apply_redactions(&grim_output.stdout) {
+                Ok(png) => png,
+                Err(e) => {
+                    // clear the clipboard, so an old capture can not be saved in its place
+                    let _ = std::process::Command::new("wl-copy")
+                        .arg("--clear")
+                        .status();
+                    return Err(format!("Screenshot not exported: {}", e));
+                }
+            };
+
             // Write the output of grim to wl-copy's stdin
             let mut wl_copy_process = std::process::Command::new("wl-copy")
                 .stdin(std::process::Stdio::piped()) // Use a pipe for stdin
@@ -502,9 +771,7 @@ impl GeometryState {
 
             // Write the grim output to wl-copy's stdin
             if let Some(stdin) = wl_copy_process.stdin.as_mut() {
-                stdin
-                    .write_all(&grim_output.stdout)
-                    .expect("Failed to write to wl-copy");
+                stdin.write_all(&png).expect("Failed to write to wl-copy");
             }
 
             // Wait for wl-copy to finish
@@ -513,13 +780,23 @@ impl GeometryState {
                 .expect("wl-copy did not run successfully");
 
             println!("Screenshot (region: {}) copied to clipboard.", grim_string);
+            Ok(())
         } else {
             // Handle the error case for grim
             let stderr = String::from_utf8_lossy(&grim_output.stderr);
-            eprintln!("Error executing grim: {}", stderr);
+            Err(format!("Error executing grim: {}", stderr))
         }
     }
 
+    /// The screenshot could not be exported: keep the window open to continue editing
+    pub fn screenshot_failed(&self, error: &str) {
+        eprintln!("{}", error);
+        self.drawing.set_exporting(false);
+        self.toolbox
+            .draw_toolbox(self)
+            .expect("Impossible to draw toolbox");
+    }
+
     pub fn save_screenshot(&self) {
         // Find home directory
         match env::home_dir() {
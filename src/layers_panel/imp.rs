@@ -0,0 +1,31 @@
+use gtk::glib;
+use gtk::subclass::prelude::*;
+use std::cell::OnceCell;
+
+// Object holding the state
+#[derive(Debug, Default)]
+pub struct LayersPanel {
+    pub list_box: OnceCell<gtk::ListBox>,
+}
+
+// The central trait for subclassing a GObject
+#[glib::object_subclass]
+impl ObjectSubclass for LayersPanel {
+    const NAME: &'static str = "LayersPanel";
+    type Type = super::LayersPanel;
+    type ParentType = gtk::Box;
+}
+
+// Trait shared by all GObjects
+impl ObjectImpl for LayersPanel {
+    fn constructed(&self) {
+        self.parent_constructed();
+        self.obj().build();
+    }
+}
+
+// Trait shared by all widgets
+impl BoxImpl for LayersPanel {}
+
+// Trait shared by all widgets
+impl WidgetImpl for LayersPanel {}
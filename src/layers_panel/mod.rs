@@ -0,0 +1,233 @@
+mod imp;
+
+use gtk::prelude::*;
+use gtk::subclass::prelude::*;
+use gtk::{gdk, glib};
+use rustshot_gtk::constants::{
+    CSS_CLASS_LAYERS_PANEL, CSS_CLASS_PRESSED, CSS_CLASS_SOLID, LAYERS_PANEL_HEIGHT,
+    LAYERS_PANEL_WIDTH,
+};
+
+use crate::drawing_area_manager::drawables::Layer;
+use crate::geometry::GeometryState;
+
+glib::wrapper! {
+    pub struct LayersPanel(ObjectSubclass<imp::LayersPanel>)
+        @extends gtk::Box,
+        @implements gtk::Accessible, gtk::Actionable, gtk::Widget, gtk::Orientable, gtk::Buildable, gtk::ConstraintTarget;
+}
+
+impl Default for LayersPanel {
+    fn default() -> Self {
+        glib::Object::new()
+    }
+}
+
+impl LayersPanel {
+    /// Create the collapsible frame; the rows are created by `refresh`
+    fn build(&self) {
+        self.set_orientation(gtk::Orientation::Vertical);
+        self.add_css_class(CSS_CLASS_LAYERS_PANEL);
+        self.set_width_request(LAYERS_PANEL_WIDTH);
+        self.set_valign(gtk::Align::Center);
+        self.set_vexpand(false);
+        self.set_hexpand(false);
+
+        let list_box = gtk::ListBox::new();
+        list_box.set_selection_mode(gtk::SelectionMode::None);
+
+        let scrolled = gtk::ScrolledWindow::new();
+        scrolled.set_policy(gtk::PolicyType::Never, gtk::PolicyType::Automatic);
+        scrolled.set_max_content_height(LAYERS_PANEL_HEIGHT);
+        scrolled.set_propagate_natural_height(true);
+        scrolled.set_child(Some(&list_box));
+
+        let expander = gtk::Expander::new(Some("\u{f5fd} Layers"));
+        expander.add_css_class(CSS_CLASS_SOLID);
+        expander.set_expanded(true);
+        expander.set_child(Some(&scrolled));
+        self.append(&expander);
+
+        self.imp()
+            .list_box
+            .set(list_box)
+            .expect("LayersPanel already built");
+    }
+
+    /// Rebuild the rows from the drawn items. The topmost item is the first row.
+    pub fn refresh(&self, geom: &GeometryState) {
+        let list_box = self.imp().list_box.get().expect("LayersPanel not built");
+        list_box.remove_all();
+
+        let items = geom.drawing.drawn_items();
+        for (idx, item) in items.iter().enumerate().rev() {
+            let row = self.create_row(geom, idx, item);
+            list_box.append(&row);
+        }
+    }
+
+    /// Refresh once the current signal handler has returned, since it may belong to a row
+    /// that is going to be destroyed
+    pub fn refresh_later(&self, geom: &GeometryState) {
+        glib::idle_add_local_once(glib::clone!(
+            #[strong]
+            geom,
+            #[weak(rename_to = panel)]
+            self,
+            move || {
+                panel.refresh(&geom);
+            }
+        ));
+    }
+
+    fn create_row(
+        &self,
+        geom: &GeometryState,
+        idx: usize,
+        item: &crate::drawing_area_manager::drawables::DrawableCollection,
+    ) -> gtk::ListBoxRow {
+        let info = item.layer_info();
+        let layer = item.layer().clone();
+
+        // 1️⃣  Type icon, color swatch and preview
+        let icon = gtk::Label::new(Some(info.icon));
+        icon.add_css_class(CSS_CLASS_SOLID);
+
+        let swatch = gtk::DrawingArea::new();
+        swatch.set_content_width(14);
+        swatch.set_content_height(14);
+        swatch.set_valign(gtk::Align::Center);
+        if let Some((r, g, b, a)) = info.color {
+            swatch.set_draw_func(move |_, cr, w, h| {
+                cr.rectangle(0.0, 0.0, w as f64, h as f64);
+                cr.set_source_rgba(r, g, b, a);
+                cr.fill_preserve().expect("LayersPanel swatch error");
+                cr.set_source_rgba(0.0, 0.0, 0.0, 1.0);
+                cr.set_line_width(1.0);
+                cr.stroke().expect("LayersPanel swatch error");
+            });
+        }
+
+        let label = gtk::Label::new(Some(&info.label));
+        label.set_hexpand(true);
+        label.set_xalign(0.0);
+        label.set_ellipsize(gtk::pango::EllipsizeMode::End);
+
+        // 2️⃣  Hide, lock and delete buttons
+        let hide_btn = gtk::ToggleButton::with_label(if layer.hidden.get() {
+            "\u{f070}"
+        } else {
+            "\u{f06e}"
+        });
+        hide_btn.add_css_class(CSS_CLASS_SOLID);
+        hide_btn.set_active(layer.hidden.get());
+        hide_btn.set_tooltip_text(Some("hide"));
+        hide_btn.connect_toggled(glib::clone!(
+            #[strong]
+            geom,
+            #[weak(rename_to = panel)]
+            self,
+            move |b| {
+                let hidden = b.is_active();
+                b.set_label(if hidden { "\u{f070}" } else { "\u{f06e}" });
+                geom.drawing.set_item_hidden(idx, hidden);
+                panel.refresh_later(&geom);
+            }
+        ));
+
+        let lock_btn = gtk::ToggleButton::with_label(if layer.locked.get() {
+            "\u{f023}"
+        } else {
+            "\u{f09c}"
+        });
+        lock_btn.add_css_class(CSS_CLASS_SOLID);
+        lock_btn.set_active(layer.locked.get());
+        lock_btn.set_tooltip_text(Some("lock"));
+        lock_btn.connect_toggled(glib::clone!(
+            #[strong]
+            geom,
+            #[weak(rename_to = panel)]
+            self,
+            move |b| {
+                let locked = b.is_active();
+                b.set_label(if locked { "\u{f023}" } else { "\u{f09c}" });
+                geom.drawing.set_item_locked(idx, locked);
+                panel.refresh_later(&geom);
+            }
+        ));
+
+        let delete_btn = gtk::Button::with_label("\u{f1f8}");
+        delete_btn.add_css_class(CSS_CLASS_SOLID);
+        delete_btn.set_tooltip_text(Some("delete"));
+        delete_btn.connect_clicked(glib::clone!(
+            #[strong]
+            geom,
+            #[weak(rename_to = panel)]
+            self,
+            move |_| {
+                geom.drawing.delete_item(idx);
+                panel.refresh_later(&geom);
+            }
+        ));
+
+        let hbox = gtk::Box::new(gtk::Orientation::Horizontal, 4);
+        hbox.append(&icon);
+        hbox.append(&swatch);
+        hbox.append(&label);
+        hbox.append(&hide_btn);
+        hbox.append(&lock_btn);
+        hbox.append(&delete_btn);
+
+        let row = gtk::ListBoxRow::new();
+        row.set_child(Some(&hbox));
+        if geom.drawing.is_selected(idx) {
+            row.add_css_class(CSS_CLASS_PRESSED);
+        }
+
+        // 3️⃣  Click selects the item, Shift+click extends the selection
+        let click = gtk::GestureClick::new();
+        click.connect_released(glib::clone!(
+            #[strong]
+            geom,
+            #[weak(rename_to = panel)]
+            self,
+            move |gesture, _, _, _| {
+                let extend = gesture
+                    .current_event_state()
+                    .contains(gdk::ModifierType::SHIFT_MASK);
+                geom.drawing.select_index(idx, extend);
+                panel.refresh_later(&geom);
+            }
+        ));
+        label.add_controller(click);
+
+        // 4️⃣  Drag and drop the rows to change the drawing order
+        let drag_source = gtk::DragSource::new();
+        drag_source.set_actions(gdk::DragAction::MOVE);
+        drag_source.set_content(Some(&gdk::ContentProvider::for_value(
+            &(idx as u32).to_value(),
+        )));
+        row.add_controller(drag_source);
+
+        let drop_target = gtk::DropTarget::new(u32::static_type(), gdk::DragAction::MOVE);
+        drop_target.connect_drop(glib::clone!(
+            #[strong]
+            geom,
+            #[weak(rename_to = panel)]
+            self,
+            #[upgrade_or]
+            false,
+            move |_, value, _, _| {
+                let Ok(from) = value.get::<u32>() else {
+                    return false;
+                };
+                geom.drawing.move_item(from as usize, idx);
+                panel.refresh_later(&geom);
+                true
+            }
+        ));
+        row.add_controller(drop_target);
+
+        row
+    }
+}
@@ -1,11 +1,14 @@
 use gtk::prelude::*;
+mod captured_screen;
 mod drawing_area_manager;
 mod drawing_area_settings;
 mod edge;
 mod geometry;
 mod handle;
+mod layers_panel;
 mod screenshot_box;
 mod spin_button;
+mod stamps;
 mod toolbox;
 mod toolbox_bar;
 mod toolbox_buttons;
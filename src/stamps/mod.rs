@@ -0,0 +1,129 @@
+use crate::captured_screen::texture_to_surface;
+use gtk::prelude::*;
+use gtk::{cairo, gdk, gdk_pixbuf, glib};
+use rustshot_gtk::constants::STAMPS_DIR;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+thread_local! {
+    // Decoded stamps by file name, so that they are not loaded again at every draw
+    static CACHE: RefCell<HashMap<String, Option<cairo::ImageSurface>>> =
+        RefCell::new(HashMap::new());
+    // Svg stamps rasterized at the size they are drawn, by file name and width in pixels
+    static RASTERS: RefCell<HashMap<(String, i32), Option<cairo::ImageSurface>>> =
+        RefCell::new(HashMap::new());
+}
+
+/// The svg stamps are rasterized at widths rounded up to a multiple of this, so that a stamp
+/// being resized does not fill the cache
+const RASTER_STEP: i32 = 64;
+const MAX_RASTER_WIDTH: i32 = 4096;
+
+/// Directory with the stamp images, e.g. ~/.config/rustshot-gtk/stamps
+pub fn stamps_dir() -> PathBuf {
+    glib::user_config_dir().join(STAMPS_DIR)
+}
+
+/// File names of the svg and png stamps, sorted.
+/// The directory is created when missing, so that users know where to put their images.
+pub fn list_stamps() -> Vec<String> {
+    let dir = stamps_dir();
+    if let Err(e) = std::fs::create_dir_all(&dir) {
+        eprintln!("Impossible to create {}: {}", dir.display(), e);
+        return Vec::new();
+    }
+
+    let Ok(entries) = std::fs::read_dir(&dir) else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| {
+                    ext.eq_ignore_ascii_case("svg") || ext.eq_ignore_ascii_case("png")
+                })
+        })
+        .filter_map(|path| path.file_name()?.to_str().map(str::to_string))
+        .collect();
+    names.sort();
+    names
+}
+
+/// Stamp image as a cairo surface, None if it can not be loaded
+pub fn load_stamp(name: &str) -> Option<cairo::ImageSurface> {
+    if name.is_empty() {
+        return None;
+    }
+    CACHE.with_borrow_mut(|cache| {
+        cache
+            .entry(name.to_string())
+            .or_insert_with(|| {
+                decode(name)
+                    .inspect_err(|e| eprintln!("Impossible to load the stamp {}: {}", name, e))
+                    .ok()
+            })
+            .clone()
+    })
+}
+
+/// Stamp image to paint about `width` device pixels wide. The svg stamps are rasterized at
+/// that size, so that they stay sharp when enlarged; the others are the decoded image.
+pub fn load_stamp_at(name: &str, width: f64) -> Option<cairo::ImageSurface> {
+    if !is_svg(name) {
+        return load_stamp(name);
+    }
+    let steps = (width / RASTER_STEP as f64).ceil() as i32;
+    let width = (steps * RASTER_STEP).clamp(RASTER_STEP, MAX_RASTER_WIDTH);
+    RASTERS.with_borrow_mut(|rasters| {
+        rasters
+            .entry((name.to_string(), width))
+            .or_insert_with(|| {
+                rasterize(name, width)
+                    .inspect_err(|e| eprintln!("Impossible to rasterize the stamp {}: {}", name, e))
+                    .ok()
+            })
+            .clone()
+    })
+}
+
+/// Forget the decoded stamps, so that edited files are loaded again
+pub fn clear_cache() {
+    CACHE.with_borrow_mut(|cache| cache.clear());
+    RASTERS.with_borrow_mut(|rasters| rasters.clear());
+}
+
+fn is_svg(name: &str) -> bool {
+    std::path::Path::new(name)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("svg"))
+}
+
+fn decode(name: &str) -> Result<cairo::ImageSurface, Box<dyn std::error::Error>> {
+    // Svg files are rasterized by the gdk-pixbuf loaders at their natural size, which gives
+    // the size of the stamp
+    let texture = gdk::Texture::from_filename(stamps_dir().join(name))?;
+    Ok(texture_to_surface(&texture)?)
+}
+
+fn rasterize(name: &str, width: i32) -> Result<cairo::ImageSurface, Box<dyn std::error::Error>> {
+    let pixbuf = gdk_pixbuf::Pixbuf::from_file_at_scale(stamps_dir().join(name), width, -1, true)?;
+    let format = if pixbuf.has_alpha() {
+        gdk::MemoryFormat::R8g8b8a8
+    } else {
+        gdk::MemoryFormat::R8g8b8
+    };
+    let texture = gdk::MemoryTexture::new(
+        pixbuf.width(),
+        pixbuf.height(),
+        format,
+        &pixbuf.read_pixel_bytes(),
+        pixbuf.rowstride() as usize,
+    )
+    .upcast::<gdk::Texture>();
+    Ok(texture_to_surface(&texture)?)
+}
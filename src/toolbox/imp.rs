@@ -15,6 +15,8 @@ pub struct Toolbox {
     pub button_pressed: Rc<Cell<bool>>,
     pub is_settings_box: Rc<Cell<bool>>,
     pub settings_box: RefCell<Option<gtk::Widget>>,
+    /// Opens the layers panel when no side gray box is wide enough to host it
+    pub layers_button: RefCell<Option<gtk::MenuButton>>,
 }
 
 #[glib::object_subclass]
@@ -34,6 +36,7 @@ impl ObjectSubclass for Toolbox {
             button_pressed: Rc::new(Cell::new(false)),
             is_settings_box: Rc::new(Cell::new(false)),
             settings_box: RefCell::new(None),
+            layers_button: RefCell::new(None),
         }
     }
 }
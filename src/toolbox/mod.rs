@@ -1,7 +1,7 @@
 mod imp;
 use super::geometry::GeometryState;
 use crate::toolbox_buttons::*;
-use rustshot_gtk::constants::TOOLBOX_BTN_SIZE;
+use rustshot_gtk::constants::{CSS_CLASS_TOOLBOX_BTN, LAYERS_PANEL_WIDTH, TOOLBOX_BTN_SIZE};
 // mod buttons;
 use crate::toolbox_bar::ToolboxBar;
 use gtk::{glib, prelude::*, subclass::prelude::*};
@@ -56,6 +56,16 @@ impl Toolbox {
     }
 
     pub fn create_toolbox_buttons(&self, geom: &GeometryState) -> std::io::Result<()> {
+        // Select
+        let btn = select_button::SelectButton::default();
+        btn.attach_gesture(geom);
+        self.add_widget_to_btn_list(ToolboxButton::Select(btn))?;
+
+        // Crop
+        let btn = crop_button::CropButton::default();
+        btn.attach_gesture(geom);
+        self.add_widget_to_btn_list(ToolboxButton::Crop(btn))?;
+
         // Full Circle
         let btn = arc_button::ArcButton::default();
         btn.attach_gesture(geom);
@@ -77,6 +87,11 @@ impl Toolbox {
         btn.attach_gesture(geom);
         self.add_widget_to_btn_list(ToolboxButton::Line(btn))?;
 
+        // Polyline and polygon
+        let btn = polygon_button::PolygonButton::default();
+        btn.attach_gesture(geom);
+        self.add_widget_to_btn_list(ToolboxButton::Polygon(btn))?;
+
         // Freehand
         let btn = freehand_button::FreehandButton::default();
         btn.attach_gesture(geom);
@@ -87,11 +102,61 @@ impl Toolbox {
         btn.attach_gesture(geom);
         self.add_widget_to_btn_list(ToolboxButton::NumberedCircles(btn))?;
 
-        // Numbered circles
+        // Pixelate and blur
+        let btn = obfuscate_button::ObfuscateButton::default();
+        btn.attach_gesture(geom);
+        self.add_widget_to_btn_list(ToolboxButton::Obfuscate(btn))?;
+
+        // Redaction
+        let btn = redact_button::RedactButton::default();
+        btn.attach_gesture(geom);
+        self.add_widget_to_btn_list(ToolboxButton::Redact(btn))?;
+
+        // Highlight box
+        let btn = highlight_button::HighlightButton::default();
+        btn.attach_gesture(geom);
+        self.add_widget_to_btn_list(ToolboxButton::Highlight(btn))?;
+
+        // Stamps
+        let btn = stamp_button::StampButton::default();
+        btn.attach_gesture(geom);
+        self.add_widget_to_btn_list(ToolboxButton::Stamp(btn))?;
+
+        // Images
+        let btn = image_button::ImageButton::default();
+        btn.attach_gesture(geom);
+        self.add_widget_to_btn_list(ToolboxButton::Image(btn))?;
+
+        // Spotlight
+        let btn = spotlight_button::SpotlightButton::default();
+        btn.attach_gesture(geom);
+        self.add_widget_to_btn_list(ToolboxButton::Spotlight(btn))?;
+
+        // Magnifier
+        let btn = magnifier_button::MagnifierButton::default();
+        btn.attach_gesture(geom);
+        self.add_widget_to_btn_list(ToolboxButton::Magnifier(btn))?;
+
+        // Measure
+        let btn = measure_button::MeasureButton::default();
+        btn.attach_gesture(geom);
+        self.add_widget_to_btn_list(ToolboxButton::Measure(btn))?;
+
+        // Eyedropper
+        let btn = eyedropper_button::EyedropperButton::default();
+        btn.attach_gesture(geom);
+        self.add_widget_to_btn_list(ToolboxButton::Eyedropper(btn))?;
+
+        // Input texts
         let btn = input_text_button::InputTextButton::default();
         btn.attach_gesture(geom);
         self.add_widget_to_btn_list(ToolboxButton::InputTexts(btn))?;
 
+        // Callouts
+        let btn = callout_button::CalloutButton::default();
+        btn.attach_gesture(geom);
+        self.add_widget_to_btn_list(ToolboxButton::Callout(btn))?;
+
         // Fullscreen
         let btn = fullscreen_button::FullscreenButton::default();
         btn.attach_gesture(geom);
@@ -120,12 +185,14 @@ impl Toolbox {
         let imp = self.imp();
 
         // Update the numbered_circle number
-        imp.buttons_list.borrow_mut()[5].update_number(
-            geom.settings
-                .numbered_circle
-                .get_value("number")
-                .get_i32()?,
-        )?;
+        let number = geom
+            .settings
+            .numbered_circle
+            .get_value("number")
+            .get_i32()?;
+        for button in imp.buttons_list.borrow().iter() {
+            button.update_number(number)?;
+        }
 
         Ok(())
     }
@@ -198,6 +265,7 @@ impl Toolbox {
                 tb.append(&settings);
             }
 
+            self.place_layers_panel(geom, &free_space);
             return Ok(());
         }
 
@@ -284,6 +352,8 @@ impl Toolbox {
             }
         }
 
+        self.place_layers_panel(geom, &free_space);
+
         let settings_box = imp.settings_box.borrow().clone();
         if let Some(settings) = settings_box {
             // Fill the bottom box
@@ -320,6 +390,68 @@ impl Toolbox {
         Ok(())
     }
 
+    /// Put the layers panel in the side gray box with enough free space, or else in a
+    /// popover opened from a button at the end of the toolbox
+    fn place_layers_panel(&self, geom: &GeometryState, free_space: &[i32]) {
+        let imp = self.imp();
+        self.detach_layers_panel(geom);
+        if geom.drawing.drawn_items().is_empty() {
+            return;
+        }
+
+        geom.layers_panel.refresh(geom);
+        if free_space[1] * TOOLBOX_BTN_SIZE >= LAYERS_PANEL_WIDTH {
+            geom.layers_panel.set_halign(gtk::Align::Start);
+            geom.right_box.append(&geom.layers_panel);
+        } else if free_space[3] * TOOLBOX_BTN_SIZE >= LAYERS_PANEL_WIDTH {
+            geom.layers_panel.set_halign(gtk::Align::End);
+            geom.left_box.append(&geom.layers_panel);
+        } else if let Some(last) = imp.buttons_list.borrow().last()
+            && let Some(parent) = last.as_ref().parent()
+            && let Ok(bar) = parent.downcast::<gtk::Box>()
+        {
+            let button = imp
+                .layers_button
+                .borrow_mut()
+                .get_or_insert_with(|| {
+                    let button = gtk::MenuButton::new();
+                    button.set_label("\u{f5fd}");
+                    button.set_tooltip_text(Some("Layers"));
+                    button.set_always_show_arrow(false);
+                    button.add_css_class(CSS_CLASS_TOOLBOX_BTN);
+                    button.set_popover(Some(&gtk::Popover::new()));
+                    button
+                })
+                .clone();
+            geom.layers_panel.set_halign(gtk::Align::Fill);
+            if let Some(popover) = button.popover() {
+                popover.set_child(Some(&geom.layers_panel));
+            }
+            bar.append(&button);
+        }
+    }
+
+    /// Take the layers panel and its popover button out of the toolbox
+    fn detach_layers_panel(&self, geom: &GeometryState) {
+        let imp = self.imp();
+        if let Some(button) = imp.layers_button.borrow().as_ref() {
+            button.popdown();
+            if let Some(popover) = button.popover() {
+                popover.set_child(None::<&gtk::Widget>);
+            }
+            if let Some(parent) = button.parent()
+                && let Ok(parent_box) = parent.downcast::<gtk::Box>()
+            {
+                parent_box.remove(button);
+            }
+        }
+        if let Some(parent) = geom.layers_panel.parent()
+            && let Ok(parent_box) = parent.downcast::<gtk::Box>()
+        {
+            parent_box.remove(&geom.layers_panel);
+        }
+    }
+
     pub fn remove_css_class(&self, class: &str) {
         let imp = self.imp();
         //  every button from their parents before attaching them again
@@ -330,6 +462,8 @@ impl Toolbox {
 
     pub fn stop_toolbox(&self, geom: &GeometryState) {
         let imp = self.imp();
+        self.detach_layers_panel(geom);
+
         // -----------------------------------------------------------------
         // 1️⃣  Remove every button from the box that currently owns it.
         // -----------------------------------------------------------------
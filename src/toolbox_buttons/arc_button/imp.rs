@@ -20,7 +20,7 @@ impl ObjectImpl for ArcButton {
         obj.set_vexpand(false);
         obj.set_halign(gtk::Align::End);
         obj.set_valign(gtk::Align::End);
-        obj.set_tooltip_text(Some("Draw circle"));
+        obj.set_tooltip_text(Some("Draw circle, ellipse, arc or pie slice"));
         obj.add_css_class(CSS_CLASS_TOOLBOX_BTN);
         obj.add_css_class(CSS_CLASS_SOLID);
         obj.set_width_request(TOOLBOX_BTN_SIZE);
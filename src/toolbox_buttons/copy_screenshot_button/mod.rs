@@ -25,6 +25,8 @@ impl CopyScreenshotButton {
             geometry,
             move |_, _, _, _| {
                 geometry.toolbox.stop_toolbox(&geometry);
+                // The selection boxes and the screen-only items must not end up in the screenshot
+                geometry.drawing.set_exporting(true);
             }
         ));
 
@@ -32,7 +34,10 @@ impl CopyScreenshotButton {
             #[strong]
             geometry,
             move |_| {
-                geometry.take_screenshot();
+                if let Err(e) = geometry.take_screenshot() {
+                    geometry.screenshot_failed(&e);
+                    return;
+                }
                 geometry.destroy();
                 //toolbox.set_visible(true);
             }
@@ -0,0 +1,34 @@
+use gtk::{glib, prelude::*, subclass::prelude::*};
+use rustshot_gtk::constants::{CSS_CLASS_SOLID, CSS_CLASS_TOOLBOX_BTN, TOOLBOX_BTN_SIZE};
+
+#[derive(Debug, Default)]
+pub struct CropButton {}
+
+#[glib::object_subclass]
+impl ObjectSubclass for CropButton {
+    const NAME: &'static str = "CropButton";
+    type Type = super::CropButton;
+    type ParentType = gtk::Button;
+}
+
+impl ObjectImpl for CropButton {
+    fn constructed(&self) {
+        self.parent_constructed();
+        let obj = self.obj();
+        obj.set_label("\u{f125}");
+        obj.set_hexpand(false);
+        obj.set_vexpand(false);
+        obj.set_halign(gtk::Align::End);
+        obj.set_valign(gtk::Align::End);
+        obj.set_tooltip_text(Some(
+            "Crop: drag a new area on the gray edges or move the handles, annotations are kept",
+        ));
+        obj.add_css_class(CSS_CLASS_TOOLBOX_BTN);
+        obj.add_css_class(CSS_CLASS_SOLID);
+        obj.set_width_request(TOOLBOX_BTN_SIZE);
+        obj.set_height_request(TOOLBOX_BTN_SIZE);
+    }
+}
+
+impl WidgetImpl for CropButton {}
+impl ButtonImpl for CropButton {}
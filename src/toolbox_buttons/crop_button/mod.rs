@@ -0,0 +1,43 @@
+mod imp;
+
+use crate::toolbox_buttons::*;
+
+use crate::geometry::GeometryState;
+use gtk::{glib, prelude::*};
+
+glib::wrapper! {
+    pub struct CropButton(ObjectSubclass<imp::CropButton>)
+        @extends gtk::Button, gtk::Widget,
+        @implements gtk::Accessible, gtk::Buildable, gtk::ConstraintTarget, gtk::Actionable;
+}
+
+impl Default for CropButton {
+    fn default() -> Self {
+        glib::Object::new()
+    }
+}
+
+impl CropButton {
+    pub fn attach_gesture(&self, geom: &GeometryState) {
+        self.connect_clicked(glib::clone!(
+            #[strong]
+            geom,
+            move |b| {
+                toggle_drawing(b.upcast_ref::<gtk::Widget>(), &geom, || {
+                    // Adjust the screenshot box instead of drawing
+                    geom.drawing.clear_selection();
+                    geom.set_cropping(true);
+
+                    // No settings for cropping
+                    geom.toolbox.stop_toolbox(&geom);
+                    geom.toolbox
+                        .set_settings_box(None)
+                        .expect("CropButton error in gesture connect_clicked set_settings_box");
+                    geom.toolbox
+                        .draw_toolbox(&geom)
+                        .expect("CropButton error in gesture connect_click draw_toolbox");
+                });
+            }
+        ));
+    }
+}
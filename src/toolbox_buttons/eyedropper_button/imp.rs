@@ -0,0 +1,32 @@
+use gtk::{glib, prelude::*, subclass::prelude::*};
+use rustshot_gtk::constants::{CSS_CLASS_SOLID, CSS_CLASS_TOOLBOX_BTN, TOOLBOX_BTN_SIZE};
+
+#[derive(Debug, Default)]
+pub struct EyedropperButton {}
+
+#[glib::object_subclass]
+impl ObjectSubclass for EyedropperButton {
+    const NAME: &'static str = "EyedropperButton";
+    type Type = super::EyedropperButton;
+    type ParentType = gtk::Button;
+}
+
+impl ObjectImpl for EyedropperButton {
+    fn constructed(&self) {
+        self.parent_constructed();
+        let obj = self.obj();
+        obj.set_label("\u{f1fb}");
+        obj.set_hexpand(false);
+        obj.set_vexpand(false);
+        obj.set_halign(gtk::Align::End);
+        obj.set_valign(gtk::Align::End);
+        obj.set_tooltip_text(Some("Pick a color from the screen"));
+        obj.add_css_class(CSS_CLASS_TOOLBOX_BTN);
+        obj.add_css_class(CSS_CLASS_SOLID);
+        obj.set_width_request(TOOLBOX_BTN_SIZE);
+        obj.set_height_request(TOOLBOX_BTN_SIZE);
+    }
+}
+
+impl WidgetImpl for EyedropperButton {}
+impl ButtonImpl for EyedropperButton {}
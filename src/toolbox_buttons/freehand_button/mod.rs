@@ -36,7 +36,10 @@ impl FreehandButton {
             move |b| {
                 toggle_drawing(b.upcast_ref::<gtk::Widget>(), &geom, || {
                     // Create drawable
-                    let drawable = DrawableCollection::FreeHands(FreeHandDraw::new(&geom.settings));
+                    let drawable = DrawableCollection::FreeHands(FreeHandDraw::new(
+                        &geom.settings,
+                        &geom.captured,
+                    ));
                     geom.drawing.create_new_drawable(&drawable);
 
                     // Set settings box
@@ -0,0 +1,59 @@
+mod imp;
+
+use crate::drawing_area_manager::drawables::{DrawableCollection, Image, ImageSource};
+use crate::toolbox_buttons::*;
+
+use crate::geometry::GeometryState;
+use crate::toolbox_settings_box::image::ImageSettingsBox;
+use gtk::{glib, prelude::*};
+
+glib::wrapper! {
+    pub struct ImageButton(ObjectSubclass<imp::ImageButton>)
+        @extends gtk::Button, gtk::Widget,
+        @implements gtk::Accessible, gtk::Buildable, gtk::ConstraintTarget, gtk::Actionable;
+}
+
+impl Default for ImageButton {
+    fn default() -> Self {
+        glib::Object::new()
+    }
+}
+
+impl ImageButton {
+    pub fn attach_gesture(&self, geom: &GeometryState) {
+        // Create settings box
+        let settings_box = ImageSettingsBox::default();
+        settings_box.new_horizontal(gtk::Align::Center);
+
+        // Picture placed by the next click, loaded from the settings box
+        let source = ImageSource::default();
+        settings_box
+            .populate_with_settings(geom, &source)
+            .expect("ImageButton attach_gesture error");
+
+        self.connect_clicked(glib::clone!(
+            #[strong]
+            geom,
+            #[strong]
+            settings_box,
+            #[strong]
+            source,
+            move |b| {
+                toggle_drawing(b.upcast_ref::<gtk::Widget>(), &geom, || {
+                    // Create drawable
+                    let drawable = DrawableCollection::Images(Image::new(&geom.settings, &source));
+                    geom.drawing.create_new_drawable(&drawable);
+
+                    // Set settings box
+                    geom.toolbox.stop_toolbox(&geom);
+                    geom.toolbox
+                        .set_settings_box(Some(settings_box.upcast_ref::<gtk::Widget>().clone()))
+                        .expect("ImageButton error in gesture connect_clicked set_settings_box");
+                    geom.toolbox
+                        .draw_toolbox(&geom)
+                        .expect("ImageButton error in gesture connect_click draw_toolbox");
+                });
+            }
+        ));
+    }
+}
@@ -0,0 +1,34 @@
+use gtk::{glib, prelude::*, subclass::prelude::*};
+use rustshot_gtk::constants::{CSS_CLASS_SOLID, CSS_CLASS_TOOLBOX_BTN, TOOLBOX_BTN_SIZE};
+
+#[derive(Debug, Default)]
+pub struct MagnifierButton {}
+
+#[glib::object_subclass]
+impl ObjectSubclass for MagnifierButton {
+    const NAME: &'static str = "MagnifierButton";
+    type Type = super::MagnifierButton;
+    type ParentType = gtk::Button;
+}
+
+impl ObjectImpl for MagnifierButton {
+    fn constructed(&self) {
+        self.parent_constructed();
+        let obj = self.obj();
+        obj.set_label("\u{f00e}");
+        obj.set_hexpand(false);
+        obj.set_vexpand(false);
+        obj.set_halign(gtk::Align::End);
+        obj.set_valign(gtk::Align::End);
+        obj.set_tooltip_text(Some(
+            "Magnify a region: drag the source, then drag the destination",
+        ));
+        obj.add_css_class(CSS_CLASS_TOOLBOX_BTN);
+        obj.add_css_class(CSS_CLASS_SOLID);
+        obj.set_width_request(TOOLBOX_BTN_SIZE);
+        obj.set_height_request(TOOLBOX_BTN_SIZE);
+    }
+}
+
+impl WidgetImpl for MagnifierButton {}
+impl ButtonImpl for MagnifierButton {}
@@ -1,14 +1,27 @@
 pub mod arc_button;
 pub mod arrow_button;
 pub mod box_button;
+pub mod callout_button;
 pub mod copy_screenshot_button;
+pub mod crop_button;
+pub mod eyedropper_button;
 pub mod freehand_button;
 pub mod fullscreen_button;
+pub mod highlight_button;
+pub mod image_button;
 pub mod input_text_button;
 pub mod line_button;
+pub mod magnifier_button;
+pub mod measure_button;
 pub mod numbered_circle_button;
+pub mod obfuscate_button;
+pub mod polygon_button;
+pub mod redact_button;
 pub mod save_screenshot_button;
 pub mod screen_recorder;
+pub mod select_button;
+pub mod spotlight_button;
+pub mod stamp_button;
 
 use crate::geometry::GeometryState;
 use gtk::prelude::*;
@@ -24,6 +37,7 @@ where
     if geom.toolbox.is_button_pressed() {
         geom.screenshot_box.set_screenshot_box_sensitivity(true);
         geom.drawing.set_drawing(false);
+        geom.set_cropping(false);
         geom.toolbox.set_button_pressed(false);
         // geom.settings_window.set_visible(false);
 
@@ -58,6 +72,10 @@ where
 
 #[derive(Debug)]
 pub enum ToolboxButton {
+    // Select
+    Select(select_button::SelectButton),
+    // Crop
+    Crop(crop_button::CropButton),
     // Full Circle
     FullCircle(arc_button::ArcButton),
     // Full Box
@@ -66,12 +84,34 @@ pub enum ToolboxButton {
     Arrow(arrow_button::ArrowButton),
     // Line
     Line(line_button::LineButton),
+    // Polyline and polygon
+    Polygon(polygon_button::PolygonButton),
     // Freehand
     Freehand(freehand_button::FreehandButton),
     // Numbered circles
     NumberedCircles(numbered_circle_button::NumberedCircleButton),
+    // Pixelate and blur
+    Obfuscate(obfuscate_button::ObfuscateButton),
+    // Redaction
+    Redact(redact_button::RedactButton),
+    // Highlight box
+    Highlight(highlight_button::HighlightButton),
+    // Stamps
+    Stamp(stamp_button::StampButton),
+    // Images
+    Image(image_button::ImageButton),
+    // Spotlight
+    Spotlight(spotlight_button::SpotlightButton),
+    // Magnifier
+    Magnifier(magnifier_button::MagnifierButton),
+    // Measure
+    Measure(measure_button::MeasureButton),
+    // Eyedropper
+    Eyedropper(eyedropper_button::EyedropperButton),
     // Input Texts
     InputTexts(input_text_button::InputTextButton),
+    // Callouts
+    Callout(callout_button::CalloutButton),
     // Fullscreen
     Fullscreen(fullscreen_button::FullscreenButton),
     // Take screenshot
@@ -94,13 +134,26 @@ impl ToolboxButton {
 impl AsRef<gtk::Widget> for ToolboxButton {
     fn as_ref(&self) -> &gtk::Widget {
         match self {
+            ToolboxButton::Select(btn) => btn.as_ref(),
+            ToolboxButton::Crop(btn) => btn.as_ref(),
             ToolboxButton::FullCircle(btn) => btn.as_ref(),
             ToolboxButton::FullBox(btn) => btn.as_ref(),
             ToolboxButton::Arrow(btn) => btn.as_ref(),
             ToolboxButton::Line(btn) => btn.as_ref(),
+            ToolboxButton::Polygon(btn) => btn.as_ref(),
             ToolboxButton::Freehand(btn) => btn.as_ref(),
             ToolboxButton::NumberedCircles(btn) => btn.as_ref(),
+            ToolboxButton::Obfuscate(btn) => btn.as_ref(),
+            ToolboxButton::Redact(btn) => btn.as_ref(),
+            ToolboxButton::Highlight(btn) => btn.as_ref(),
+            ToolboxButton::Stamp(btn) => btn.as_ref(),
+            ToolboxButton::Image(btn) => btn.as_ref(),
+            ToolboxButton::Spotlight(btn) => btn.as_ref(),
+            ToolboxButton::Magnifier(btn) => btn.as_ref(),
+            ToolboxButton::Measure(btn) => btn.as_ref(),
+            ToolboxButton::Eyedropper(btn) => btn.as_ref(),
             ToolboxButton::InputTexts(btn) => btn.as_ref(),
+            ToolboxButton::Callout(btn) => btn.as_ref(),
             ToolboxButton::Fullscreen(btn) => btn.as_ref(),
             ToolboxButton::TakeScreenshot(btn) => btn.as_ref(),
             ToolboxButton::SaveScreenshot(btn) => btn.as_ref(),
@@ -0,0 +1,34 @@
+use gtk::{glib, prelude::*, subclass::prelude::*};
+use rustshot_gtk::constants::{CSS_CLASS_SOLID, CSS_CLASS_TOOLBOX_BTN, TOOLBOX_BTN_SIZE};
+
+#[derive(Debug, Default)]
+pub struct PolygonButton {}
+
+#[glib::object_subclass]
+impl ObjectSubclass for PolygonButton {
+    const NAME: &'static str = "PolygonButton";
+    type Type = super::PolygonButton;
+    type ParentType = gtk::Button;
+}
+
+impl ObjectImpl for PolygonButton {
+    fn constructed(&self) {
+        self.parent_constructed();
+        let obj = self.obj();
+        obj.set_label("\u{f5ee}");
+        obj.set_hexpand(false);
+        obj.set_vexpand(false);
+        obj.set_halign(gtk::Align::End);
+        obj.set_valign(gtk::Align::End);
+        obj.set_tooltip_text(Some(
+            "Draw polyline or polygon: click the vertices, double click or Enter to finish",
+        ));
+        obj.add_css_class(CSS_CLASS_TOOLBOX_BTN);
+        obj.add_css_class(CSS_CLASS_SOLID);
+        obj.set_width_request(TOOLBOX_BTN_SIZE);
+        obj.set_height_request(TOOLBOX_BTN_SIZE);
+    }
+}
+
+impl WidgetImpl for PolygonButton {}
+impl ButtonImpl for PolygonButton {}
@@ -25,6 +25,8 @@ impl SaveScreenshotButton {
             geometry,
             move |_, _, _, _| {
                 geometry.toolbox.stop_toolbox(&geometry);
+                // The selection boxes and the screen-only items must not end up in the screenshot
+                geometry.drawing.set_exporting(true);
             }
         ));
 
@@ -32,7 +34,12 @@ impl SaveScreenshotButton {
             #[strong]
             geometry,
             move |_| {
-                geometry.take_screenshot();
+                // Never save the clipboard when the export failed
+                if let Err(e) = geometry.take_screenshot() {
+                    geometry.screenshot_failed(&e);
+                    return;
+                }
+                geometry.drawing.set_exporting(false);
                 geometry.save_screenshot();
             }
         ));
@@ -0,0 +1,62 @@
+mod imp;
+
+use crate::drawing_area_manager::drawables::{DrawableCollection, Spotlight, SpotlightRegions};
+use crate::toolbox_buttons::*;
+
+use crate::geometry::GeometryState;
+use crate::toolbox_settings_box::spotlight::SpotlightSettingsBox;
+use gtk::{glib, prelude::*};
+
+glib::wrapper! {
+    pub struct SpotlightButton(ObjectSubclass<imp::SpotlightButton>)
+        @extends gtk::Button, gtk::Widget,
+        @implements gtk::Accessible, gtk::Buildable, gtk::ConstraintTarget, gtk::Actionable;
+}
+
+impl Default for SpotlightButton {
+    fn default() -> Self {
+        glib::Object::new()
+    }
+}
+
+impl SpotlightButton {
+    pub fn attach_gesture(&self, geom: &GeometryState) {
+        // Create settings box
+        let settings_box = SpotlightSettingsBox::default();
+        settings_box.new_horizontal(gtk::Align::Center);
+        settings_box
+            .populate_with_settings(geom)
+            .expect("SpotlightButton attach_gesture error");
+
+        // All the spotlights are composed together
+        let regions = SpotlightRegions::default();
+
+        self.connect_clicked(glib::clone!(
+            #[strong]
+            geom,
+            #[strong]
+            settings_box,
+            #[strong]
+            regions,
+            move |b| {
+                toggle_drawing(b.upcast_ref::<gtk::Widget>(), &geom, || {
+                    // Create drawable
+                    let drawable =
+                        DrawableCollection::Spotlights(Spotlight::new(&geom.settings, &regions));
+                    geom.drawing.create_new_drawable(&drawable);
+
+                    // Set settings box
+                    geom.toolbox.stop_toolbox(&geom);
+                    geom.toolbox
+                        .set_settings_box(Some(settings_box.upcast_ref::<gtk::Widget>().clone()))
+                        .expect(
+                            "SpotlightButton error in gesture connect_clicked set_settings_box",
+                        );
+                    geom.toolbox
+                        .draw_toolbox(&geom)
+                        .expect("SpotlightButton error in gesture connect_click draw_toolbox");
+                });
+            }
+        ));
+    }
+}
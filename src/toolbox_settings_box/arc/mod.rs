@@ -249,7 +249,86 @@ impl ArcSettingsBox {
         ));
         fill_color_btn.add_controller(gest);
 
+        // shape (circle from the center, ellipse in the dragged box)
+        let shapes = ["circle", "ellipse"];
+        let shape = settings.get_value("shape").get_string()?;
+        let shape_dropdown = crate::toolbox_settings_box::label_dropdown(
+            &shapes,
+            shapes.iter().position(|s| *s == shape).unwrap_or(0) as u32,
+            "circle or ellipse",
+        );
+        shape_dropdown.connect_selected_item_notify(glib::clone!(
+            #[strong]
+            settings,
+            move |dw| {
+                let Some(shape) = shapes.get(dw.selected() as usize) else {
+                    println!("arc shape not found");
+                    return;
+                };
+                settings
+                    .set_value("shape", SettingValue::String(shape.to_string()))
+                    .expect("Error in ArcSettingsBox");
+            }
+        ));
+
+        // style (full, open arc or pie slice)
+        let styles = ["full", "arc", "pie"];
+        let style = settings.get_value("style").get_string()?;
+        let style_dropdown = crate::toolbox_settings_box::label_dropdown(
+            &styles,
+            styles.iter().position(|s| *s == style).unwrap_or(0) as u32,
+            "full, arc or pie slice",
+        );
+        style_dropdown.connect_selected_item_notify(glib::clone!(
+            #[strong]
+            settings,
+            move |dw| {
+                let Some(style) = styles.get(dw.selected() as usize) else {
+                    println!("arc style not found");
+                    return;
+                };
+                settings
+                    .set_value("style", SettingValue::String(style.to_string()))
+                    .expect("Error in ArcSettingsBox");
+            }
+        ));
+
+        // start and end angles, in degrees clockwise from 3 o'clock
+        let start_spin =
+            IntegerInput::new(settings.get_value("start_angle").get_f64()? as i32, 0, 360);
+        start_spin.connect_value_changed(glib::clone!(
+            #[strong]
+            settings,
+            move |s| {
+                settings
+                    .set_value("start_angle", SettingValue::F64(s.value() as f64))
+                    .expect("Error in ArcSettingsBox");
+            }
+        ));
+        start_spin.set_tooltip_text(Some("change start angle (degrees)"));
+
+        let end_spin = IntegerInput::new(settings.get_value("end_angle").get_f64()? as i32, 0, 360);
+        end_spin.connect_value_changed(glib::clone!(
+            #[strong]
+            settings,
+            move |s| {
+                settings
+                    .set_value("end_angle", SettingValue::F64(s.value() as f64))
+                    .expect("Error in ArcSettingsBox");
+            }
+        ));
+        end_spin.set_tooltip_text(Some("change end angle (degrees)"));
+
         // set children
+        let shape_box: gtk::Box = gtk::Box::new(gtk::Orientation::Horizontal, 4);
+        shape_box.append(&shape_dropdown);
+        shape_box.append(&style_dropdown);
+        shape_box.append(&start_spin);
+        shape_box.append(&end_spin);
+        crate::toolbox_settings_box::add_expandable_row(
+            self, "\u{f200}", "shape", "fas", shape_box, true,
+        );
+
         let border_box: gtk::Box = gtk::Box::new(gtk::Orientation::Horizontal, 4);
         border_box.append(&border_checkbox);
         border_box.append(&spin);
@@ -258,13 +337,50 @@ impl ArcSettingsBox {
             self, "\u{f853}", "border", "fal", border_box, true,
         );
 
+        // gradient fill
+        let gradient_box = crate::toolbox_settings_box::gradient_box(
+            &settings.get_value("fill_mode").get_string()?,
+            &settings.get_value("fill_stops").get_string()?,
+            settings.get_value("fill_angle").get_f64()?,
+            glib::clone!(
+                #[strong]
+                settings,
+                move |key: &str, value: SettingValue| {
+                    settings
+                        .set_value(key, value)
+                        .expect("Error in ArcSettingsBox");
+                }
+            ),
+        );
+
         let fill_box: gtk::Box = gtk::Box::new(gtk::Orientation::Horizontal, 4);
         fill_box.append(&fill_checkbox);
         fill_box.append(&fill_color_btn);
+        fill_box.append(&gradient_box);
         crate::toolbox_settings_box::add_expandable_row(
             self, "\u{f575}", "fill", "fas", fill_box, true,
         );
 
+        // drop shadow
+        crate::toolbox_settings_box::add_shadow_row(
+            self,
+            geom,
+            glib::clone!(
+                #[strong]
+                settings,
+                move |key| settings.get_value(key)
+            ),
+            glib::clone!(
+                #[strong]
+                settings,
+                move |key, value| {
+                    settings
+                        .set_value(key, value)
+                        .expect("Error in ArcSettingsBox");
+                }
+            ),
+        );
+
         Ok(())
     }
 }
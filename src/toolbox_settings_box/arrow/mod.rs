@@ -141,7 +141,253 @@ impl ArrowSettingsBox {
         ));
         color_color_btn.add_controller(gest);
 
+        // outline color
+        let outline_color_dialog = gtk::ColorDialog::new();
+        let outline_color_btn = gtk::ColorDialogButton::new(Some(outline_color_dialog.clone()));
+
+        // get actual color
+        let outline_color = gtk::gdk::RGBA::new(
+            settings.get_value("outline_r").get_f64()? as f32,
+            settings.get_value("outline_g").get_f64()? as f32,
+            settings.get_value("outline_b").get_f64()? as f32,
+            settings.get_value("outline_a").get_f64()? as f32,
+        );
+        outline_color_btn.set_rgba(&outline_color);
+
+        let gest = gtk::GestureClick::new();
+        gest.connect_pressed(glib::clone!(
+            #[strong]
+            geom,
+            #[weak]
+            outline_color_dialog,
+            #[weak]
+            outline_color_btn,
+            move |_, _, _, _| {
+                // create color dialog
+                let cancellable = gio::Cancellable::new();
+
+                // clone geometry
+                let geom = geom.clone();
+
+                // Dialog
+                let btn = outline_color_btn.clone();
+                outline_color_dialog.set_title("Pick color");
+                outline_color_dialog.choose_rgba(
+                    Some(&geom.window),
+                    Some(&outline_color),
+                    Some(&cancellable),
+                    // gtk::gio::Cancellable::NONE,
+                    move |res| {
+                        if let Ok(color) = res {
+                            btn.set_rgba(&color);
+                            let r: f64 = color.red() as f64;
+                            let g: f64 = color.green() as f64;
+                            let b: f64 = color.blue() as f64;
+                            let a: f64 = color.alpha() as f64;
+                            geom.settings
+                                .arrow
+                                .set_value("outline_r", SettingValue::F64(r))
+                                .expect("ColorChoser Error");
+                            geom.settings
+                                .arrow
+                                .set_value("outline_g", SettingValue::F64(g))
+                                .expect("ColorChoser Error");
+                            geom.settings
+                                .arrow
+                                .set_value("outline_b", SettingValue::F64(b))
+                                .expect("ColorChoser Error");
+                            geom.settings
+                                .arrow
+                                .set_value("outline_a", SettingValue::F64(a))
+                                .expect("ColorChoser Error");
+                            // geom.drawing.set_rgba(r, g, b, a);
+                        } else {
+                            println!("No color found");
+                        }
+                    },
+                );
+            }
+        ));
+        outline_color_btn.add_controller(gest);
+
+        // outline check box and size
+        let outline_checkbox = gtk::CheckButton::with_label("");
+        outline_checkbox.set_active(settings.get_value("outline").get_bool()?);
+        outline_checkbox.set_tooltip_text(Some("disable outline"));
+        outline_checkbox.connect_toggled(glib::clone!(
+            #[strong]
+            settings,
+            move |cb| {
+                let is_active = cb.is_active();
+                if is_active {
+                    cb.set_tooltip_text(Some("disable outline"));
+                } else {
+                    cb.set_tooltip_text(Some("enable outline"));
+                }
+                settings
+                    .set_value("outline", SettingValue::Bool(is_active))
+                    .expect("outline_checkbox error");
+            }
+        ));
+
+        let outline_spin =
+            IntegerInput::new(settings.get_value("outline_size").get_f64()? as i32, 1, 50);
+        outline_spin.connect_value_changed(glib::clone!(
+            #[strong]
+            settings,
+            move |s| {
+                let sz = s.value();
+                if sz > 0 {
+                    settings
+                        .set_value("outline_size", SettingValue::F64(sz as f64))
+                        .expect("Error in ArrowSettingButton");
+                }
+            }
+        ));
+        outline_spin.set_tooltip_text(Some("change outline size"));
+
+        // head style and size
+        let head_styles = ["open", "triangle", "dot", "bar"];
+        let head_style = settings.get_value("head_style").get_string()?;
+        let head_dropdown = crate::toolbox_settings_box::label_dropdown(
+            &["\u{f105}", "\u{f0da}", "\u{f111}", "|"],
+            head_styles
+                .iter()
+                .position(|h| *h == head_style)
+                .unwrap_or(0) as u32,
+            "change head style",
+        );
+        head_dropdown.connect_selected_item_notify(glib::clone!(
+            #[strong]
+            settings,
+            move |dw| {
+                let Some(style) = head_styles.get(dw.selected() as usize) else {
+                    println!("head style not found");
+                    return;
+                };
+                settings
+                    .set_value("head_style", SettingValue::String(style.to_string()))
+                    .expect("Error in ArrowSettingButton");
+            }
+        ));
+
+        let head_spin =
+            IntegerInput::new(settings.get_value("arrow_size").get_f64()? as i32, 1, 100);
+        head_spin.connect_value_changed(glib::clone!(
+            #[strong]
+            settings,
+            move |s| {
+                let sz = s.value();
+                if sz > 0 {
+                    settings
+                        .set_value("arrow_size", SettingValue::F64(sz as f64))
+                        .expect("Error in ArrowSettingButton");
+                }
+            }
+        ));
+        head_spin.set_tooltip_text(Some("change head size"));
+
+        // double headed and curved
+        let double_checkbox = gtk::CheckButton::with_label("");
+        double_checkbox.set_active(settings.get_value("double_headed").get_bool()?);
+        double_checkbox.set_tooltip_text(Some("double headed"));
+        double_checkbox.connect_toggled(glib::clone!(
+            #[strong]
+            settings,
+            move |cb| {
+                settings
+                    .set_value("double_headed", SettingValue::Bool(cb.is_active()))
+                    .expect("double_checkbox error");
+            }
+        ));
+
+        let curved_checkbox = gtk::CheckButton::with_label("");
+        curved_checkbox.set_active(settings.get_value("curved").get_bool()?);
+        curved_checkbox
+            .set_tooltip_text(Some("curved, drag the control point with the select tool"));
+        curved_checkbox.connect_toggled(glib::clone!(
+            #[strong]
+            settings,
+            move |cb| {
+                settings
+                    .set_value("curved", SettingValue::Bool(cb.is_active()))
+                    .expect("curved_checkbox error");
+            }
+        ));
+
+        // dash pattern
+        let dashes = ["solid", "dashed", "dotted", "custom"];
+        let dash = settings.get_value("dash").get_string()?;
+        let dash_dropdown = crate::toolbox_settings_box::label_dropdown(
+            &["\u{f068}", "- -", "···", "\u{f1de}"],
+            dashes.iter().position(|d| *d == dash).unwrap_or(0) as u32,
+            "change dash pattern",
+        );
+
+        // custom pattern, dash and gap lengths in pixels
+        let pattern_entry = gtk::Entry::new();
+        pattern_entry.set_text(&settings.get_value("dash_pattern").get_string()?);
+        pattern_entry.set_placeholder_text(Some("8,4"));
+        pattern_entry.set_width_chars(6);
+        pattern_entry.set_sensitive(dash == "custom");
+        pattern_entry.set_tooltip_text(Some("custom dash pattern: dash,gap,... in px"));
+        pattern_entry.connect_changed(glib::clone!(
+            #[strong]
+            settings,
+            move |e| {
+                settings
+                    .set_value("dash_pattern", SettingValue::String(e.text().to_string()))
+                    .expect("Error in ArrowSettingButton");
+            }
+        ));
+
+        dash_dropdown.connect_selected_item_notify(glib::clone!(
+            #[strong]
+            settings,
+            #[weak]
+            pattern_entry,
+            move |dw| {
+                let Some(dash) = dashes.get(dw.selected() as usize) else {
+                    println!("dash pattern not found");
+                    return;
+                };
+                pattern_entry.set_sensitive(*dash == "custom");
+                settings
+                    .set_value("dash", SettingValue::String(dash.to_string()))
+                    .expect("Error in ArrowSettingButton");
+            }
+        ));
+
         // set child
+        let head_box: gtk::Box = gtk::Box::new(gtk::Orientation::Horizontal, 4);
+        head_box.append(&head_dropdown);
+        head_box.append(&head_spin);
+        head_box.append(&double_checkbox);
+        head_box.append(&curved_checkbox);
+        crate::toolbox_settings_box::add_expandable_row(
+            self, "\u{f061}", "head", "fas", head_box, true,
+        );
+
+        let outline_box: gtk::Box = gtk::Box::new(gtk::Orientation::Horizontal, 4);
+        outline_box.append(&outline_checkbox);
+        outline_box.append(&outline_spin);
+        outline_box.append(&outline_color_btn);
+        crate::toolbox_settings_box::add_expandable_row(
+            self,
+            "\u{f853}",
+            "outline",
+            "fal",
+            outline_box,
+            true,
+        );
+
+        let dash_box: gtk::Box = gtk::Box::new(gtk::Orientation::Horizontal, 4);
+        dash_box.append(&dash_dropdown);
+        dash_box.append(&pattern_entry);
+        crate::toolbox_settings_box::add_expandable_row(
+            self, "\u{f5cb}", "dash", "fas", dash_box, true,
+        );
+
         let fill_box: gtk::Box = gtk::Box::new(gtk::Orientation::Horizontal, 4);
         fill_box.append(&color_color_btn);
         fill_box.append(&spin);
@@ -149,6 +395,26 @@ impl ArrowSettingsBox {
             self, "\u{f575}", "fill", "fas", fill_box, true,
         );
 
+        // drop shadow
+        crate::toolbox_settings_box::add_shadow_row(
+            self,
+            geom,
+            glib::clone!(
+                #[strong]
+                settings,
+                move |key| settings.get_value(key)
+            ),
+            glib::clone!(
+                #[strong]
+                settings,
+                move |key, value| {
+                    settings
+                        .set_value(key, value)
+                        .expect("Error in ArrowSettingsBox");
+                }
+            ),
+        );
+
         Ok(())
     }
 }
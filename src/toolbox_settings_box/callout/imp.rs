@@ -0,0 +1,23 @@
+use gtk::glib;
+use gtk::subclass::prelude::*;
+
+// Object holding the state
+#[derive(Debug, Default)]
+pub struct CalloutSettingsBox {}
+
+// The central trait for subclassing a GObject
+#[glib::object_subclass]
+impl ObjectSubclass for CalloutSettingsBox {
+    const NAME: &'static str = "CalloutSettingsBox";
+    type Type = super::CalloutSettingsBox;
+    type ParentType = gtk::Box;
+}
+
+// Trait shared by all GObjects
+impl ObjectImpl for CalloutSettingsBox {}
+
+// Trait shared by all widgets
+impl BoxImpl for CalloutSettingsBox {}
+
+// Trait shared by all widgets
+impl WidgetImpl for CalloutSettingsBox {}
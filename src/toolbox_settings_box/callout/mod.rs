@@ -0,0 +1,124 @@
+mod imp;
+
+use gtk::glib;
+use gtk::prelude::*;
+use rustshot_gtk::constants::{CSS_CLASS_TOOLBOX_BAR, TOOLBOX_BTN_SIZE, TOOLBOX_SETTINGS_BTN_SIZE};
+
+use crate::drawing_area_settings::SettingValue;
+use crate::geometry::GeometryState;
+use crate::spin_button::IntegerInput;
+use crate::toolbox_settings_box::input_text::InputTextSettingsBox;
+
+glib::wrapper! {
+    pub struct CalloutSettingsBox(ObjectSubclass<imp::CalloutSettingsBox>)
+        @extends gtk::Box,
+        @implements gtk::Accessible,  gtk::Actionable, gtk::Widget, gtk::Orientable, gtk::Buildable, gtk::ConstraintTarget;
+}
+
+impl Default for CalloutSettingsBox {
+    fn default() -> Self {
+        glib::Object::new()
+    }
+}
+
+impl CalloutSettingsBox {
+    pub fn new_vertical(&self, size: i32, align: gtk::Align) {
+        self.set_orientation(gtk::Orientation::Vertical);
+        self.add_css_class(CSS_CLASS_TOOLBOX_BAR);
+        self.set_width_request(size);
+        self.set_halign(align);
+        self.set_valign(gtk::Align::Center);
+    }
+
+    pub fn new_horizontal(&self, align: gtk::Align) {
+        self.set_orientation(gtk::Orientation::Horizontal);
+        self.add_css_class(CSS_CLASS_TOOLBOX_BAR);
+        self.set_height_request(TOOLBOX_SETTINGS_BTN_SIZE);
+        self.set_valign(align);
+    }
+
+    pub fn new_fullscreen(&self, geom: &GeometryState) {
+        // create toolbox
+        self.set_orientation(gtk::Orientation::Horizontal);
+        self.add_css_class(CSS_CLASS_TOOLBOX_BAR);
+        self.set_halign(gtk::Align::Center);
+        self.set_height_request(TOOLBOX_BTN_SIZE);
+        self.set_margin_start(geom.full_w / 2);
+        self.set_margin_top(10);
+        self.set_margin_bottom(geom.full_h - TOOLBOX_BTN_SIZE);
+    }
+
+    pub fn populate_with_settings(&self, geom: &GeometryState) -> std::io::Result<()> {
+        // get callout settings, the style is the one of the input texts
+        let settings = geom.settings.callout.clone();
+
+        // wrap width
+        let wrap_spin =
+            IntegerInput::new(settings.get_value("wrap_width").get_f64()? as i32, 20, 2000);
+        wrap_spin.connect_value_changed(glib::clone!(
+            #[strong]
+            settings,
+            move |s| {
+                settings
+                    .set_value("wrap_width", SettingValue::F64(s.value() as f64))
+                    .expect("Error in CalloutSettingsBox");
+            }
+        ));
+        wrap_spin.set_tooltip_text(Some("change text wrap width"));
+
+        // corner radius
+        let radius_spin = IntegerInput::new(
+            settings.get_value("corner_radius").get_f64()? as i32,
+            0,
+            100,
+        );
+        radius_spin.connect_value_changed(glib::clone!(
+            #[strong]
+            settings,
+            move |s| {
+                settings
+                    .set_value("corner_radius", SettingValue::F64(s.value() as f64))
+                    .expect("Error in CalloutSettingsBox");
+            }
+        ));
+        radius_spin.set_tooltip_text(Some("change corner radius"));
+
+        // set children
+        let bubble_box: gtk::Box = gtk::Box::new(gtk::Orientation::Horizontal, 4);
+        bubble_box.append(&wrap_spin);
+        bubble_box.append(&radius_spin);
+        crate::toolbox_settings_box::add_expandable_row(
+            self, "\u{f075}", "bubble", "fas", bubble_box, true,
+        );
+
+        // border, fill and font rows of the input texts, moved into this box
+        let style_box = InputTextSettingsBox::default();
+        style_box.populate_with_settings(geom)?;
+        while let Some(child) = style_box.first_child() {
+            style_box.remove(&child);
+            self.append(&child);
+        }
+
+        // drop shadow
+        crate::toolbox_settings_box::add_shadow_row(
+            self,
+            geom,
+            glib::clone!(
+                #[strong]
+                settings,
+                move |key| settings.get_value(key)
+            ),
+            glib::clone!(
+                #[strong]
+                settings,
+                move |key, value| {
+                    settings
+                        .set_value(key, value)
+                        .expect("Error in CalloutSettingsBox");
+                }
+            ),
+        );
+
+        Ok(())
+    }
+}
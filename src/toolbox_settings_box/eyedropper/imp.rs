@@ -0,0 +1,23 @@
+use gtk::glib;
+use gtk::subclass::prelude::*;
+
+// Object holding the state
+#[derive(Debug, Default)]
+pub struct EyedropperSettingsBox {}
+
+// The central trait for subclassing a GObject
+#[glib::object_subclass]
+impl ObjectSubclass for EyedropperSettingsBox {
+    const NAME: &'static str = "EyedropperSettingsBox";
+    type Type = super::EyedropperSettingsBox;
+    type ParentType = gtk::Box;
+}
+
+// Trait shared by all GObjects
+impl ObjectImpl for EyedropperSettingsBox {}
+
+// Trait shared by all widgets
+impl BoxImpl for EyedropperSettingsBox {}
+
+// Trait shared by all widgets
+impl WidgetImpl for EyedropperSettingsBox {}
@@ -0,0 +1,154 @@
+mod imp;
+
+use gtk::glib;
+use gtk::prelude::*;
+use rustshot_gtk::constants::{CSS_CLASS_TOOLBOX_BAR, TOOLBOX_BTN_SIZE, TOOLBOX_SETTINGS_BTN_SIZE};
+
+use crate::drawing_area_settings::SettingValue;
+use crate::geometry::GeometryState;
+
+glib::wrapper! {
+    pub struct EyedropperSettingsBox(ObjectSubclass<imp::EyedropperSettingsBox>)
+        @extends gtk::Box,
+        @implements gtk::Accessible,  gtk::Actionable, gtk::Widget, gtk::Orientable, gtk::Buildable, gtk::ConstraintTarget;
+}
+
+impl Default for EyedropperSettingsBox {
+    fn default() -> Self {
+        glib::Object::new()
+    }
+}
+
+impl EyedropperSettingsBox {
+    pub fn new_vertical(&self, size: i32, align: gtk::Align) {
+        self.set_orientation(gtk::Orientation::Vertical);
+        self.add_css_class(CSS_CLASS_TOOLBOX_BAR);
+        self.set_width_request(size);
+        self.set_halign(align);
+        self.set_valign(gtk::Align::Center);
+    }
+
+    pub fn new_horizontal(&self, align: gtk::Align) {
+        self.set_orientation(gtk::Orientation::Horizontal);
+        self.add_css_class(CSS_CLASS_TOOLBOX_BAR);
+        self.set_height_request(TOOLBOX_SETTINGS_BTN_SIZE);
+        self.set_valign(align);
+    }
+
+    pub fn new_fullscreen(&self, geom: &GeometryState) {
+        // create toolbox
+        self.set_orientation(gtk::Orientation::Horizontal);
+        self.add_css_class(CSS_CLASS_TOOLBOX_BAR);
+        self.set_halign(gtk::Align::Center);
+        self.set_height_request(TOOLBOX_BTN_SIZE);
+        self.set_margin_start(geom.full_w / 2);
+        self.set_margin_top(10);
+        self.set_margin_bottom(geom.full_h - TOOLBOX_BTN_SIZE);
+    }
+
+    pub fn populate_with_settings(&self, geom: &GeometryState) -> std::io::Result<()> {
+        // get eyedropper settings
+        let settings = geom.settings.eyedropper.clone();
+
+        // what to do with the picked color
+        let actions = ["copy", "tool"];
+        let action = settings.get_value("action").get_string()?;
+        let action_dropdown = crate::toolbox_settings_box::label_dropdown(
+            &["\u{f0c5}", "\u{f53f}"],
+            actions.iter().position(|a| *a == action).unwrap_or(0) as u32,
+            "copy the color or set it as a tool color",
+        );
+
+        // clipboard format
+        let formats = ["hex", "rgb", "hsl"];
+        let format = settings.get_value("format").get_string()?;
+        let format_dropdown = crate::toolbox_settings_box::label_dropdown(
+            &["HEX", "RGB", "HSL"],
+            formats.iter().position(|f| *f == format).unwrap_or(0) as u32,
+            "format copied to the clipboard",
+        );
+        format_dropdown.set_sensitive(action == "copy");
+        format_dropdown.connect_selected_item_notify(glib::clone!(
+            #[strong]
+            settings,
+            move |dw| {
+                let Some(format) = formats.get(dw.selected() as usize) else {
+                    println!("eyedropper format not found");
+                    return;
+                };
+                settings
+                    .set_value("format", SettingValue::String(format.to_string()))
+                    .expect("Error in EyedropperSettingsBox");
+            }
+        ));
+
+        // tool that gets the color
+        let targets = [
+            ("rect.border", "box border"),
+            ("rect.fill", "box fill"),
+            ("arc.border", "circle border"),
+            ("arc.fill", "circle fill"),
+            ("arrow.color", "arrow"),
+            ("line.color", "line"),
+            ("polygon.border", "polygon border"),
+            ("polygon.fill", "polygon fill"),
+            ("freehand.color", "freehand"),
+            ("numbered_circle.fill", "number fill"),
+            ("input_text.font", "text"),
+            ("redact.color", "redaction"),
+            ("measure.color", "ruler"),
+        ];
+        let target = settings.get_value("target").get_string()?;
+        let labels: Vec<&str> = targets.iter().map(|(_, label)| *label).collect();
+        let target_dropdown = crate::toolbox_settings_box::label_dropdown(
+            &labels,
+            targets.iter().position(|(t, _)| *t == target).unwrap_or(0) as u32,
+            "tool that gets the picked color",
+        );
+        target_dropdown.set_sensitive(action == "tool");
+        target_dropdown.connect_selected_item_notify(glib::clone!(
+            #[strong]
+            settings,
+            move |dw| {
+                let Some((target, _)) = targets.get(dw.selected() as usize) else {
+                    println!("eyedropper target not found");
+                    return;
+                };
+                settings
+                    .set_value("target", SettingValue::String(target.to_string()))
+                    .expect("Error in EyedropperSettingsBox");
+            }
+        ));
+
+        action_dropdown.connect_selected_item_notify(glib::clone!(
+            #[strong]
+            settings,
+            #[weak]
+            format_dropdown,
+            #[weak]
+            target_dropdown,
+            move |dw| {
+                let Some(action) = actions.get(dw.selected() as usize) else {
+                    println!("eyedropper action not found");
+                    return;
+                };
+                format_dropdown.set_sensitive(*action == "copy");
+                target_dropdown.set_sensitive(*action == "tool");
+                settings
+                    .set_value("action", SettingValue::String(action.to_string()))
+                    .expect("Error in EyedropperSettingsBox");
+            }
+        ));
+
+        // set children
+        let pick_box: gtk::Box = gtk::Box::new(gtk::Orientation::Horizontal, 4);
+        pick_box.append(&action_dropdown);
+        pick_box.append(&format_dropdown);
+        pick_box.append(&target_dropdown);
+        crate::toolbox_settings_box::add_expandable_row(
+            self, "\u{f1fb}", "pick", "fas", pick_box, true,
+        );
+
+        Ok(())
+    }
+}
@@ -6,7 +6,7 @@ use rustshot_gtk::constants::{
     CSS_CLASS_SOLID, CSS_CLASS_TOOLBOX_BAR, TOOLBOX_BTN_SIZE, TOOLBOX_SETTINGS_BTN_SIZE,
 };
 
-use crate::drawing_area_settings::SettingValue;
+use crate::drawing_area_settings::{FreehandSettingsRc, SettingValue};
 use crate::geometry::GeometryState;
 use crate::spin_button::IntegerInput;
 
@@ -56,8 +56,23 @@ impl FreehandSettingsBox {
         // get freehand settings
         let settings = geom.settings.freehand.clone();
 
-        // size
-        let spin = IntegerInput::new(settings.get_value("size").get_f64()? as i32, 0, 100);
+        // size, the eraser keeps its own so that it does not change the pen width
+        let size_key = |settings: &FreehandSettingsRc| {
+            if settings
+                .get_value("tool")
+                .get_string()
+                .is_ok_and(|tool| tool == "eraser")
+            {
+                "eraser_size"
+            } else {
+                "size"
+            }
+        };
+        let spin = IntegerInput::new(
+            settings.get_value(size_key(&settings)).get_f64()? as i32,
+            0,
+            100,
+        );
         spin.connect_value_changed(glib::clone!(
             // #[strong]
             // spin,
@@ -67,7 +82,7 @@ impl FreehandSettingsBox {
                 let sz = s.value();
                 if sz.abs() > 0 && sz.signum() > 0 {
                     settings
-                        .set_value("size", SettingValue::F64(sz as f64))
+                        .set_value(size_key(&settings), SettingValue::F64(sz as f64))
                         .expect("Error in FreehandSettingButton");
                 }
             }
@@ -142,8 +157,8 @@ impl FreehandSettingsBox {
         ));
         color_color_btn.add_controller(gest);
 
-        // Pen tools (pen, highlighter, fountain pen)
-        let line_tools = vec!["\u{f304}", "\u{f591}", "\u{f5ac}"];
+        // Pen tools (pen, highlighter, fountain pen, eraser)
+        let line_tools = vec!["\u{f304}", "\u{f591}", "\u{f5ac}", "\u{f12d}"];
         let model = gtk::StringList::new(&line_tools);
 
         let factory = gtk::SignalListItemFactory::new();
@@ -190,6 +205,8 @@ impl FreehandSettingsBox {
         line_tool_dropdown.connect_selected_item_notify(glib::clone!(
             #[strong]
             settings,
+            #[strong]
+            spin,
             move |dw| {
                 match dw.selected() {
                     0 => {
@@ -211,8 +228,16 @@ impl FreehandSettingsBox {
                             .unwrap();
                         settings.set_value("size", SettingValue::F64(4.0)).unwrap();
                     }
+                    3 => {
+                        settings
+                            .set_value("tool", SettingValue::String("eraser".to_string()))
+                            .unwrap();
+                    }
                     _ => println!/*;unreachable!*/("line_tool not found"),
                 };
+                if let Ok(size) = settings.get_value(size_key(&settings)).get_f64() {
+                    spin.set_value(size as i32);
+                }
             }
         ));
 
@@ -226,6 +251,26 @@ impl FreehandSettingsBox {
 
         self.append(&line_tool_dropdown);
 
+        // drop shadow
+        crate::toolbox_settings_box::add_shadow_row(
+            self,
+            geom,
+            glib::clone!(
+                #[strong]
+                settings,
+                move |key| settings.get_value(key)
+            ),
+            glib::clone!(
+                #[strong]
+                settings,
+                move |key, value| {
+                    settings
+                        .set_value(key, value)
+                        .expect("Error in FreehandSettingsBox");
+                }
+            ),
+        );
+
         Ok(())
     }
 }
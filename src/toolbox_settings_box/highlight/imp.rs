@@ -0,0 +1,23 @@
+use gtk::glib;
+use gtk::subclass::prelude::*;
+
+// Object holding the state
+#[derive(Debug, Default)]
+pub struct HighlightSettingsBox {}
+
+// The central trait for subclassing a GObject
+#[glib::object_subclass]
+impl ObjectSubclass for HighlightSettingsBox {
+    const NAME: &'static str = "HighlightSettingsBox";
+    type Type = super::HighlightSettingsBox;
+    type ParentType = gtk::Box;
+}
+
+// Trait shared by all GObjects
+impl ObjectImpl for HighlightSettingsBox {}
+
+// Trait shared by all widgets
+impl BoxImpl for HighlightSettingsBox {}
+
+// Trait shared by all widgets
+impl WidgetImpl for HighlightSettingsBox {}
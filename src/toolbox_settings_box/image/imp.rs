@@ -0,0 +1,23 @@
+use gtk::glib;
+use gtk::subclass::prelude::*;
+
+// Object holding the state
+#[derive(Debug, Default)]
+pub struct ImageSettingsBox {}
+
+// The central trait for subclassing a GObject
+#[glib::object_subclass]
+impl ObjectSubclass for ImageSettingsBox {
+    const NAME: &'static str = "ImageSettingsBox";
+    type Type = super::ImageSettingsBox;
+    type ParentType = gtk::Box;
+}
+
+// Trait shared by all GObjects
+impl ObjectImpl for ImageSettingsBox {}
+
+// Trait shared by all widgets
+impl BoxImpl for ImageSettingsBox {}
+
+// Trait shared by all widgets
+impl WidgetImpl for ImageSettingsBox {}
@@ -0,0 +1,172 @@
+mod imp;
+
+use gtk::prelude::*;
+use gtk::{gdk, gio, glib};
+use rustshot_gtk::constants::{
+    CSS_CLASS_SOLID, CSS_CLASS_TOOLBOX_BAR, TOOLBOX_BTN_SIZE, TOOLBOX_SETTINGS_BTN_SIZE,
+};
+
+use crate::captured_screen::texture_to_surface;
+use crate::drawing_area_manager::drawables::ImageSource;
+use crate::geometry::GeometryState;
+
+glib::wrapper! {
+    pub struct ImageSettingsBox(ObjectSubclass<imp::ImageSettingsBox>)
+        @extends gtk::Box,
+        @implements gtk::Accessible,  gtk::Actionable, gtk::Widget, gtk::Orientable, gtk::Buildable, gtk::ConstraintTarget;
+}
+
+impl Default for ImageSettingsBox {
+    fn default() -> Self {
+        glib::Object::new()
+    }
+}
+
+impl ImageSettingsBox {
+    pub fn new_vertical(&self, size: i32, align: gtk::Align) {
+        self.set_orientation(gtk::Orientation::Vertical);
+        self.add_css_class(CSS_CLASS_TOOLBOX_BAR);
+        self.set_width_request(size);
+        self.set_halign(align);
+        self.set_valign(gtk::Align::Center);
+    }
+
+    pub fn new_horizontal(&self, align: gtk::Align) {
+        self.set_orientation(gtk::Orientation::Horizontal);
+        self.add_css_class(CSS_CLASS_TOOLBOX_BAR);
+        self.set_height_request(TOOLBOX_SETTINGS_BTN_SIZE);
+        self.set_valign(align);
+    }
+
+    pub fn new_fullscreen(&self, geom: &GeometryState) {
+        // create toolbox
+        self.set_orientation(gtk::Orientation::Horizontal);
+        self.add_css_class(CSS_CLASS_TOOLBOX_BAR);
+        self.set_halign(gtk::Align::Center);
+        self.set_height_request(TOOLBOX_BTN_SIZE);
+        self.set_margin_start(geom.full_w / 2);
+        self.set_margin_top(10);
+        self.set_margin_bottom(geom.full_h - TOOLBOX_BTN_SIZE);
+    }
+
+    pub fn populate_with_settings(
+        &self,
+        geom: &GeometryState,
+        source: &ImageSource,
+    ) -> std::io::Result<()> {
+        // size of the loaded picture
+        let size_label = gtk::Label::new(Some("no image"));
+        size_label.set_tooltip_text(Some("click on the screen to place the image"));
+
+        // open a file
+        let open_btn = gtk::Button::with_label("\u{f07c}");
+        open_btn.add_css_class(CSS_CLASS_SOLID);
+        open_btn.set_tooltip_text(Some("open an image file"));
+        open_btn.connect_clicked(glib::clone!(
+            #[strong]
+            geom,
+            #[strong]
+            source,
+            #[weak]
+            size_label,
+            move |_| {
+                let filter = gtk::FileFilter::new();
+                filter.set_name(Some("Images"));
+                filter.add_mime_type("image/*");
+                let filters = gio::ListStore::new::<gtk::FileFilter>();
+                filters.append(&filter);
+
+                let dialog = gtk::FileDialog::builder()
+                    .title("Open Image")
+                    .accept_label("Open")
+                    .filters(&filters)
+                    .modal(true)
+                    .build();
+
+                let cancellable = gio::Cancellable::new();
+                let source = source.clone();
+                dialog.open(Some(&geom.window), Some(&cancellable), move |file| {
+                    let texture = match file {
+                        Ok(file) => gdk::Texture::from_file(&file),
+                        Err(err) => {
+                            eprintln!("Error selecting file: {}", err);
+                            return;
+                        }
+                    };
+                    match texture {
+                        Ok(texture) => Self::set_source(&source, &texture, &size_label),
+                        Err(err) => eprintln!("Impossible to load the image: {}", err),
+                    }
+                });
+            }
+        ));
+
+        // paste from the clipboard
+        let paste_btn = gtk::Button::with_label("\u{f0ea}");
+        paste_btn.add_css_class(CSS_CLASS_SOLID);
+        paste_btn.set_tooltip_text(Some("paste an image from the clipboard"));
+        paste_btn.connect_clicked(glib::clone!(
+            #[strong]
+            geom,
+            #[strong]
+            source,
+            #[weak]
+            size_label,
+            move |_| {
+                let cancellable = gio::Cancellable::new();
+                let source = source.clone();
+                geom.window.clipboard().read_texture_async(
+                    Some(&cancellable),
+                    move |res| match res {
+                        Ok(Some(texture)) => Self::set_source(&source, &texture, &size_label),
+                        Ok(None) => println!("No image in the clipboard"),
+                        Err(err) => eprintln!("Impossible to paste the image: {}", err),
+                    },
+                );
+            }
+        ));
+
+        // set children
+        let image_box: gtk::Box = gtk::Box::new(gtk::Orientation::Horizontal, 4);
+        image_box.append(&open_btn);
+        image_box.append(&paste_btn);
+        image_box.append(&size_label);
+        crate::toolbox_settings_box::add_expandable_row(
+            self, "\u{f03e}", "image", "fas", image_box, true,
+        );
+
+        let settings = geom.settings.image.clone();
+        // drop shadow
+        crate::toolbox_settings_box::add_shadow_row(
+            self,
+            geom,
+            glib::clone!(
+                #[strong]
+                settings,
+                move |key| settings.get_value(key)
+            ),
+            glib::clone!(
+                #[strong]
+                settings,
+                move |key, value| {
+                    settings
+                        .set_value(key, value)
+                        .expect("Error in ImageSettingsBox");
+                }
+            ),
+        );
+
+        Ok(())
+    }
+
+    /// Make `texture` the picture placed by the next click
+    fn set_source(source: &ImageSource, texture: &gdk::Texture, size_label: &gtk::Label) {
+        match texture_to_surface(texture) {
+            Ok(surface) => {
+                size_label.set_label(&format!("{}x{}", surface.width(), surface.height()));
+                *source.borrow_mut() = Some(surface);
+            }
+            Err(err) => eprintln!("Impossible to convert the image: {}", err),
+        }
+    }
+}
@@ -368,9 +368,26 @@ impl InputTextSettingsBox {
             self, "\u{f853}", "border", "fal", border_box, false,
         );
 
+        // gradient fill
+        let gradient_box = crate::toolbox_settings_box::gradient_box(
+            &settings.get_value("fill_mode").get_string()?,
+            &settings.get_value("fill_stops").get_string()?,
+            settings.get_value("fill_angle").get_f64()?,
+            glib::clone!(
+                #[strong]
+                settings,
+                move |key: &str, value: SettingValue| {
+                    settings
+                        .set_value(key, value)
+                        .expect("Error in InputTextSettingsBox");
+                }
+            ),
+        );
+
         let fill_box: gtk::Box = gtk::Box::new(gtk::Orientation::Horizontal, 4);
         fill_box.append(&fill_checkbox);
         fill_box.append(&fill_color_btn);
+        fill_box.append(&gradient_box);
         crate::toolbox_settings_box::add_expandable_row(
             self, "\u{f575}", "fill", "fas", fill_box, false,
         );
@@ -382,6 +399,26 @@ impl InputTextSettingsBox {
             self, "\u{f034}", "font", "fas", font_box, false,
         );
 
+        // drop shadow
+        crate::toolbox_settings_box::add_shadow_row(
+            self,
+            geom,
+            glib::clone!(
+                #[strong]
+                settings,
+                move |key| settings.get_value(key)
+            ),
+            glib::clone!(
+                #[strong]
+                settings,
+                move |key, value| {
+                    settings
+                        .set_value(key, value)
+                        .expect("Error in InputTextSettingsBox");
+                }
+            ),
+        );
+
         Ok(())
     }
 }
@@ -232,6 +232,49 @@ impl LineSettingsBox {
         //     }
         // ));
 
+        // dash pattern
+        let dashes = ["solid", "dashed", "dotted", "custom"];
+        let dash = settings.get_value("dash").get_string()?;
+        let dash_dropdown = crate::toolbox_settings_box::label_dropdown(
+            &["\u{f068}", "- -", "···", "\u{f1de}"],
+            dashes.iter().position(|d| *d == dash).unwrap_or(0) as u32,
+            "change dash pattern",
+        );
+
+        // custom pattern, dash and gap lengths in pixels
+        let pattern_entry = gtk::Entry::new();
+        pattern_entry.set_text(&settings.get_value("dash_pattern").get_string()?);
+        pattern_entry.set_placeholder_text(Some("8,4"));
+        pattern_entry.set_width_chars(6);
+        pattern_entry.set_sensitive(dash == "custom");
+        pattern_entry.set_tooltip_text(Some("custom dash pattern: dash,gap,... in px"));
+        pattern_entry.connect_changed(glib::clone!(
+            #[strong]
+            settings,
+            move |e| {
+                settings
+                    .set_value("dash_pattern", SettingValue::String(e.text().to_string()))
+                    .expect("Error in LineSettingButton");
+            }
+        ));
+
+        dash_dropdown.connect_selected_item_notify(glib::clone!(
+            #[strong]
+            settings,
+            #[weak]
+            pattern_entry,
+            move |dw| {
+                let Some(dash) = dashes.get(dw.selected() as usize) else {
+                    println!("dash pattern not found");
+                    return;
+                };
+                pattern_entry.set_sensitive(*dash == "custom");
+                settings
+                    .set_value("dash", SettingValue::String(dash.to_string()))
+                    .expect("Error in LineSettingButton");
+            }
+        ));
+
         // set child
         let fill_box: gtk::Box = gtk::Box::new(gtk::Orientation::Horizontal, 4);
         fill_box.append(&color_color_btn);
@@ -241,6 +284,13 @@ impl LineSettingsBox {
         );
 
         self.append(&line_cap_dropdown);
+
+        let dash_box: gtk::Box = gtk::Box::new(gtk::Orientation::Horizontal, 4);
+        dash_box.append(&dash_dropdown);
+        dash_box.append(&pattern_entry);
+        crate::toolbox_settings_box::add_expandable_row(
+            self, "\u{f5cb}", "dash", "fas", dash_box, true,
+        );
         // let line_cap: gtk::Box = gtk::Box::new(gtk::Orientation::Horizontal, 4);
         // line_cap.append(&line_cap_dropdown);
         // crate::toolbox_settings_box::add_expandable_row(
@@ -258,6 +308,26 @@ impl LineSettingsBox {
         //     true,
         // );
 
+        // drop shadow
+        crate::toolbox_settings_box::add_shadow_row(
+            self,
+            geom,
+            glib::clone!(
+                #[strong]
+                settings,
+                move |key| settings.get_value(key)
+            ),
+            glib::clone!(
+                #[strong]
+                settings,
+                move |key, value| {
+                    settings
+                        .set_value(key, value)
+                        .expect("Error in LineSettingsBox");
+                }
+            ),
+        );
+
         Ok(())
     }
 }
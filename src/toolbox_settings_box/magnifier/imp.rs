@@ -0,0 +1,23 @@
+use gtk::glib;
+use gtk::subclass::prelude::*;
+
+// Object holding the state
+#[derive(Debug, Default)]
+pub struct MagnifierSettingsBox {}
+
+// The central trait for subclassing a GObject
+#[glib::object_subclass]
+impl ObjectSubclass for MagnifierSettingsBox {
+    const NAME: &'static str = "MagnifierSettingsBox";
+    type Type = super::MagnifierSettingsBox;
+    type ParentType = gtk::Box;
+}
+
+// Trait shared by all GObjects
+impl ObjectImpl for MagnifierSettingsBox {}
+
+// Trait shared by all widgets
+impl BoxImpl for MagnifierSettingsBox {}
+
+// Trait shared by all widgets
+impl WidgetImpl for MagnifierSettingsBox {}
@@ -0,0 +1,276 @@
+mod imp;
+
+use gtk::prelude::*;
+use gtk::{gio, glib};
+use rustshot_gtk::constants::{
+    CSS_CLASS_SOLID, CSS_CLASS_TOOLBOX_BAR, TOOLBOX_BTN_SIZE, TOOLBOX_SETTINGS_BTN_SIZE,
+};
+
+use crate::drawing_area_settings::SettingValue;
+use crate::geometry::GeometryState;
+use crate::spin_button::IntegerInput;
+
+glib::wrapper! {
+    pub struct MagnifierSettingsBox(ObjectSubclass<imp::MagnifierSettingsBox>)
+        @extends gtk::Box,
+        @implements gtk::Accessible,  gtk::Actionable, gtk::Widget, gtk::Orientable, gtk::Buildable, gtk::ConstraintTarget;
+}
+
+impl Default for MagnifierSettingsBox {
+    fn default() -> Self {
+        glib::Object::new()
+    }
+}
+
+impl MagnifierSettingsBox {
+    pub fn new_vertical(&self, size: i32, align: gtk::Align) {
+        self.set_orientation(gtk::Orientation::Vertical);
+        self.add_css_class(CSS_CLASS_TOOLBOX_BAR);
+        self.set_width_request(size);
+        self.set_halign(align);
+        self.set_valign(gtk::Align::Center);
+    }
+
+    pub fn new_horizontal(&self, align: gtk::Align) {
+        self.set_orientation(gtk::Orientation::Horizontal);
+        self.add_css_class(CSS_CLASS_TOOLBOX_BAR);
+        self.set_height_request(TOOLBOX_SETTINGS_BTN_SIZE);
+        self.set_valign(align);
+    }
+
+    pub fn new_fullscreen(&self, geom: &GeometryState) {
+        // create toolbox
+        self.set_orientation(gtk::Orientation::Horizontal);
+        self.add_css_class(CSS_CLASS_TOOLBOX_BAR);
+        self.set_halign(gtk::Align::Center);
+        self.set_height_request(TOOLBOX_BTN_SIZE);
+        self.set_margin_start(geom.full_w / 2);
+        self.set_margin_top(10);
+        self.set_margin_bottom(geom.full_h - TOOLBOX_BTN_SIZE);
+    }
+
+    pub fn populate_with_settings(&self, geom: &GeometryState) -> std::io::Result<()> {
+        // get magnifier settings
+        let settings = geom.settings.magnifier.clone();
+
+        // zoom factor
+        let zoom_spin = IntegerInput::new(settings.get_value("zoom").get_f64()? as i32, 2, 16);
+        zoom_spin.connect_value_changed(glib::clone!(
+            #[strong]
+            settings,
+            move |s| {
+                let sz = s.value();
+                if sz > 1 {
+                    settings
+                        .set_value("zoom", SettingValue::F64(sz as f64))
+                        .expect("Error in MagnifierSettingsBox");
+                }
+            }
+        ));
+        zoom_spin.set_tooltip_text(Some("change zoom factor"));
+
+        // border size
+        let spin = IntegerInput::new(settings.get_value("border_size").get_f64()? as i32, 0, 100);
+        spin.connect_value_changed(glib::clone!(
+            #[strong]
+            settings,
+            move |s| {
+                let sz = s.value();
+                if sz.abs() > 0 && sz.signum() > 0 {
+                    settings
+                        .set_value("border_size", SettingValue::F64(sz as f64))
+                        .expect("Error in MagnifierSettingsBox");
+                }
+            }
+        ));
+        spin.set_tooltip_text(Some("change border size"));
+
+        // border color
+        let border_color_dialog = gtk::ColorDialog::new();
+        let border_color_btn = gtk::ColorDialogButton::new(Some(border_color_dialog.clone()));
+        border_color_btn.set_tooltip_text(Some("change border color"));
+
+        // get actual color
+        let color = gtk::gdk::RGBA::new(
+            settings.get_value("border_r").get_f64()? as f32,
+            settings.get_value("border_g").get_f64()? as f32,
+            settings.get_value("border_b").get_f64()? as f32,
+            settings.get_value("border_a").get_f64()? as f32,
+        );
+        border_color_btn.set_rgba(&color);
+
+        let gest = gtk::GestureClick::new();
+        gest.connect_pressed(glib::clone!(
+            #[strong]
+            geom,
+            #[weak]
+            border_color_dialog,
+            #[weak]
+            border_color_btn,
+            move |_, _, _, _| {
+                // create color dialog
+                let cancellable = gio::Cancellable::new();
+
+                // clone geometry
+                let geom = geom.clone();
+
+                // Dialog
+                let btn = border_color_btn.clone();
+                border_color_dialog.set_title("Pick color");
+                border_color_dialog.choose_rgba(
+                    Some(&geom.window),
+                    Some(&color),
+                    Some(&cancellable),
+                    // gtk::gio::Cancellable::NONE,
+                    move |res| {
+                        if let Ok(color) = res {
+                            btn.set_rgba(&color);
+                            let r: f64 = color.red() as f64;
+                            let g: f64 = color.green() as f64;
+                            let b: f64 = color.blue() as f64;
+                            let a: f64 = color.alpha() as f64;
+                            geom.settings
+                                .magnifier
+                                .set_value("border_r", SettingValue::F64(r))
+                                .expect("ColorChoser Error");
+                            geom.settings
+                                .magnifier
+                                .set_value("border_g", SettingValue::F64(g))
+                                .expect("ColorChoser Error");
+                            geom.settings
+                                .magnifier
+                                .set_value("border_b", SettingValue::F64(b))
+                                .expect("ColorChoser Error");
+                            geom.settings
+                                .magnifier
+                                .set_value("border_a", SettingValue::F64(a))
+                                .expect("ColorChoser Error");
+                            // geom.drawing.set_rgba(r, g, b, a);
+                        } else {
+                            println!("No color found");
+                        }
+                    },
+                );
+            }
+        ));
+        border_color_btn.add_controller(gest);
+
+        // connector check box
+        let connector_checkbox = gtk::CheckButton::with_label("");
+        connector_checkbox.set_active(settings.get_value("connector").get_bool()?);
+        connector_checkbox.set_tooltip_text(Some("disable connector line"));
+        connector_checkbox.connect_toggled(glib::clone!(
+            #[strong]
+            settings,
+            move |cb| {
+                let is_active = cb.is_active();
+                if is_active {
+                    cb.set_tooltip_text(Some("disable connector line"));
+                } else {
+                    cb.set_tooltip_text(Some("enable connector line"));
+                }
+                settings
+                    .set_value("connector", SettingValue::Bool(is_active))
+                    .expect("connector_checkbox error");
+            }
+        ));
+
+        // Shapes (circle, rect)
+        let shapes = vec!["\u{f111}", "\u{f096}"];
+        let model = gtk::StringList::new(&shapes);
+
+        let factory = gtk::SignalListItemFactory::new();
+        factory.connect_setup(move |_factory, list_item| {
+            let label = gtk::Label::new(None);
+            label.add_css_class(CSS_CLASS_SOLID);
+            list_item
+                .downcast_ref::<gtk::ListItem>()
+                .expect("Expected a ListItem")
+                .set_child(Some(&label));
+        });
+
+        factory.connect_bind(|_factory, list_item| {
+            let list_item = list_item
+                .downcast_ref::<gtk::ListItem>()
+                .expect("Expected a ListItem");
+
+            let item = list_item
+                .item()
+                .and_then(|obj| obj.downcast::<gtk::StringObject>().ok())
+                .expect("Expected a StringObject");
+
+            let label = list_item
+                .child()
+                .and_then(|child| child.downcast::<gtk::Label>().ok())
+                .expect("Expected a Label child");
+
+            label.set_label(&item.string());
+        });
+
+        let shape_dropdown = gtk::DropDown::builder()
+            .model(&model)
+            .factory(&factory)
+            .build();
+        shape_dropdown.set_tooltip_text(Some("circle or rectangle"));
+        shape_dropdown.add_css_class(CSS_CLASS_SOLID);
+        if settings.get_value("shape").get_string()? == "rect" {
+            shape_dropdown.set_selected(1);
+        }
+        shape_dropdown.connect_selected_item_notify(glib::clone!(
+            #[strong]
+            settings,
+            move |dw| {
+                let shape = match dw.selected() {
+                    0 => "circle",
+                    1 => "rect",
+                    _ => {
+                        println!("magnifier shape not found");
+                        return;
+                    }
+                };
+                settings
+                    .set_value("shape", SettingValue::String(shape.to_string()))
+                    .expect("Error in MagnifierSettingsBox");
+            }
+        ));
+
+        // set children
+        let zoom_box: gtk::Box = gtk::Box::new(gtk::Orientation::Horizontal, 4);
+        zoom_box.append(&zoom_spin);
+        crate::toolbox_settings_box::add_expandable_row(
+            self, "\u{f00e}", "zoom", "fas", zoom_box, true,
+        );
+
+        let border_box: gtk::Box = gtk::Box::new(gtk::Orientation::Horizontal, 4);
+        border_box.append(&spin);
+        border_box.append(&border_color_btn);
+        border_box.append(&connector_checkbox);
+        crate::toolbox_settings_box::add_expandable_row(
+            self, "\u{f853}", "border", "fal", border_box, true,
+        );
+
+        self.append(&shape_dropdown);
+
+        // drop shadow
+        crate::toolbox_settings_box::add_shadow_row(
+            self,
+            geom,
+            glib::clone!(
+                #[strong]
+                settings,
+                move |key| settings.get_value(key)
+            ),
+            glib::clone!(
+                #[strong]
+                settings,
+                move |key, value| {
+                    settings
+                        .set_value(key, value)
+                        .expect("Error in MagnifierSettingsBox");
+                }
+            ),
+        );
+
+        Ok(())
+    }
+}
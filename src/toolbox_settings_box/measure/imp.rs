@@ -0,0 +1,23 @@
+use gtk::glib;
+use gtk::subclass::prelude::*;
+
+// Object holding the state
+#[derive(Debug, Default)]
+pub struct MeasureSettingsBox {}
+
+// The central trait for subclassing a GObject
+#[glib::object_subclass]
+impl ObjectSubclass for MeasureSettingsBox {
+    const NAME: &'static str = "MeasureSettingsBox";
+    type Type = super::MeasureSettingsBox;
+    type ParentType = gtk::Box;
+}
+
+// Trait shared by all GObjects
+impl ObjectImpl for MeasureSettingsBox {}
+
+// Trait shared by all widgets
+impl BoxImpl for MeasureSettingsBox {}
+
+// Trait shared by all widgets
+impl WidgetImpl for MeasureSettingsBox {}
@@ -0,0 +1,213 @@
+mod imp;
+
+use gtk::prelude::*;
+use gtk::{gio, glib};
+use rustshot_gtk::constants::{CSS_CLASS_TOOLBOX_BAR, TOOLBOX_BTN_SIZE, TOOLBOX_SETTINGS_BTN_SIZE};
+
+use crate::drawing_area_settings::SettingValue;
+use crate::geometry::GeometryState;
+use crate::spin_button::IntegerInput;
+
+glib::wrapper! {
+    pub struct MeasureSettingsBox(ObjectSubclass<imp::MeasureSettingsBox>)
+        @extends gtk::Box,
+        @implements gtk::Accessible,  gtk::Actionable, gtk::Widget, gtk::Orientable, gtk::Buildable, gtk::ConstraintTarget;
+}
+
+impl Default for MeasureSettingsBox {
+    fn default() -> Self {
+        glib::Object::new()
+    }
+}
+
+impl MeasureSettingsBox {
+    pub fn new_vertical(&self, size: i32, align: gtk::Align) {
+        self.set_orientation(gtk::Orientation::Vertical);
+        self.add_css_class(CSS_CLASS_TOOLBOX_BAR);
+        self.set_width_request(size);
+        self.set_halign(align);
+        self.set_valign(gtk::Align::Center);
+    }
+
+    pub fn new_horizontal(&self, align: gtk::Align) {
+        self.set_orientation(gtk::Orientation::Horizontal);
+        self.add_css_class(CSS_CLASS_TOOLBOX_BAR);
+        self.set_height_request(TOOLBOX_SETTINGS_BTN_SIZE);
+        self.set_valign(align);
+    }
+
+    pub fn new_fullscreen(&self, geom: &GeometryState) {
+        // create toolbox
+        self.set_orientation(gtk::Orientation::Horizontal);
+        self.add_css_class(CSS_CLASS_TOOLBOX_BAR);
+        self.set_halign(gtk::Align::Center);
+        self.set_height_request(TOOLBOX_BTN_SIZE);
+        self.set_margin_start(geom.full_w / 2);
+        self.set_margin_top(10);
+        self.set_margin_bottom(geom.full_h - TOOLBOX_BTN_SIZE);
+    }
+
+    pub fn populate_with_settings(&self, geom: &GeometryState) -> std::io::Result<()> {
+        // get measure settings
+        let settings = geom.settings.measure.clone();
+
+        // label font size
+        let font_spin = IntegerInput::new(settings.get_value("font_size").get_f64()? as i32, 6, 72);
+        font_spin.connect_value_changed(glib::clone!(
+            #[strong]
+            settings,
+            move |s| {
+                let sz = s.value();
+                if sz > 0 {
+                    settings
+                        .set_value("font_size", SettingValue::F64(sz as f64))
+                        .expect("Error in MeasureSettingsBox");
+                }
+            }
+        ));
+        font_spin.set_tooltip_text(Some("change label font size"));
+
+        // line size
+        let spin = IntegerInput::new(settings.get_value("size").get_f64()? as i32, 0, 100);
+        spin.connect_value_changed(glib::clone!(
+            #[strong]
+            settings,
+            move |s| {
+                let sz = s.value();
+                if sz.abs() > 0 && sz.signum() > 0 {
+                    settings
+                        .set_value("size", SettingValue::F64(sz as f64))
+                        .expect("Error in MeasureSettingsBox");
+                }
+            }
+        ));
+        spin.set_tooltip_text(Some("change line size"));
+
+        // line color
+        let color_dialog = gtk::ColorDialog::new();
+        let color_btn = gtk::ColorDialogButton::new(Some(color_dialog.clone()));
+        color_btn.set_tooltip_text(Some("change color"));
+
+        // get actual color
+        let color = gtk::gdk::RGBA::new(
+            settings.get_value("color_r").get_f64()? as f32,
+            settings.get_value("color_g").get_f64()? as f32,
+            settings.get_value("color_b").get_f64()? as f32,
+            settings.get_value("color_a").get_f64()? as f32,
+        );
+        color_btn.set_rgba(&color);
+
+        let gest = gtk::GestureClick::new();
+        gest.connect_pressed(glib::clone!(
+            #[strong]
+            geom,
+            #[weak]
+            color_dialog,
+            #[weak]
+            color_btn,
+            move |_, _, _, _| {
+                // create color dialog
+                let cancellable = gio::Cancellable::new();
+
+                // clone geometry
+                let geom = geom.clone();
+
+                // Dialog
+                let btn = color_btn.clone();
+                color_dialog.set_title("Pick color");
+                color_dialog.choose_rgba(
+                    Some(&geom.window),
+                    Some(&color),
+                    Some(&cancellable),
+                    // gtk::gio::Cancellable::NONE,
+                    move |res| {
+                        if let Ok(color) = res {
+                            btn.set_rgba(&color);
+                            let r: f64 = color.red() as f64;
+                            let g: f64 = color.green() as f64;
+                            let b: f64 = color.blue() as f64;
+                            let a: f64 = color.alpha() as f64;
+                            geom.settings
+                                .measure
+                                .set_value("color_r", SettingValue::F64(r))
+                                .expect("ColorChoser Error");
+                            geom.settings
+                                .measure
+                                .set_value("color_g", SettingValue::F64(g))
+                                .expect("ColorChoser Error");
+                            geom.settings
+                                .measure
+                                .set_value("color_b", SettingValue::F64(b))
+                                .expect("ColorChoser Error");
+                            geom.settings
+                                .measure
+                                .set_value("color_a", SettingValue::F64(a))
+                                .expect("ColorChoser Error");
+                            // geom.drawing.set_rgba(r, g, b, a);
+                        } else {
+                            println!("No color found");
+                        }
+                    },
+                );
+            }
+        ));
+        color_btn.add_controller(gest);
+
+        // keep the measurement in the screenshot, or show it on screen only
+        let export_checkbox = gtk::CheckButton::with_label("");
+        export_checkbox.set_active(settings.get_value("export").get_bool()?);
+        export_checkbox.set_tooltip_text(Some("keep in the screenshot"));
+        export_checkbox.connect_toggled(glib::clone!(
+            #[strong]
+            settings,
+            move |cb| {
+                let is_active = cb.is_active();
+                if is_active {
+                    cb.set_tooltip_text(Some("keep in the screenshot"));
+                } else {
+                    cb.set_tooltip_text(Some("show on screen only"));
+                }
+                settings
+                    .set_value("export", SettingValue::Bool(is_active))
+                    .expect("export_checkbox error");
+            }
+        ));
+
+        // set children
+        let line_box: gtk::Box = gtk::Box::new(gtk::Orientation::Horizontal, 4);
+        line_box.append(&spin);
+        line_box.append(&color_btn);
+        crate::toolbox_settings_box::add_expandable_row(
+            self, "\u{f545}", "ruler", "fas", line_box, true,
+        );
+
+        let label_box: gtk::Box = gtk::Box::new(gtk::Orientation::Horizontal, 4);
+        label_box.append(&font_spin);
+        label_box.append(&export_checkbox);
+        crate::toolbox_settings_box::add_expandable_row(
+            self, "\u{f031}", "label", "fas", label_box, true,
+        );
+
+        // drop shadow
+        crate::toolbox_settings_box::add_shadow_row(
+            self,
+            geom,
+            glib::clone!(
+                #[strong]
+                settings,
+                move |key| settings.get_value(key)
+            ),
+            glib::clone!(
+                #[strong]
+                settings,
+                move |key, value| {
+                    settings
+                        .set_value(key, value)
+                        .expect("Error in MeasureSettingsBox");
+                }
+            ),
+        );
+
+        Ok(())
+    }
+}
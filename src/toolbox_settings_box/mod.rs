@@ -1,14 +1,32 @@
 pub mod arc;
 pub mod arrow;
+pub mod callout;
+pub mod eyedropper;
 pub mod freehand;
+pub mod highlight;
+pub mod image;
 pub mod input_text;
 pub mod line;
+pub mod magnifier;
+pub mod measure;
 pub mod numbered_circle;
+pub mod obfuscate;
+pub mod polygon;
 pub mod rect;
+pub mod redact;
+pub mod select;
+pub mod spotlight;
+pub mod stamp;
 
 use gtk::glib;
 use gtk::prelude::*;
 use gtk::{Box as GtkBox, Expander, Revealer};
+use rustshot_gtk::constants::CSS_CLASS_SOLID;
+use std::rc::Rc;
+
+use crate::drawing_area_settings::SettingValue;
+use crate::geometry::GeometryState;
+use crate::spin_button::IntegerInput;
 
 // --------------------------------------------------------------
 // Helper that creates a label that expands into a *horizontal*
@@ -53,6 +71,298 @@ pub fn add_expandable_row<T: IsA<gtk::Box>>(
     parent.append(&revealer);
 }
 
+// --------------------------------------------------------------
+// Helper that creates a drop down of labels (glyphs or words),
+// with the `selected` one active.
+// --------------------------------------------------------------
+pub fn label_dropdown(labels: &[&str], selected: u32, tooltip_text: &str) -> gtk::DropDown {
+    let model = gtk::StringList::new(labels);
+
+    let factory = gtk::SignalListItemFactory::new();
+    factory.connect_setup(move |_factory, list_item| {
+        let label = gtk::Label::new(None);
+        label.add_css_class(CSS_CLASS_SOLID);
+        list_item
+            .downcast_ref::<gtk::ListItem>()
+            .expect("Expected a ListItem")
+            .set_child(Some(&label));
+    });
+
+    factory.connect_bind(|_factory, list_item| {
+        let list_item = list_item
+            .downcast_ref::<gtk::ListItem>()
+            .expect("Expected a ListItem");
+
+        let item = list_item
+            .item()
+            .and_then(|obj| obj.downcast::<gtk::StringObject>().ok())
+            .expect("Expected a StringObject");
+
+        let label = list_item
+            .child()
+            .and_then(|child| child.downcast::<gtk::Label>().ok())
+            .expect("Expected a Label child");
+
+        label.set_label(&item.string());
+    });
+
+    let dropdown = gtk::DropDown::builder()
+        .model(&model)
+        .factory(&factory)
+        .build();
+    dropdown.set_tooltip_text(Some(tooltip_text));
+    dropdown.add_css_class(CSS_CLASS_SOLID);
+    dropdown.set_selected(selected);
+    dropdown
+}
+
+/// Fill mode (solid color, linear or radial gradient), gradient angle and color stops, shared
+/// by the settings boxes of the filled shapes. `set` stores a value in the tool settings.
+pub fn gradient_box<S>(mode: &str, stops: &str, angle: f64, set: S) -> GtkBox
+where
+    S: Fn(&str, SettingValue) + Clone + 'static,
+{
+    let modes = ["solid", "linear", "radial"];
+    let mode_dropdown = label_dropdown(
+        &["\u{f0c8}", "\u{f07e}", "\u{f192}"],
+        modes.iter().position(|m| *m == mode).unwrap_or(0) as u32,
+        "change fill: solid, linear or radial gradient",
+    );
+
+    // angle of the linear gradient, 0° left to right, 90° top to bottom
+    let angle_spin = IntegerInput::new(angle.round() as i32, 0, 360);
+    angle_spin.set_tooltip_text(Some("change gradient angle"));
+    angle_spin.set_sensitive(mode == "linear");
+    angle_spin.connect_value_changed(glib::clone!(
+        #[strong]
+        set,
+        move |s| set("fill_angle", SettingValue::F64(s.value() as f64))
+    ));
+
+    // color stops, e.g. "#ffffff 0%, #3584e4 100%"
+    let stops_entry = gtk::Entry::new();
+    stops_entry.set_text(stops);
+    stops_entry.set_placeholder_text(Some("#ffffff 0%, #3584e4 100%"));
+    stops_entry.set_width_chars(16);
+    stops_entry.set_sensitive(mode != "solid");
+    stops_entry.set_tooltip_text(Some("gradient stops: #color offset%, ... (at least two)"));
+    stops_entry.connect_changed(glib::clone!(
+        #[strong]
+        set,
+        move |e| set("fill_stops", SettingValue::String(e.text().to_string()))
+    ));
+
+    mode_dropdown.connect_selected_item_notify(glib::clone!(
+        #[weak]
+        angle_spin,
+        #[weak]
+        stops_entry,
+        move |dw| {
+            let Some(mode) = modes.get(dw.selected() as usize) else {
+                println!("fill mode not found");
+                return;
+            };
+            angle_spin.set_sensitive(*mode == "linear");
+            stops_entry.set_sensitive(*mode != "solid");
+            set("fill_mode", SettingValue::String(mode.to_string()));
+        }
+    ));
+
+    let gradient_box = GtkBox::new(gtk::Orientation::Horizontal, 4);
+    gradient_box.append(&mode_dropdown);
+    gradient_box.append(&angle_spin);
+    gradient_box.append(&stops_entry);
+    gradient_box
+}
+
+/// Drop shadow row: whether the tool follows the global shadow switch, always has a shadow or
+/// never has one, plus the global switch, offset, blur and color. Offset, blur and color are
+/// the tool own ones ("shadow_offset_x", "shadow_r", ...) when its shadow is always on, the
+/// global ones otherwise. `get` and `set` access the settings of the tool.
+pub fn add_shadow_row<T, G, S>(parent: &T, geom: &GeometryState, get: G, set: S)
+where
+    T: IsA<gtk::Box>,
+    G: Fn(&str) -> SettingValue + 'static,
+    S: Fn(&str, SettingValue) + 'static,
+{
+    let shadow = geom.settings.shadow.clone();
+    let (get, set) = (Rc::new(get), Rc::new(set));
+
+    // offset, blur and color of this tool or global, keys given as the global ones
+    let tool_key = |key: &str| format!("shadow_{}", key.trim_start_matches("color_"));
+    let is_tool = glib::clone!(
+        #[strong]
+        get,
+        move || get("shadow").get_string().unwrap_or_default() == "on"
+    );
+    let get_param = Rc::new(glib::clone!(
+        #[strong]
+        shadow,
+        #[strong]
+        get,
+        #[strong]
+        is_tool,
+        move |key: &str| {
+            if is_tool() {
+                get(&tool_key(key))
+            } else {
+                shadow.get_value(key)
+            }
+        }
+    ));
+    let set_param = Rc::new(glib::clone!(
+        #[strong]
+        shadow,
+        #[strong]
+        set,
+        move |key: &str, value: SettingValue| {
+            if is_tool() {
+                set(&tool_key(key), value);
+            } else {
+                shadow
+                    .set_value(key, value)
+                    .expect("Error in add_shadow_row");
+            }
+        }
+    ));
+
+    // global switch
+    let enabled_checkbox = gtk::CheckButton::with_label("");
+    enabled_checkbox.set_tooltip_text(Some("shadow for all the tools"));
+    enabled_checkbox.connect_toggled(glib::clone!(
+        #[strong]
+        shadow,
+        move |cb| {
+            shadow
+                .set_value("enabled", SettingValue::Bool(cb.is_active()))
+                .expect("Error in add_shadow_row");
+        }
+    ));
+
+    // offset and blur radius, in pixels
+    let spins = [
+        ("offset_x", -50, 50, "change shadow horizontal offset"),
+        ("offset_y", -50, 50, "change shadow vertical offset"),
+        ("blur", 0, 50, "change shadow blur radius"),
+    ]
+    .map(|(key, min, max, tooltip)| {
+        let spin = IntegerInput::new(0, min, max);
+        spin.set_tooltip_text(Some(tooltip));
+        spin.connect_value_changed(glib::clone!(
+            #[strong]
+            set_param,
+            move |s| set_param(key, SettingValue::F64(s.value() as f64))
+        ));
+        (key, spin)
+    });
+
+    // color, with alpha
+    let color_dialog = gtk::ColorDialog::new();
+    color_dialog.set_with_alpha(true);
+    let color_btn = gtk::ColorDialogButton::new(Some(color_dialog.clone()));
+    color_btn.set_tooltip_text(Some("change shadow color"));
+
+    let gest = gtk::GestureClick::new();
+    gest.connect_pressed(glib::clone!(
+        #[strong]
+        geom,
+        #[strong]
+        set_param,
+        #[weak]
+        color_dialog,
+        #[weak]
+        color_btn,
+        move |_, _, _, _| {
+            let cancellable = gtk::gio::Cancellable::new();
+            let btn = color_btn.clone();
+            let set_param = set_param.clone();
+            color_dialog.set_title("Pick shadow color");
+            color_dialog.choose_rgba(
+                Some(&geom.window),
+                Some(&btn.rgba()),
+                Some(&cancellable),
+                move |res| {
+                    if let Ok(color) = res {
+                        btn.set_rgba(&color);
+                        for (key, value) in [
+                            ("color_r", color.red()),
+                            ("color_g", color.green()),
+                            ("color_b", color.blue()),
+                            ("color_a", color.alpha()),
+                        ] {
+                            set_param(key, SettingValue::F64(value as f64));
+                        }
+                    } else {
+                        println!("No color found");
+                    }
+                },
+            );
+        }
+    ));
+    color_btn.add_controller(gest);
+
+    // Show the values in use: the global ones may have been changed from another tool, and
+    // switching the mode swaps the tool and the global ones
+    let refresh = Rc::new(glib::clone!(
+        #[strong]
+        shadow,
+        #[strong]
+        enabled_checkbox,
+        #[strong]
+        color_btn,
+        #[strong]
+        spins,
+        move || {
+            enabled_checkbox.set_active(shadow.get_value("enabled").get_bool().unwrap_or(false));
+            for (key, spin) in spins.iter() {
+                let value = get_param(key).get_f64().unwrap_or_default();
+                spin.set_value(value.round() as i32);
+            }
+            let channel = |key: &str| get_param(key).get_f64().unwrap_or_default() as f32;
+            color_btn.set_rgba(&gtk::gdk::RGBA::new(
+                channel("color_r"),
+                channel("color_g"),
+                channel("color_b"),
+                channel("color_a"),
+            ));
+        }
+    ));
+
+    // this tool: global, on or off
+    let modes = ["global", "on", "off"];
+    let mode = get("shadow").get_string().unwrap_or_default();
+    let mode_dropdown = label_dropdown(
+        &["\u{f0ac}", "\u{f00c}", "\u{f00d}"],
+        modes.iter().position(|m| *m == mode).unwrap_or(0) as u32,
+        "shadow for this tool: global, always with its own style, or never",
+    );
+    mode_dropdown.connect_selected_item_notify(glib::clone!(
+        #[strong]
+        refresh,
+        move |dw| {
+            let Some(mode) = modes.get(dw.selected() as usize) else {
+                println!("shadow mode not found");
+                return;
+            };
+            set("shadow", SettingValue::String(mode.to_string()));
+            refresh();
+        }
+    ));
+
+    let shadow_box = GtkBox::new(gtk::Orientation::Horizontal, 4);
+    shadow_box.append(&mode_dropdown);
+    shadow_box.append(&enabled_checkbox);
+    for (_, spin) in spins.iter() {
+        shadow_box.append(spin);
+    }
+    shadow_box.append(&color_btn);
+
+    refresh();
+    shadow_box.connect_map(move |_| refresh());
+
+    add_expandable_row(parent, "\u{f0c5}", "shadow", "fas", shadow_box, false);
+}
+
 // pub fn get_factory(line_tools: Vec<&str>) -> std::io::Result<gtk::SignalListItemFactory> {
 //     let store = gtk::gio::ListStore::new::<gtk::StringObject>();
 //     for (icon, txt) in line_tools.iter() {
@@ -3,7 +3,9 @@ mod imp;
 use gtk::glib::subclass::types::ObjectSubclassIsExt;
 use gtk::prelude::*;
 use gtk::{gio, glib, pango};
-use rustshot_gtk::constants::{CSS_CLASS_TOOLBOX_BAR, TOOLBOX_BTN_SIZE, TOOLBOX_SETTINGS_BTN_SIZE};
+use rustshot_gtk::constants::{
+    CSS_CLASS_SOLID, CSS_CLASS_TOOLBOX_BAR, TOOLBOX_BTN_SIZE, TOOLBOX_SETTINGS_BTN_SIZE,
+};
 
 use crate::drawing_area_settings::SettingValue;
 use crate::geometry::GeometryState;
@@ -393,6 +395,126 @@ impl NumberedCircleSettingsBox {
         ));
         imp.number_spin.set_tooltip_text(Some("change number"));
 
+        // numbering style
+        let styles = ["numbers", "letters", "roman"];
+        let style = settings.get_value("style").get_string()?;
+        let style_dropdown = crate::toolbox_settings_box::label_dropdown(
+            &["1 2 3", "A B C", "I II III"],
+            styles.iter().position(|s| *s == style).unwrap_or(0) as u32,
+            "change numbering style",
+        );
+        style_dropdown.connect_selected_item_notify(glib::clone!(
+            #[strong]
+            settings,
+            move |dw| {
+                let Some(style) = styles.get(dw.selected() as usize) else {
+                    println!("numbering style not found");
+                    return;
+                };
+                settings
+                    .set_value("style", SettingValue::String(style.to_string()))
+                    .expect("Error in NumberedCircleSettingButton");
+            }
+        ));
+
+        // custom prefix, e.g. "Step "
+        let prefix_entry = gtk::Entry::new();
+        prefix_entry.set_text(&settings.get_value("prefix").get_string()?);
+        prefix_entry.set_placeholder_text(Some("prefix"));
+        prefix_entry.set_width_chars(6);
+        prefix_entry.set_tooltip_text(Some("text before the number"));
+        prefix_entry.connect_changed(glib::clone!(
+            #[strong]
+            settings,
+            move |e| {
+                settings
+                    .set_value("prefix", SettingValue::String(e.text().to_string()))
+                    .expect("Error in NumberedCircleSettingButton");
+            }
+        ));
+
+        // restart the numbering from 1, as a new sequence
+        let restart_btn = gtk::Button::with_label("\u{f2ea}");
+        restart_btn.add_css_class(CSS_CLASS_SOLID);
+        restart_btn.set_tooltip_text(Some("restart numbering"));
+        restart_btn.connect_clicked(glib::clone!(
+            #[strong]
+            settings,
+            #[weak(rename_to = number_spin)]
+            imp.number_spin,
+            move |_| {
+                let sequence = settings
+                    .get_value("sequence")
+                    .get_i32()
+                    .expect("Error in NumberedCircleSettingButton");
+                settings
+                    .set_value("sequence", SettingValue::I32(sequence + 1))
+                    .expect("Error in NumberedCircleSettingButton");
+                settings
+                    .set_value("sequence_start", SettingValue::I32(1))
+                    .expect("Error in NumberedCircleSettingButton");
+                number_spin.set_value(1);
+            }
+        ));
+
+        // renumber the placed badges in placement order
+        let renumber_btn = gtk::Button::with_label("\u{f162}");
+        renumber_btn.add_css_class(CSS_CLASS_SOLID);
+        renumber_btn.set_tooltip_text(Some("renumber in placement order"));
+        renumber_btn.connect_clicked(glib::clone!(
+            #[strong]
+            geom,
+            #[weak(rename_to = number_spin)]
+            imp.number_spin,
+            move |_| {
+                geom.drawing.renumber_badges(&geom.settings);
+                let number = geom
+                    .settings
+                    .numbered_circle
+                    .get_value("number")
+                    .get_i32()
+                    .expect("Error in NumberedCircleSettingButton");
+                number_spin.set_value(number);
+                geom.layers_panel.refresh(&geom);
+            }
+        ));
+
+        // renumber automatically when a badge is removed
+        let renumber_checkbox = gtk::CheckButton::with_label("");
+        renumber_checkbox.set_active(settings.get_value("renumber").get_bool()?);
+        renumber_checkbox.set_tooltip_text(Some("renumber when a badge is removed"));
+        renumber_checkbox.connect_toggled(glib::clone!(
+            #[strong]
+            settings,
+            move |cb| {
+                settings
+                    .set_value("renumber", SettingValue::Bool(cb.is_active()))
+                    .expect("Error in NumberedCircleSettingButton");
+            }
+        ));
+
+        // badge shape
+        let shapes = ["circle", "square", "rounded"];
+        let shape = settings.get_value("shape").get_string()?;
+        let shape_dropdown = crate::toolbox_settings_box::label_dropdown(
+            &["\u{f111}", "\u{f0c8}", "\u{f2fa}"],
+            shapes.iter().position(|s| *s == shape).unwrap_or(0) as u32,
+            "change badge shape",
+        );
+        shape_dropdown.connect_selected_item_notify(glib::clone!(
+            #[strong]
+            settings,
+            move |dw| {
+                let Some(shape) = shapes.get(dw.selected() as usize) else {
+                    println!("badge shape not found");
+                    return;
+                };
+                settings
+                    .set_value("shape", SettingValue::String(shape.to_string()))
+                    .expect("Error in NumberedCircleSettingButton");
+            }
+        ));
+
         // set children
         let border_box: gtk::Box = gtk::Box::new(gtk::Orientation::Horizontal, 4);
         border_box.append(&border_checkbox);
@@ -402,10 +524,28 @@ impl NumberedCircleSettingsBox {
             self, "\u{f853}", "border", "fal", border_box, false,
         );
 
+        // gradient fill
+        let gradient_box = crate::toolbox_settings_box::gradient_box(
+            &settings.get_value("fill_mode").get_string()?,
+            &settings.get_value("fill_stops").get_string()?,
+            settings.get_value("fill_angle").get_f64()?,
+            glib::clone!(
+                #[strong]
+                settings,
+                move |key: &str, value: SettingValue| {
+                    settings
+                        .set_value(key, value)
+                        .expect("Error in NumberedCircleSettingsBox");
+                }
+            ),
+        );
+
         let fill_box: gtk::Box = gtk::Box::new(gtk::Orientation::Horizontal, 4);
         fill_box.append(&fill_checkbox);
         fill_box.append(&radius_spin);
         fill_box.append(&fill_color_btn);
+        fill_box.append(&shape_dropdown);
+        fill_box.append(&gradient_box);
         crate::toolbox_settings_box::add_expandable_row(
             self, "\u{f575}", "fill", "fas", fill_box, false,
         );
@@ -419,6 +559,11 @@ impl NumberedCircleSettingsBox {
 
         let number_box: gtk::Box = gtk::Box::new(gtk::Orientation::Horizontal, 4);
         number_box.append(&imp.number_spin);
+        number_box.append(&style_dropdown);
+        number_box.append(&prefix_entry);
+        number_box.append(&restart_btn);
+        number_box.append(&renumber_btn);
+        number_box.append(&renumber_checkbox);
         crate::toolbox_settings_box::add_expandable_row(
             self,
             "\u{31}",
@@ -428,6 +573,26 @@ impl NumberedCircleSettingsBox {
             true,
         );
 
+        // drop shadow
+        crate::toolbox_settings_box::add_shadow_row(
+            self,
+            geom,
+            glib::clone!(
+                #[strong]
+                settings,
+                move |key| settings.get_value(key)
+            ),
+            glib::clone!(
+                #[strong]
+                settings,
+                move |key, value| {
+                    settings
+                        .set_value(key, value)
+                        .expect("Error in NumberedCircleSettingsBox");
+                }
+            ),
+        );
+
         Ok(())
     }
 
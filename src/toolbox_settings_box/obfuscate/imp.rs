@@ -0,0 +1,23 @@
+use gtk::glib;
+use gtk::subclass::prelude::*;
+
+// Object holding the state
+#[derive(Debug, Default)]
+pub struct ObfuscateSettingsBox {}
+
+// The central trait for subclassing a GObject
+#[glib::object_subclass]
+impl ObjectSubclass for ObfuscateSettingsBox {
+    const NAME: &'static str = "ObfuscateSettingsBox";
+    type Type = super::ObfuscateSettingsBox;
+    type ParentType = gtk::Box;
+}
+
+// Trait shared by all GObjects
+impl ObjectImpl for ObfuscateSettingsBox {}
+
+// Trait shared by all widgets
+impl BoxImpl for ObfuscateSettingsBox {}
+
+// Trait shared by all widgets
+impl WidgetImpl for ObfuscateSettingsBox {}
@@ -0,0 +1,197 @@
+mod imp;
+
+use gtk::glib;
+use gtk::prelude::*;
+use rustshot_gtk::constants::{
+    CSS_CLASS_SOLID, CSS_CLASS_TOOLBOX_BAR, TOOLBOX_BTN_SIZE, TOOLBOX_SETTINGS_BTN_SIZE,
+};
+
+use crate::drawing_area_manager::drawables::new_obfuscate_drawable;
+use crate::drawing_area_settings::SettingValue;
+use crate::geometry::GeometryState;
+use crate::spin_button::IntegerInput;
+
+glib::wrapper! {
+    pub struct ObfuscateSettingsBox(ObjectSubclass<imp::ObfuscateSettingsBox>)
+        @extends gtk::Box,
+        @implements gtk::Accessible,  gtk::Actionable, gtk::Widget, gtk::Orientable, gtk::Buildable, gtk::ConstraintTarget;
+}
+
+impl Default for ObfuscateSettingsBox {
+    fn default() -> Self {
+        glib::Object::new()
+    }
+}
+
+impl ObfuscateSettingsBox {
+    pub fn new_vertical(&self, size: i32, align: gtk::Align) {
+        self.set_orientation(gtk::Orientation::Vertical);
+        self.add_css_class(CSS_CLASS_TOOLBOX_BAR);
+        self.set_width_request(size);
+        self.set_halign(align);
+        self.set_valign(gtk::Align::Center);
+    }
+
+    pub fn new_horizontal(&self, align: gtk::Align) {
+        self.set_orientation(gtk::Orientation::Horizontal);
+        self.add_css_class(CSS_CLASS_TOOLBOX_BAR);
+        self.set_height_request(TOOLBOX_SETTINGS_BTN_SIZE);
+        self.set_valign(align);
+    }
+
+    pub fn new_fullscreen(&self, geom: &GeometryState) {
+        // create toolbox
+        self.set_orientation(gtk::Orientation::Horizontal);
+        self.add_css_class(CSS_CLASS_TOOLBOX_BAR);
+        self.set_halign(gtk::Align::Center);
+        self.set_height_request(TOOLBOX_BTN_SIZE);
+        self.set_margin_start(geom.full_w / 2);
+        self.set_margin_top(10);
+        self.set_margin_bottom(geom.full_h - TOOLBOX_BTN_SIZE);
+    }
+
+    pub fn populate_with_settings(&self, geom: &GeometryState) -> std::io::Result<()> {
+        // get obfuscate settings
+        let settings = geom.settings.obfuscate.clone();
+
+        // pixel size
+        let pixel_spin =
+            IntegerInput::new(settings.get_value("pixel_size").get_f64()? as i32, 2, 100);
+        pixel_spin.connect_value_changed(glib::clone!(
+            #[strong]
+            settings,
+            move |s| {
+                let sz = s.value();
+                if sz > 1 {
+                    settings
+                        .set_value("pixel_size", SettingValue::F64(sz as f64))
+                        .expect("Error in ObfuscateSettingsBox");
+                }
+            }
+        ));
+        pixel_spin.set_tooltip_text(Some("change pixel size"));
+
+        // blur radius
+        let blur_spin =
+            IntegerInput::new(settings.get_value("blur_radius").get_f64()? as i32, 1, 100);
+        blur_spin.connect_value_changed(glib::clone!(
+            #[strong]
+            settings,
+            move |s| {
+                let sz = s.value();
+                if sz > 0 {
+                    settings
+                        .set_value("blur_radius", SettingValue::F64(sz as f64))
+                        .expect("Error in ObfuscateSettingsBox");
+                }
+            }
+        ));
+        blur_spin.set_tooltip_text(Some("change blur radius"));
+
+        // Tools (pixelate, blur)
+        let tools = vec!["\u{f00a}", "\u{f0c2}"];
+        let model = gtk::StringList::new(&tools);
+
+        let factory = gtk::SignalListItemFactory::new();
+        factory.connect_setup(move |_factory, list_item| {
+            let label = gtk::Label::new(None);
+            label.add_css_class(CSS_CLASS_SOLID);
+            list_item
+                .downcast_ref::<gtk::ListItem>()
+                .expect("Expected a ListItem")
+                .set_child(Some(&label));
+        });
+
+        factory.connect_bind(|_factory, list_item| {
+            let list_item = list_item
+                .downcast_ref::<gtk::ListItem>()
+                .expect("Expected a ListItem");
+
+            let item = list_item
+                .item()
+                .and_then(|obj| obj.downcast::<gtk::StringObject>().ok())
+                .expect("Expected a StringObject");
+
+            let label = list_item
+                .child()
+                .and_then(|child| child.downcast::<gtk::Label>().ok())
+                .expect("Expected a Label child");
+
+            label.set_label(&item.string());
+        });
+
+        let tool_dropdown = gtk::DropDown::builder()
+            .model(&model)
+            .factory(&factory)
+            .build();
+        tool_dropdown.set_tooltip_text(Some("pixelate or blur"));
+        tool_dropdown.add_css_class(CSS_CLASS_SOLID);
+        if settings.get_value("tool").get_string()? == "blur" {
+            tool_dropdown.set_selected(1);
+        }
+        tool_dropdown.connect_selected_item_notify(glib::clone!(
+            #[strong]
+            geom,
+            move |dw| {
+                let tool = match dw.selected() {
+                    0 => "pixelate",
+                    1 => "blur",
+                    _ => {
+                        println!("obfuscate tool not found");
+                        return;
+                    }
+                };
+                geom.settings
+                    .obfuscate
+                    .set_value("tool", SettingValue::String(tool.to_string()))
+                    .expect("Error in ObfuscateSettingsBox");
+
+                // Replace the pending drawable with the chosen tool
+                let drawable = new_obfuscate_drawable(&geom.settings, &geom.captured);
+                geom.drawing.create_new_drawable(&drawable);
+            }
+        ));
+
+        // set children
+        let pixelate_box: gtk::Box = gtk::Box::new(gtk::Orientation::Horizontal, 4);
+        pixelate_box.append(&pixel_spin);
+        crate::toolbox_settings_box::add_expandable_row(
+            self,
+            "\u{f00a}",
+            "pixelate",
+            "fas",
+            pixelate_box,
+            true,
+        );
+
+        let blur_box: gtk::Box = gtk::Box::new(gtk::Orientation::Horizontal, 4);
+        blur_box.append(&blur_spin);
+        crate::toolbox_settings_box::add_expandable_row(
+            self, "\u{f0c2}", "blur", "fas", blur_box, true,
+        );
+
+        self.append(&tool_dropdown);
+
+        // drop shadow
+        crate::toolbox_settings_box::add_shadow_row(
+            self,
+            geom,
+            glib::clone!(
+                #[strong]
+                settings,
+                move |key| settings.get_value(key)
+            ),
+            glib::clone!(
+                #[strong]
+                settings,
+                move |key, value| {
+                    settings
+                        .set_value(key, value)
+                        .expect("Error in ObfuscateSettingsBox");
+                }
+            ),
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,23 @@
+use gtk::glib;
+use gtk::subclass::prelude::*;
+
+// Object holding the state
+#[derive(Debug, Default)]
+pub struct PolygonSettingsBox {}
+
+// The central trait for subclassing a GObject
+#[glib::object_subclass]
+impl ObjectSubclass for PolygonSettingsBox {
+    const NAME: &'static str = "PolygonSettingsBox";
+    type Type = super::PolygonSettingsBox;
+    type ParentType = gtk::Box;
+}
+
+// Trait shared by all GObjects
+impl ObjectImpl for PolygonSettingsBox {}
+
+// Trait shared by all widgets
+impl BoxImpl for PolygonSettingsBox {}
+
+// Trait shared by all widgets
+impl WidgetImpl for PolygonSettingsBox {}
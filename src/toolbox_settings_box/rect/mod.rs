@@ -251,7 +251,73 @@ impl RectSettingsBox {
         ));
         fill_color_btn.add_controller(gest);
 
+        // corner radius
+        let radius_spin = IntegerInput::new(
+            settings.get_value("corner_radius").get_f64()? as i32,
+            0,
+            200,
+        );
+        radius_spin.connect_value_changed(glib::clone!(
+            #[strong]
+            settings,
+            move |s| {
+                settings
+                    .set_value("corner_radius", SettingValue::F64(s.value() as f64))
+                    .expect("Error in RectSettingButton");
+            }
+        ));
+        radius_spin.set_tooltip_text(Some("change corner radius"));
+
+        // dash pattern
+        let dashes = ["solid", "dashed", "dotted", "dash_dot"];
+        let dash = settings.get_value("dash").get_string()?;
+        let dash_dropdown = crate::toolbox_settings_box::label_dropdown(
+            &["\u{f068}", "- -", "···", "-·-"],
+            dashes.iter().position(|d| *d == dash).unwrap_or(0) as u32,
+            "change border dash pattern",
+        );
+        dash_dropdown.connect_selected_item_notify(glib::clone!(
+            #[strong]
+            settings,
+            move |dw| {
+                let Some(dash) = dashes.get(dw.selected() as usize) else {
+                    println!("dash pattern not found");
+                    return;
+                };
+                settings
+                    .set_value("dash", SettingValue::String(dash.to_string()))
+                    .expect("Error in RectSettingButton");
+            }
+        ));
+
+        // highlight style: stroke only, wide and translucent
+        let highlight_checkbox = gtk::CheckButton::with_label("");
+        highlight_checkbox.set_active(settings.get_value("style").get_string()? == "highlight");
+        highlight_checkbox.set_tooltip_text(Some("stroke-only highlight"));
+        highlight_checkbox.connect_toggled(glib::clone!(
+            #[strong]
+            settings,
+            move |cb| {
+                let style = if cb.is_active() {
+                    "highlight"
+                } else {
+                    "normal"
+                };
+                settings
+                    .set_value("style", SettingValue::String(style.to_string()))
+                    .expect("highlight_checkbox error");
+            }
+        ));
+
         // set children
+        let style_box: gtk::Box = gtk::Box::new(gtk::Orientation::Horizontal, 4);
+        style_box.append(&radius_spin);
+        style_box.append(&dash_dropdown);
+        style_box.append(&highlight_checkbox);
+        crate::toolbox_settings_box::add_expandable_row(
+            self, "\u{f5cb}", "style", "fas", style_box, true,
+        );
+
         let border_box: gtk::Box = gtk::Box::new(gtk::Orientation::Horizontal, 4);
         border_box.append(&border_checkbox);
         border_box.append(&spin);
@@ -260,13 +326,50 @@ impl RectSettingsBox {
             self, "\u{f853}", "border", "fal", border_box, true,
         );
 
+        // gradient fill
+        let gradient_box = crate::toolbox_settings_box::gradient_box(
+            &settings.get_value("fill_mode").get_string()?,
+            &settings.get_value("fill_stops").get_string()?,
+            settings.get_value("fill_angle").get_f64()?,
+            glib::clone!(
+                #[strong]
+                settings,
+                move |key: &str, value: SettingValue| {
+                    settings
+                        .set_value(key, value)
+                        .expect("Error in RectSettingsBox");
+                }
+            ),
+        );
+
         let fill_box: gtk::Box = gtk::Box::new(gtk::Orientation::Horizontal, 4);
         fill_box.append(&fill_checkbox);
         fill_box.append(&fill_color_btn);
+        fill_box.append(&gradient_box);
         crate::toolbox_settings_box::add_expandable_row(
             self, "\u{f575}", "fill", "fas", fill_box, true,
         );
 
+        // drop shadow
+        crate::toolbox_settings_box::add_shadow_row(
+            self,
+            geom,
+            glib::clone!(
+                #[strong]
+                settings,
+                move |key| settings.get_value(key)
+            ),
+            glib::clone!(
+                #[strong]
+                settings,
+                move |key, value| {
+                    settings
+                        .set_value(key, value)
+                        .expect("Error in RectSettingsBox");
+                }
+            ),
+        );
+
         Ok(())
     }
 }
@@ -0,0 +1,23 @@
+use gtk::glib;
+use gtk::subclass::prelude::*;
+
+// Object holding the state
+#[derive(Debug, Default)]
+pub struct RedactSettingsBox {}
+
+// The central trait for subclassing a GObject
+#[glib::object_subclass]
+impl ObjectSubclass for RedactSettingsBox {
+    const NAME: &'static str = "RedactSettingsBox";
+    type Type = super::RedactSettingsBox;
+    type ParentType = gtk::Box;
+}
+
+// Trait shared by all GObjects
+impl ObjectImpl for RedactSettingsBox {}
+
+// Trait shared by all widgets
+impl BoxImpl for RedactSettingsBox {}
+
+// Trait shared by all widgets
+impl WidgetImpl for RedactSettingsBox {}
@@ -0,0 +1,151 @@
+mod imp;
+
+use gtk::prelude::*;
+use gtk::{gio, glib};
+use rustshot_gtk::constants::{CSS_CLASS_TOOLBOX_BAR, TOOLBOX_BTN_SIZE, TOOLBOX_SETTINGS_BTN_SIZE};
+
+use crate::drawing_area_settings::SettingValue;
+use crate::geometry::GeometryState;
+
+glib::wrapper! {
+    pub struct RedactSettingsBox(ObjectSubclass<imp::RedactSettingsBox>)
+        @extends gtk::Box,
+        @implements gtk::Accessible,  gtk::Actionable, gtk::Widget, gtk::Orientable, gtk::Buildable, gtk::ConstraintTarget;
+}
+
+impl Default for RedactSettingsBox {
+    fn default() -> Self {
+        glib::Object::new()
+    }
+}
+
+impl RedactSettingsBox {
+    pub fn new_vertical(&self, size: i32, align: gtk::Align) {
+        self.set_orientation(gtk::Orientation::Vertical);
+        self.add_css_class(CSS_CLASS_TOOLBOX_BAR);
+        self.set_width_request(size);
+        self.set_halign(align);
+        self.set_valign(gtk::Align::Center);
+    }
+
+    pub fn new_horizontal(&self, align: gtk::Align) {
+        self.set_orientation(gtk::Orientation::Horizontal);
+        self.add_css_class(CSS_CLASS_TOOLBOX_BAR);
+        self.set_height_request(TOOLBOX_SETTINGS_BTN_SIZE);
+        self.set_valign(align);
+    }
+
+    pub fn new_fullscreen(&self, geom: &GeometryState) {
+        // create toolbox
+        self.set_orientation(gtk::Orientation::Horizontal);
+        self.add_css_class(CSS_CLASS_TOOLBOX_BAR);
+        self.set_halign(gtk::Align::Center);
+        self.set_height_request(TOOLBOX_BTN_SIZE);
+        self.set_margin_start(geom.full_w / 2);
+        self.set_margin_top(10);
+        self.set_margin_bottom(geom.full_h - TOOLBOX_BTN_SIZE);
+    }
+
+    pub fn populate_with_settings(&self, geom: &GeometryState) -> std::io::Result<()> {
+        // get redact settings
+        let settings = geom.settings.redact.clone();
+
+        // redaction color, always opaque
+        let color_dialog = gtk::ColorDialog::new();
+        color_dialog.set_with_alpha(false);
+        let color_btn = gtk::ColorDialogButton::new(Some(color_dialog.clone()));
+        color_btn.set_tooltip_text(Some("change redaction color"));
+
+        // get actual color
+        let color = gtk::gdk::RGBA::new(
+            settings.get_value("color_r").get_f64()? as f32,
+            settings.get_value("color_g").get_f64()? as f32,
+            settings.get_value("color_b").get_f64()? as f32,
+            1.0,
+        );
+        color_btn.set_rgba(&color);
+
+        let gest = gtk::GestureClick::new();
+        gest.connect_pressed(glib::clone!(
+            #[strong]
+            geom,
+            #[weak]
+            color_dialog,
+            #[weak]
+            color_btn,
+            move |_, _, _, _| {
+                // create color dialog
+                let cancellable = gio::Cancellable::new();
+
+                // clone geometry
+                let geom = geom.clone();
+
+                // Dialog
+                let btn = color_btn.clone();
+                color_dialog.set_title("Pick color");
+                color_dialog.choose_rgba(
+                    Some(&geom.window),
+                    Some(&color),
+                    Some(&cancellable),
+                    move |res| {
+                        if let Ok(color) = res {
+                            btn.set_rgba(&color);
+                            let r: f64 = color.red() as f64;
+                            let g: f64 = color.green() as f64;
+                            let b: f64 = color.blue() as f64;
+                            geom.settings
+                                .redact
+                                .set_value("color_r", SettingValue::F64(r))
+                                .expect("ColorChoser Error");
+                            geom.settings
+                                .redact
+                                .set_value("color_g", SettingValue::F64(g))
+                                .expect("ColorChoser Error");
+                            geom.settings
+                                .redact
+                                .set_value("color_b", SettingValue::F64(b))
+                                .expect("ColorChoser Error");
+                        } else {
+                            println!("No color found");
+                        }
+                    },
+                );
+            }
+        ));
+        color_btn.add_controller(gest);
+
+        // set children
+        let color_box: gtk::Box = gtk::Box::new(gtk::Orientation::Horizontal, 4);
+        color_box.append(&color_btn);
+        crate::toolbox_settings_box::add_expandable_row(
+            self,
+            "\u{f070}",
+            "redaction",
+            "fas",
+            color_box,
+            true,
+        );
+
+        // drop shadow
+        crate::toolbox_settings_box::add_shadow_row(
+            self,
+            geom,
+            glib::clone!(
+                #[strong]
+                settings,
+                move |key| settings.get_value(key)
+            ),
+            glib::clone!(
+                #[strong]
+                settings,
+                move |key, value| {
+                    settings
+                        .set_value(key, value)
+                        .expect("Error in RedactSettingsBox");
+                }
+            ),
+        );
+
+        Ok(())
+    }
+}
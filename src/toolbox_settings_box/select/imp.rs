@@ -0,0 +1,23 @@
+use gtk::glib;
+use gtk::subclass::prelude::*;
+
+// Object holding the state
+#[derive(Debug, Default)]
+pub struct SelectSettingsBox {}
+
+// The central trait for subclassing a GObject
+#[glib::object_subclass]
+impl ObjectSubclass for SelectSettingsBox {
+    const NAME: &'static str = "SelectSettingsBox";
+    type Type = super::SelectSettingsBox;
+    type ParentType = gtk::Box;
+}
+
+// Trait shared by all GObjects
+impl ObjectImpl for SelectSettingsBox {}
+
+// Trait shared by all widgets
+impl BoxImpl for SelectSettingsBox {}
+
+// Trait shared by all widgets
+impl WidgetImpl for SelectSettingsBox {}
@@ -0,0 +1,132 @@
+mod imp;
+
+use gtk::glib;
+use gtk::prelude::*;
+use rustshot_gtk::constants::{
+    CSS_CLASS_SOLID, CSS_CLASS_TOOLBOX_BAR, TOOLBOX_BTN_SIZE, TOOLBOX_SETTINGS_BTN_SIZE,
+};
+
+use crate::geometry::GeometryState;
+
+glib::wrapper! {
+    pub struct SelectSettingsBox(ObjectSubclass<imp::SelectSettingsBox>)
+        @extends gtk::Box,
+        @implements gtk::Accessible,  gtk::Actionable, gtk::Widget, gtk::Orientable, gtk::Buildable, gtk::ConstraintTarget;
+}
+
+impl Default for SelectSettingsBox {
+    fn default() -> Self {
+        glib::Object::new()
+    }
+}
+
+impl SelectSettingsBox {
+    pub fn new_vertical(&self, size: i32, align: gtk::Align) {
+        self.set_orientation(gtk::Orientation::Vertical);
+        self.add_css_class(CSS_CLASS_TOOLBOX_BAR);
+        self.set_width_request(size);
+        self.set_halign(align);
+        self.set_valign(gtk::Align::Center);
+    }
+
+    pub fn new_horizontal(&self, align: gtk::Align) {
+        self.set_orientation(gtk::Orientation::Horizontal);
+        self.add_css_class(CSS_CLASS_TOOLBOX_BAR);
+        self.set_height_request(TOOLBOX_SETTINGS_BTN_SIZE);
+        self.set_valign(align);
+    }
+
+    pub fn new_fullscreen(&self, geom: &GeometryState) {
+        // create toolbox
+        self.set_orientation(gtk::Orientation::Horizontal);
+        self.add_css_class(CSS_CLASS_TOOLBOX_BAR);
+        self.set_halign(gtk::Align::Center);
+        self.set_height_request(TOOLBOX_BTN_SIZE);
+        self.set_margin_start(geom.full_w / 2);
+        self.set_margin_top(10);
+        self.set_margin_bottom(geom.full_h - TOOLBOX_BTN_SIZE);
+    }
+
+    /// Create a small icon button acting on the current selection
+    fn selection_button<F>(
+        &self,
+        geom: &GeometryState,
+        label: &str,
+        tooltip: &str,
+        action: F,
+    ) -> gtk::Button
+    where
+        F: Fn(&GeometryState) + 'static,
+    {
+        let btn = gtk::Button::with_label(label);
+        btn.add_css_class(CSS_CLASS_SOLID);
+        btn.set_tooltip_text(Some(tooltip));
+        btn.connect_clicked(glib::clone!(
+            #[strong]
+            geom,
+            move |_| {
+                action(&geom);
+            }
+        ));
+        btn
+    }
+
+    pub fn populate_with_settings(&self, geom: &GeometryState) -> std::io::Result<()> {
+        // group / ungroup
+        let group_btn = self.selection_button(geom, "\u{f247}", "group (Ctrl+G)", |geom| {
+            geom.drawing.group_selection();
+        });
+        let ungroup_btn =
+            self.selection_button(geom, "\u{f248}", "ungroup (Ctrl+Shift+G)", |geom| {
+                geom.drawing.ungroup_selection();
+            });
+
+        let group_box: gtk::Box = gtk::Box::new(gtk::Orientation::Horizontal, 4);
+        group_box.append(&group_btn);
+        group_box.append(&ungroup_btn);
+        crate::toolbox_settings_box::add_expandable_row(
+            self, "\u{f247}", "group", "fas", group_box, true,
+        );
+
+        // drawing order
+        let front_btn = self.selection_button(geom, "\u{f102}", "bring to front (Home)", |geom| {
+            geom.drawing.bring_selection_to_front();
+        });
+        let raise_btn = self.selection_button(geom, "\u{f106}", "raise (Page Up)", |geom| {
+            geom.drawing.raise_selection();
+        });
+        let lower_btn = self.selection_button(geom, "\u{f107}", "lower (Page Down)", |geom| {
+            geom.drawing.lower_selection();
+        });
+        let back_btn = self.selection_button(geom, "\u{f103}", "send to back (End)", |geom| {
+            geom.drawing.send_selection_to_back();
+        });
+
+        let order_box: gtk::Box = gtk::Box::new(gtk::Orientation::Horizontal, 4);
+        order_box.append(&front_btn);
+        order_box.append(&raise_btn);
+        order_box.append(&lower_btn);
+        order_box.append(&back_btn);
+        crate::toolbox_settings_box::add_expandable_row(
+            self,
+            "\u{f5fd}",
+            "drawing order",
+            "fas",
+            order_box,
+            true,
+        );
+
+        // delete
+        let delete_btn = self.selection_button(geom, "\u{f1f8}", "delete (Delete)", |geom| {
+            geom.drawing.delete_selection();
+        });
+
+        let delete_box: gtk::Box = gtk::Box::new(gtk::Orientation::Horizontal, 4);
+        delete_box.append(&delete_btn);
+        crate::toolbox_settings_box::add_expandable_row(
+            self, "\u{f1f8}", "delete", "fas", delete_box, true,
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,23 @@
+use gtk::glib;
+use gtk::subclass::prelude::*;
+
+// Object holding the state
+#[derive(Debug, Default)]
+pub struct SpotlightSettingsBox {}
+
+// The central trait for subclassing a GObject
+#[glib::object_subclass]
+impl ObjectSubclass for SpotlightSettingsBox {
+    const NAME: &'static str = "SpotlightSettingsBox";
+    type Type = super::SpotlightSettingsBox;
+    type ParentType = gtk::Box;
+}
+
+// Trait shared by all GObjects
+impl ObjectImpl for SpotlightSettingsBox {}
+
+// Trait shared by all widgets
+impl BoxImpl for SpotlightSettingsBox {}
+
+// Trait shared by all widgets
+impl WidgetImpl for SpotlightSettingsBox {}
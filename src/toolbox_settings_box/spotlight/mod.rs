@@ -0,0 +1,165 @@
+mod imp;
+
+use gtk::glib;
+use gtk::prelude::*;
+use rustshot_gtk::constants::{
+    CSS_CLASS_SOLID, CSS_CLASS_TOOLBOX_BAR, TOOLBOX_BTN_SIZE, TOOLBOX_SETTINGS_BTN_SIZE,
+};
+
+use crate::drawing_area_settings::SettingValue;
+use crate::geometry::GeometryState;
+use crate::spin_button::IntegerInput;
+
+glib::wrapper! {
+    pub struct SpotlightSettingsBox(ObjectSubclass<imp::SpotlightSettingsBox>)
+        @extends gtk::Box,
+        @implements gtk::Accessible,  gtk::Actionable, gtk::Widget, gtk::Orientable, gtk::Buildable, gtk::ConstraintTarget;
+}
+
+impl Default for SpotlightSettingsBox {
+    fn default() -> Self {
+        glib::Object::new()
+    }
+}
+
+impl SpotlightSettingsBox {
+    pub fn new_vertical(&self, size: i32, align: gtk::Align) {
+        self.set_orientation(gtk::Orientation::Vertical);
+        self.add_css_class(CSS_CLASS_TOOLBOX_BAR);
+        self.set_width_request(size);
+        self.set_halign(align);
+        self.set_valign(gtk::Align::Center);
+    }
+
+    pub fn new_horizontal(&self, align: gtk::Align) {
+        self.set_orientation(gtk::Orientation::Horizontal);
+        self.add_css_class(CSS_CLASS_TOOLBOX_BAR);
+        self.set_height_request(TOOLBOX_SETTINGS_BTN_SIZE);
+        self.set_valign(align);
+    }
+
+    pub fn new_fullscreen(&self, geom: &GeometryState) {
+        // create toolbox
+        self.set_orientation(gtk::Orientation::Horizontal);
+        self.add_css_class(CSS_CLASS_TOOLBOX_BAR);
+        self.set_halign(gtk::Align::Center);
+        self.set_height_request(TOOLBOX_BTN_SIZE);
+        self.set_margin_start(geom.full_w / 2);
+        self.set_margin_top(10);
+        self.set_margin_bottom(geom.full_h - TOOLBOX_BTN_SIZE);
+    }
+
+    pub fn populate_with_settings(&self, geom: &GeometryState) -> std::io::Result<()> {
+        // get spotlight settings
+        let settings = geom.settings.spotlight.clone();
+
+        // dim amount, in percent
+        let dim_spin = IntegerInput::new(
+            (settings.get_value("dim").get_f64()? * 100.0).round() as i32,
+            0,
+            100,
+        );
+        dim_spin.connect_value_changed(glib::clone!(
+            #[strong]
+            settings,
+            move |s| {
+                settings
+                    .set_value("dim", SettingValue::F64(s.value() as f64 / 100.0))
+                    .expect("Error in SpotlightSettingsBox");
+            }
+        ));
+        dim_spin.set_tooltip_text(Some(
+            "change how much this spotlight dims the rest (%), the strongest spotlight sets the dim",
+        ));
+
+        // Shapes (rect, ellipse)
+        let shapes = vec!["\u{f096}", "\u{f111}"];
+        let model = gtk::StringList::new(&shapes);
+
+        let factory = gtk::SignalListItemFactory::new();
+        factory.connect_setup(move |_factory, list_item| {
+            let label = gtk::Label::new(None);
+            label.add_css_class(CSS_CLASS_SOLID);
+            list_item
+                .downcast_ref::<gtk::ListItem>()
+                .expect("Expected a ListItem")
+                .set_child(Some(&label));
+        });
+
+        factory.connect_bind(|_factory, list_item| {
+            let list_item = list_item
+                .downcast_ref::<gtk::ListItem>()
+                .expect("Expected a ListItem");
+
+            let item = list_item
+                .item()
+                .and_then(|obj| obj.downcast::<gtk::StringObject>().ok())
+                .expect("Expected a StringObject");
+
+            let label = list_item
+                .child()
+                .and_then(|child| child.downcast::<gtk::Label>().ok())
+                .expect("Expected a Label child");
+
+            label.set_label(&item.string());
+        });
+
+        let shape_dropdown = gtk::DropDown::builder()
+            .model(&model)
+            .factory(&factory)
+            .build();
+        shape_dropdown.set_tooltip_text(Some("rectangle or ellipse"));
+        shape_dropdown.add_css_class(CSS_CLASS_SOLID);
+        if settings.get_value("shape").get_string()? == "ellipse" {
+            shape_dropdown.set_selected(1);
+        }
+        shape_dropdown.connect_selected_item_notify(glib::clone!(
+            #[strong]
+            settings,
+            move |dw| {
+                let shape = match dw.selected() {
+                    0 => "rect",
+                    1 => "ellipse",
+                    _ => {
+                        println!("spotlight shape not found");
+                        return;
+                    }
+                };
+                settings
+                    .set_value("shape", SettingValue::String(shape.to_string()))
+                    .expect("Error in SpotlightSettingsBox");
+            }
+        ));
+
+        // set children
+        let dim_box: gtk::Box = gtk::Box::new(gtk::Orientation::Horizontal, 4);
+        dim_box.append(&dim_spin);
+        crate::toolbox_settings_box::add_expandable_row(
+            self, "\u{f186}", "dim", "fas", dim_box, true,
+        );
+
+        self.append(&shape_dropdown);
+
+        // drop shadow
+        crate::toolbox_settings_box::add_shadow_row(
+            self,
+            geom,
+            glib::clone!(
+                #[strong]
+                settings,
+                move |key| settings.get_value(key)
+            ),
+            glib::clone!(
+                #[strong]
+                settings,
+                move |key, value| {
+                    settings
+                        .set_value(key, value)
+                        .expect("Error in SpotlightSettingsBox");
+                }
+            ),
+        );
+
+        Ok(())
+    }
+}
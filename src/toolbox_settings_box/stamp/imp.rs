@@ -0,0 +1,23 @@
+use gtk::glib;
+use gtk::subclass::prelude::*;
+
+// Object holding the state
+#[derive(Debug, Default)]
+pub struct StampSettingsBox {}
+
+// The central trait for subclassing a GObject
+#[glib::object_subclass]
+impl ObjectSubclass for StampSettingsBox {
+    const NAME: &'static str = "StampSettingsBox";
+    type Type = super::StampSettingsBox;
+    type ParentType = gtk::Box;
+}
+
+// Trait shared by all GObjects
+impl ObjectImpl for StampSettingsBox {}
+
+// Trait shared by all widgets
+impl BoxImpl for StampSettingsBox {}
+
+// Trait shared by all widgets
+impl WidgetImpl for StampSettingsBox {}
@@ -0,0 +1,221 @@
+mod imp;
+
+use gtk::glib;
+use gtk::prelude::*;
+use rustshot_gtk::constants::{
+    CSS_CLASS_SOLID, CSS_CLASS_TOOLBOX_BAR, STAMP_THUMBNAIL_SIZE, TOOLBOX_BTN_SIZE,
+    TOOLBOX_SETTINGS_BTN_SIZE,
+};
+
+use crate::drawing_area_settings::SettingValue;
+use crate::geometry::GeometryState;
+use crate::spin_button::IntegerInput;
+use crate::stamps;
+
+glib::wrapper! {
+    pub struct StampSettingsBox(ObjectSubclass<imp::StampSettingsBox>)
+        @extends gtk::Box,
+        @implements gtk::Accessible,  gtk::Actionable, gtk::Widget, gtk::Orientable, gtk::Buildable, gtk::ConstraintTarget;
+}
+
+impl Default for StampSettingsBox {
+    fn default() -> Self {
+        glib::Object::new()
+    }
+}
+
+impl StampSettingsBox {
+    pub fn new_vertical(&self, size: i32, align: gtk::Align) {
+        self.set_orientation(gtk::Orientation::Vertical);
+        self.add_css_class(CSS_CLASS_TOOLBOX_BAR);
+        self.set_width_request(size);
+        self.set_halign(align);
+        self.set_valign(gtk::Align::Center);
+    }
+
+    pub fn new_horizontal(&self, align: gtk::Align) {
+        self.set_orientation(gtk::Orientation::Horizontal);
+        self.add_css_class(CSS_CLASS_TOOLBOX_BAR);
+        self.set_height_request(TOOLBOX_SETTINGS_BTN_SIZE);
+        self.set_valign(align);
+    }
+
+    pub fn new_fullscreen(&self, geom: &GeometryState) {
+        // create toolbox
+        self.set_orientation(gtk::Orientation::Horizontal);
+        self.add_css_class(CSS_CLASS_TOOLBOX_BAR);
+        self.set_halign(gtk::Align::Center);
+        self.set_height_request(TOOLBOX_BTN_SIZE);
+        self.set_margin_start(geom.full_w / 2);
+        self.set_margin_top(10);
+        self.set_margin_bottom(geom.full_h - TOOLBOX_BTN_SIZE);
+    }
+
+    pub fn populate_with_settings(&self, geom: &GeometryState) -> std::io::Result<()> {
+        // get stamp settings
+        let settings = geom.settings.stamp.clone();
+
+        // stamp picker, with a thumbnail of every image in the stamps directory
+        let model = gtk::StringList::new(&[]);
+        let factory = gtk::SignalListItemFactory::new();
+        factory.connect_setup(move |_factory, list_item| {
+            let image = gtk::Image::new();
+            image.set_pixel_size(STAMP_THUMBNAIL_SIZE);
+            list_item
+                .downcast_ref::<gtk::ListItem>()
+                .expect("Expected a ListItem")
+                .set_child(Some(&image));
+        });
+        factory.connect_bind(|_factory, list_item| {
+            let list_item = list_item
+                .downcast_ref::<gtk::ListItem>()
+                .expect("Expected a ListItem");
+            let name = list_item
+                .item()
+                .and_then(|obj| obj.downcast::<gtk::StringObject>().ok())
+                .expect("Expected a StringObject")
+                .string();
+            let image = list_item
+                .child()
+                .and_then(|child| child.downcast::<gtk::Image>().ok())
+                .expect("Expected an Image child");
+            image.set_from_file(Some(stamps::stamps_dir().join(name.as_str())));
+            image.set_tooltip_text(Some(&name));
+        });
+        let stamp_dropdown = gtk::DropDown::builder()
+            .model(&model)
+            .factory(&factory)
+            .build();
+        stamp_dropdown.set_tooltip_text(Some(&format!(
+            "pick a stamp from {}",
+            stamps::stamps_dir().display()
+        )));
+        Self::load_stamps(
+            &model,
+            &stamp_dropdown,
+            &settings.get_value("file").get_string()?,
+        );
+
+        stamp_dropdown.connect_selected_item_notify(glib::clone!(
+            #[strong]
+            settings,
+            move |dw| {
+                let Some(name) = dw
+                    .selected_item()
+                    .and_then(|obj| obj.downcast::<gtk::StringObject>().ok())
+                else {
+                    return;
+                };
+                settings
+                    .set_value("file", SettingValue::String(name.string().to_string()))
+                    .expect("Error in StampSettingsBox");
+            }
+        ));
+
+        // reload the stamps directory, after adding or editing images
+        let reload_btn = gtk::Button::with_label("\u{f2f9}");
+        reload_btn.add_css_class(CSS_CLASS_SOLID);
+        reload_btn.set_tooltip_text(Some("reload stamps"));
+        reload_btn.connect_clicked(glib::clone!(
+            #[strong]
+            settings,
+            #[weak]
+            model,
+            #[weak]
+            stamp_dropdown,
+            move |_| {
+                stamps::clear_cache();
+                let file = settings.get_value("file").get_string().unwrap_or_default();
+                Self::load_stamps(&model, &stamp_dropdown, &file);
+            }
+        ));
+
+        // scale in percent
+        let scale_spin = IntegerInput::new(
+            (settings.get_value("scale").get_f64()? * 100.0).round() as i32,
+            5,
+            1000,
+        );
+        scale_spin.connect_value_changed(glib::clone!(
+            #[strong]
+            settings,
+            move |s| {
+                let sz = s.value();
+                if sz > 0 {
+                    settings
+                        .set_value("scale", SettingValue::F64(sz as f64 / 100.0))
+                        .expect("Error in StampSettingsBox");
+                }
+            }
+        ));
+        scale_spin.set_tooltip_text(Some("change scale (%)"));
+
+        // rotation in degrees
+        let rotation_spin = IntegerInput::new(
+            settings.get_value("rotation").get_f64()?.round() as i32,
+            -180,
+            180,
+        );
+        rotation_spin.connect_value_changed(glib::clone!(
+            #[strong]
+            settings,
+            move |s| {
+                settings
+                    .set_value("rotation", SettingValue::F64(s.value() as f64))
+                    .expect("Error in StampSettingsBox");
+            }
+        ));
+        rotation_spin.set_tooltip_text(Some("change rotation (degrees)"));
+
+        // set children
+        let stamp_box: gtk::Box = gtk::Box::new(gtk::Orientation::Horizontal, 4);
+        stamp_box.append(&stamp_dropdown);
+        stamp_box.append(&reload_btn);
+        crate::toolbox_settings_box::add_expandable_row(
+            self, "\u{f5bf}", "stamp", "fas", stamp_box, true,
+        );
+
+        let transform_box: gtk::Box = gtk::Box::new(gtk::Orientation::Horizontal, 4);
+        transform_box.append(&scale_spin);
+        transform_box.append(&rotation_spin);
+        crate::toolbox_settings_box::add_expandable_row(
+            self,
+            "\u{f2f1}",
+            "scale and rotation",
+            "fas",
+            transform_box,
+            true,
+        );
+
+        // drop shadow
+        crate::toolbox_settings_box::add_shadow_row(
+            self,
+            geom,
+            glib::clone!(
+                #[strong]
+                settings,
+                move |key| settings.get_value(key)
+            ),
+            glib::clone!(
+                #[strong]
+                settings,
+                move |key, value| {
+                    settings
+                        .set_value(key, value)
+                        .expect("Error in StampSettingsBox");
+                }
+            ),
+        );
+
+        Ok(())
+    }
+
+    /// Fill the picker with the stamps on disk and select `file`, or the first stamp
+    fn load_stamps(model: &gtk::StringList, dropdown: &gtk::DropDown, file: &str) {
+        let names = stamps::list_stamps();
+        let names: Vec<&str> = names.iter().map(String::as_str).collect();
+        model.splice(0, model.n_items(), &names);
+        let selected = names.iter().position(|name| *name == file).unwrap_or(0);
+        dropdown.set_selected(selected as u32);
+    }
+}